@@ -15,7 +15,6 @@
 /// Std io errors are wrapped in Io(::std::io::Error).
 ///
 /// Boolean parse errors from Std str ParseBoolError are wrapped in BoolStr(::std::str::ParseBoolError).
-
 pub mod errors {
     use thiserror::Error;
 
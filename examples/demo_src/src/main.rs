@@ -373,8 +373,8 @@ fn handle_data_option(src: &mut SacnSource, split_input: Vec<&str>) -> Result<bo
 
     let mut data: Vec<u8> = Vec::new();
 
-    for i in 4..split_input.len() {
-        data.push(split_input[i].parse().unwrap());
+    for s in &split_input[4..] {
+        data.push(s.parse().unwrap());
     }
 
     if sync_uni == 0 {
@@ -422,8 +422,8 @@ fn handle_unicast_option(src: &mut SacnSource, split_input: Vec<&str>) -> Result
 
     let mut data: Vec<u8> = Vec::new();
 
-    for i in 5..split_input.len() {
-        data.push(split_input[i].parse().unwrap());
+    for s in &split_input[5..] {
+        data.push(s.parse().unwrap());
     }
 
     if sync_uni == 0 {
@@ -431,9 +431,10 @@ fn handle_unicast_option(src: &mut SacnSource, split_input: Vec<&str>) -> Result
             &[universe],
             &data,
             Some(priority),
-            Some(
-                SocketAddr::new(IpAddr::V4(dst_ip.parse().unwrap()), ACN_SDT_MULTICAST_PORT).into(),
-            ),
+            Some(SocketAddr::new(
+                IpAddr::V4(dst_ip.parse().unwrap()),
+                ACN_SDT_MULTICAST_PORT,
+            )),
             None,
         )?;
     } else {
@@ -441,9 +442,10 @@ fn handle_unicast_option(src: &mut SacnSource, split_input: Vec<&str>) -> Result
             &[universe],
             &data,
             Some(priority),
-            Some(
-                SocketAddr::new(IpAddr::V4(dst_ip.parse().unwrap()), ACN_SDT_MULTICAST_PORT).into(),
-            ),
+            Some(SocketAddr::new(
+                IpAddr::V4(dst_ip.parse().unwrap()),
+                ACN_SDT_MULTICAST_PORT,
+            )),
             Some(sync_uni),
         )?;
     }
@@ -482,7 +484,7 @@ fn handle_data_over_time_option(src: &mut SacnSource, split_input: Vec<&str>) ->
     let start_time = Instant::now();
 
     while start_time.elapsed() < duration {
-        let x: f64 = (start_time.elapsed().as_millis() as f64) / (1000 as f64);
+        let x: f64 = (start_time.elapsed().as_millis() as f64) / 1000_f64;
         let d: u8 = (255.0 * x.sin()) as u8;
 
         let mut data: [u8; 513] = [d; 513];
@@ -546,8 +548,8 @@ fn handle_test_preset_option(src: &mut SacnSource, split_input: Vec<&str>) -> Re
                 src,
                 universe,
                 universe_2,
-                std::u8::MAX / 2,
-                std::u8::MAX,
+                u8::MAX / 2,
+                u8::MAX,
                 None,
             )?;
         }
@@ -567,8 +569,8 @@ fn handle_test_preset_option(src: &mut SacnSource, split_input: Vec<&str>) -> Re
                 src,
                 universe,
                 universe_2,
-                std::u8::MAX / 2,
-                std::u8::MAX,
+                u8::MAX / 2,
+                u8::MAX,
                 Some(addr),
             )?;
         }
@@ -640,12 +642,11 @@ fn run_test_moving_channel_preset(src: &mut SacnSource, universe: u16) -> Result
 
     while start_time.elapsed() < TEST_PRESET_DURATION {
         // Use a 0 startcode so skip first value.
-        for i in 1..data.len() {
+        for (i, d) in data.iter_mut().enumerate().skip(1) {
             let x: f64 = ((start_time.elapsed().as_millis() as f64)
                 + (i as f64) * MOVING_CHANNEL_TEST_WAVE_OFFSET)
                 / MOVING_CHANNEL_TEST_WAVE_PERIOD;
-            let d: u8 = ((std::u8::MAX as f64) * x.sin()) as u8;
-            data[i] = d;
+            *d = ((u8::MAX as f64) * x.sin()) as u8;
         }
 
         src.send(&[universe], &data, None, None, None)?;
@@ -671,7 +672,7 @@ fn run_test_rapid_changes_preset(src: &mut SacnSource, universe: u16) -> Result<
         let mut data = if counter < (TEST_PRESET_RAPID_CHANGE_PERIOD / 2) {
             [0; UNIVERSE_CHANNEL_CAPACITY]
         } else {
-            [std::u8::MAX; UNIVERSE_CHANNEL_CAPACITY]
+            [u8::MAX; UNIVERSE_CHANNEL_CAPACITY]
         };
 
         // Use a zero startcode.
@@ -713,7 +714,7 @@ fn run_test_high_data_rate(
             src.send(&[universe], &data, None, None, None)?;
         }
 
-        counter = counter + 0.05;
+        counter += 0.05;
         sleep(TEST_PRESET_UPDATE_PERIOD);
     }
 
@@ -1148,10 +1149,7 @@ fn handle_input(src: &mut SacnSource) -> Result<bool> {
 
                     let universe: u16 = split_input[1].parse().unwrap();
                     let dst_ip = split_input[2];
-                    src.send_sync_packet(
-                        universe,
-                        Some(SocketAddr::from_str(dst_ip).unwrap().into()),
-                    )?;
+                    src.send_sync_packet(universe, Some(SocketAddr::from_str(dst_ip).unwrap()))?;
                     Ok(true)
                 }
                 ACTION_REGISTER_OPTION => {
@@ -1196,17 +1194,13 @@ fn handle_input(src: &mut SacnSource) -> Result<bool> {
                 }
                 ACTION_ALL_DATA_OPTION => handle_all_data_option(src, split_input),
                 ACTION_TEST_PRESENT_OPTION => handle_test_preset_option(src, split_input),
-                x => {
-                    return Err(std::io::Error::new(
-                        std::io::ErrorKind::InvalidInput,
-                        format!("Unknown input type: {}", x),
-                    )
-                    .into());
-                }
+                x => Err(std::io::Error::new(
+                    std::io::ErrorKind::InvalidInput,
+                    format!("Unknown input type: {}", x),
+                )
+                .into()),
             }
         }
-        Err(e) => {
-            return Err(e.into());
-        }
+        Err(e) => Err(e.into()),
     }
 }
@@ -0,0 +1,197 @@
+// Copyright 2020 sacn Developers
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! Command line handling shared by the sACN binaries.
+
+// Each binary only uses some of the helpers.
+#![allow(dead_code)]
+
+use sacn::packet::{
+    E131_DISCOVERY_UNIVERSE, E131_MAX_MULTICAST_UNIVERSE, E131_MIN_MULTICAST_UNIVERSE,
+};
+
+use std::fmt::Write;
+use std::process::ExitCode;
+use std::str::FromStr;
+
+/// The command line arguments of a binary, read flag by flag.
+pub struct Args {
+    /// The arguments not yet read, not including the name of the binary.
+    args: std::vec::IntoIter<String>,
+}
+
+impl Args {
+    /// Returns the arguments the binary was run with.
+    pub fn from_env() -> Args {
+        Args::new(std::env::args().skip(1).collect())
+    }
+
+    /// Returns the given arguments, which shouldn't include the name of the binary.
+    pub fn new(args: Vec<String>) -> Args {
+        Args {
+            args: args.into_iter(),
+        }
+    }
+
+    /// Returns the value which follows the given flag.
+    ///
+    /// # Errors
+    /// Returned if there are no more arguments.
+    pub fn value(&mut self, flag: &str) -> Result<String, String> {
+        self.args
+            .next()
+            .ok_or_else(|| format!("{flag} requires a value"))
+    }
+
+    /// Returns the value which follows the given flag, parsed.
+    ///
+    /// # Errors
+    /// Returned if there are no more arguments or the value doesn't parse.
+    pub fn parse<T: FromStr>(&mut self, flag: &str) -> Result<T, String> {
+        let value = self.value(flag)?;
+        value
+            .parse()
+            .map_err(|_| format!("Invalid value for {flag}: {value}"))
+    }
+}
+
+impl Iterator for Args {
+    type Item = String;
+
+    fn next(&mut self) -> Option<String> {
+        self.args.next()
+    }
+}
+
+/// Parses a list of universes separated by commas, each either a single universe or an inclusive range such as `1-4`.
+///
+/// # Errors
+/// Returned if a universe doesn't parse or is outwith the range of data universes, or a range is empty.
+pub fn parse_universes(list: &str) -> Result<Vec<u16>, String> {
+    let mut universes = Vec::new();
+    for part in list.split(',').filter(|p| !p.is_empty()) {
+        let (first, last) = match part.split_once('-') {
+            Some((first, last)) => (parse_universe(first)?, parse_universe(last)?),
+            None => {
+                let universe = parse_universe(part)?;
+                (universe, universe)
+            }
+        };
+        if first > last {
+            return Err(format!("Empty universe range: {part}"));
+        }
+        universes.extend(first..=last);
+    }
+    universes.sort_unstable();
+    universes.dedup();
+    Ok(universes)
+}
+
+/// Parses a single data universe.
+///
+/// # Errors
+/// Returned if the universe doesn't parse or is outwith the range of data universes.
+pub fn parse_universe(universe: &str) -> Result<u16, String> {
+    match universe.trim().parse() {
+        Ok(u) if (E131_MIN_MULTICAST_UNIVERSE..=E131_MAX_MULTICAST_UNIVERSE).contains(&u) => Ok(u),
+        Ok(E131_DISCOVERY_UNIVERSE) => Err(format!(
+            "Universe {E131_DISCOVERY_UNIVERSE} is the discovery universe, not a data universe"
+        )),
+        _ => Err(format!(
+            "Invalid universe {universe}, must be {E131_MIN_MULTICAST_UNIVERSE}-{E131_MAX_MULTICAST_UNIVERSE}"
+        )),
+    }
+}
+
+/// Formats sorted universes compactly as ranges, e.g. 1-4,7.
+pub fn universe_ranges(universes: &[u16]) -> String {
+    let mut ranges: Vec<(u16, u16)> = Vec::new();
+    for &u in universes {
+        match ranges.last_mut() {
+            Some((_, last)) if last.checked_add(1) == Some(u) => *last = u,
+            _ => ranges.push((u, u)),
+        }
+    }
+    ranges
+        .iter()
+        .map(|&(first, last)| {
+            if first == last {
+                first.to_string()
+            } else {
+                format!("{first}-{last}")
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// Returns the given string as a quoted JSON string.
+pub fn json_string(s: &str) -> String {
+    let mut quoted = String::with_capacity(s.len() + 2);
+    quoted.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            c if c.is_control() => {
+                let _ = write!(quoted, "\\u{:04x}", c as u32);
+            }
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+/// Returns the given string as a CSV field, quoted if it contains a separator, quote or line break.
+pub fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+/// Prints the given error about the command line along with the usage of the binary and returns the exit code for a usage error.
+pub fn usage_error(name: &str, usage: &str, error: &str) -> ExitCode {
+    eprintln!("{name}: {error}\n\n{usage}");
+    ExitCode::from(2)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// Universe lists should accept single universes and ranges, sorted without duplicates.
+    #[test]
+    fn test_parse_universes() {
+        assert_eq!(parse_universes("3,1-2,2").unwrap(), vec![1, 2, 3]);
+        assert_eq!(parse_universes("63999").unwrap(), vec![63999]);
+        for invalid in ["0", "64000", "64214", "2-1", "a", "1-"] {
+            assert!(parse_universes(invalid).is_err(), "{invalid}");
+        }
+    }
+
+    /// Universes should be listed as ranges of consecutive universes.
+    #[test]
+    fn test_universe_ranges() {
+        assert_eq!(universe_ranges(&[]), "");
+        assert_eq!(universe_ranges(&[5]), "5");
+        assert_eq!(universe_ranges(&[1, 2, 3, 4, 7, 9, 10]), "1-4,7,9-10");
+    }
+
+    /// Strings should be escaped for JSON and quoted for CSV only when needed.
+    #[test]
+    fn test_escape_strings() {
+        assert_eq!(json_string("a\"b\\c\n\u{1}"), "\"a\\\"b\\\\c\\n\\u0001\"");
+        assert_eq!(csv_field("plain"), "plain");
+        assert_eq!(csv_field("a,\"b\""), "\"a,\"\"b\"\"\"");
+    }
+}
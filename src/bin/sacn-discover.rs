@@ -0,0 +1,255 @@
+// Copyright 2020 sacn Developers
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! sacn-discover, a command line tool which lists the sACN sources on the network.
+//!
+//! Listens for universe discovery packets as per ANSI E1.31-2018 Section 4.3 for long enough that every source should have sent
+//! one, then prints the name, CID and universes of each source discovered.
+
+mod cli;
+
+use cli::{Args, csv_field, json_string, universe_ranges, usage_error};
+
+use sacn::error::errors::*;
+use sacn::interface::NetworkInterface;
+use sacn::packet::*;
+use sacn::receive::{DiscoveredSacnSource, SacnReceiver};
+
+use std::io;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::process::ExitCode;
+use std::time::{Duration, Instant};
+
+const USAGE: &str = "\
+Usage: sacn-discover [OPTIONS]
+
+Listens for universe discovery packets and lists the sources found and the universes they are sending.
+
+Options:
+  -t, --time <SECONDS>    How long to listen for [default: 12]
+  -i, --interface <IF>    Interface to listen on, by name, #index or address
+  -6, --ipv6              Listen over IPv6
+  -f, --format <FORMAT>   Output format, one of human, json or csv [default: human]
+  -h, --help              Print this help";
+
+/// How long to listen for by default, long enough to receive a discovery packet from every source as they are sent every
+/// E131_UNIVERSE_DISCOVERY_INTERVAL.
+const DEFAULT_LISTEN_TIME: Duration =
+    Duration::from_secs(E131_UNIVERSE_DISCOVERY_INTERVAL.as_secs() + 2);
+
+/// How the sources are printed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Format {
+    Human,
+    Json,
+    Csv,
+}
+
+/// The options given on the command line.
+#[derive(Debug)]
+struct Options {
+    time: Duration,
+    interface: Option<NetworkInterface>,
+    ipv6: bool,
+    format: Format,
+}
+
+/// Parses the command line, returning None if help was asked for.
+fn parse_options(mut args: Args) -> std::result::Result<Option<Options>, String> {
+    let mut options = Options {
+        time: DEFAULT_LISTEN_TIME,
+        interface: None,
+        ipv6: false,
+        format: Format::Human,
+    };
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-h" | "--help" => return Ok(None),
+            "-t" | "--time" => {
+                let secs: f64 = args.parse(&arg)?;
+                if !(secs > 0.0 && secs.is_finite()) {
+                    return Err(format!("Invalid listen time {secs}"));
+                }
+                options.time = Duration::from_secs_f64(secs);
+            }
+            "-i" | "--interface" => {
+                options.interface = Some(
+                    args.value(&arg)?
+                        .parse()
+                        .map_err(|e: SacnError| e.to_string())?,
+                )
+            }
+            "-6" | "--ipv6" => options.ipv6 = true,
+            "-f" | "--format" => {
+                options.format = match args.value(&arg)?.as_str() {
+                    "human" => Format::Human,
+                    "json" => Format::Json,
+                    "csv" => Format::Csv,
+                    f => return Err(format!("Unknown format {f}")),
+                }
+            }
+            a => return Err(format!("Unknown argument {a}")),
+        }
+    }
+    Ok(Some(options))
+}
+
+/// Listens for universe discovery packets for the given time and returns the sources discovered, sorted by name then CID.
+fn discover(options: &Options) -> Result<Vec<DiscoveredSacnSource>> {
+    let ip = if options.ipv6 {
+        IpAddr::V6(Ipv6Addr::UNSPECIFIED)
+    } else {
+        IpAddr::V4(Ipv4Addr::UNSPECIFIED)
+    };
+    let mut rcv = SacnReceiver::with_ip(SocketAddr::new(ip, ACN_SDT_MULTICAST_PORT), None)?;
+    if let Some(interface) = &options.interface {
+        rcv.set_interfaces(std::slice::from_ref(interface))?;
+    }
+
+    let deadline = Instant::now() + options.time;
+    loop {
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        if remaining.is_zero() {
+            break;
+        }
+        match rcv.recv_event(Some(remaining)) {
+            Ok(_) => {}
+            Err(SacnError::Io(e))
+                if matches!(
+                    e.kind(),
+                    io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut
+                ) => {}
+            // Malformed packets from other devices shouldn't stop discovery.
+            Err(SacnError::SacnParsePackError(_)) => {}
+            Err(e) => return Err(e),
+        }
+    }
+
+    let mut sources = rcv.get_discovered_sources();
+    sources.sort_by(|a, b| (&a.name, a.cid).cmp(&(&b.name, b.cid)));
+    Ok(sources)
+}
+
+/// Formats the discovered sources, one line per source with a header for CSV.
+fn format_sources(sources: &[DiscoveredSacnSource], format: Format) -> Vec<String> {
+    let mut lines = Vec::new();
+    match format {
+        Format::Human => {
+            if sources.is_empty() {
+                lines.push("No sources discovered".to_string());
+            }
+            for source in sources {
+                let universes = source.get_all_universes();
+                lines.push(format!(
+                    "{} ({}): {} universe{} {}",
+                    source.name,
+                    source.cid,
+                    universes.len(),
+                    if universes.len() == 1 { "" } else { "s" },
+                    universe_ranges(&universes)
+                ));
+            }
+        }
+        Format::Json => {
+            for source in sources {
+                let universes = source
+                    .get_all_universes()
+                    .iter()
+                    .map(u16::to_string)
+                    .collect::<Vec<_>>()
+                    .join(",");
+                lines.push(format!(
+                    "{{\"name\":{},\"cid\":\"{}\",\"universes\":[{universes}]}}",
+                    json_string(&source.name),
+                    source.cid
+                ));
+            }
+        }
+        Format::Csv => {
+            lines.push("name,cid,universes".to_string());
+            for source in sources {
+                lines.push(format!(
+                    "{},{},{}",
+                    csv_field(&source.name),
+                    source.cid,
+                    csv_field(&universe_ranges(&source.get_all_universes()))
+                ));
+            }
+        }
+    }
+    lines
+}
+
+fn main() -> ExitCode {
+    let options = match parse_options(Args::from_env()) {
+        Ok(Some(options)) => options,
+        Ok(None) => {
+            println!("{USAGE}");
+            return ExitCode::SUCCESS;
+        }
+        Err(e) => return usage_error("sacn-discover", USAGE, &e),
+    };
+
+    match discover(&options) {
+        Ok(sources) => {
+            for line in format_sources(&sources, options.format) {
+                println!("{line}");
+            }
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("sacn-discover: {e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn args(args: &[&str]) -> Args {
+        Args::new(args.iter().map(|a| a.to_string()).collect())
+    }
+
+    /// Options should be parsed and validated.
+    #[test]
+    fn test_parse_options() {
+        let options = parse_options(args(&[])).unwrap().unwrap();
+        assert_eq!(options.time, DEFAULT_LISTEN_TIME);
+        assert!(options.time > E131_UNIVERSE_DISCOVERY_INTERVAL);
+        assert_eq!(options.format, Format::Human);
+
+        let options = parse_options(args(&["-t", "0.5", "-6", "-f", "csv", "-i", "lo"]))
+            .unwrap()
+            .unwrap();
+        assert_eq!(options.time, Duration::from_millis(500));
+        assert!(options.ipv6);
+        assert_eq!(options.format, Format::Csv);
+        assert_eq!(
+            options.interface,
+            Some(NetworkInterface::Name("lo".to_string()))
+        );
+
+        assert!(parse_options(args(&["-h"])).unwrap().is_none());
+        for invalid in [&["-t", "0"][..], &["-t", "x"], &["-f", "xml"], &["1"]] {
+            assert!(parse_options(args(invalid)).is_err(), "{invalid:?}");
+        }
+    }
+
+    /// With no sources the human output should say so and the other formats should be empty apart from the CSV header.
+    #[test]
+    fn test_format_no_sources() {
+        assert_eq!(
+            format_sources(&[], Format::Human),
+            vec!["No sources discovered"]
+        );
+        assert!(format_sources(&[], Format::Json).is_empty());
+        assert_eq!(format_sources(&[], Format::Csv), vec!["name,cid,universes"]);
+    }
+}
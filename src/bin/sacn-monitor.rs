@@ -0,0 +1,938 @@
+// Copyright 2020 sacn Developers
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! sacn-monitor, a terminal user interface which monitors universes and the sources sending them.
+//!
+//! Shows a grid of the live levels of the 512 channels of the selected universe with the channels that just changed highlighted,
+//! the winning source of the universe, or that the sources are merged slot by slot when any of them sends per-address priority
+//! (start code 0xDD), and a list of every source with its priority, CID, address and packet rate. The arrow keys
+//! move between universes and scroll the grid. Only a terminal is needed so it can be run over SSH.
+//!
+//! Requires the `tui` feature.
+
+mod cli;
+
+use cli::{Args, parse_universes, universe_ranges, usage_error};
+
+use sacn::error::errors::*;
+use sacn::interface::NetworkInterface;
+use sacn::merge::{MergeSource, Merger};
+use sacn::packet::*;
+use sacn::receive::{DEFAULT_MERGER, DMXData, ReceiveEvent, SacnReceiver};
+
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use crossterm::style::{
+    Attribute, Color, Print, ResetColor, SetAttribute, SetBackgroundColor, SetForegroundColor,
+};
+use crossterm::{cursor, execute, queue, terminal};
+use uuid::Uuid;
+
+use std::collections::{BTreeMap, HashMap};
+use std::io::{self, Write};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::process::ExitCode;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+const USAGE: &str = "\
+Usage: sacn-monitor [OPTIONS] <UNIVERSES>
+
+Monitors the levels of the given universes, e.g. 1,5-8, and the sources sending them.
+
+Keys:
+  Left/Right, Tab     Previous / next universe
+  Up/Down             Scroll the channel grid
+  q, Esc              Quit
+
+Options:
+  -i, --interface <IF>  Interface to listen on, by name, #index or address
+  -6, --ipv6            Listen over IPv6
+  -h, --help            Print this help";
+
+/// The number of DMX channels in a universe, not including the start code.
+const CHANNELS: usize = UNIVERSE_CHANNEL_CAPACITY - 1;
+
+/// How long a channel stays highlighted after its level changes.
+const CHANGE_HIGHLIGHT: Duration = Duration::from_millis(1000);
+
+/// How often the packet rate of each source is recalculated.
+const RATE_INTERVAL: Duration = Duration::from_secs(1);
+
+/// The time between redraws of the screen.
+const FRAME_INTERVAL: Duration = Duration::from_millis(50);
+
+/// The longest a receive waits so that key presses are handled promptly.
+const MAX_RECV_WAIT: Duration = Duration::from_millis(20);
+
+/// The width of the channel number at the start of each row of the grid.
+const ROW_LABEL_WIDTH: usize = 5;
+
+/// The width of each channel in the grid.
+const CELL_WIDTH: usize = 4;
+
+/// The options given on the command line.
+#[derive(Debug)]
+struct Options {
+    universes: Vec<u16>,
+    interface: Option<NetworkInterface>,
+    ipv6: bool,
+}
+
+/// Parses the command line, returning None if help was asked for.
+fn parse_options(mut args: Args) -> std::result::Result<Option<Options>, String> {
+    let mut options = Options {
+        universes: Vec::new(),
+        interface: None,
+        ipv6: false,
+    };
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-h" | "--help" => return Ok(None),
+            "-i" | "--interface" => {
+                options.interface = Some(
+                    args.value(&arg)?
+                        .parse()
+                        .map_err(|e: SacnError| e.to_string())?,
+                )
+            }
+            "-6" | "--ipv6" => options.ipv6 = true,
+            a if a.starts_with('-') => return Err(format!("Unknown option {a}")),
+            list => options.universes.extend(parse_universes(list)?),
+        }
+    }
+
+    options.universes.sort_unstable();
+    options.universes.dedup();
+    if options.universes.is_empty() {
+        return Err("At least one universe must be given".to_string());
+    }
+    Ok(Some(options))
+}
+
+/// The details of the latest data of a source for a universe, seen when the receiver arbitrates between the sources of the universe.
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Observation {
+    cid: Uuid,
+    universe: u16,
+    name: Option<String>,
+    addr: Option<SocketAddr>,
+    priority: u8,
+    per_address_priority: bool,
+    recv_timestamp: Instant,
+}
+
+impl Observation {
+    /// Returns the observation of the given source, or None if its data didn't come from a source e.g. it was released by a data loss
+    /// policy.
+    fn from_source(source: &MergeSource) -> Option<Observation> {
+        let data = source.data;
+        Some(Observation {
+            cid: data.src_cid?,
+            universe: data.universe,
+            name: data.src_name.clone(),
+            addr: data.src_addr,
+            priority: data.priority,
+            per_address_priority: source.per_address_priority.is_some(),
+            recv_timestamp: data.recv_timestamp,
+        })
+    }
+}
+
+/// Returns a merger which wraps the default merger of the receiver, so unsynchronised data is arbitrated as it would be without the
+/// monitor, and also records every source taking part so that sources which aren't winning a universe are still seen.
+///
+/// As a merger set on a receiver is also used for data waiting for synchronisation, same priority synchronised data is HTP merged rather
+/// than the newest being kept as the receiver does by default.
+fn observing_merger(observations: Arc<Mutex<Vec<Observation>>>) -> impl Merger {
+    let mut merger = DEFAULT_MERGER;
+    move |sources: &[MergeSource]| {
+        if let Ok(mut observations) = observations.lock() {
+            observations.extend(sources.iter().filter_map(Observation::from_source));
+        }
+        merger.merge(sources)
+    }
+}
+
+/// What is known about a source sending a universe.
+#[derive(Clone, Debug)]
+struct SourceState {
+    name: Option<String>,
+    addr: Option<SocketAddr>,
+    priority: u8,
+    per_address_priority: bool,
+    last_seen: Instant,
+    packets: u32,
+    rate: f64,
+}
+
+/// The levels of a universe and when each channel last changed.
+#[derive(Clone, Debug)]
+struct UniverseView {
+    levels: [u8; CHANNELS],
+    changed: [Option<Instant>; CHANNELS],
+    last_update: Option<Instant>,
+}
+
+impl UniverseView {
+    fn new() -> UniverseView {
+        UniverseView {
+            levels: [0; CHANNELS],
+            changed: [None; CHANNELS],
+            last_update: None,
+        }
+    }
+}
+
+/// The source or sources whose data is used for a universe.
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Winner {
+    /// No source is sending the universe.
+    None,
+
+    /// A single source has the highest priority.
+    Source(Uuid),
+
+    /// Several sources share the highest priority and so their data is merged.
+    Merged { count: usize, priority: u8 },
+
+    /// A source is sending per-address priority (start code 0xDD) data so the receiver arbitrates each slot separately and there is no
+    /// single winner, every source sending the universe may contribute.
+    PerAddress { count: usize },
+}
+
+/// How a piece of text is drawn.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Style {
+    Normal,
+    Dim,
+    Bold,
+    Changed,
+    Selected,
+}
+
+/// A piece of text drawn in a single style.
+type Span = (String, Style);
+
+/// The state of the monitor, updated from the events of the receiver and the keys pressed.
+#[derive(Debug)]
+struct Monitor {
+    universes: Vec<u16>,
+    selected: usize,
+    scroll: usize,
+    views: HashMap<u16, UniverseView>,
+    sources: BTreeMap<(Uuid, u16), SourceState>,
+    rate_sampled: Instant,
+}
+
+impl Monitor {
+    fn new(universes: Vec<u16>, now: Instant) -> Monitor {
+        let views = universes
+            .iter()
+            .map(|u| (*u, UniverseView::new()))
+            .collect();
+        Monitor {
+            universes,
+            selected: 0,
+            scroll: 0,
+            views,
+            sources: BTreeMap::new(),
+            rate_sampled: now,
+        }
+    }
+
+    /// Returns the universe currently shown.
+    fn selected_universe(&self) -> u16 {
+        self.universes[self.selected]
+    }
+
+    /// Updates the source the observation is of, counting a packet if its data is newer than that last seen.
+    fn observe(&mut self, observation: &Observation) {
+        match self
+            .sources
+            .get_mut(&(observation.cid, observation.universe))
+        {
+            Some(source) => {
+                if observation.recv_timestamp > source.last_seen {
+                    source.packets += 1;
+                    source.last_seen = observation.recv_timestamp;
+                }
+                source.priority = observation.priority;
+                source.per_address_priority = observation.per_address_priority;
+                source.name.clone_from(&observation.name);
+                source.addr = observation.addr;
+            }
+            None => {
+                self.sources.insert(
+                    (observation.cid, observation.universe),
+                    SourceState {
+                        name: observation.name.clone(),
+                        addr: observation.addr,
+                        priority: observation.priority,
+                        per_address_priority: observation.per_address_priority,
+                        last_seen: observation.recv_timestamp,
+                        packets: 1,
+                        rate: 0.0,
+                    },
+                );
+            }
+        }
+    }
+
+    /// Handles an event from the receiver.
+    fn handle_event(&mut self, event: &ReceiveEvent, now: Instant) {
+        match event {
+            ReceiveEvent::Data(data) => {
+                for d in data {
+                    self.apply(d, now);
+                }
+            }
+            ReceiveEvent::SourceLost(cid) => self.sources.retain(|(c, _), _| c != cid),
+            ReceiveEvent::UniverseTerminated(cid, universe)
+            | ReceiveEvent::UniverseTimedOut(cid, universe) => {
+                self.sources.remove(&(*cid, *universe));
+            }
+            ReceiveEvent::SourceDiscovered(_) | ReceiveEvent::SequenceError(..) => {}
+        }
+    }
+
+    /// Updates the levels of a universe with the given data, marking the channels which changed.
+    fn apply(&mut self, data: &DMXData, now: Instant) {
+        if data.values.first() != Some(&E131_NULL_START_CODE) {
+            return;
+        }
+        let Some(view) = self.views.get_mut(&data.universe) else {
+            return;
+        };
+        for channel in 0..CHANNELS {
+            let level = data.values.get(channel + 1).copied().unwrap_or(0);
+            if view.last_update.is_some() && view.levels[channel] != level {
+                view.changed[channel] = Some(now);
+            }
+            view.levels[channel] = level;
+        }
+        view.last_update = Some(now);
+    }
+
+    /// Recalculates the packet rate of each source if RATE_INTERVAL has passed since it was last calculated.
+    fn update_rates(&mut self, now: Instant) {
+        let elapsed = now.saturating_duration_since(self.rate_sampled);
+        if elapsed < RATE_INTERVAL {
+            return;
+        }
+        for source in self.sources.values_mut() {
+            source.rate = f64::from(source.packets) / elapsed.as_secs_f64();
+            source.packets = 0;
+        }
+        self.rate_sampled = now;
+    }
+
+    /// Returns the source or sources whose data is used for the given universe, those with the highest priority unless per-address priority
+    /// is in use in which case all the sources of the universe are arbitrated slot by slot, as the receiver does by default.
+    fn winner(&self, universe: u16) -> Winner {
+        let sources: Vec<(&Uuid, &SourceState)> = self
+            .sources
+            .iter()
+            .filter(|((_, u), _)| *u == universe)
+            .map(|((cid, _), s)| (cid, s))
+            .collect();
+        if sources.iter().any(|(_, s)| s.per_address_priority) {
+            return Winner::PerAddress {
+                count: sources.len(),
+            };
+        }
+        let priorities: Vec<(Uuid, u8)> =
+            sources.iter().map(|(cid, s)| (**cid, s.priority)).collect();
+        let Some(priority) = priorities.iter().map(|(_, p)| *p).max() else {
+            return Winner::None;
+        };
+        let winners: Vec<Uuid> = priorities
+            .iter()
+            .filter(|(_, p)| *p == priority)
+            .map(|(cid, _)| *cid)
+            .collect();
+        match winners[..] {
+            [cid] => Winner::Source(cid),
+            _ => Winner::Merged {
+                count: winners.len(),
+                priority,
+            },
+        }
+    }
+
+    /// Handles a key press, returning true if the monitor should quit.
+    fn handle_key(&mut self, key: KeyEvent) -> bool {
+        let count = self.universes.len();
+        match key.code {
+            KeyCode::Char('q') | KeyCode::Esc => return true,
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => return true,
+            KeyCode::Right | KeyCode::Tab => {
+                self.selected = (self.selected + 1) % count;
+                self.scroll = 0;
+            }
+            KeyCode::Left | KeyCode::BackTab => {
+                self.selected = (self.selected + count - 1) % count;
+                self.scroll = 0;
+            }
+            KeyCode::Down => self.scroll += 1,
+            KeyCode::Up => self.scroll = self.scroll.saturating_sub(1),
+            _ => {}
+        }
+        false
+    }
+
+    /// Returns the lines to draw for a terminal of the given size.
+    ///
+    /// The grid is given the space left over after the header, the source list and the help line and scrolls if it doesn't fit. The scroll
+    /// position is limited to the rows of the grid as the terminal may be resized.
+    fn render(&mut self, width: usize, height: usize, now: Instant) -> Vec<Vec<Span>> {
+        let universe = self.selected_universe();
+        let mut lines = vec![self.render_title(), self.render_tabs(), Vec::new()];
+
+        let columns = [32, 16, 8, 4, 2, 1]
+            .into_iter()
+            .find(|c| ROW_LABEL_WIDTH + c * CELL_WIDTH <= width)
+            .unwrap_or(1);
+        let rows = CHANNELS.div_ceil(columns);
+        let source_lines = self.render_sources(universe);
+        let source_height = source_lines.len().min(height / 3).max(2);
+        let grid_height = height
+            .saturating_sub(lines.len() + source_height + 2)
+            .clamp(1, rows);
+        self.scroll = self.scroll.min(rows - grid_height);
+
+        let view = &self.views[&universe];
+        for row in self.scroll..self.scroll + grid_height {
+            let mut line = vec![(format!("{:>4} ", row * columns + 1), Style::Dim)];
+            for channel in row * columns..((row + 1) * columns).min(CHANNELS) {
+                let level = view.levels[channel];
+                let style = if view.changed[channel]
+                    .is_some_and(|t| now.saturating_duration_since(t) < CHANGE_HIGHLIGHT)
+                {
+                    Style::Changed
+                } else if level == 0 {
+                    Style::Dim
+                } else {
+                    Style::Normal
+                };
+                line.push((format!("{level:>4}"), style));
+            }
+            lines.push(line);
+        }
+
+        lines.push(Vec::new());
+        lines.extend(source_lines.into_iter().take(source_height));
+        while lines.len() < height.saturating_sub(1) {
+            lines.push(Vec::new());
+        }
+        lines.push(vec![(
+            "Left/Right universe  Up/Down scroll  q quit".to_string(),
+            Style::Dim,
+        )]);
+        lines
+    }
+
+    /// Returns the title line, naming the selected universe and its winning source.
+    fn render_title(&self) -> Vec<Span> {
+        let universe = self.selected_universe();
+        let winner = match self.winner(universe) {
+            Winner::None => "no sources".to_string(),
+            Winner::Source(cid) => {
+                let source = &self.sources[&(cid, universe)];
+                format!(
+                    "winning source {} priority {}",
+                    source.name.as_deref().unwrap_or("?"),
+                    source.priority
+                )
+            }
+            Winner::Merged { count, priority } => {
+                format!("{count} sources merged at priority {priority}")
+            }
+            Winner::PerAddress { count } => {
+                format!("{count} sources merged by per-address priority")
+            }
+        };
+        vec![(
+            format!(
+                "sacn-monitor  universe {universe} ({}/{})  {winner}",
+                self.selected + 1,
+                self.universes.len()
+            ),
+            Style::Bold,
+        )]
+    }
+
+    /// Returns the line listing the universes, with the selected universe highlighted and universes with no sources dimmed.
+    fn render_tabs(&self) -> Vec<Span> {
+        self.universes
+            .iter()
+            .enumerate()
+            .map(|(i, u)| {
+                let style = if i == self.selected {
+                    Style::Selected
+                } else if self.winner(*u) == Winner::None {
+                    Style::Dim
+                } else {
+                    Style::Normal
+                };
+                (format!(" {u} "), style)
+            })
+            .collect()
+    }
+
+    /// Returns the lines of the source list, one per source with the source winning the given universe marked.
+    ///
+    /// The priority is that of the source on the given universe, or its highest if it isn't sending the universe, and the rate is the
+    /// total packets per second across all the universes it is sending.
+    fn render_sources(&self, universe: u16) -> Vec<Vec<Span>> {
+        let mut lines = vec![vec![(
+            format!(
+                "  {:<20} {:<36} {:<22} {:>4} {:>7}  Universes",
+                "Source", "CID", "Address", "Prio", "Pkt/s"
+            ),
+            Style::Bold,
+        )]];
+
+        let mut by_cid: BTreeMap<Uuid, Vec<(u16, &SourceState)>> = BTreeMap::new();
+        for ((cid, u), source) in &self.sources {
+            by_cid.entry(*cid).or_default().push((*u, source));
+        }
+        let winner = self.winner(universe);
+        for (cid, universes) in by_cid {
+            let sending = universes.iter().find(|(u, _)| *u == universe);
+            let (_, latest) = universes
+                .iter()
+                .max_by_key(|(_, s)| s.last_seen)
+                .expect("Each source has at least one universe");
+            let priority = sending
+                .map(|(_, s)| s.priority)
+                .or_else(|| universes.iter().map(|(_, s)| s.priority).max())
+                .unwrap_or_default();
+            let marker = match winner {
+                Winner::Source(c) if c == cid => "*",
+                Winner::Merged { priority: p, .. }
+                    if sending.is_some_and(|(_, s)| s.priority == p) =>
+                {
+                    "+"
+                }
+                Winner::PerAddress { .. } if sending.is_some() => "+",
+                _ => " ",
+            };
+            let mut name: String = latest
+                .name
+                .as_deref()
+                .unwrap_or("?")
+                .chars()
+                .take(20)
+                .collect();
+            if name.is_empty() {
+                name.push('?');
+            }
+            let addr = latest
+                .addr
+                .map_or_else(|| "?".to_string(), |a| a.ip().to_string());
+            let rate: f64 = universes.iter().map(|(_, s)| s.rate).sum();
+            let numbers: Vec<u16> = universes.iter().map(|(u, _)| *u).collect();
+            lines.push(vec![(
+                format!(
+                    "{marker} {name:<20} {cid:<36} {addr:<22} {priority:>4} {rate:>7.1}  {}",
+                    universe_ranges(&numbers)
+                ),
+                if sending.is_some() {
+                    Style::Normal
+                } else {
+                    Style::Dim
+                },
+            )]);
+        }
+        if lines.len() == 1 {
+            lines.push(vec![("  No sources".to_string(), Style::Dim)]);
+        }
+        lines
+    }
+}
+
+/// Puts the terminal into raw mode on the alternate screen for as long as it exists, restoring it when dropped even on a panic.
+struct TerminalGuard;
+
+impl TerminalGuard {
+    fn enter() -> io::Result<TerminalGuard> {
+        terminal::enable_raw_mode()?;
+        let guard = TerminalGuard;
+        execute!(io::stdout(), terminal::EnterAlternateScreen, cursor::Hide)?;
+        Ok(guard)
+    }
+}
+
+impl Drop for TerminalGuard {
+    fn drop(&mut self) {
+        let _ = execute!(io::stdout(), cursor::Show, terminal::LeaveAlternateScreen);
+        let _ = terminal::disable_raw_mode();
+    }
+}
+
+/// Draws the given lines, cutting them off at the given width.
+fn draw(out: &mut impl Write, lines: &[Vec<Span>], width: usize) -> io::Result<()> {
+    for (y, line) in lines.iter().enumerate() {
+        queue!(out, cursor::MoveTo(0, y as u16))?;
+        let mut remaining = width;
+        for (text, style) in line {
+            let text: String = text.chars().take(remaining).collect();
+            remaining -= text.chars().count();
+            match style {
+                Style::Normal => {}
+                Style::Dim => queue!(out, SetForegroundColor(Color::DarkGrey))?,
+                Style::Bold => queue!(out, SetAttribute(Attribute::Bold))?,
+                Style::Changed => queue!(
+                    out,
+                    SetForegroundColor(Color::Black),
+                    SetBackgroundColor(Color::Yellow)
+                )?,
+                Style::Selected => queue!(out, SetAttribute(Attribute::Reverse))?,
+            }
+            queue!(out, Print(text), SetAttribute(Attribute::Reset), ResetColor)?;
+        }
+        queue!(out, terminal::Clear(terminal::ClearType::UntilNewLine))?;
+    }
+    queue!(out, terminal::Clear(terminal::ClearType::FromCursorDown))?;
+    out.flush()
+}
+
+/// Receives from the universes and draws the monitor until quit.
+fn run(options: &Options) -> Result<()> {
+    let ip = if options.ipv6 {
+        IpAddr::V6(Ipv6Addr::UNSPECIFIED)
+    } else {
+        IpAddr::V4(Ipv4Addr::UNSPECIFIED)
+    };
+    let mut rcv = SacnReceiver::with_ip(SocketAddr::new(ip, ACN_SDT_MULTICAST_PORT), None)?;
+    if let Some(interface) = &options.interface {
+        rcv.set_interfaces(std::slice::from_ref(interface))?;
+    }
+    rcv.listen_universes(&options.universes)?;
+    let observations = Arc::new(Mutex::new(Vec::new()));
+    rcv.set_merger(observing_merger(observations.clone()));
+
+    let _guard = TerminalGuard::enter()?;
+    let mut stdout = io::stdout();
+    let mut monitor = Monitor::new(options.universes.clone(), Instant::now());
+    let mut next_frame = Instant::now();
+    loop {
+        while event::poll(Duration::ZERO)? {
+            if let Event::Key(key) = event::read()?
+                && key.kind != KeyEventKind::Release
+                && monitor.handle_key(key)
+            {
+                return Ok(());
+            }
+        }
+
+        match rcv.recv_event(Some(MAX_RECV_WAIT)) {
+            Ok(event) => monitor.handle_event(&event, Instant::now()),
+            Err(SacnError::Io(e))
+                if matches!(
+                    e.kind(),
+                    io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut
+                ) => {}
+            // Malformed or out of sequence packets from other devices shouldn't stop the monitor.
+            Err(SacnError::SacnParsePackError(_)) | Err(SacnError::OutOfSequence(..)) => {}
+            Err(e) => return Err(e),
+        }
+        if let Ok(mut observations) = observations.lock() {
+            for observation in observations.drain(..) {
+                monitor.observe(&observation);
+            }
+        }
+
+        let now = Instant::now();
+        monitor.update_rates(now);
+        if now >= next_frame {
+            let (width, height) = terminal::size()?;
+            let lines = monitor.render(width.into(), height.into(), now);
+            draw(&mut stdout, &lines, width.into())?;
+            next_frame = now + FRAME_INTERVAL;
+        }
+    }
+}
+
+fn main() -> ExitCode {
+    let options = match parse_options(Args::from_env()) {
+        Ok(Some(options)) => options,
+        Ok(None) => {
+            println!("{USAGE}");
+            return ExitCode::SUCCESS;
+        }
+        Err(e) => return usage_error("sacn-monitor", USAGE, &e),
+    };
+
+    match run(&options) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("sacn-monitor: {e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const CID_A: Uuid = Uuid::from_bytes([1; 16]);
+    const CID_B: Uuid = Uuid::from_bytes([2; 16]);
+
+    fn data(universe: u16, levels: &[u8], now: Instant) -> DMXData {
+        let mut values = vec![E131_NULL_START_CODE];
+        values.extend_from_slice(levels);
+        DMXData {
+            universe,
+            values,
+            sync_uni: 0,
+            priority: 100,
+            src_cid: Some(CID_A),
+            preview: false,
+            src_addr: Some("192.168.1.10:5568".parse().unwrap()),
+            src_name: Some("Desk".to_string()),
+            recv_interface: None,
+            recv_timestamp: now,
+        }
+    }
+
+    fn observation(cid: Uuid, universe: u16, priority: u8, at: Instant) -> Observation {
+        Observation {
+            cid,
+            universe,
+            name: Some(format!("Source {}", cid.as_bytes()[0])),
+            addr: Some(SocketAddr::new(
+                IpAddr::V4(Ipv4Addr::new(10, 0, 0, cid.as_bytes()[0])),
+                5568,
+            )),
+            priority,
+            per_address_priority: false,
+            recv_timestamp: at,
+        }
+    }
+
+    fn text(line: &[Span]) -> String {
+        line.iter().map(|(t, _)| t.as_str()).collect()
+    }
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    /// Levels should be updated from NULL START code data, highlighting the channels which changed for CHANGE_HIGHLIGHT.
+    #[test]
+    fn test_levels_and_changes() {
+        let start = Instant::now();
+        let mut monitor = Monitor::new(vec![1, 2], start);
+        monitor.apply(&data(1, &[10, 20], start), start);
+        assert_eq!(monitor.views[&1].levels[..3], [10, 20, 0]);
+        assert!(monitor.views[&1].changed.iter().all(Option::is_none));
+
+        let later = start + Duration::from_millis(100);
+        monitor.apply(&data(1, &[10, 30, 40], later), later);
+        assert_eq!(
+            monitor.views[&1].changed[..3],
+            [None, Some(later), Some(later)]
+        );
+
+        // Other start codes and universes not being monitored are ignored.
+        let mut priorities = data(1, &[200; 512], later);
+        priorities.values[0] = 0xdd;
+        monitor.apply(&priorities, later);
+        monitor.apply(&data(3, &[1], later), later);
+        assert_eq!(monitor.views[&1].levels[..3], [10, 30, 40]);
+        assert!(!monitor.views.contains_key(&3));
+
+        let lines = monitor.render(80, 50, later);
+        let row = lines.iter().find(|l| text(l).starts_with("   1 ")).unwrap();
+        assert_eq!(text(row), format!("   1   10  30  40{}", "   0".repeat(13)));
+        assert_eq!(
+            &row[1..4],
+            &[
+                ("  10".to_string(), Style::Normal),
+                ("  30".to_string(), Style::Changed),
+                ("  40".to_string(), Style::Changed)
+            ]
+        );
+        assert_eq!(row[4].1, Style::Dim);
+
+        let lines = monitor.render(80, 50, later + CHANGE_HIGHLIGHT);
+        let row = lines.iter().find(|l| text(l).starts_with("   1 ")).unwrap();
+        assert_eq!(row[2].1, Style::Normal);
+    }
+
+    /// The source with the highest priority should win a universe, with sources sharing the highest priority merged.
+    #[test]
+    fn test_winner() {
+        let start = Instant::now();
+        let mut monitor = Monitor::new(vec![1, 2], start);
+        assert_eq!(monitor.winner(1), Winner::None);
+
+        monitor.observe(&observation(CID_A, 1, 100, start));
+        monitor.observe(&observation(CID_B, 1, 150, start));
+        monitor.observe(&observation(CID_B, 2, 100, start));
+        assert_eq!(monitor.winner(1), Winner::Source(CID_B));
+        assert!(text(&monitor.render_title()).ends_with("winning source Source 2 priority 150"));
+
+        monitor.observe(&observation(CID_A, 1, 150, start));
+        assert_eq!(
+            monitor.winner(1),
+            Winner::Merged {
+                count: 2,
+                priority: 150
+            }
+        );
+
+        monitor.handle_event(&ReceiveEvent::UniverseTimedOut(CID_B, 1), start);
+        assert_eq!(monitor.winner(1), Winner::Source(CID_A));
+        monitor.handle_event(&ReceiveEvent::SourceLost(CID_B), start);
+        assert_eq!(monitor.winner(2), Winner::None);
+
+        // Per-address priority makes the receiver arbitrate every slot so no single source wins, even one with a higher priority.
+        let mut per_address = observation(CID_A, 2, 50, start);
+        per_address.per_address_priority = true;
+        monitor.observe(&observation(CID_B, 2, 200, start));
+        monitor.observe(&per_address);
+        assert_eq!(monitor.winner(2), Winner::PerAddress { count: 2 });
+        monitor.handle_key(key(KeyCode::Right));
+        assert!(
+            text(&monitor.render_title()).ends_with("2 sources merged by per-address priority")
+        );
+        assert!(
+            monitor.render_sources(2)[1..]
+                .iter()
+                .all(|l| text(l).starts_with('+'))
+        );
+    }
+
+    /// The observing merger should produce the same data as the default merger of the receiver while recording every source.
+    #[test]
+    fn test_observing_merger() {
+        let start = Instant::now();
+        let a = data(1, &[255, 0, 10], start);
+        let mut b = data(1, &[0, 255, 5], start);
+        b.src_cid = Some(CID_B);
+        let pap = [E131_PER_ADDRESS_PRIORITY_START_CODE, 100, 100, 100];
+        let sources = [
+            MergeSource {
+                data: &a,
+                per_address_priority: None,
+            },
+            MergeSource {
+                data: &b,
+                per_address_priority: Some(&pap),
+            },
+        ];
+
+        let observations = Arc::new(Mutex::new(Vec::new()));
+        let merged = observing_merger(observations.clone())
+            .merge(&sources)
+            .unwrap();
+        let mut default_merger = DEFAULT_MERGER;
+        assert_eq!(
+            merged.values,
+            default_merger.merge(&sources).unwrap().values
+        );
+        assert_eq!(merged.values, vec![0, 255, 255, 10]);
+
+        let observations = observations.lock().unwrap();
+        assert_eq!(observations.len(), 2);
+        assert_eq!(
+            (observations[0].cid, observations[0].per_address_priority),
+            (CID_A, false)
+        );
+        assert_eq!(
+            (observations[1].cid, observations[1].per_address_priority),
+            (CID_B, true)
+        );
+    }
+
+    /// Each new packet from a source should be counted towards its rate, the same data seen again in later merges should not.
+    #[test]
+    fn test_source_list() {
+        let start = Instant::now();
+        let mut monitor = Monitor::new(vec![1, 2], start);
+        for i in 0..10 {
+            let at = start + Duration::from_millis(i * 100);
+            monitor.observe(&observation(CID_A, 1, 120, at));
+            monitor.observe(&observation(CID_A, 1, 120, at));
+            monitor.observe(&observation(CID_A, 2, 90, at));
+        }
+        monitor.observe(&observation(CID_B, 2, 100, start));
+
+        monitor.update_rates(start + RATE_INTERVAL / 2);
+        assert_eq!(monitor.sources[&(CID_A, 1)].rate, 0.0);
+        monitor.update_rates(start + RATE_INTERVAL);
+        assert_eq!(monitor.sources[&(CID_A, 1)].rate, 10.0);
+
+        let lines = monitor.render_sources(1);
+        assert_eq!(lines.len(), 3);
+        assert!(text(&lines[0]).contains("Pkt/s"));
+        assert_eq!(
+            text(&lines[1]),
+            format!(
+                "* {:<20} {CID_A:<36} {:<22}  120    20.0  1-2",
+                "Source 1", "10.0.0.1"
+            )
+        );
+        assert_eq!(lines[1][0].1, Style::Normal);
+        assert!(text(&lines[2]).starts_with("  Source 2"));
+        assert_eq!(lines[2][0].1, Style::Dim);
+
+        let empty = Monitor::new(vec![1], start);
+        assert_eq!(text(&empty.render_sources(1)[1]), "  No sources");
+    }
+
+    /// The arrow keys should move between universes, wrapping around, and scroll the grid within its rows.
+    #[test]
+    fn test_navigation() {
+        let start = Instant::now();
+        let mut monitor = Monitor::new(vec![1, 5, 9], start);
+        assert!(!monitor.handle_key(key(KeyCode::Left)));
+        assert_eq!(monitor.selected_universe(), 9);
+        monitor.handle_key(key(KeyCode::Right));
+        monitor.handle_key(key(KeyCode::Tab));
+        assert_eq!(monitor.selected_universe(), 5);
+        assert_eq!(text(&monitor.render_tabs()), " 1  5  9 ");
+        assert_eq!(monitor.render_tabs()[1].1, Style::Selected);
+
+        // 16 channels per row at 80 columns gives 32 rows, 23 of which fit beside the header, source list and help.
+        for _ in 0..50 {
+            monitor.handle_key(key(KeyCode::Down));
+        }
+        let lines = monitor.render(80, 30, start);
+        assert_eq!(lines.len(), 30);
+        assert_eq!(monitor.scroll, 9);
+        assert!(lines.iter().any(|l| text(l).starts_with(" 497 ")));
+        monitor.handle_key(key(KeyCode::Up));
+        assert_eq!(monitor.scroll, 8);
+
+        // Switching universe returns to the top of the grid.
+        monitor.handle_key(key(KeyCode::BackTab));
+        assert_eq!((monitor.selected_universe(), monitor.scroll), (1, 0));
+
+        assert!(monitor.handle_key(key(KeyCode::Char('q'))));
+        assert!(monitor.handle_key(KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL)));
+    }
+
+    /// At least one universe must be given.
+    #[test]
+    fn test_parse_options() {
+        let args = |a: &[&str]| Args::new(a.iter().map(|s| s.to_string()).collect());
+        let options = parse_options(args(&["5,1-2", "-6", "-i", "#2"]))
+            .unwrap()
+            .unwrap();
+        assert_eq!(options.universes, vec![1, 2, 5]);
+        assert!(options.ipv6);
+        assert_eq!(options.interface, Some(NetworkInterface::Index(2)));
+        assert!(parse_options(args(&["-h"])).unwrap().is_none());
+        assert!(parse_options(args(&[])).is_err());
+        assert!(parse_options(args(&["1", "-x"])).is_err());
+    }
+}
@@ -0,0 +1,481 @@
+// Copyright 2020 sacn Developers
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! sacn-send, a command line tool which sends DMX levels to universes.
+//!
+//! Sends static levels, channel ranges, a chase or the frames of a file to the given universes at a fixed rate, optionally with a
+//! priority, synchronisation address, as preview data or by unicast. The streams are terminated when it finishes, including when it is
+//! stopped by SIGINT (Ctrl-C) or SIGTERM.
+
+mod cli;
+
+use cli::{Args, parse_universe, parse_universes, usage_error};
+
+use sacn::error::errors::*;
+use sacn::interface::NetworkInterface;
+use sacn::packet::*;
+use sacn::source::SacnSource;
+
+use std::fs;
+use std::net::{IpAddr, SocketAddr};
+use std::process::ExitCode;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread::sleep;
+use std::time::{Duration, Instant};
+
+const USAGE: &str = "\
+Usage: sacn-send [OPTIONS] <UNIVERSES>
+
+Sends DMX levels to each of the given universes, e.g. 1,5-8, until stopped with Ctrl-C, then terminates the streams.
+
+Levels (all channels are 0 unless set):
+  -l, --levels <LEVELS>       Static levels from channel 1, e.g. 255,128,0
+  -s, --set <CHANNELS=LEVEL>  Set a channel or range of channels to a level, e.g. 1-10=255, can be repeated
+  -c, --chase <WIDTH>         Chase a block of WIDTH channels along the universe, moving one channel per frame
+      --level <LEVEL>         Level of the chase [default: 255]
+  -F, --file <PATH>           Send each line of the file in turn as a frame of levels separated by commas or spaces, looping
+
+Sending:
+  -p, --priority <PRIORITY>   Priority to send with [default: 100]
+  -y, --sync <UNIVERSE>       Synchronisation address, a synchronisation packet is sent after each frame
+      --preview               Mark the data as preview data
+  -u, --unicast <ADDR>        Send by unicast to the given IP or IP:port instead of multicast
+  -i, --interface <IF>        Interface to send on, by name, #index or address
+  -6, --ipv6                  Send over IPv6
+  -r, --rate <FPS>            Frames sent per second [default: 44]
+  -n, --name <NAME>           Source name [default: sacn-send]
+  -d, --duration <SECONDS>    Stop after the given time
+  -1, --once                  Send a single frame then stop
+  -h, --help                  Print this help";
+
+/// The number of DMX channels in a universe, not including the start code.
+const CHANNELS: usize = UNIVERSE_CHANNEL_CAPACITY - 1;
+
+/// The highest rate frames can be sent at.
+const MAX_RATE: f64 = 1000.0;
+
+/// The longest a wait between frames sleeps before checking if sending should stop.
+const MAX_SLEEP: Duration = Duration::from_millis(100);
+
+/// Set when SIGINT or SIGTERM is received so that sending stops and the streams are terminated.
+static STOP: AtomicBool = AtomicBool::new(false);
+
+extern "C" fn request_stop(_signal: libc::c_int) {
+    STOP.store(true, Ordering::SeqCst);
+}
+
+/// Makes SIGINT and SIGTERM stop sending rather than exiting the process, so that the streams are terminated.
+fn handle_stop_signals() {
+    let handler = request_stop as extern "C" fn(libc::c_int) as libc::sighandler_t;
+    // SAFETY: request_stop only stores to an atomic which is async-signal-safe.
+    unsafe {
+        libc::signal(libc::SIGINT, handler);
+        libc::signal(libc::SIGTERM, handler);
+    }
+}
+
+/// Sleeps until the given time, returning early if sending should stop.
+fn sleep_until(deadline: Instant) {
+    loop {
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        if remaining.is_zero() || STOP.load(Ordering::SeqCst) {
+            return;
+        }
+        sleep(remaining.min(MAX_SLEEP));
+    }
+}
+
+/// The levels sent in each frame.
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Pattern {
+    /// The same levels every frame.
+    Static(Vec<u8>),
+
+    /// A block of channels at a level moving one channel per frame.
+    Chase { width: usize, level: u8 },
+
+    /// Each frame of levels in turn.
+    Frames(Vec<Vec<u8>>),
+}
+
+impl Pattern {
+    /// Returns the property values, including the start code, of the given frame.
+    fn frame(&self, index: usize) -> Vec<u8> {
+        let mut values = vec![0; UNIVERSE_CHANNEL_CAPACITY];
+        match self {
+            Pattern::Static(levels) => values[1..=levels.len()].copy_from_slice(levels),
+            Pattern::Chase { width, level } => {
+                for i in 0..*width {
+                    values[1 + (index + i) % CHANNELS] = *level;
+                }
+            }
+            Pattern::Frames(frames) => {
+                let levels = &frames[index % frames.len()];
+                values[1..=levels.len()].copy_from_slice(levels);
+            }
+        }
+        values
+    }
+}
+
+/// The options given on the command line.
+#[derive(Debug)]
+struct Options {
+    universes: Vec<u16>,
+    pattern: Pattern,
+    priority: u8,
+    sync: Option<u16>,
+    preview: bool,
+    unicast: Option<SocketAddr>,
+    interface: Option<NetworkInterface>,
+    ipv6: bool,
+    period: Duration,
+    name: String,
+    duration: Option<Duration>,
+    once: bool,
+}
+
+/// Parses the command line, returning None if help was asked for.
+fn parse_options(mut args: Args) -> std::result::Result<Option<Options>, String> {
+    let mut options = Options {
+        universes: Vec::new(),
+        pattern: Pattern::Static(Vec::new()),
+        priority: E131_DEFAULT_PRIORITY,
+        sync: None,
+        preview: false,
+        unicast: None,
+        interface: None,
+        ipv6: false,
+        period: Duration::from_secs_f64(1.0 / 44.0),
+        name: "sacn-send".to_string(),
+        duration: None,
+        once: false,
+    };
+    let mut levels = vec![0; CHANNELS];
+    let mut chase = None;
+    let mut chase_level = 255;
+    let mut file = None;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-h" | "--help" => return Ok(None),
+            "-l" | "--levels" => {
+                let given = parse_levels(&args.value(&arg)?)?;
+                levels[..given.len()].copy_from_slice(&given);
+            }
+            "-s" | "--set" => {
+                let (channels, level) = parse_set(&args.value(&arg)?)?;
+                levels[channels].fill(level);
+            }
+            "-c" | "--chase" => match args.parse(&arg)? {
+                w @ 1..=CHANNELS => chase = Some(w),
+                w => {
+                    return Err(format!(
+                        "Chase width must be 1-{CHANNELS}, width provided: {w}"
+                    ));
+                }
+            },
+            "--level" => chase_level = args.parse(&arg)?,
+            "-F" | "--file" => file = Some(args.value(&arg)?),
+            "-p" | "--priority" => match args.parse(&arg)? {
+                p @ 0..=E131_MAX_PRIORITY => options.priority = p,
+                p => {
+                    return Err(format!(
+                        "Priority must be 0-{E131_MAX_PRIORITY}, priority provided: {p}"
+                    ));
+                }
+            },
+            "-y" | "--sync" => options.sync = Some(parse_universe(&args.value(&arg)?)?),
+            "--preview" => options.preview = true,
+            "-u" | "--unicast" => options.unicast = Some(parse_destination(&args.value(&arg)?)?),
+            "-i" | "--interface" => {
+                options.interface = Some(
+                    args.value(&arg)?
+                        .parse()
+                        .map_err(|e: SacnError| e.to_string())?,
+                )
+            }
+            "-6" | "--ipv6" => options.ipv6 = true,
+            "-r" | "--rate" => {
+                let rate: f64 = args.parse(&arg)?;
+                if !(rate > 0.0 && rate <= MAX_RATE) {
+                    return Err(format!(
+                        "Rate must be greater than 0 and at most {MAX_RATE}, rate provided: {rate}"
+                    ));
+                }
+                options.period = Duration::from_secs_f64(1.0 / rate);
+            }
+            "-n" | "--name" => options.name = args.value(&arg)?,
+            "-d" | "--duration" => {
+                let secs: f64 = args.parse(&arg)?;
+                if !(secs >= 0.0 && secs.is_finite()) {
+                    return Err(format!("Invalid duration {secs}"));
+                }
+                options.duration = Some(Duration::from_secs_f64(secs));
+            }
+            "-1" | "--once" => options.once = true,
+            a if a.starts_with('-') => return Err(format!("Unknown option {a}")),
+            list => options.universes.extend(parse_universes(list)?),
+        }
+    }
+
+    options.universes.sort_unstable();
+    options.universes.dedup();
+    if options.universes.is_empty() {
+        return Err("At least one universe must be given".to_string());
+    }
+
+    options.pattern = match (chase, file) {
+        (Some(_), Some(_)) => return Err("Only one of --chase and --file can be given".to_string()),
+        (Some(width), None) => Pattern::Chase {
+            width,
+            level: chase_level,
+        },
+        (None, Some(path)) => {
+            let contents =
+                fs::read_to_string(&path).map_err(|e| format!("Cannot read {path}: {e}"))?;
+            Pattern::Frames(parse_frames(&contents)?)
+        }
+        (None, None) => Pattern::Static(levels),
+    };
+    Ok(Some(options))
+}
+
+/// Parses levels separated by commas or whitespace, at most one per channel.
+fn parse_levels(list: &str) -> std::result::Result<Vec<u8>, String> {
+    let levels = list
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|l| !l.is_empty())
+        .map(|l| {
+            l.parse()
+                .map_err(|_| format!("Invalid level {l}, must be 0-255"))
+        })
+        .collect::<std::result::Result<Vec<u8>, String>>()?;
+    if levels.len() > CHANNELS {
+        return Err(format!(
+            "At most {CHANNELS} levels can be given, {} levels provided",
+            levels.len()
+        ));
+    }
+    Ok(levels)
+}
+
+/// Parses a channel or inclusive range of channels and the level to set them to, e.g. `1-10=255`, returning the indexes of the channels.
+fn parse_set(set: &str) -> std::result::Result<(std::ops::RangeInclusive<usize>, u8), String> {
+    let (channels, level) = set
+        .split_once('=')
+        .ok_or_else(|| format!("Invalid channel level {set}, expected CHANNELS=LEVEL"))?;
+    let level = level
+        .trim()
+        .parse()
+        .map_err(|_| format!("Invalid level {level}, must be 0-255"))?;
+    let channel = |c: &str| match c.trim().parse() {
+        Ok(c @ 1..=CHANNELS) => Ok(c - 1),
+        _ => Err(format!("Invalid channel {c}, must be 1-{CHANNELS}")),
+    };
+    let (first, last) = match channels.split_once('-') {
+        Some((first, last)) => (channel(first)?, channel(last)?),
+        None => (channel(channels)?, channel(channels)?),
+    };
+    if first > last {
+        return Err(format!("Empty channel range {channels}"));
+    }
+    Ok((first..=last, level))
+}
+
+/// Parses a unicast destination, either an IP address which is sent to on the ACN port or an IP address and port.
+fn parse_destination(addr: &str) -> std::result::Result<SocketAddr, String> {
+    addr.parse()
+        .or_else(|_| {
+            addr.parse()
+                .map(|ip: IpAddr| SocketAddr::new(ip, ACN_SDT_MULTICAST_PORT))
+        })
+        .map_err(|_| format!("Invalid unicast destination {addr}"))
+}
+
+/// Parses a file of frames, one frame of levels per line. Blank lines and lines starting with # are ignored.
+fn parse_frames(contents: &str) -> std::result::Result<Vec<Vec<u8>>, String> {
+    let frames = contents
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty() && !line.trim_start().starts_with('#'))
+        .map(|(i, line)| parse_levels(line).map_err(|e| format!("Line {}: {e}", i + 1)))
+        .collect::<std::result::Result<Vec<_>, String>>()?;
+    if frames.is_empty() {
+        return Err("The file contains no frames".to_string());
+    }
+    Ok(frames)
+}
+
+/// Sends frames until the duration has passed or SIGINT / SIGTERM is received, or a single frame if asked to, then terminates the streams.
+fn run(options: &Options) -> Result<()> {
+    let mut src = if options.ipv6 {
+        SacnSource::new_v6(&options.name)?
+    } else {
+        SacnSource::new_v4(&options.name)?
+    };
+    if let Some(interface) = &options.interface {
+        src.set_interfaces(std::slice::from_ref(interface))?;
+    }
+    src.register_universes(&options.universes)?;
+    if let Some(sync) = options.sync {
+        src.register_universe(sync)?;
+    }
+    src.set_preview_mode(options.preview)?;
+
+    handle_stop_signals();
+    let start = Instant::now();
+    let mut next = start;
+    let mut frame = 0;
+    loop {
+        let values = options.pattern.frame(frame);
+        for universe in &options.universes {
+            src.send(
+                &[*universe],
+                &values,
+                Some(options.priority),
+                options.unicast,
+                options.sync,
+            )?;
+        }
+        if let Some(sync) = options.sync {
+            src.send_sync_packet(sync, options.unicast)?;
+        }
+        frame += 1;
+
+        next += options.period;
+        if options.once || options.duration.is_some_and(|d| next - start > d) {
+            break;
+        }
+        sleep_until(next);
+        if STOP.load(Ordering::SeqCst) {
+            break;
+        }
+    }
+
+    for universe in &options.universes {
+        src.terminate_stream(*universe, E131_NULL_START_CODE)?;
+    }
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    let options = match parse_options(Args::from_env()) {
+        Ok(Some(options)) => options,
+        Ok(None) => {
+            println!("{USAGE}");
+            return ExitCode::SUCCESS;
+        }
+        Err(e) => return usage_error("sacn-send", USAGE, &e),
+    };
+
+    match run(&options) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("sacn-send: {e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn args(args: &[&str]) -> Args {
+        Args::new(args.iter().map(|a| a.to_string()).collect())
+    }
+
+    /// Levels and channel ranges should combine into a single static frame, with later settings overriding earlier ones.
+    #[test]
+    fn test_static_levels() {
+        let options = parse_options(args(&[
+            "1,3", "-l", "10,20,30", "-s", "2-4=255", "-s", "4=0",
+        ]))
+        .unwrap()
+        .unwrap();
+        assert_eq!(options.universes, vec![1, 3]);
+        let frame = options.pattern.frame(0);
+        assert_eq!(frame.len(), UNIVERSE_CHANNEL_CAPACITY);
+        assert_eq!(&frame[..6], &[0, 10, 255, 255, 0, 0]);
+        assert_eq!(options.pattern.frame(5), frame);
+
+        for invalid in ["0=1", "513=1", "3-2=1", "1=256", "1"] {
+            assert!(parse_set(invalid).is_err(), "{invalid}");
+        }
+        assert!(parse_levels(&"0,".repeat(513)).is_err());
+    }
+
+    /// A chase should move one channel per frame, wrapping around the end of the universe.
+    #[test]
+    fn test_chase() {
+        let pattern = Pattern::Chase {
+            width: 2,
+            level: 200,
+        };
+        assert_eq!(&pattern.frame(0)[..4], &[0, 200, 200, 0]);
+        assert_eq!(&pattern.frame(1)[..4], &[0, 0, 200, 200]);
+        let wrapped = pattern.frame(CHANNELS - 1);
+        assert_eq!((wrapped[1], wrapped[CHANNELS]), (200, 200));
+        assert!(parse_options(args(&["1", "-c", "0"])).is_err());
+    }
+
+    /// Each line of a file should be a frame, sent in turn and looping, ignoring blank lines and comments.
+    #[test]
+    fn test_frames_file() {
+        let frames = parse_frames("# a comment\n1, 2\n\n3 4 5\n").unwrap();
+        assert_eq!(frames, vec![vec![1, 2], vec![3, 4, 5]]);
+        let pattern = Pattern::Frames(frames);
+        assert_eq!(&pattern.frame(1)[..4], &[0, 3, 4, 5]);
+        assert_eq!(&pattern.frame(2)[..3], &[0, 1, 2]);
+
+        assert_eq!(
+            parse_frames("1\n300\n").unwrap_err(),
+            "Line 2: Invalid level 300, must be 0-255"
+        );
+        assert!(parse_frames("# only comments\n").is_err());
+    }
+
+    /// Sending options should be parsed and validated.
+    #[test]
+    fn test_parse_options() {
+        let options = parse_options(args(&[
+            "5",
+            "-p",
+            "150",
+            "-y",
+            "7",
+            "-u",
+            "10.0.0.1",
+            "-r",
+            "10",
+            "--preview",
+            "-1",
+        ]))
+        .unwrap()
+        .unwrap();
+        assert_eq!(options.priority, 150);
+        assert_eq!(options.sync, Some(7));
+        assert_eq!(options.unicast, Some("10.0.0.1:5568".parse().unwrap()));
+        assert_eq!(options.period, Duration::from_millis(100));
+        assert!(options.preview && options.once);
+        assert_eq!(
+            parse_destination("10.0.0.1:6000").unwrap(),
+            "10.0.0.1:6000".parse().unwrap()
+        );
+
+        for invalid in [
+            &["-l", "1"][..],
+            &["1", "-p", "201"],
+            &["1", "-r", "0"],
+            &["1", "-c", "2", "-F", "levels.txt"],
+            &["1", "-u", "nowhere"],
+        ] {
+            assert!(parse_options(args(invalid)).is_err(), "{invalid:?}");
+        }
+    }
+}
@@ -0,0 +1,808 @@
+// Copyright 2020 sacn Developers
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! sacn-sniff, a command line sACN protocol analyser.
+//!
+//! Joins the multicast groups of the given universes, or the universe discovery universe, and prints every packet received decoded
+//! layer by layer. Sequence gaps, priority changes and the preview, termination and force synchronisation options are flagged and the
+//! packet rate of each universe is printed periodically. Output is human readable, JSON lines or CSV.
+
+mod cli;
+
+use cli::{Args, csv_field, json_string, parse_universes, usage_error};
+
+use sacn::error::errors::*;
+use sacn::interface::NetworkInterface;
+use sacn::packet::*;
+
+use socket2::{Domain, Protocol, Socket, Type};
+use uuid::Uuid;
+
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Write as _;
+use std::io::{self, Write};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, UdpSocket};
+use std::process::ExitCode;
+use std::time::{Duration, Instant};
+
+const USAGE: &str = "\
+Usage: sacn-sniff [OPTIONS] [UNIVERSES]
+
+Prints every sACN packet received on the given universes, e.g. 1,5-8. With no universes the universe discovery universe is joined.
+
+Options:
+  -d, --discovery         Also join the universe discovery universe
+  -i, --interface <IF>    Interface to join on, by name, #index or address
+  -6, --ipv6              Use IPv6 multicast
+  -f, --format <FORMAT>   Output format: human, json or csv [default: human]
+  -r, --rate <SECONDS>    Interval between packet rate reports, 0 to disable [default: 1]
+  -c, --count <N>         Exit after N packets
+  -v, --values            Print the property values of data packets
+  -h, --help              Print this help";
+
+/// Size of the buffer packets are received into, larger than any valid sACN packet.
+const RECV_BUF_SIZE: usize = 1500;
+
+/// The longest a receive waits before checking if a rate report is due.
+const MAX_RECV_WAIT: Duration = Duration::from_millis(250);
+
+/// How packets and rates are printed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Format {
+    Human,
+    Json,
+    Csv,
+}
+
+/// The options given on the command line.
+#[derive(Debug)]
+struct Options {
+    universes: Vec<u16>,
+    interface: Option<NetworkInterface>,
+    ipv6: bool,
+    format: Format,
+    rate_interval: Option<Duration>,
+    count: Option<u64>,
+    values: bool,
+}
+
+/// Parses the command line, returning None if help was asked for.
+fn parse_options(mut args: Args) -> std::result::Result<Option<Options>, String> {
+    let mut options = Options {
+        universes: Vec::new(),
+        interface: None,
+        ipv6: false,
+        format: Format::Human,
+        rate_interval: Some(Duration::from_secs(1)),
+        count: None,
+        values: false,
+    };
+    let mut discovery = false;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-h" | "--help" => return Ok(None),
+            "-d" | "--discovery" => discovery = true,
+            "-i" | "--interface" => {
+                options.interface = Some(
+                    args.value(&arg)?
+                        .parse()
+                        .map_err(|e: SacnError| e.to_string())?,
+                )
+            }
+            "-6" | "--ipv6" => options.ipv6 = true,
+            "-f" | "--format" => {
+                options.format = match args.value(&arg)?.as_str() {
+                    "human" => Format::Human,
+                    "json" => Format::Json,
+                    "csv" => Format::Csv,
+                    f => return Err(format!("Unknown format {f}")),
+                }
+            }
+            "-r" | "--rate" => {
+                let secs: f64 = args.parse(&arg)?;
+                options.rate_interval = if secs == 0.0 {
+                    None
+                } else if secs > 0.0 && secs.is_finite() {
+                    Some(Duration::from_secs_f64(secs))
+                } else {
+                    return Err(format!("Invalid rate interval {secs}"));
+                };
+            }
+            "-c" | "--count" => options.count = Some(args.parse(&arg)?),
+            "-v" | "--values" => options.values = true,
+            a if a.starts_with('-') => return Err(format!("Unknown option {a}")),
+            list => options.universes.extend(parse_universes(list)?),
+        }
+    }
+
+    options.universes.sort_unstable();
+    options.universes.dedup();
+    if discovery || options.universes.is_empty() {
+        options.universes.push(E131_DISCOVERY_UNIVERSE);
+    }
+    Ok(Some(options))
+}
+
+/// Tracks the state of each stream to flag sequence gaps and priority changes, and counts packets for the rate reports.
+#[derive(Debug, Default)]
+struct Analyser {
+    /// The last data sequence number of each source and universe.
+    data_sequences: HashMap<(Uuid, u16), u8>,
+
+    /// The last synchronisation sequence number of each source and synchronisation address.
+    sync_sequences: HashMap<(Uuid, u16), u8>,
+
+    /// The last priority of each source and universe.
+    priorities: HashMap<(Uuid, u16), u8>,
+
+    /// The packets received on each universe since the last rate report.
+    counts: BTreeMap<u16, u64>,
+}
+
+impl Analyser {
+    /// Records the given packet, returning the notable things about it.
+    fn analyse(&mut self, packet: &AcnRootLayerProtocol) -> Vec<String> {
+        let cid = packet.pdu.cid;
+        let mut flags = Vec::new();
+        match &packet.pdu.data {
+            E131RootLayerData::DataPacket(data) => {
+                *self.counts.entry(data.universe).or_default() += 1;
+                let key = (cid, data.universe);
+                sequence_flag(
+                    &mut self.data_sequences,
+                    key,
+                    data.sequence_number,
+                    &mut flags,
+                );
+                if let Some(previous) = self.priorities.insert(key, data.priority)
+                    && previous != data.priority
+                {
+                    flags.push(format!("priority {previous}->{}", data.priority));
+                }
+                if data.preview_data {
+                    flags.push("preview".to_string());
+                }
+                if data.force_synchronization {
+                    flags.push("force-sync".to_string());
+                }
+                if data.stream_terminated {
+                    flags.push("terminated".to_string());
+                    self.data_sequences.remove(&key);
+                    self.priorities.remove(&key);
+                }
+            }
+            E131RootLayerData::SynchronizationPacket(sync) => {
+                *self.counts.entry(sync.synchronization_address).or_default() += 1;
+                sequence_flag(
+                    &mut self.sync_sequences,
+                    (cid, sync.synchronization_address),
+                    sync.sequence_number,
+                    &mut flags,
+                );
+            }
+            E131RootLayerData::UniverseDiscoveryPacket(_) => {
+                *self.counts.entry(E131_DISCOVERY_UNIVERSE).or_default() += 1;
+            }
+        }
+        flags
+    }
+
+    /// Returns the packets per second of each universe over the given interval since the last call, and starts counting again.
+    fn take_rates(&mut self, interval: Duration) -> Vec<(u16, f64)> {
+        let secs = interval.as_secs_f64().max(f64::EPSILON);
+        let rates = self
+            .counts
+            .iter()
+            .map(|(u, count)| (*u, *count as f64 / secs))
+            .collect();
+        self.counts.values_mut().for_each(|c| *c = 0);
+        rates
+    }
+}
+
+/// Flags the sequence number of a packet if it doesn't follow the last one of its stream, as per ANSI E1.31-2018 Section 6.7.2.
+fn sequence_flag(
+    last: &mut HashMap<(Uuid, u16), u8>,
+    key: (Uuid, u16),
+    sequence: u8,
+    flags: &mut Vec<String>,
+) {
+    if let Some(previous) = last.insert(key, sequence) {
+        match sequence.wrapping_sub(previous) as i8 {
+            1 => {}
+            0 => flags.push("duplicate".to_string()),
+            diff if diff > 1 => flags.push(format!("gap {}", diff - 1)),
+            diff => flags.push(format!("out-of-order {diff}")),
+        }
+    }
+}
+
+/// The name of the kind of the given packet.
+fn kind(packet: &AcnRootLayerProtocol) -> &'static str {
+    match packet.pdu.data {
+        E131RootLayerData::DataPacket(_) => "data",
+        E131RootLayerData::SynchronizationPacket(_) => "sync",
+        E131RootLayerData::UniverseDiscoveryPacket(_) => "discovery",
+    }
+}
+
+/// The root layer and framing layer vectors of the given packet.
+fn vectors(packet: &AcnRootLayerProtocol) -> (u32, u32) {
+    match packet.pdu.data {
+        E131RootLayerData::DataPacket(_) => (VECTOR_ROOT_E131_DATA, VECTOR_E131_DATA_PACKET),
+        E131RootLayerData::SynchronizationPacket(_) => (
+            VECTOR_ROOT_E131_EXTENDED,
+            VECTOR_E131_EXTENDED_SYNCHRONIZATION,
+        ),
+        E131RootLayerData::UniverseDiscoveryPacket(_) => {
+            (VECTOR_ROOT_E131_EXTENDED, VECTOR_E131_EXTENDED_DISCOVERY)
+        }
+    }
+}
+
+/// Joins the given values with spaces.
+fn join<T: ToString>(values: &[T]) -> String {
+    values
+        .iter()
+        .map(T::to_string)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// The columns of the CSV output.
+const CSV_HEADER: &str = "time,src,kind,len,root_vector,cid,framing_vector,source_name,universe,priority,sync_address,sequence,preview,terminated,force_sync,start_code,slots,page,last_page,flags,values,rate";
+
+/// Returns a CSV row with the given value in each named column of CSV_HEADER, leaving the other columns empty.
+fn csv_row(values: &[(&str, String)]) -> String {
+    CSV_HEADER
+        .split(',')
+        .map(|column| {
+            values
+                .iter()
+                .find(|(name, _)| *name == column)
+                .map_or("", |(_, value)| value.as_str())
+        })
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// Formats packets and rate reports.
+struct Printer {
+    format: Format,
+    values: bool,
+}
+
+impl Printer {
+    /// Formats a received packet.
+    fn packet(
+        &self,
+        time: Duration,
+        src: SocketAddr,
+        len: usize,
+        packet: &AcnRootLayerProtocol,
+        flags: &[String],
+    ) -> String {
+        match self.format {
+            Format::Human => self.human(time, src, len, packet, flags),
+            Format::Json => self.json(time, src, len, packet, flags),
+            Format::Csv => self.csv(time, src, len, packet, flags),
+        }
+    }
+
+    fn human(
+        &self,
+        time: Duration,
+        src: SocketAddr,
+        len: usize,
+        packet: &AcnRootLayerProtocol,
+        flags: &[String],
+    ) -> String {
+        let (root_vector, framing_vector) = vectors(packet);
+        let mut line = format!(
+            "{:.6} {src} {} len={len} | root vector=0x{root_vector:x} cid={} | framing vector=0x{framing_vector:x}",
+            time.as_secs_f64(),
+            kind(packet),
+            packet.pdu.cid,
+        );
+        match &packet.pdu.data {
+            E131RootLayerData::DataPacket(data) => {
+                let values = &data.data.property_values;
+                let _ = write!(
+                    line,
+                    " name={:?} universe={} priority={} sync={} seq={} preview={} terminated={} force_sync={} | dmp start_code=0x{:02x} slots={}",
+                    data.source_name,
+                    data.universe,
+                    data.priority,
+                    data.synchronization_address,
+                    data.sequence_number,
+                    data.preview_data as u8,
+                    data.stream_terminated as u8,
+                    data.force_synchronization as u8,
+                    values.first().copied().unwrap_or_default(),
+                    values.len().saturating_sub(1),
+                );
+                if self.values && values.len() > 1 {
+                    let _ = write!(line, " values=[{}]", join(&values[1..]));
+                }
+            }
+            E131RootLayerData::SynchronizationPacket(sync) => {
+                let _ = write!(
+                    line,
+                    " sync={} seq={}",
+                    sync.synchronization_address, sync.sequence_number
+                );
+            }
+            E131RootLayerData::UniverseDiscoveryPacket(discovery) => {
+                let _ = write!(
+                    line,
+                    " name={:?} | discovery page={}/{} universes=[{}]",
+                    discovery.source_name,
+                    discovery.data.page,
+                    discovery.data.last_page,
+                    join(&discovery.data.universes),
+                );
+            }
+        }
+        if !flags.is_empty() {
+            let _ = write!(line, " !! {}", flags.join(", "));
+        }
+        line
+    }
+
+    fn json(
+        &self,
+        time: Duration,
+        src: SocketAddr,
+        len: usize,
+        packet: &AcnRootLayerProtocol,
+        flags: &[String],
+    ) -> String {
+        let (root_vector, framing_vector) = vectors(packet);
+        let mut line = format!(
+            "{{\"type\":\"packet\",\"time\":{:.6},\"src\":{},\"kind\":\"{}\",\"len\":{len},\"root\":{{\"vector\":{root_vector},\"cid\":\"{}\"}},\"framing\":{{\"vector\":{framing_vector}",
+            time.as_secs_f64(),
+            json_string(&src.to_string()),
+            kind(packet),
+            packet.pdu.cid,
+        );
+        match &packet.pdu.data {
+            E131RootLayerData::DataPacket(data) => {
+                let values = &data.data.property_values;
+                let _ = write!(
+                    line,
+                    ",\"source_name\":{},\"universe\":{},\"priority\":{},\"sync_address\":{},\"sequence\":{},\"preview\":{},\"terminated\":{},\"force_sync\":{}}},\"dmp\":{{\"start_code\":{},\"slots\":{}",
+                    json_string(&data.source_name),
+                    data.universe,
+                    data.priority,
+                    data.synchronization_address,
+                    data.sequence_number,
+                    data.preview_data,
+                    data.stream_terminated,
+                    data.force_synchronization,
+                    values.first().copied().unwrap_or_default(),
+                    values.len().saturating_sub(1),
+                );
+                if self.values {
+                    let slots: Vec<String> = values.iter().skip(1).map(u8::to_string).collect();
+                    let _ = write!(line, ",\"values\":[{}]", slots.join(","));
+                }
+                line.push('}');
+            }
+            E131RootLayerData::SynchronizationPacket(sync) => {
+                let _ = write!(
+                    line,
+                    ",\"sync_address\":{},\"sequence\":{}}}",
+                    sync.synchronization_address, sync.sequence_number
+                );
+            }
+            E131RootLayerData::UniverseDiscoveryPacket(discovery) => {
+                let universes: Vec<String> = discovery
+                    .data
+                    .universes
+                    .iter()
+                    .map(u16::to_string)
+                    .collect();
+                let _ = write!(
+                    line,
+                    ",\"source_name\":{}}},\"discovery\":{{\"page\":{},\"last_page\":{},\"universes\":[{}]}}",
+                    json_string(&discovery.source_name),
+                    discovery.data.page,
+                    discovery.data.last_page,
+                    universes.join(","),
+                );
+            }
+        }
+        let flags: Vec<String> = flags.iter().map(|f| json_string(f)).collect();
+        let _ = write!(line, ",\"flags\":[{}]}}", flags.join(","));
+        line
+    }
+
+    fn csv(
+        &self,
+        time: Duration,
+        src: SocketAddr,
+        len: usize,
+        packet: &AcnRootLayerProtocol,
+        flags: &[String],
+    ) -> String {
+        let (root_vector, framing_vector) = vectors(packet);
+        let mut row = vec![
+            ("time", format!("{:.6}", time.as_secs_f64())),
+            ("src", src.to_string()),
+            ("kind", kind(packet).to_string()),
+            ("len", len.to_string()),
+            ("root_vector", root_vector.to_string()),
+            ("cid", packet.pdu.cid.to_string()),
+            ("framing_vector", framing_vector.to_string()),
+            ("flags", csv_field(&flags.join(";"))),
+        ];
+        match &packet.pdu.data {
+            E131RootLayerData::DataPacket(data) => {
+                let values = &data.data.property_values;
+                row.extend([
+                    ("source_name", csv_field(&data.source_name)),
+                    ("universe", data.universe.to_string()),
+                    ("priority", data.priority.to_string()),
+                    ("sync_address", data.synchronization_address.to_string()),
+                    ("sequence", data.sequence_number.to_string()),
+                    ("preview", data.preview_data.to_string()),
+                    ("terminated", data.stream_terminated.to_string()),
+                    ("force_sync", data.force_synchronization.to_string()),
+                    (
+                        "start_code",
+                        values.first().copied().unwrap_or_default().to_string(),
+                    ),
+                    ("slots", values.len().saturating_sub(1).to_string()),
+                ]);
+                if self.values && values.len() > 1 {
+                    row.push(("values", join(&values[1..])));
+                }
+            }
+            E131RootLayerData::SynchronizationPacket(sync) => {
+                row.extend([
+                    ("sync_address", sync.synchronization_address.to_string()),
+                    ("sequence", sync.sequence_number.to_string()),
+                ]);
+            }
+            E131RootLayerData::UniverseDiscoveryPacket(discovery) => {
+                row.extend([
+                    ("source_name", csv_field(&discovery.source_name)),
+                    ("page", discovery.data.page.to_string()),
+                    ("last_page", discovery.data.last_page.to_string()),
+                    ("values", join(&discovery.data.universes)),
+                ]);
+            }
+        }
+        csv_row(&row)
+    }
+
+    /// Formats a packet which couldn't be parsed.
+    fn invalid(&self, time: Duration, src: SocketAddr, len: usize, error: &SacnError) -> String {
+        let error = error.to_string();
+        match self.format {
+            Format::Human => format!(
+                "{:.6} {src} invalid len={len} !! {error}",
+                time.as_secs_f64()
+            ),
+            Format::Json => format!(
+                "{{\"type\":\"invalid\",\"time\":{:.6},\"src\":{},\"len\":{len},\"error\":{}}}",
+                time.as_secs_f64(),
+                json_string(&src.to_string()),
+                json_string(&error)
+            ),
+            Format::Csv => csv_row(&[
+                ("time", format!("{:.6}", time.as_secs_f64())),
+                ("src", src.to_string()),
+                ("kind", "invalid".to_string()),
+                ("len", len.to_string()),
+                ("flags", csv_field(&error)),
+            ]),
+        }
+    }
+
+    /// Formats a report of the packet rate of each universe.
+    fn rates(&self, time: Duration, rates: &[(u16, f64)]) -> Vec<String> {
+        match self.format {
+            Format::Human => {
+                let rates: Vec<String> =
+                    rates.iter().map(|(u, r)| format!("{u}={r:.1}/s")).collect();
+                vec![format!(
+                    "{:.6} rates {}",
+                    time.as_secs_f64(),
+                    rates.join(" ")
+                )]
+            }
+            Format::Json => {
+                let rates: Vec<String> = rates
+                    .iter()
+                    .map(|(u, r)| format!("\"{u}\":{r:.3}"))
+                    .collect();
+                vec![format!(
+                    "{{\"type\":\"rates\",\"time\":{:.6},\"rates\":{{{}}}}}",
+                    time.as_secs_f64(),
+                    rates.join(",")
+                )]
+            }
+            Format::Csv => rates
+                .iter()
+                .map(|(u, r)| {
+                    csv_row(&[
+                        ("time", format!("{:.6}", time.as_secs_f64())),
+                        ("kind", "rate".to_string()),
+                        ("universe", u.to_string()),
+                        ("rate", format!("{r:.3}")),
+                    ])
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Creates a socket bound to the ACN port which other sACN receivers on the machine can also bind to.
+fn bind_socket(ipv6: bool) -> Result<UdpSocket> {
+    let (domain, ip) = if ipv6 {
+        (Domain::IPV6, IpAddr::V6(Ipv6Addr::UNSPECIFIED))
+    } else {
+        (Domain::IPV4, IpAddr::V4(Ipv4Addr::UNSPECIFIED))
+    };
+    let socket = Socket::new(domain, Type::DGRAM, Some(Protocol::UDP))?;
+    socket.set_reuse_address(true)?;
+    #[cfg(unix)]
+    socket.set_reuse_port(true)?;
+    if ipv6 {
+        socket.set_only_v6(true)?;
+    }
+    socket.bind(&SocketAddr::new(ip, ACN_SDT_MULTICAST_PORT).into())?;
+    Ok(socket.into())
+}
+
+/// Joins the multicast group of the given universe on the given interface, or the interface chosen by the OS.
+fn join_universe(
+    socket: &UdpSocket,
+    universe: u16,
+    ipv6: bool,
+    interface: Option<&NetworkInterface>,
+) -> Result<()> {
+    if ipv6 {
+        let group = universe_to_ipv6_multicast_addr(universe)?;
+        let index = interface
+            .map(NetworkInterface::index)
+            .transpose()?
+            .unwrap_or(0);
+        socket.join_multicast_v6(group.as_socket_ipv6().unwrap().ip(), index)?;
+    } else {
+        let group = universe_to_ipv4_multicast_addr(universe)?;
+        let addr = match interface.map(|i| i.addr(false)).transpose()? {
+            Some(IpAddr::V4(addr)) => addr,
+            _ => Ipv4Addr::UNSPECIFIED,
+        };
+        socket.join_multicast_v4(group.as_socket_ipv4().unwrap().ip(), &addr)?;
+    }
+    Ok(())
+}
+
+/// Receives and prints packets until the count is reached or printing fails.
+fn run(options: &Options) -> Result<()> {
+    let socket = bind_socket(options.ipv6)?;
+    for universe in &options.universes {
+        join_universe(&socket, *universe, options.ipv6, options.interface.as_ref())?;
+    }
+
+    let printer = Printer {
+        format: options.format,
+        values: options.values,
+    };
+    let mut analyser = Analyser::default();
+    let mut out = io::stdout().lock();
+    if options.format == Format::Csv {
+        writeln!(out, "{CSV_HEADER}")?;
+    }
+
+    let start = Instant::now();
+    let mut last_report = start;
+    let mut received = 0;
+    let mut buf = [0; RECV_BUF_SIZE];
+    while options.count.is_none_or(|c| received < c) {
+        let wait = match options.rate_interval {
+            Some(interval) => (last_report + interval)
+                .saturating_duration_since(Instant::now())
+                .clamp(Duration::from_millis(1), MAX_RECV_WAIT),
+            None => MAX_RECV_WAIT,
+        };
+        socket.set_read_timeout(Some(wait))?;
+        match socket.recv_from(&mut buf) {
+            Ok((len, src)) => {
+                received += 1;
+                let time = start.elapsed();
+                let line = match AcnRootLayerProtocol::parse(&buf[..len]) {
+                    Ok(packet) => {
+                        let flags = analyser.analyse(&packet);
+                        printer.packet(time, src, len, &packet, &flags)
+                    }
+                    Err(e) => printer.invalid(time, src, len, &e),
+                };
+                writeln!(out, "{line}")?;
+            }
+            Err(e)
+                if matches!(
+                    e.kind(),
+                    io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut
+                ) => {}
+            Err(e) => return Err(e.into()),
+        }
+
+        if let Some(interval) = options.rate_interval {
+            let now = Instant::now();
+            if now >= last_report + interval {
+                let rates = analyser.take_rates(now - last_report);
+                if !rates.is_empty() {
+                    for line in printer.rates(now - start, &rates) {
+                        writeln!(out, "{line}")?;
+                    }
+                }
+                last_report = now;
+            }
+        }
+        out.flush()?;
+    }
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    let options = match parse_options(Args::from_env()) {
+        Ok(Some(options)) => options,
+        Ok(None) => {
+            println!("{USAGE}");
+            return ExitCode::SUCCESS;
+        }
+        Err(e) => return usage_error("sacn-sniff", USAGE, &e),
+    };
+
+    match run(&options) {
+        Ok(()) => ExitCode::SUCCESS,
+        // The output was closed, e.g. piped into head.
+        Err(SacnError::Io(e)) if e.kind() == io::ErrorKind::BrokenPipe => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("sacn-sniff: {e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn data_packet(
+        sequence_number: u8,
+        priority: u8,
+        stream_terminated: bool,
+    ) -> AcnRootLayerProtocol<'static> {
+        AcnRootLayerProtocol {
+            pdu: E131RootLayer {
+                cid: Uuid::from_bytes([1; 16]),
+                data: E131RootLayerData::DataPacket(DataPacketFramingLayer {
+                    source_name: "Sniffed, \"Source\"".into(),
+                    priority,
+                    synchronization_address: 0,
+                    sequence_number,
+                    preview_data: false,
+                    stream_terminated,
+                    force_synchronization: false,
+                    universe: 1,
+                    data: DataPacketDmpLayer {
+                        property_values: vec![0, 255, 128].into(),
+                    },
+                }),
+            },
+        }
+    }
+
+    /// Sequence gaps, duplicates, reordering and priority changes should be flagged per stream, restarting after termination.
+    #[test]
+    fn test_analyse_flags() {
+        let mut analyser = Analyser::default();
+        assert!(analyser.analyse(&data_packet(254, 100, false)).is_empty());
+        assert!(analyser.analyse(&data_packet(255, 100, false)).is_empty());
+        assert_eq!(analyser.analyse(&data_packet(2, 100, false)), vec!["gap 2"]);
+        assert_eq!(
+            analyser.analyse(&data_packet(2, 100, false)),
+            vec!["duplicate"]
+        );
+        assert_eq!(
+            analyser.analyse(&data_packet(0, 150, false)),
+            vec!["out-of-order -2", "priority 100->150"]
+        );
+        assert_eq!(
+            analyser.analyse(&data_packet(1, 150, true)),
+            vec!["terminated"]
+        );
+        assert!(analyser.analyse(&data_packet(100, 50, false)).is_empty());
+
+        assert_eq!(analyser.take_rates(Duration::from_secs(2)), vec![(1, 3.5)]);
+        assert_eq!(analyser.take_rates(Duration::from_secs(1)), vec![(1, 0.0)]);
+    }
+
+    /// Returns the number of columns in the given CSV row, ignoring separators within quoted fields.
+    fn csv_columns(row: &str) -> usize {
+        let mut quoted = false;
+        let mut columns = 1;
+        for c in row.chars() {
+            match c {
+                '"' => quoted = !quoted,
+                ',' if !quoted => columns += 1,
+                _ => {}
+            }
+        }
+        columns
+    }
+
+    /// Each format should include the fields of every layer of the packet and its flags, with one CSV column per header column.
+    #[test]
+    fn test_print_formats() {
+        let packet = data_packet(7, 100, false);
+        let src: SocketAddr = "10.0.0.1:5568".parse().unwrap();
+        let flags = vec!["gap 1".to_string()];
+        let time = Duration::from_millis(1500);
+        let printer = |format| Printer {
+            format,
+            values: true,
+        };
+
+        let human = printer(Format::Human).packet(time, src, 128, &packet, &flags);
+        assert!(human.starts_with("1.500000 10.0.0.1:5568 data len=128 | root vector=0x4"));
+        assert!(human.contains("universe=1 priority=100 sync=0 seq=7"));
+        assert!(human.ends_with("start_code=0x00 slots=2 values=[255 128] !! gap 1"));
+
+        let json = printer(Format::Json).packet(time, src, 128, &packet, &flags);
+        assert!(json.contains("\"source_name\":\"Sniffed, \\\"Source\\\"\""));
+        assert!(json.ends_with(
+            "\"dmp\":{\"start_code\":0,\"slots\":2,\"values\":[255,128]},\"flags\":[\"gap 1\"]}"
+        ));
+
+        let csv = printer(Format::Csv).packet(time, src, 128, &packet, &flags);
+        assert!(csv.ends_with(
+            ",\"Sniffed, \"\"Source\"\"\",1,100,0,7,false,false,false,0,2,,,gap 1,255 128,"
+        ));
+        let columns = CSV_HEADER.split(',').count();
+        assert_eq!(csv_columns(&csv), columns);
+        let error = SacnError::SacnParsePackError(
+            sacn::sacn_parse_pack_error::ParsePacketError::ParseInvalidPriority(201),
+        );
+        let invalid = printer(Format::Csv).invalid(time, src, 10, &error);
+        assert_eq!(csv_columns(&invalid), columns);
+        let rate = printer(Format::Csv).rates(time, &[(1, 44.0)]);
+        assert_eq!(rate[0], "1.500000,,rate,,,,,,1,,,,,,,,,,,,,44.000");
+        assert_eq!(csv_columns(&rate[0]), columns);
+    }
+
+    /// Universes and the discovery universe should be joined as asked for.
+    #[test]
+    fn test_parse_options() {
+        let args = |args: &[&str]| Args::new(args.iter().map(|a| a.to_string()).collect());
+        let options = parse_options(args(&[])).unwrap().unwrap();
+        assert_eq!(options.universes, vec![E131_DISCOVERY_UNIVERSE]);
+
+        let options = parse_options(args(&["2-3", "-f", "json", "1", "-r", "0"]))
+            .unwrap()
+            .unwrap();
+        assert_eq!(options.universes, vec![1, 2, 3]);
+        assert_eq!(options.format, Format::Json);
+        assert_eq!(options.rate_interval, None);
+
+        let options = parse_options(args(&["1", "--discovery", "-i", "#2"]))
+            .unwrap()
+            .unwrap();
+        assert_eq!(options.universes, vec![1, E131_DISCOVERY_UNIVERSE]);
+        assert_eq!(options.interface, Some(NetworkInterface::Index(2)));
+
+        assert!(parse_options(args(&["-h"])).unwrap().is_none());
+        assert!(parse_options(args(&["-f", "xml"])).is_err());
+        assert!(parse_options(args(&["-c"])).is_err());
+    }
+}
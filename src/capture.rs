@@ -0,0 +1,1144 @@
+// Copyright 2020 sacn Developers
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! Reading sACN from packet captures, e.g. those saved by Wireshark or tcpdump, and replaying them into a receiver.
+//!
+//! [`CaptureReader`] reads pcap and pcapng files and returns the payloads of the UDP datagrams sent to the ACN port along with when
+//! they were captured. Ethernet (including VLAN tagged frames), Linux cooked, raw IP and BSD loopback captures are supported, over IPv4
+//! and IPv6. Fragmented IPv4 datagrams are skipped.
+//!
+//! [`CaptureTransport`] is a [`Transport`] which receives the packets of a capture, so a receiver created with
+//! (SacnReceiver::with_capture)[crate::receive::SacnReceiver::with_capture] handles them exactly as if they had arrived on the network:
+//! sequence numbers are checked, synchronisation and discovery are handled and sources are merged. The capture is replayed either at the
+//! speed it was captured or as fast as possible. Either way the receiver's clock follows the capture timestamps, so timeouts happen
+//! where they did when the capture was taken.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use sacn::capture::{CaptureTransport, ReplayTiming};
+//! use sacn::error::errors::SacnError;
+//! use sacn::receive::SacnReceiver;
+//!
+//! use std::io;
+//!
+//! let capture = CaptureTransport::open("venue.pcapng", ReplayTiming::AsFastAsPossible).unwrap();
+//! let mut dmx_rcv = SacnReceiver::with_capture(capture, None).unwrap();
+//! dmx_rcv.listen_universes(&[1]).unwrap();
+//!
+//! loop {
+//!     match dmx_rcv.recv(None) {
+//!         Ok(data) => println!("{:?}", data),
+//!         // The end of the capture.
+//!         Err(SacnError::Io(e)) if e.kind() == io::ErrorKind::UnexpectedEof => break,
+//!         Err(e) => println!("{}", e),
+//!     }
+//! }
+//! ```
+
+use crate::clock::ManualClock;
+use crate::error::errors::*;
+use crate::packet::ACN_SDT_MULTICAST_PORT;
+use crate::transport::Transport;
+
+use byteorder::{BigEndian, ByteOrder, LittleEndian};
+
+use std::fmt;
+use std::fs::File;
+use std::io::{self, BufReader, Read};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::path::Path;
+use std::sync::{Mutex, MutexGuard};
+use std::thread::sleep;
+use std::time::{Duration, Instant};
+
+/// Magic number at the start of a pcap file with microsecond timestamps, as read in the byte order the file was written in.
+const PCAP_MAGIC_MICROS: u32 = 0xA1B2_C3D4;
+
+/// Magic number at the start of a pcap file with nanosecond timestamps, as read in the byte order the file was written in.
+const PCAP_MAGIC_NANOS: u32 = 0xA1B2_3C4D;
+
+/// Length of the global header at the start of a pcap file, including the magic number.
+const PCAP_HEADER_LEN: usize = 24;
+
+/// Length of the header of each packet record in a pcap file.
+const PCAP_RECORD_HEADER_LEN: usize = 16;
+
+/// Block type of a pcapng Section Header Block, the same in either byte order.
+const PCAPNG_SECTION_HEADER_BLOCK: u32 = 0x0A0D_0D0A;
+
+/// Byte order magic within a pcapng Section Header Block, as read in the byte order of the section.
+const PCAPNG_BYTE_ORDER_MAGIC: u32 = 0x1A2B_3C4D;
+
+/// Block type of a pcapng Interface Description Block.
+const PCAPNG_INTERFACE_DESCRIPTION_BLOCK: u32 = 1;
+
+/// Block type of the obsolete pcapng Packet Block.
+const PCAPNG_PACKET_BLOCK: u32 = 2;
+
+/// Block type of a pcapng Simple Packet Block.
+const PCAPNG_SIMPLE_PACKET_BLOCK: u32 = 3;
+
+/// Block type of a pcapng Enhanced Packet Block.
+const PCAPNG_ENHANCED_PACKET_BLOCK: u32 = 6;
+
+/// Option code of the timestamp resolution of a pcapng interface.
+const PCAPNG_IF_TSRESOL: u16 = 9;
+
+/// Option code of the timestamp offset, in seconds, of a pcapng interface.
+const PCAPNG_IF_TSOFFSET: u16 = 14;
+
+/// The largest record or block that is read from a capture, anything larger is taken to be a corrupt file rather than allocated.
+const CAPTURE_MAX_RECORD_LEN: usize = 16 * 1024 * 1024;
+
+/// BSD loopback encapsulation, a 4 byte address family in the byte order of the capturing machine.
+const LINKTYPE_NULL: u16 = 0;
+
+/// Ethernet II frames.
+const LINKTYPE_ETHERNET: u16 = 1;
+
+/// Raw IP packets, as used by some platforms in place of LINKTYPE_RAW.
+const LINKTYPE_RAW_ALT: [u16; 2] = [12, 14];
+
+/// Raw IP packets.
+const LINKTYPE_RAW: u16 = 101;
+
+/// OpenBSD loopback encapsulation, a 4 byte address family in network byte order.
+const LINKTYPE_LOOP: u16 = 108;
+
+/// Linux cooked capture, e.g. when capturing on the "any" interface.
+const LINKTYPE_LINUX_SLL: u16 = 113;
+
+/// Raw IPv4 packets.
+const LINKTYPE_IPV4: u16 = 228;
+
+/// Raw IPv6 packets.
+const LINKTYPE_IPV6: u16 = 229;
+
+/// Linux cooked capture version 2.
+const LINKTYPE_LINUX_SLL2: u16 = 276;
+
+/// EtherType of IPv4.
+const ETHERTYPE_IPV4: u16 = 0x0800;
+
+/// EtherType of IPv6.
+const ETHERTYPE_IPV6: u16 = 0x86DD;
+
+/// EtherTypes of 802.1Q VLAN tags, which are followed by another EtherType.
+const ETHERTYPE_VLAN: [u16; 3] = [0x8100, 0x88A8, 0x9100];
+
+/// IP protocol number of UDP.
+const IP_PROTOCOL_UDP: u8 = 17;
+
+/// IPv6 extension headers which are skipped to find the UDP header: hop-by-hop, routing and destination options.
+const IPV6_EXTENSION_HEADERS: [u8; 3] = [0, 43, 60];
+
+/// Length of a UDP header.
+const UDP_HEADER_LEN: usize = 8;
+
+/// A UDP datagram read from a packet capture.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CapturedPacket {
+    /// When the packet was captured, as the time since the Unix epoch.
+    pub timestamp: Duration,
+
+    /// The address the packet was sent from.
+    pub src: SocketAddr,
+
+    /// The address the packet was sent to.
+    pub dst: SocketAddr,
+
+    /// The UDP payload of the packet, this is the sACN packet itself.
+    pub data: Vec<u8>,
+}
+
+/// The format of a packet capture, and the state needed to read the records which follow its header.
+#[derive(Clone, Debug)]
+enum CaptureFormat {
+    /// A pcap file, which has a single link type for every packet.
+    Pcap {
+        /// True if the file was written in big endian byte order.
+        big_endian: bool,
+
+        /// True if the timestamps have nanosecond rather than microsecond resolution.
+        nanos: bool,
+
+        /// The link type of every packet in the file.
+        link_type: u16,
+    },
+
+    /// A pcapng file, made of sections which each describe their own interfaces.
+    Pcapng {
+        /// True if the current section was written in big endian byte order.
+        big_endian: bool,
+
+        /// The interfaces of the current section, in the order they were described.
+        interfaces: Vec<PcapngInterface>,
+
+        /// The timestamp of the last packet read, used for Simple Packet Blocks which don't have their own.
+        last_timestamp: Duration,
+    },
+}
+
+/// A network interface described in a pcapng file.
+#[derive(Clone, Copy, Debug)]
+struct PcapngInterface {
+    /// The link type of packets captured on the interface.
+    link_type: u16,
+
+    /// The number of timestamp units in a second.
+    units_per_sec: u64,
+
+    /// The offset in seconds added to the timestamps of packets captured on the interface.
+    offset_secs: i64,
+}
+
+/// Reads the UDP datagrams sent to a port, by default the ACN port, from a pcap or pcapng file.
+///
+/// The reader is an iterator over the packets in the order they appear in the capture. Packets which aren't UDP, are sent to another port
+/// or were truncated when captured are skipped. The iterator returns an InvalidCapture error and then stops if the capture is corrupt.
+pub struct CaptureReader<R: Read> {
+    /// The capture being read.
+    reader: R,
+
+    /// The format of the capture.
+    format: CaptureFormat,
+
+    /// The destination port of the datagrams which are returned, None to return every UDP datagram.
+    port: Option<u16>,
+
+    /// True once the end of the capture, or an error, has been reached.
+    finished: bool,
+}
+
+impl CaptureReader<BufReader<File>> {
+    /// Opens the pcap or pcapng file at the given path.
+    ///
+    /// # Errors
+    /// Io: Returned if the file cannot be opened or read.
+    ///
+    /// InvalidCapture: Returned if the file isn't a pcap or pcapng file.
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<CaptureReader<BufReader<File>>> {
+        CaptureReader::new(BufReader::new(File::open(path)?))
+    }
+}
+
+impl<R: Read> CaptureReader<R> {
+    /// Creates a reader of the pcap or pcapng capture read from the given reader, reading the header of the capture.
+    ///
+    /// # Errors
+    /// Io: Returned if the header cannot be read.
+    ///
+    /// InvalidCapture: Returned if the capture isn't in the pcap or pcapng format.
+    pub fn new(mut reader: R) -> Result<CaptureReader<R>> {
+        let mut magic = [0; 4];
+        read_exact_or_eof(&mut reader, &mut magic)?
+            .then_some(())
+            .ok_or_else(|| invalid("The capture is empty"))?;
+
+        let format = if BigEndian::read_u32(&magic) == PCAPNG_SECTION_HEADER_BLOCK {
+            let mut format = CaptureFormat::Pcapng {
+                big_endian: false,
+                interfaces: Vec::new(),
+                last_timestamp: Duration::ZERO,
+            };
+            read_section_header(&mut reader, &mut format)?;
+            format
+        } else {
+            let (big_endian, nanos) =
+                match (LittleEndian::read_u32(&magic), BigEndian::read_u32(&magic)) {
+                    (PCAP_MAGIC_MICROS, _) => (false, false),
+                    (PCAP_MAGIC_NANOS, _) => (false, true),
+                    (_, PCAP_MAGIC_MICROS) => (true, false),
+                    (_, PCAP_MAGIC_NANOS) => (true, true),
+                    _ => return Err(invalid("Not a pcap or pcapng capture")),
+                };
+            let mut header = [0; PCAP_HEADER_LEN - 4];
+            reader.read_exact(&mut header)?;
+            CaptureFormat::Pcap {
+                big_endian,
+                nanos,
+                // The upper bits of the link type hold the FCS length and flags.
+                link_type: read_u32(big_endian, &header[16..20]) as u16,
+            }
+        };
+
+        Ok(CaptureReader {
+            reader,
+            format,
+            port: Some(ACN_SDT_MULTICAST_PORT),
+            finished: false,
+        })
+    }
+
+    /// Sets the destination port of the UDP datagrams returned, by default ACN_SDT_MULTICAST_PORT.
+    ///
+    /// # Arguments
+    /// port: The port, None to return every UDP datagram in the capture.
+    pub fn set_port(&mut self, port: Option<u16>) {
+        self.port = port;
+    }
+
+    /// Returns the destination port of the UDP datagrams returned, None if every UDP datagram is returned.
+    pub fn port(&self) -> Option<u16> {
+        self.port
+    }
+
+    /// Reads the next UDP datagram sent to the port from the capture, None at the end of the capture.
+    ///
+    /// # Errors
+    /// Io: Returned if the capture cannot be read.
+    ///
+    /// InvalidCapture: Returned if the capture is corrupt.
+    fn read_packet(&mut self) -> Result<Option<CapturedPacket>> {
+        loop {
+            let Some((timestamp, link_type, frame)) = self.read_frame()? else {
+                return Ok(None);
+            };
+            if let Some((src, dst, payload)) = parse_udp(link_type, &frame)
+                && self.port.is_none_or(|p| p == dst.port())
+            {
+                return Ok(Some(CapturedPacket {
+                    timestamp,
+                    src,
+                    dst,
+                    data: frame[payload].to_vec(),
+                }));
+            }
+        }
+    }
+
+    /// Reads the next captured frame, along with its timestamp and link type, None at the end of the capture.
+    ///
+    /// # Errors
+    /// See (read_packet)[CaptureReader::read_packet].
+    fn read_frame(&mut self) -> Result<Option<(Duration, u16, Vec<u8>)>> {
+        match self.format {
+            CaptureFormat::Pcap {
+                big_endian,
+                nanos,
+                link_type,
+            } => {
+                let mut header = [0; PCAP_RECORD_HEADER_LEN];
+                if !read_exact_or_eof(&mut self.reader, &mut header)? {
+                    return Ok(None);
+                }
+                let secs = u64::from(read_u32(big_endian, &header[0..4]));
+                let frac = read_u32(big_endian, &header[4..8]);
+                let captured_len = read_u32(big_endian, &header[8..12]) as usize;
+                if captured_len > CAPTURE_MAX_RECORD_LEN {
+                    return Err(invalid("Packet record too long"));
+                }
+                let mut frame = vec![0; captured_len];
+                self.reader.read_exact(&mut frame)?;
+
+                let frac_nanos = if nanos {
+                    frac
+                } else {
+                    frac.saturating_mul(1000)
+                };
+                let timestamp = Duration::from_secs(secs) + Duration::from_nanos(frac_nanos.into());
+                Ok(Some((timestamp, link_type, frame)))
+            }
+            CaptureFormat::Pcapng { .. } => self.read_pcapng_frame(),
+        }
+    }
+
+    /// Reads blocks from a pcapng capture until one holding a packet is found, returning the packet as for
+    /// (read_frame)[CaptureReader::read_frame].
+    ///
+    /// # Errors
+    /// See (read_packet)[CaptureReader::read_packet].
+    fn read_pcapng_frame(&mut self) -> Result<Option<(Duration, u16, Vec<u8>)>> {
+        loop {
+            let CaptureFormat::Pcapng {
+                big_endian,
+                ref mut interfaces,
+                ref mut last_timestamp,
+            } = self.format
+            else {
+                unreachable!("Only called for pcapng captures");
+            };
+
+            let mut block_type = [0; 4];
+            if !read_exact_or_eof(&mut self.reader, &mut block_type)? {
+                return Ok(None);
+            }
+            if BigEndian::read_u32(&block_type) == PCAPNG_SECTION_HEADER_BLOCK {
+                read_section_header(&mut self.reader, &mut self.format)?;
+                continue;
+            }
+            let block_type = read_u32(big_endian, &block_type);
+            let body = read_block_body(&mut self.reader, big_endian)?;
+
+            match block_type {
+                PCAPNG_INTERFACE_DESCRIPTION_BLOCK => {
+                    interfaces.push(parse_interface(big_endian, &body)?);
+                }
+                PCAPNG_ENHANCED_PACKET_BLOCK | PCAPNG_PACKET_BLOCK => {
+                    if body.len() < 20 {
+                        return Err(invalid("Packet block too short"));
+                    }
+                    let interface_id = if block_type == PCAPNG_ENHANCED_PACKET_BLOCK {
+                        read_u32(big_endian, &body[0..4]) as usize
+                    } else {
+                        read_u16(big_endian, &body[0..2]) as usize
+                    };
+                    let interface = *interfaces
+                        .get(interface_id)
+                        .ok_or_else(|| invalid("Packet captured on an undescribed interface"))?;
+                    let units = (u64::from(read_u32(big_endian, &body[4..8])) << 32)
+                        | u64::from(read_u32(big_endian, &body[8..12]));
+                    let captured_len = read_u32(big_endian, &body[12..16]) as usize;
+                    let frame = body
+                        .get(20..20 + captured_len)
+                        .ok_or_else(|| invalid("Packet block shorter than its packet"))?;
+
+                    *last_timestamp = interface.timestamp(units);
+                    return Ok(Some((*last_timestamp, interface.link_type, frame.to_vec())));
+                }
+                PCAPNG_SIMPLE_PACKET_BLOCK => {
+                    let interface = *interfaces
+                        .first()
+                        .ok_or_else(|| invalid("Packet captured on an undescribed interface"))?;
+                    if body.len() < 4 {
+                        return Err(invalid("Simple packet block too short"));
+                    }
+                    let original_len = read_u32(big_endian, &body[0..4]) as usize;
+                    let frame = &body[4..body.len().min(4 + original_len)];
+                    return Ok(Some((*last_timestamp, interface.link_type, frame.to_vec())));
+                }
+                // Statistics, name resolution and any other blocks don't hold packets.
+                _ => {}
+            }
+        }
+    }
+}
+
+impl<R: Read> Iterator for CaptureReader<R> {
+    type Item = Result<CapturedPacket>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+        let res = self.read_packet().transpose();
+        if !matches!(res, Some(Ok(_))) {
+            self.finished = true;
+        }
+        res
+    }
+}
+
+impl<R: Read> fmt::Debug for CaptureReader<R> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CaptureReader")
+            .field("format", &self.format)
+            .field("port", &self.port)
+            .field("finished", &self.finished)
+            .finish_non_exhaustive()
+    }
+}
+
+impl PcapngInterface {
+    /// Returns the time since the Unix epoch of the given number of timestamp units of this interface.
+    fn timestamp(&self, units: u64) -> Duration {
+        let secs = units / self.units_per_sec;
+        let nanos = (u128::from(units % self.units_per_sec) * 1_000_000_000
+            / u128::from(self.units_per_sec)) as u32;
+        let secs = secs.saturating_add_signed(self.offset_secs);
+        Duration::new(secs, nanos)
+    }
+}
+
+/// How the packets of a capture are replayed by a [`CaptureTransport`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ReplayTiming {
+    /// Each packet is received at the same time after the first as it was captured, replaying the capture at its original speed.
+    Original,
+
+    /// Each packet is received as soon as it is asked for.
+    #[default]
+    AsFastAsPossible,
+}
+
+/// A transport which receives the sACN packets of a packet capture, see the (module documentation)[crate::capture].
+///
+/// Every packet in the capture sent to the port of the transport is received, whichever multicast groups have been joined, as the
+/// receiver ignores universes it isn't listening to. The transport's [`ManualClock`] is advanced to the capture timestamp of each
+/// packet as it is received. Once every packet has been received, receiving returns an UnexpectedEof error. Sending is unsupported.
+pub struct CaptureTransport {
+    /// The packets still to be received and the progress of the replay.
+    state: Mutex<ReplayState>,
+
+    /// How the packets are replayed.
+    timing: ReplayTiming,
+
+    /// The clock advanced to the timestamp of each packet as it is received.
+    clock: ManualClock,
+
+    /// The address the transport appears to be bound to, the port is the port of the packets read from the capture.
+    addr: SocketAddr,
+}
+
+/// The progress of replaying a capture.
+struct ReplayState {
+    /// The capture being replayed.
+    packets: CaptureReader<Box<dyn Read + Send>>,
+
+    /// A packet read from the capture which isn't due to be received yet.
+    next: Option<CapturedPacket>,
+
+    /// The capture timestamp of the first packet and the real time it was received, set once the first packet is received.
+    start: Option<(Duration, Instant)>,
+
+    /// The latest capture timestamp the clock has been advanced to.
+    clock_timestamp: Option<Duration>,
+}
+
+impl CaptureTransport {
+    /// Opens the pcap or pcapng file at the given path for replay.
+    ///
+    /// # Arguments
+    /// path: The path of the capture.
+    ///
+    /// timing: Whether to replay at the original speed or as fast as possible.
+    ///
+    /// # Errors
+    /// See (CaptureReader::open)[CaptureReader::open].
+    pub fn open<P: AsRef<Path>>(path: P, timing: ReplayTiming) -> Result<CaptureTransport> {
+        CaptureTransport::new(BufReader::new(File::open(path)?), timing)
+    }
+
+    /// Creates a transport which replays the pcap or pcapng capture read from the given reader.
+    ///
+    /// # Arguments
+    /// reader: The capture.
+    ///
+    /// timing: Whether to replay at the original speed or as fast as possible.
+    ///
+    /// # Errors
+    /// See (CaptureReader::new)[CaptureReader::new].
+    pub fn new<R: Read + Send + 'static>(
+        reader: R,
+        timing: ReplayTiming,
+    ) -> Result<CaptureTransport> {
+        let boxed: Box<dyn Read + Send> = Box::new(reader);
+        Ok(CaptureTransport::from_reader(
+            CaptureReader::new(boxed)?,
+            timing,
+        ))
+    }
+
+    /// Creates a transport which replays the packets of the given reader, e.g. after changing the port of the packets it reads.
+    ///
+    /// # Arguments
+    /// packets: The reader of the capture.
+    ///
+    /// timing: Whether to replay at the original speed or as fast as possible.
+    pub fn from_reader(
+        packets: CaptureReader<Box<dyn Read + Send>>,
+        timing: ReplayTiming,
+    ) -> CaptureTransport {
+        let port = packets.port().unwrap_or(ACN_SDT_MULTICAST_PORT);
+        CaptureTransport {
+            state: Mutex::new(ReplayState {
+                packets,
+                next: None,
+                start: None,
+                clock_timestamp: None,
+            }),
+            timing,
+            clock: ManualClock::new(),
+            addr: SocketAddr::new(IpAddr::V4(Ipv4Addr::UNSPECIFIED), port),
+        }
+    }
+
+    /// Returns a handle to the clock which follows the capture timestamps of the packets received, to be set as the clock of the
+    /// receiver handling them. (SacnReceiver::with_capture)[crate::receive::SacnReceiver::with_capture] does this automatically.
+    pub fn clock(&self) -> ManualClock {
+        self.clock.clone()
+    }
+
+    /// Locks the progress of the replay.
+    fn lock(&self) -> MutexGuard<'_, ReplayState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl fmt::Debug for CaptureTransport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CaptureTransport")
+            .field("timing", &self.timing)
+            .field("clock", &self.clock)
+            .field("addr", &self.addr)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Transport for CaptureTransport {
+    fn local_addr(&self) -> io::Result<SocketAddr> {
+        Ok(self.addr)
+    }
+
+    fn send_to(&self, _packet: &[u8], _dst: SocketAddr) -> io::Result<usize> {
+        Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "Packets cannot be sent on a capture being replayed",
+        ))
+    }
+
+    fn recv_from(
+        &self,
+        buf: &mut [u8],
+        timeout: Option<Duration>,
+    ) -> io::Result<(usize, SocketAddr)> {
+        let mut state = self.lock();
+        let packet = match state.next.take() {
+            Some(p) => p,
+            None => match state.packets.next() {
+                Some(Ok(p)) => p,
+                Some(Err(SacnError::Io(e))) => return Err(e),
+                Some(Err(e)) => {
+                    return Err(io::Error::new(io::ErrorKind::InvalidData, e.to_string()));
+                }
+                None => {
+                    return Err(io::Error::new(
+                        io::ErrorKind::UnexpectedEof,
+                        "Every packet in the capture has been replayed",
+                    ));
+                }
+            },
+        };
+
+        if self.timing == ReplayTiming::Original {
+            let (first, start) = *state
+                .start
+                .get_or_insert((packet.timestamp, Instant::now()));
+            let due = start + packet.timestamp.saturating_sub(first);
+            let now = Instant::now();
+            if due > now {
+                match timeout {
+                    Some(t) if now + t < due => {
+                        // Not due within the timeout so it is kept for the next receive.
+                        state.next = Some(packet);
+                        drop(state);
+                        sleep(t);
+                        return Err(io::Error::new(
+                            io::ErrorKind::WouldBlock,
+                            "No packet due within the timeout",
+                        ));
+                    }
+                    _ => sleep(due - now),
+                }
+            }
+        }
+
+        // Captures can be slightly out of order, the clock never goes backwards.
+        if let Some(previous) = state.clock_timestamp {
+            self.clock
+                .advance(packet.timestamp.saturating_sub(previous));
+        }
+        state.clock_timestamp = Some(
+            state
+                .clock_timestamp
+                .map_or(packet.timestamp, |p| p.max(packet.timestamp)),
+        );
+
+        let len = packet.data.len().min(buf.len());
+        buf[..len].copy_from_slice(&packet.data[..len]);
+        Ok((len, packet.src))
+    }
+
+    fn join_multicast(&self, _group: IpAddr) -> io::Result<()> {
+        Ok(())
+    }
+
+    fn leave_multicast(&self, _group: IpAddr) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Reads exactly enough bytes to fill the given buffer, returning false if the reader was already at its end.
+///
+/// # Errors
+/// Io: Returned if reading fails or the reader ends part way through the buffer.
+fn read_exact_or_eof<R: Read>(reader: &mut R, buf: &mut [u8]) -> io::Result<bool> {
+    let mut read = 0;
+    while read < buf.len() {
+        match reader.read(&mut buf[read..]) {
+            Ok(0) if read == 0 => return Ok(false),
+            Ok(0) => {
+                return Err(io::Error::new(
+                    io::ErrorKind::UnexpectedEof,
+                    "Capture ends part way through a record",
+                ));
+            }
+            Ok(n) => read += n,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(true)
+}
+
+/// Reads the rest of a pcapng Section Header Block, after its block type, starting a new section of the given format.
+///
+/// # Errors
+/// InvalidCapture: Returned if the byte order magic isn't valid.
+fn read_section_header<R: Read>(reader: &mut R, format: &mut CaptureFormat) -> Result<()> {
+    let mut header = [0; 8];
+    reader.read_exact(&mut header)?;
+    let big_endian = match (
+        BigEndian::read_u32(&header[4..8]),
+        LittleEndian::read_u32(&header[4..8]),
+    ) {
+        (PCAPNG_BYTE_ORDER_MAGIC, _) => true,
+        (_, PCAPNG_BYTE_ORDER_MAGIC) => false,
+        _ => return Err(invalid("Invalid pcapng byte order magic")),
+    };
+    let total_len = read_u32(big_endian, &header[0..4]) as usize;
+    if !(16..=CAPTURE_MAX_RECORD_LEN).contains(&total_len) {
+        return Err(invalid("Invalid pcapng section header length"));
+    }
+    // The version, section length, options and trailing length aren't needed.
+    io::copy(&mut reader.take((total_len - 12) as u64), &mut io::sink())?;
+
+    *format = CaptureFormat::Pcapng {
+        big_endian,
+        interfaces: Vec::new(),
+        last_timestamp: Duration::ZERO,
+    };
+    Ok(())
+}
+
+/// Reads the body of a pcapng block after its block type, checking the trailing length and returning the body without either length.
+///
+/// # Errors
+/// InvalidCapture: Returned if the lengths of the block are invalid.
+fn read_block_body<R: Read>(reader: &mut R, big_endian: bool) -> Result<Vec<u8>> {
+    let mut len = [0; 4];
+    reader.read_exact(&mut len)?;
+    let total_len = read_u32(big_endian, &len) as usize;
+    if !(12..=CAPTURE_MAX_RECORD_LEN).contains(&total_len) || !total_len.is_multiple_of(4) {
+        return Err(invalid("Invalid pcapng block length"));
+    }
+    let mut body = vec![0; total_len - 8];
+    reader.read_exact(&mut body)?;
+    let trailing = body.split_off(total_len - 12);
+    if read_u32(big_endian, &trailing) as usize != total_len {
+        return Err(invalid("Mismatched pcapng block lengths"));
+    }
+    Ok(body)
+}
+
+/// Parses the body of a pcapng Interface Description Block.
+///
+/// # Errors
+/// InvalidCapture: Returned if the block is too short or has an unsupported timestamp resolution.
+fn parse_interface(big_endian: bool, body: &[u8]) -> Result<PcapngInterface> {
+    if body.len() < 8 {
+        return Err(invalid("Interface description block too short"));
+    }
+    let mut interface = PcapngInterface {
+        link_type: read_u16(big_endian, &body[0..2]),
+        units_per_sec: 1_000_000,
+        offset_secs: 0,
+    };
+
+    let mut options = &body[8..];
+    while options.len() >= 4 {
+        let code = read_u16(big_endian, &options[0..2]);
+        let len = read_u16(big_endian, &options[2..4]) as usize;
+        let Some(value) = options.get(4..4 + len) else {
+            break;
+        };
+        match code {
+            0 => break,
+            PCAPNG_IF_TSRESOL if len == 1 => {
+                let exponent = u32::from(value[0] & 0x7F);
+                let units = if value[0] & 0x80 == 0 {
+                    10u64.checked_pow(exponent)
+                } else {
+                    1u64.checked_shl(exponent)
+                };
+                interface.units_per_sec = units
+                    .filter(|u| *u > 0)
+                    .ok_or_else(|| invalid("Unsupported timestamp resolution"))?;
+            }
+            PCAPNG_IF_TSOFFSET if len == 8 => {
+                interface.offset_secs = if big_endian {
+                    BigEndian::read_i64(value)
+                } else {
+                    LittleEndian::read_i64(value)
+                };
+            }
+            _ => {}
+        }
+        // Option values are padded to 32 bits.
+        options = options.get(4 + len.div_ceil(4) * 4..).unwrap_or_default();
+    }
+    Ok(interface)
+}
+
+/// Parses a captured frame of the given link type, returning the source and destination of the UDP datagram it holds and the range of
+/// the frame holding the UDP payload, None if the frame doesn't hold a whole UDP datagram.
+fn parse_udp(
+    link_type: u16,
+    frame: &[u8],
+) -> Option<(SocketAddr, SocketAddr, std::ops::Range<usize>)> {
+    let ip_start = match link_type {
+        LINKTYPE_NULL | LINKTYPE_LOOP => 4,
+        LINKTYPE_RAW | LINKTYPE_IPV4 | LINKTYPE_IPV6 => 0,
+        t if LINKTYPE_RAW_ALT.contains(&t) => 0,
+        LINKTYPE_ETHERNET => {
+            let mut offset = 12;
+            let mut ether_type = BigEndian::read_u16(frame.get(offset..offset + 2)?);
+            while ETHERTYPE_VLAN.contains(&ether_type) {
+                offset += 4;
+                ether_type = BigEndian::read_u16(frame.get(offset..offset + 2)?);
+            }
+            if ether_type != ETHERTYPE_IPV4 && ether_type != ETHERTYPE_IPV6 {
+                return None;
+            }
+            offset + 2
+        }
+        LINKTYPE_LINUX_SLL => 16,
+        LINKTYPE_LINUX_SLL2 => 20,
+        _ => return None,
+    };
+    let ip = frame.get(ip_start..)?;
+
+    let (src_ip, dst_ip, udp_start): (IpAddr, IpAddr, usize) = match ip.first()? >> 4 {
+        4 => {
+            let header_len = usize::from(ip[0] & 0x0F) * 4;
+            let flags_fragment = BigEndian::read_u16(ip.get(6..8)?);
+            // More fragments set or a non-zero offset, the datagram isn't whole.
+            if header_len < 20 || *ip.get(9)? != IP_PROTOCOL_UDP || flags_fragment & 0x3FFF != 0 {
+                return None;
+            }
+            let src: [u8; 4] = ip.get(12..16)?.try_into().ok()?;
+            let dst: [u8; 4] = ip.get(16..20)?.try_into().ok()?;
+            (
+                Ipv4Addr::from(src).into(),
+                Ipv4Addr::from(dst).into(),
+                header_len,
+            )
+        }
+        6 => {
+            let src: [u8; 16] = ip.get(8..24)?.try_into().ok()?;
+            let dst: [u8; 16] = ip.get(24..40)?.try_into().ok()?;
+            let mut next_header = *ip.get(6)?;
+            let mut offset = 40;
+            while IPV6_EXTENSION_HEADERS.contains(&next_header) {
+                next_header = *ip.get(offset)?;
+                offset += (usize::from(*ip.get(offset + 1)?) + 1) * 8;
+            }
+            if next_header != IP_PROTOCOL_UDP {
+                return None;
+            }
+            (
+                Ipv6Addr::from(src).into(),
+                Ipv6Addr::from(dst).into(),
+                offset,
+            )
+        }
+        _ => return None,
+    };
+
+    let udp = ip.get(udp_start..udp_start + UDP_HEADER_LEN)?;
+    let src_port = BigEndian::read_u16(&udp[0..2]);
+    let dst_port = BigEndian::read_u16(&udp[2..4]);
+    let udp_len = usize::from(BigEndian::read_u16(&udp[4..6]));
+    let payload_start = ip_start + udp_start + UDP_HEADER_LEN;
+    let payload_end = ip_start + udp_start + udp_len;
+    if udp_len < UDP_HEADER_LEN || payload_end > frame.len() {
+        // Truncated when captured.
+        return None;
+    }
+
+    Some((
+        SocketAddr::new(src_ip, src_port),
+        SocketAddr::new(dst_ip, dst_port),
+        payload_start..payload_end,
+    ))
+}
+
+/// Reads a u16 in the given byte order.
+fn read_u16(big_endian: bool, buf: &[u8]) -> u16 {
+    if big_endian {
+        BigEndian::read_u16(buf)
+    } else {
+        LittleEndian::read_u16(buf)
+    }
+}
+
+/// Reads a u32 in the given byte order.
+fn read_u32(big_endian: bool, buf: &[u8]) -> u32 {
+    if big_endian {
+        BigEndian::read_u32(buf)
+    } else {
+        LittleEndian::read_u32(buf)
+    }
+}
+
+/// Returns an InvalidCapture error with the given message.
+fn invalid(msg: &str) -> SacnError {
+    SacnError::InvalidCapture(msg.to_string())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// Returns an IPv4 packet holding a UDP datagram with the given payload.
+    fn ipv4_udp(src: [u8; 4], dst: [u8; 4], dst_port: u16, payload: &[u8]) -> Vec<u8> {
+        let udp_len = (UDP_HEADER_LEN + payload.len()) as u16;
+        let mut packet = vec![0x45, 0, 0, 0, 0, 0, 0, 0, 64, IP_PROTOCOL_UDP, 0, 0];
+        packet[2..4].copy_from_slice(&(20 + udp_len).to_be_bytes());
+        packet.extend_from_slice(&src);
+        packet.extend_from_slice(&dst);
+        packet.extend_from_slice(&50000u16.to_be_bytes());
+        packet.extend_from_slice(&dst_port.to_be_bytes());
+        packet.extend_from_slice(&udp_len.to_be_bytes());
+        packet.extend_from_slice(&[0, 0]);
+        packet.extend_from_slice(payload);
+        packet
+    }
+
+    /// Returns an IPv6 packet with a hop-by-hop options header holding a UDP datagram with the given payload.
+    fn ipv6_udp(src: Ipv6Addr, dst: Ipv6Addr, payload: &[u8]) -> Vec<u8> {
+        let udp_len = (UDP_HEADER_LEN + payload.len()) as u16;
+        let mut packet = vec![0x60, 0, 0, 0];
+        packet.extend_from_slice(&(8 + udp_len).to_be_bytes());
+        packet.extend_from_slice(&[0, 1]);
+        packet.extend_from_slice(&src.octets());
+        packet.extend_from_slice(&dst.octets());
+        packet.extend_from_slice(&[IP_PROTOCOL_UDP, 0, 0, 0, 0, 0, 0, 0]);
+        packet.extend_from_slice(&50000u16.to_be_bytes());
+        packet.extend_from_slice(&ACN_SDT_MULTICAST_PORT.to_be_bytes());
+        packet.extend_from_slice(&udp_len.to_be_bytes());
+        packet.extend_from_slice(&[0, 0]);
+        packet.extend_from_slice(payload);
+        packet
+    }
+
+    /// Returns an Ethernet frame with the given VLAN tags holding the given IP packet.
+    fn ethernet(vlans: usize, ether_type: u16, ip: &[u8]) -> Vec<u8> {
+        let mut frame = vec![0; 12];
+        for _ in 0..vlans {
+            frame.extend_from_slice(&[0x81, 0x00, 0, 1]);
+        }
+        frame.extend_from_slice(&ether_type.to_be_bytes());
+        frame.extend_from_slice(ip);
+        frame
+    }
+
+    /// Returns a pcap capture of the given frames and their timestamps in microseconds, in the given byte order.
+    fn pcap(big_endian: bool, nanos: bool, link_type: u32, frames: &[(u64, Vec<u8>)]) -> Vec<u8> {
+        let u32_bytes = |v: u32| {
+            if big_endian {
+                v.to_be_bytes()
+            } else {
+                v.to_le_bytes()
+            }
+        };
+        let magic = if nanos {
+            PCAP_MAGIC_NANOS
+        } else {
+            PCAP_MAGIC_MICROS
+        };
+        let mut capture = u32_bytes(magic).to_vec();
+        capture.extend_from_slice(&[0; 12]);
+        capture.extend_from_slice(&u32_bytes(65535));
+        capture.extend_from_slice(&u32_bytes(link_type));
+        for (micros, frame) in frames {
+            let frac = if nanos {
+                micros % 1_000_000 * 1000
+            } else {
+                micros % 1_000_000
+            };
+            capture.extend_from_slice(&u32_bytes((micros / 1_000_000) as u32));
+            capture.extend_from_slice(&u32_bytes(frac as u32));
+            capture.extend_from_slice(&u32_bytes(frame.len() as u32));
+            capture.extend_from_slice(&u32_bytes(frame.len() as u32));
+            capture.extend_from_slice(frame);
+        }
+        capture
+    }
+
+    /// Returns a little endian pcapng block of the given type and body.
+    fn pcapng_block(block_type: u32, body: &[u8]) -> Vec<u8> {
+        let padded = body.len().div_ceil(4) * 4;
+        let total_len = (12 + padded) as u32;
+        let mut block = block_type.to_le_bytes().to_vec();
+        block.extend_from_slice(&total_len.to_le_bytes());
+        block.extend_from_slice(body);
+        block.resize(8 + padded, 0);
+        block.extend_from_slice(&total_len.to_le_bytes());
+        block
+    }
+
+    /// Returns a little endian pcapng section header block.
+    fn pcapng_section() -> Vec<u8> {
+        let mut body = PCAPNG_BYTE_ORDER_MAGIC.to_le_bytes().to_vec();
+        body.extend_from_slice(&[1, 0, 0, 0]);
+        body.extend_from_slice(&u64::MAX.to_le_bytes());
+        pcapng_block(PCAPNG_SECTION_HEADER_BLOCK, &body)
+    }
+
+    /// Returns a little endian pcapng interface description block with the given link type and timestamp resolution option.
+    fn pcapng_interface(link_type: u16, tsresol: Option<u8>) -> Vec<u8> {
+        let mut body = link_type.to_le_bytes().to_vec();
+        body.extend_from_slice(&[0, 0, 0, 0, 0, 0]);
+        if let Some(r) = tsresol {
+            body.extend_from_slice(&PCAPNG_IF_TSRESOL.to_le_bytes());
+            body.extend_from_slice(&1u16.to_le_bytes());
+            body.extend_from_slice(&[r, 0, 0, 0]);
+            body.extend_from_slice(&[0, 0, 0, 0]);
+        }
+        pcapng_block(PCAPNG_INTERFACE_DESCRIPTION_BLOCK, &body)
+    }
+
+    /// Returns a little endian pcapng enhanced packet block.
+    fn pcapng_packet(interface: u32, units: u64, frame: &[u8]) -> Vec<u8> {
+        let mut body = interface.to_le_bytes().to_vec();
+        body.extend_from_slice(&((units >> 32) as u32).to_le_bytes());
+        body.extend_from_slice(&(units as u32).to_le_bytes());
+        body.extend_from_slice(&(frame.len() as u32).to_le_bytes());
+        body.extend_from_slice(&(frame.len() as u32).to_le_bytes());
+        body.extend_from_slice(frame);
+        pcapng_block(PCAPNG_ENHANCED_PACKET_BLOCK, &body)
+    }
+
+    fn read_all(capture: Vec<u8>) -> Vec<CapturedPacket> {
+        CaptureReader::new(io::Cursor::new(capture))
+            .unwrap()
+            .collect::<Result<Vec<CapturedPacket>>>()
+            .unwrap()
+    }
+
+    /// pcap files in either byte order and timestamp resolution should give the UDP payloads sent to the ACN port, skipping others.
+    #[test]
+    fn test_read_pcap() {
+        let frames = vec![
+            (
+                1_500_000,
+                ethernet(
+                    0,
+                    ETHERTYPE_IPV4,
+                    &ipv4_udp([10, 0, 0, 1], [239, 255, 0, 1], 5568, &[1, 2]),
+                ),
+            ),
+            // Sent to another port.
+            (
+                1_600_000,
+                ethernet(
+                    0,
+                    ETHERTYPE_IPV4,
+                    &ipv4_udp([10, 0, 0, 1], [10, 0, 0, 2], 80, &[9]),
+                ),
+            ),
+            // Not IP.
+            (1_700_000, ethernet(0, 0x0806, &[0; 28])),
+            (
+                2_000_001,
+                ethernet(
+                    2,
+                    ETHERTYPE_IPV4,
+                    &ipv4_udp([10, 0, 0, 3], [10, 0, 0, 2], 5568, &[3]),
+                ),
+            ),
+        ];
+
+        for (big_endian, nanos) in [(false, false), (true, false), (false, true), (true, true)] {
+            let packets = read_all(pcap(big_endian, nanos, 1, &frames));
+            assert_eq!(packets.len(), 2);
+            assert_eq!(packets[0].timestamp, Duration::from_millis(1500));
+            assert_eq!(packets[0].src, "10.0.0.1:50000".parse().unwrap());
+            assert_eq!(packets[0].dst, "239.255.0.1:5568".parse().unwrap());
+            assert_eq!(packets[0].data, vec![1, 2]);
+            assert_eq!(packets[1].timestamp, Duration::from_micros(2_000_001));
+            assert_eq!(packets[1].data, vec![3]);
+        }
+
+        let mut all_ports =
+            CaptureReader::new(io::Cursor::new(pcap(false, false, 1, &frames))).unwrap();
+        all_ports.set_port(None);
+        assert_eq!(all_ports.count(), 3);
+    }
+
+    /// pcapng files should use the link type and timestamp resolution of the interface each packet was captured on.
+    #[test]
+    fn test_read_pcapng() {
+        let v6_src: Ipv6Addr = "fe80::1".parse().unwrap();
+        let v6_dst: Ipv6Addr = "ff18::8301".parse().unwrap();
+        let mut sll = vec![0; 14];
+        sll.extend_from_slice(&ETHERTYPE_IPV4.to_be_bytes());
+        sll.extend_from_slice(&ipv4_udp([10, 0, 0, 1], [239, 255, 0, 2], 5568, &[2]));
+
+        let mut capture = pcapng_section();
+        capture.extend(pcapng_interface(LINKTYPE_ETHERNET, None));
+        capture.extend(pcapng_interface(LINKTYPE_LINUX_SLL, Some(9)));
+        capture.extend(pcapng_packet(
+            0,
+            3_000_000,
+            &ethernet(0, ETHERTYPE_IPV6, &ipv6_udp(v6_src, v6_dst, &[1])),
+        ));
+        capture.extend(pcapng_block(5, &[0; 8]));
+        capture.extend(pcapng_packet(1, 3_500_000_000, &sll));
+        // A new section forgets the interfaces of the previous one.
+        capture.extend(pcapng_section());
+        capture.extend(pcapng_interface(LINKTYPE_RAW, Some(0x80 | 10)));
+        capture.extend(pcapng_packet(
+            0,
+            4 * 1024,
+            &ipv4_udp([10, 0, 0, 5], [10, 0, 0, 6], 5568, &[3]),
+        ));
+
+        let packets = read_all(capture);
+        assert_eq!(packets.len(), 3);
+        assert_eq!(packets[0].timestamp, Duration::from_secs(3));
+        assert_eq!(packets[0].src, SocketAddr::new(v6_src.into(), 50000));
+        assert_eq!(packets[0].dst, SocketAddr::new(v6_dst.into(), 5568));
+        assert_eq!(packets[0].data, vec![1]);
+        assert_eq!(packets[1].timestamp, Duration::from_millis(3500));
+        assert_eq!(packets[1].data, vec![2]);
+        assert_eq!(packets[2].timestamp, Duration::from_secs(4));
+        assert_eq!(packets[2].data, vec![3]);
+    }
+
+    /// Truncated and fragmented datagrams should be skipped while corrupt captures should give an InvalidCapture error.
+    #[test]
+    fn test_read_invalid_captures() {
+        let mut truncated = ipv4_udp([10, 0, 0, 1], [10, 0, 0, 2], 5568, &[1, 2, 3]);
+        truncated.truncate(truncated.len() - 1);
+        let mut fragment = ipv4_udp([10, 0, 0, 1], [10, 0, 0, 2], 5568, &[1]);
+        fragment[6] = 0x20;
+        let frames = vec![(0, truncated), (0, fragment)];
+        assert!(read_all(pcap(false, false, u32::from(LINKTYPE_RAW), &frames)).is_empty());
+
+        assert!(matches!(
+            CaptureReader::new(io::Cursor::new(vec![1, 2, 3, 4, 5])),
+            Err(SacnError::InvalidCapture(_))
+        ));
+        assert!(matches!(
+            CaptureReader::new(io::Cursor::new(Vec::new())),
+            Err(SacnError::InvalidCapture(_))
+        ));
+
+        // A packet on an interface which was never described.
+        let mut capture = pcapng_section();
+        capture.extend(pcapng_packet(0, 0, &[0; 4]));
+        let mut reader = CaptureReader::new(io::Cursor::new(capture)).unwrap();
+        assert!(matches!(
+            reader.next(),
+            Some(Err(SacnError::InvalidCapture(_)))
+        ));
+        assert!(reader.next().is_none());
+    }
+}
@@ -0,0 +1,113 @@
+// Copyright 2020 sacn Developers
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! Clocks that sources and receivers read the current time from for their protocol timing.
+//!
+//! Every timeout in ANSI E1.31-2018, such as the E131_NETWORK_DATA_LOSS_TIMEOUT after which a source is lost and the
+//! E131_UNIVERSE_DISCOVERY_INTERVAL between universe discovery packets, is measured using the [`Clock`] of the receiver or source. By
+//! default this is the [`SystemClock`]. A [`ManualClock`] only moves when told to, so the timeouts can be tested without waiting for
+//! them, for example with (SacnReceiver::set_clock)[crate::receive::SacnReceiver::set_clock].
+//!
+//! The clock doesn't affect how long a call waits for packets to arrive, a timeout given to
+//! (SacnReceiver::recv)[crate::receive::SacnReceiver::recv] is always real time.
+//!
+//! # Examples
+//!
+//! ```
+//! use sacn::clock::{Clock, ManualClock};
+//!
+//! use std::time::Duration;
+//!
+//! let clock = ManualClock::new();
+//! let start = clock.now();
+//!
+//! // Clones share the same time.
+//! clock.clone().advance(Duration::from_secs(3));
+//! assert_eq!(clock.now() - start, Duration::from_secs(3));
+//! ```
+
+use std::fmt;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// A source of the current time.
+///
+/// Implementations must be monotonic, the time returned must never be earlier than a time returned before.
+pub trait Clock: fmt::Debug + Send + Sync {
+    /// Returns the current time.
+    fn now(&self) -> Instant;
+}
+
+/// The clock of the operating system, this is the clock used by sources and receivers unless another is set.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+}
+
+/// A clock which only moves when advanced, for testing timeouts without waiting for them.
+///
+/// Cloning a ManualClock gives another handle to the same clock, so a test can keep a handle to advance the clock of a receiver or
+/// source it has been given to.
+#[derive(Clone, Debug)]
+pub struct ManualClock {
+    /// The current time of the clock, shared by all its handles.
+    now: Arc<Mutex<Instant>>,
+}
+
+impl ManualClock {
+    /// Creates a new clock, stopped at the current time of the system clock.
+    pub fn new() -> ManualClock {
+        ManualClock {
+            now: Arc::new(Mutex::new(Instant::now())),
+        }
+    }
+
+    /// Moves the clock forward by the given duration.
+    ///
+    /// # Arguments
+    /// duration: The time to move forward by.
+    pub fn advance(&self, duration: Duration) {
+        let mut now = self.now.lock().unwrap_or_else(|e| e.into_inner());
+        *now += duration;
+    }
+}
+
+impl Default for ManualClock {
+    fn default() -> Self {
+        ManualClock::new()
+    }
+}
+
+impl Clock for ManualClock {
+    fn now(&self) -> Instant {
+        *self.now.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// A manual clock should only move when advanced and all its handles should see the same time.
+    #[test]
+    fn test_manual_clock_advance() {
+        let clock = ManualClock::new();
+        let handle = clock.clone();
+        let start = clock.now();
+
+        std::thread::sleep(Duration::from_millis(10));
+        assert_eq!(clock.now(), start);
+
+        handle.advance(Duration::from_millis(2500));
+        assert_eq!(clock.now(), start + Duration::from_millis(2500));
+        assert_eq!(handle.now(), clock.now());
+    }
+}
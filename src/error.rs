@@ -194,6 +194,42 @@ pub mod errors {
         #[error("Source not found: {0}")]
         SourceNotFound(Uuid),
 
+        /// Attempted to use a network interface which couldn't be found, e.g. there is no interface with the given name.
+        ///
+        /// # Arguments
+        /// String: The name, index or address of the interface that was not found.
+        #[error("Network interface not found: {0}")]
+        InterfaceNotFound(String),
+
+        /// Attempted an operation which needs the UDP sockets of a source or receiver on one which uses a transport instead,
+        /// e.g. setting the multicast TTL of a source using an in-memory transport.
+        ///
+        /// # Arguments
+        /// String: A message describing the operation that was not supported.
+        #[error("Operation attempted is unsupported when using a transport: {0}")]
+        TransportUnsupported(String),
+
+        /// Attempted to read a packet capture which isn't a valid pcap or pcapng file, or uses a format which isn't supported.
+        ///
+        /// # Arguments
+        /// String: A message describing what was wrong with the capture.
+        #[error("The packet capture is invalid or unsupported: {0}")]
+        InvalidCapture(String),
+
+        /// Attempted to read a DMX recording which isn't valid, e.g. it wasn't written by a (Recorder)[crate::recording::Recorder] or is corrupt.
+        ///
+        /// # Arguments
+        /// String: A message describing what was wrong with the recording.
+        #[error("The recording is invalid or unsupported: {0}")]
+        InvalidRecording(String),
+
+        /// Attempted to play a recording at a speed which isn't a finite number greater than 0.
+        ///
+        /// # Arguments
+        /// f64: The speed that was given.
+        #[error("Playback speed must be a finite number greater than 0, speed provided: {0}")]
+        InvalidPlaybackSpeed(f64),
+
         /// Thrown to indicate that the operation attempted is unsupported on the current OS
         /// For example this is used to indicate that multicast-IPv6 isn't supported current on Windows.
         ///
@@ -222,6 +258,22 @@ pub mod errors {
         #[error("Universe list has length 0, must provide at least one universe")]
         UniverseListEmpty(),
 
+        /// Returned if a refresh rate of 0 frames per second is given, this would mean that data is never sent.
+        ///
+        /// # Arguments
+        /// u32: The refresh rate that was given.
+        #[error("Refresh rate must be at least 1 frame per second, refresh rate provided: {0}")]
+        InvalidRefreshRate(u32),
+
+        /// Returned if a keep-alive interval outwith the range allowed by ANSI E1.31-2018 Section 6.6.2 is given.
+        ///
+        /// # Arguments
+        /// Duration: The keep-alive interval that was given.
+        #[error(
+            "Keep-alive interval must be within the allowed range of [E131_KEEP_ALIVE_MIN_INTERVAL, E131_KEEP_ALIVE_MAX_INTERVAL], interval provided: {0:?}"
+        )]
+        InvalidKeepAliveInterval(std::time::Duration),
+
         /// Returned if the receiver has a source limit of 0
         #[error(
             "Source_limit has a value of Some(0) which would indicate this receiver can never receive from any source"
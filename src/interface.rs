@@ -0,0 +1,348 @@
+#![warn(missing_docs)]
+// Copyright 2020 sacn Developers
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! Selection of the network interfaces (NICs) that sACN is sent and received on.
+//!
+//! By default the operating system picks the interface used for multicast based on its routing table. On machines connected to several
+//! networks, e.g. separate show and office networks, the interfaces can instead be chosen explicitly by name, by index or by one of their
+//! addresses. A source given several interfaces sends each packet on all of them, e.g. to drive redundant primary and backup networks.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use sacn::interface::NetworkInterface;
+//! use sacn::receive::SacnReceiver;
+//! use sacn::packet::ACN_SDT_MULTICAST_PORT;
+//!
+//! use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+//!
+//! let addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::UNSPECIFIED), ACN_SDT_MULTICAST_PORT);
+//!
+//! let mut dmx_rcv = SacnReceiver::with_ip(addr, None).unwrap();
+//! dmx_rcv.set_interfaces(&[NetworkInterface::Name("eth1".to_string())]).unwrap();
+//! dmx_rcv.listen_universes(&[1]).unwrap();
+//! ```
+//!
+//! ```no_run
+//! use sacn::interface::NetworkInterface;
+//! use sacn::source::SacnSource;
+//!
+//! let mut src = SacnSource::new_v4("Source").unwrap();
+//! src.set_interfaces(&[
+//!     NetworkInterface::Name("primary".to_string()),
+//!     NetworkInterface::Name("backup".to_string()),
+//! ])
+//! .unwrap();
+//! ```
+
+use crate::error::errors::*;
+
+use std::fmt;
+use std::net::IpAddr;
+use std::str::FromStr;
+
+/// A network interface chosen by name, index or address.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum NetworkInterface {
+    /// The interface with the given name, e.g. `eth1`.
+    Name(String),
+
+    /// The interface with the given index as used by the operating system, e.g. the scope id of an IPv6 link-local address.
+    Index(u32),
+
+    /// The interface which has the given address assigned to it.
+    Addr(IpAddr),
+}
+
+impl NetworkInterface {
+    /// Returns the index of the interface as used by the operating system.
+    ///
+    /// # Errors
+    /// InterfaceNotFound: Returned if there is no interface with the given name or address.
+    ///
+    /// OsOperationUnsupported: Returned if interfaces cannot be looked up by name or address on the current OS.
+    pub fn index(&self) -> Result<u32> {
+        match self {
+            NetworkInterface::Index(index) => Ok(*index),
+            NetworkInterface::Name(name) => name_to_index(name),
+            NetworkInterface::Addr(addr) => addr_to_index(*addr),
+        }
+    }
+
+    /// Returns an address of the given IP version which is assigned to the interface.
+    ///
+    /// # Arguments
+    /// ipv6: True if an IPv6 address should be returned, false for an IPv4 address.
+    ///
+    /// # Errors
+    /// InterfaceNotFound: Returned if there is no such interface or it has no address of the given IP version.
+    ///
+    /// OsOperationUnsupported: Returned if the addresses of interfaces cannot be looked up on the current OS, the address of an interface
+    ///     given by an address of the same IP version is always available.
+    pub fn addr(&self, ipv6: bool) -> Result<IpAddr> {
+        if let NetworkInterface::Addr(addr) = self
+            && addr.is_ipv6() == ipv6
+        {
+            return Ok(*addr);
+        }
+
+        index_to_addr(self.index()?, ipv6)?.ok_or_else(|| {
+            SacnError::InterfaceNotFound(format!(
+                "{self} (no {} address)",
+                if ipv6 { "IPv6" } else { "IPv4" }
+            ))
+        })
+    }
+}
+
+impl fmt::Display for NetworkInterface {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            NetworkInterface::Name(name) => write!(f, "{name}"),
+            NetworkInterface::Index(index) => write!(f, "#{index}"),
+            NetworkInterface::Addr(addr) => write!(f, "{addr}"),
+        }
+    }
+}
+
+/// Parses an interface in the form it is displayed: an address, `#` followed by an index, or otherwise a name.
+impl FromStr for NetworkInterface {
+    type Err = SacnError;
+
+    fn from_str(s: &str) -> Result<NetworkInterface> {
+        if s.is_empty() {
+            return Err(SacnError::InterfaceNotFound(s.to_string()));
+        }
+        if let Ok(addr) = s.parse() {
+            return Ok(NetworkInterface::Addr(addr));
+        }
+        match s.strip_prefix('#') {
+            Some(index) => index
+                .parse()
+                .map(NetworkInterface::Index)
+                .map_err(|_| SacnError::InterfaceNotFound(s.to_string())),
+            None => Ok(NetworkInterface::Name(s.to_string())),
+        }
+    }
+}
+
+/// Returns the name of the interface with the given index, or None if there is no such interface or names cannot be looked up on
+/// the current OS.
+///
+/// # Arguments
+/// index: The index of the interface, e.g. the interface a packet was received on, see (DMXData)[crate::receive::DMXData].
+#[cfg(not(target_os = "windows"))]
+pub fn interface_name(index: u32) -> Option<String> {
+    let mut name = [0 as libc::c_char; libc::IF_NAMESIZE];
+    // SAFETY: name is IF_NAMESIZE bytes long as required by if_indextoname, which null terminates the name it writes on success.
+    let res = unsafe { libc::if_indextoname(index, name.as_mut_ptr()) };
+    if res.is_null() {
+        return None;
+    }
+    // SAFETY: if_indextoname succeeded so name contains a null terminated string.
+    let name = unsafe { std::ffi::CStr::from_ptr(name.as_ptr()) };
+    Some(name.to_string_lossy().into_owned())
+}
+
+/// Returns the name of the interface with the given index, or None if there is no such interface or names cannot be looked up on
+/// the current OS.
+///
+/// Interface names cannot currently be looked up on Windows so this always returns None.
+#[cfg(target_os = "windows")]
+pub fn interface_name(_index: u32) -> Option<String> {
+    None
+}
+
+/// Returns the index of the interface with the given name.
+///
+/// # Errors
+/// InterfaceNotFound: Returned if there is no interface with the given name.
+#[cfg(not(target_os = "windows"))]
+fn name_to_index(name: &str) -> Result<u32> {
+    let c_name =
+        std::ffi::CString::new(name).map_err(|_| SacnError::InterfaceNotFound(name.to_string()))?;
+    // SAFETY: c_name is a valid null terminated string for the duration of the call.
+    match unsafe { libc::if_nametoindex(c_name.as_ptr()) } {
+        0 => Err(SacnError::InterfaceNotFound(name.to_string())),
+        index => Ok(index),
+    }
+}
+
+/// Returns the index of the interface with the given name.
+///
+/// # Errors
+/// OsOperationUnsupported: Interfaces cannot currently be looked up by name on Windows.
+#[cfg(target_os = "windows")]
+fn name_to_index(_name: &str) -> Result<u32> {
+    Err(SacnError::OsOperationUnsupported(
+        "Looking up network interfaces by name is currently unsupported on Windows".to_string(),
+    ))
+}
+
+/// Returns the index of the interface which has the given address assigned to it.
+///
+/// # Errors
+/// InterfaceNotFound: Returned if no interface has the given address.
+///
+/// Io: Returned if the interfaces cannot be listed.
+#[cfg(not(target_os = "windows"))]
+fn addr_to_index(addr: IpAddr) -> Result<u32> {
+    find_ifaddr(|index, a| (a == addr).then_some(index))?
+        .ok_or_else(|| SacnError::InterfaceNotFound(addr.to_string()))
+}
+
+/// Returns the first address of the given IP version assigned to the interface with the given index, None if there isn't one.
+///
+/// # Errors
+/// Io: Returned if the interfaces cannot be listed.
+#[cfg(not(target_os = "windows"))]
+fn index_to_addr(index: u32, ipv6: bool) -> Result<Option<IpAddr>> {
+    find_ifaddr(|i, a| (i == index && a.is_ipv6() == ipv6).then_some(a))
+}
+
+/// Calls f with the interface index and address of each IPv4 or IPv6 address assigned to an interface until it returns Some.
+///
+/// # Errors
+/// Io: Returned if the interfaces cannot be listed.
+#[cfg(not(target_os = "windows"))]
+fn find_ifaddr<T>(mut f: impl FnMut(u32, IpAddr) -> Option<T>) -> Result<Option<T>> {
+    let mut ifaddrs: *mut libc::ifaddrs = std::ptr::null_mut();
+    // SAFETY: getifaddrs writes a pointer to a linked list which is freed below.
+    if unsafe { libc::getifaddrs(&mut ifaddrs) } != 0 {
+        return Err(std::io::Error::last_os_error().into());
+    }
+
+    let mut found = None;
+    let mut cur = ifaddrs;
+    while !cur.is_null() && found.is_none() {
+        // SAFETY: cur is a non-null entry of the list returned by getifaddrs which hasn't been freed yet.
+        let ifa = unsafe { &*cur };
+        // SAFETY: ifa_addr is either null or points to a sockaddr of the family it states, ifa_name is a null terminated string.
+        let entry = unsafe {
+            if ifa.ifa_addr.is_null() {
+                None
+            } else {
+                sockaddr_ip(ifa.ifa_addr).map(|a| (libc::if_nametoindex(ifa.ifa_name), a))
+            }
+        };
+        if let Some((index, addr)) = entry
+            && index != 0
+        {
+            found = f(index, addr);
+        }
+        cur = ifa.ifa_next;
+    }
+
+    // SAFETY: ifaddrs was returned by getifaddrs and isn't used after this.
+    unsafe { libc::freeifaddrs(ifaddrs) };
+
+    Ok(found)
+}
+
+/// Returns the index of the interface which has the given address assigned to it.
+///
+/// # Errors
+/// OsOperationUnsupported: Interfaces cannot currently be looked up by address on Windows.
+#[cfg(target_os = "windows")]
+fn addr_to_index(_addr: IpAddr) -> Result<u32> {
+    Err(SacnError::OsOperationUnsupported(
+        "Looking up network interfaces by address is currently unsupported on Windows".to_string(),
+    ))
+}
+
+/// Returns the first address of the given IP version assigned to the interface with the given index.
+///
+/// # Errors
+/// OsOperationUnsupported: The addresses of interfaces cannot currently be looked up on Windows.
+#[cfg(target_os = "windows")]
+fn index_to_addr(_index: u32, _ipv6: bool) -> Result<Option<IpAddr>> {
+    Err(SacnError::OsOperationUnsupported(
+        "Looking up the addresses of network interfaces is currently unsupported on Windows"
+            .to_string(),
+    ))
+}
+
+/// Returns the IP address held in the given sockaddr, or None if it isn't an IPv4 or IPv6 address.
+///
+/// # Safety
+/// addr must point to a valid sockaddr of the size indicated by its family.
+#[cfg(not(target_os = "windows"))]
+unsafe fn sockaddr_ip(addr: *const libc::sockaddr) -> Option<IpAddr> {
+    // SAFETY: the caller guarantees that addr points to a sockaddr of the size indicated by its family.
+    unsafe {
+        match (*addr).sa_family as libc::c_int {
+            libc::AF_INET => {
+                let a = &*addr.cast::<libc::sockaddr_in>();
+                Some(IpAddr::from(u32::from_be(a.sin_addr.s_addr).to_be_bytes()))
+            }
+            libc::AF_INET6 => {
+                let a = &*addr.cast::<libc::sockaddr_in6>();
+                Some(IpAddr::from(a.sin6_addr.s6_addr))
+            }
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use std::net::Ipv4Addr;
+
+    /// The loopback interface should be found by name and by address and map back to the same name.
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_loopback_interface_index() {
+        let index = NetworkInterface::Name("lo".to_string()).index().unwrap();
+        assert_eq!(
+            NetworkInterface::Addr(IpAddr::V4(Ipv4Addr::LOCALHOST))
+                .index()
+                .unwrap(),
+            index
+        );
+        assert_eq!(NetworkInterface::Index(index).index().unwrap(), index);
+        assert_eq!(interface_name(index).as_deref(), Some("lo"));
+        assert_eq!(
+            NetworkInterface::Index(index).addr(false).unwrap(),
+            IpAddr::V4(Ipv4Addr::LOCALHOST)
+        );
+    }
+
+    /// Interfaces should parse from the form they are displayed in.
+    #[test]
+    fn test_interface_from_str() {
+        for interface in [
+            NetworkInterface::Name("eth1".to_string()),
+            NetworkInterface::Index(3),
+            NetworkInterface::Addr(IpAddr::V4(Ipv4Addr::new(10, 0, 0, 1))),
+            NetworkInterface::Addr("fe80::1".parse().unwrap()),
+        ] {
+            assert_eq!(
+                interface.to_string().parse::<NetworkInterface>().unwrap(),
+                interface
+            );
+        }
+        assert!("#eth".parse::<NetworkInterface>().is_err());
+        assert!("".parse::<NetworkInterface>().is_err());
+    }
+
+    /// Interfaces that don't exist should be reported as not found.
+    #[test]
+    #[cfg(not(target_os = "windows"))]
+    fn test_unknown_interface() {
+        match NetworkInterface::Name("sacn-no-such-if".to_string()).index() {
+            Err(SacnError::InterfaceNotFound(name)) => assert_eq!(name, "sacn-no-such-if"),
+            res => panic!("Expected InterfaceNotFound, got {res:?}"),
+        }
+        match NetworkInterface::Addr(IpAddr::V4(Ipv4Addr::new(192, 0, 2, 254))).index() {
+            Err(SacnError::InterfaceNotFound(_)) => {}
+            res => panic!("Expected InterfaceNotFound, got {res:?}"),
+        }
+    }
+}
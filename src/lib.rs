@@ -216,3 +216,21 @@ pub mod source;
 
 /// The receive module handles the receiving of sACN on the network.
 pub mod receive;
+
+/// The merge module handles merging the data from multiple sources sending the same universe.
+pub mod merge;
+
+/// The interface module handles choosing the network interfaces sACN is sent and received on.
+pub mod interface;
+
+/// The transport module allows sACN to be sent and received over something other than the default UDP sockets, e.g. an in-memory network.
+pub mod transport;
+
+/// The clock module provides the time that sources and receivers measure protocol timeouts with, allowing it to be controlled in tests.
+pub mod clock;
+
+/// The capture module reads sACN from pcap and pcapng packet captures, replaying them into a receiver.
+pub mod capture;
+
+/// The recording module records DMX data received by a receiver to a file and plays recordings back out through a source.
+pub mod recording;
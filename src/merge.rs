@@ -0,0 +1,591 @@
+#![warn(missing_docs)]
+// Copyright 2020 sacn Developers
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! Merging / arbitration of DMX data from multiple sources sending the same universe as per ANSI E1.31-2018 Section 6.2.3.
+//!
+//! A SacnReceiver uses a [`Merger`] to combine the data from every source currently sending a universe into the single set of
+//! values that is returned. Several common merge algorithms are provided and a custom algorithm can be used either by implementing
+//! the trait or by passing a closure.
+//!
+//! # Examples
+//!
+//! ```
+//! use sacn::merge::{LtpMerger, MergeSource};
+//! use sacn::receive::SacnReceiver;
+//! use sacn::packet::ACN_SDT_MULTICAST_PORT;
+//!
+//! use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+//!
+//! let addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), ACN_SDT_MULTICAST_PORT);
+//! let mut dmx_rcv = SacnReceiver::with_ip(addr, None).unwrap();
+//!
+//! // Use one of the provided merge algorithms.
+//! dmx_rcv.set_merger(LtpMerger::new());
+//!
+//! // Or a closure, in this case always using the data from the source which sent most recently.
+//! dmx_rcv.set_merger(|sources: &[MergeSource]| Ok(sources[sources.len() - 1].data.clone()));
+//! ```
+
+use crate::error::errors::*;
+use crate::packet::E131_NETWORK_DATA_LOSS_TIMEOUT;
+use crate::receive::DMXData;
+
+use std::cmp::max;
+use std::collections::HashMap;
+use std::time::Instant;
+
+/// The uuid crate is used for identifying the sources being merged.
+use uuid::Uuid;
+
+/// The data from a single source contributing to a universe that is being merged.
+#[derive(Clone, Copy, Debug)]
+pub struct MergeSource<'a> {
+    /// The latest data received from the source for the universe.
+    pub data: &'a DMXData,
+
+    /// The latest per-address priority (start code 0xDD) data received from the source for the universe if there is any.
+    /// This includes the start code so index i is the priority of data.values[i]. A priority of 0 (or an index past the end)
+    /// indicates that the source isn't sending that slot.
+    pub per_address_priority: Option<&'a [u8]>,
+}
+
+/// A merge algorithm used to combine the data of all the sources sending the same universe into a single result as per
+/// ANSI E1.31-2018 Section 6.2.3.4 Requirements for Merging and Arbitrating.
+///
+/// A merger is given mutable access to itself so it can keep state between merges, for example when each slot last changed.
+///
+/// This is implemented for any `FnMut(&[MergeSource]) -> Result<DMXData>` closure so a closure can be used directly.
+pub trait Merger: Send {
+    /// Merges the data of the given sources into a single result.
+    ///
+    /// # Arguments
+    /// sources: The sources contributing to the universe in the order their data was received, oldest first. This always contains at
+    ///     least one source and all sources are for the same universe.
+    ///
+    /// # Errors
+    /// Should return a DmxMergeError if the data cannot be merged.
+    fn merge(&mut self, sources: &[MergeSource]) -> Result<DMXData>;
+
+    /// Called by the receiver when it no longer has any sources for the given universe, either because the universe was muted or
+    /// because all its sources terminated, timed out or were released by the data loss policy. A merger which keeps state per universe
+    /// should forget the state of the universe.
+    ///
+    /// By default this does nothing.
+    fn forget_universe(&mut self, _universe: u16) {}
+}
+
+impl<F> Merger for F
+where
+    F: FnMut(&[MergeSource]) -> Result<DMXData> + Send,
+{
+    fn merge(&mut self, sources: &[MergeSource]) -> Result<DMXData> {
+        self(sources)
+    }
+}
+
+/// Adapts a function which merges 2 pieces of DMXData into a Merger, this is what (set_merge_fn)[SacnReceiver::set_merge_fn] uses.
+///
+/// Only the sources with the highest priority are used and they are merged one at a time in the order they were received, the
+/// oldest first. If any source has per-address priority data then the sources are instead merged as per [`PriorityHtpMerger`].
+#[derive(Clone, Copy, Debug)]
+pub struct PairwiseMerger {
+    /// The function used to merge 2 DMXData, the first argument is the existing data and the second the new data.
+    func: fn(&DMXData, &DMXData) -> Result<DMXData>,
+}
+
+impl PairwiseMerger {
+    /// Creates a new PairwiseMerger which uses the given function to merge data.
+    ///
+    /// # Arguments
+    /// func: The merge function to use, should take the existing and then the new DMXData and return the merged result.
+    pub const fn new(func: fn(&DMXData, &DMXData) -> Result<DMXData>) -> PairwiseMerger {
+        PairwiseMerger { func }
+    }
+}
+
+impl Merger for PairwiseMerger {
+    fn merge(&mut self, sources: &[MergeSource]) -> Result<DMXData> {
+        if sources.iter().any(|s| s.per_address_priority.is_some()) {
+            return priority_htp_merge(sources);
+        }
+
+        let highest_priority = match sources.iter().map(|s| s.data.priority).max() {
+            Some(p) => p,
+            None => return Err(SacnError::DmxMergeError()),
+        };
+
+        let mut contenders = sources
+            .iter()
+            .filter(|s| s.data.priority == highest_priority);
+
+        // Must be at least 1 source with the highest priority.
+        let mut res: DMXData = contenders.next().unwrap().data.clone();
+        for s in contenders {
+            res = (self.func)(&res, s.data)?;
+        }
+
+        Ok(res)
+    }
+}
+
+/// Performs a highest takes precedence (HTP) merge of all sources regardless of their priority, each slot is the highest value
+/// any source is sending for that slot.
+///
+/// All sources must have the same start code otherwise a DmxMergeError is returned.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct HtpMerger;
+
+impl HtpMerger {
+    /// Creates a new HtpMerger.
+    pub fn new() -> HtpMerger {
+        HtpMerger
+    }
+}
+
+impl Merger for HtpMerger {
+    fn merge(&mut self, sources: &[MergeSource]) -> Result<DMXData> {
+        let start_code = common_start_code(sources)?;
+
+        if sources.len() == 1 {
+            return Ok(sources[0].data.clone());
+        }
+
+        let mut values: Vec<u8> = vec![0; max_len(sources)];
+        values[0] = start_code;
+        for s in sources {
+            for (v, n) in values.iter_mut().zip(s.data.values.iter()).skip(1) {
+                *v = max(*v, *n);
+            }
+        }
+
+        Ok(merged_data(sources, values))
+    }
+}
+
+/// Performs a merge where the highest priority source wins each slot and if multiple sources have the same highest priority for a slot
+/// then the highest value is used (HTP).
+///
+/// The priority of a source for a slot is taken from its per-address priority (start code 0xDD) data if it has any, with a priority of
+/// 0 meaning that the source isn't sending that slot. Otherwise the packet priority of the source is used for every slot, with a minimum
+/// of 1 so that the source is always considered. A slot which no source is sending is output as 0.
+///
+/// All sources must have the same start code otherwise a DmxMergeError is returned.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct PriorityHtpMerger;
+
+impl PriorityHtpMerger {
+    /// Creates a new PriorityHtpMerger.
+    pub fn new() -> PriorityHtpMerger {
+        PriorityHtpMerger
+    }
+}
+
+impl Merger for PriorityHtpMerger {
+    fn merge(&mut self, sources: &[MergeSource]) -> Result<DMXData> {
+        priority_htp_merge(sources)
+    }
+}
+
+/// Performs a latest takes precedence (LTP) merge, each slot takes the value from the source which most recently changed that slot.
+///
+/// Priority is ignored. A source which hasn't changed a slot since it started sending is treated as having changed it when it was first
+/// seen. If multiple sources changed a slot at the same time then the source whose data was received last is used.
+///
+/// The merger keeps the previous values of each source to detect changes, sources which haven't been seen for
+/// E131_NETWORK_DATA_LOSS_TIMEOUT are forgotten as are universes the receiver no longer has any sources for.
+///
+/// All sources must have the same start code otherwise a DmxMergeError is returned.
+#[derive(Clone, Debug, Default)]
+pub struct LtpMerger {
+    /// The last values and per-slot change times of each source, keyed by universe and then source CID.
+    sources: HashMap<u16, HashMap<Option<Uuid>, LtpSourceState>>,
+}
+
+/// The state kept by the LtpMerger for each source on each universe.
+#[derive(Clone, Debug)]
+struct LtpSourceState {
+    /// The last values received from the source.
+    values: Vec<u8>,
+
+    /// The time each slot last changed value.
+    changed: Vec<Instant>,
+
+    /// The time the source's data was last seen by the merger.
+    last_seen: Instant,
+}
+
+impl LtpMerger {
+    /// Creates a new LtpMerger with no record of any sources.
+    pub fn new() -> LtpMerger {
+        LtpMerger {
+            sources: HashMap::new(),
+        }
+    }
+}
+
+impl Merger for LtpMerger {
+    fn merge(&mut self, sources: &[MergeSource]) -> Result<DMXData> {
+        let start_code = common_start_code(sources)?;
+
+        // Times are compared against the newest data rather than the system clock as the receiver's clock may be a different one.
+        let newest = sources.iter().map(|s| s.data.recv_timestamp).max();
+        let universe_srcs = self.sources.entry(sources[0].data.universe).or_default();
+        if let Some(newest) = newest {
+            universe_srcs.retain(|_cid, s| {
+                newest.saturating_duration_since(s.last_seen) < E131_NETWORK_DATA_LOSS_TIMEOUT
+            });
+        }
+
+        for s in sources {
+            let data = s.data;
+            let state = universe_srcs
+                .entry(data.src_cid)
+                .or_insert_with(|| LtpSourceState {
+                    values: data.values.clone(),
+                    changed: vec![data.recv_timestamp; data.values.len()],
+                    last_seen: data.recv_timestamp,
+                });
+
+            for (i, v) in data.values.iter().enumerate() {
+                if state.values.get(i) != Some(v) {
+                    if i < state.changed.len() {
+                        state.changed[i] = data.recv_timestamp;
+                    } else {
+                        state.changed.push(data.recv_timestamp);
+                    }
+                }
+            }
+            state.values.clone_from(&data.values);
+            state.changed.truncate(data.values.len());
+            state.last_seen = max(state.last_seen, data.recv_timestamp);
+        }
+
+        if sources.len() == 1 {
+            return Ok(sources[0].data.clone());
+        }
+
+        let mut values: Vec<u8> = vec![0; max_len(sources)];
+        values[0] = start_code;
+        for (i, v) in values.iter_mut().enumerate().skip(1) {
+            let mut latest: Option<Instant> = None;
+            for s in sources {
+                if let (Some(level), Some(state)) =
+                    (s.data.values.get(i), universe_srcs.get(&s.data.src_cid))
+                {
+                    let changed = state.changed[i];
+                    if latest.is_none_or(|l| changed >= l) {
+                        latest = Some(changed);
+                        *v = *level;
+                    }
+                }
+            }
+        }
+
+        Ok(merged_data(sources, values))
+    }
+
+    fn forget_universe(&mut self, universe: u16) {
+        self.sources.remove(&universe);
+    }
+}
+
+/// Uses the data from a single chosen source, identified by its CID, whenever that source is sending the universe.
+///
+/// If the chosen source isn't sending the universe then the other sources are merged as per [`PriorityHtpMerger`].
+#[derive(Clone, Copy, Debug)]
+pub struct FollowCidMerger {
+    /// The CID of the source to follow.
+    cid: Uuid,
+}
+
+impl FollowCidMerger {
+    /// Creates a new FollowCidMerger which follows the source with the given CID.
+    ///
+    /// # Arguments
+    /// cid: The CID of the source to follow.
+    pub fn new(cid: Uuid) -> FollowCidMerger {
+        FollowCidMerger { cid }
+    }
+
+    /// Returns the CID of the source being followed.
+    pub fn cid(&self) -> Uuid {
+        self.cid
+    }
+}
+
+impl Merger for FollowCidMerger {
+    fn merge(&mut self, sources: &[MergeSource]) -> Result<DMXData> {
+        match sources.iter().find(|s| s.data.src_cid == Some(self.cid)) {
+            Some(s) => Ok(s.data.clone()),
+            None => priority_htp_merge(sources),
+        }
+    }
+}
+
+/// Merges the given sources as described by [`PriorityHtpMerger`].
+///
+/// # Errors
+/// Returns a DmxMergeError if there are no sources or the sources have different start codes.
+pub(crate) fn priority_htp_merge(sources: &[MergeSource]) -> Result<DMXData> {
+    let start_code = common_start_code(sources)?;
+
+    let mut values: Vec<u8> = vec![0; max_len(sources)];
+    values[0] = start_code;
+
+    for (slot, value) in values.iter_mut().enumerate().skip(1) {
+        let mut best_priority: u8 = 0;
+        for s in sources {
+            let level = match s.data.values.get(slot) {
+                Some(l) => *l,
+                None => continue, // Source isn't sending this slot.
+            };
+
+            let priority = match s.per_address_priority {
+                Some(p) => p.get(slot).copied().unwrap_or(0),
+                None => max(s.data.priority, 1),
+            };
+
+            if priority == 0 {
+                continue;
+            }
+
+            if priority > best_priority || (priority == best_priority && level > *value) {
+                best_priority = priority;
+                *value = level;
+            }
+        }
+    }
+
+    Ok(merged_data(sources, values))
+}
+
+/// Returns the start code shared by all the given sources.
+///
+/// # Errors
+/// Returns a DmxMergeError if there are no sources, a source has no values or the sources have different start codes.
+fn common_start_code(sources: &[MergeSource]) -> Result<u8> {
+    let start_code = match sources.first().and_then(|s| s.data.values.first()) {
+        Some(s) => *s,
+        None => return Err(SacnError::DmxMergeError()),
+    };
+
+    if sources
+        .iter()
+        .any(|s| s.data.values.first() != Some(&start_code))
+    {
+        return Err(SacnError::DmxMergeError());
+    }
+
+    Ok(start_code)
+}
+
+/// Returns the length of the longest data of the given sources.
+fn max_len(sources: &[MergeSource]) -> usize {
+    sources
+        .iter()
+        .map(|s| s.data.values.len())
+        .max()
+        .unwrap_or(0)
+}
+
+/// Creates the DMXData for the result of merging the given sources with the given values.
+///
+/// The result has the highest packet priority of the sources, is marked as preview if any source is, has the timestamp of the newest data
+/// and only has a src_cid, src_addr, src_name and recv_interface if there is a single source.
+fn merged_data(sources: &[MergeSource], values: Vec<u8>) -> DMXData {
+    DMXData {
+        universe: sources[0].data.universe,
+        values,
+        sync_uni: sources[0].data.sync_uni,
+        priority: sources.iter().map(|s| s.data.priority).max().unwrap_or(0),
+        src_cid: if sources.len() == 1 {
+            sources[0].data.src_cid
+        } else {
+            None
+        },
+        preview: sources.iter().any(|s| s.data.preview),
+        src_addr: if sources.len() == 1 {
+            sources[0].data.src_addr
+        } else {
+            None
+        },
+        src_name: if sources.len() == 1 {
+            sources[0].data.src_name.clone()
+        } else {
+            None
+        },
+        recv_interface: if sources.len() == 1 {
+            sources[0].data.recv_interface
+        } else {
+            None
+        },
+        recv_timestamp: sources
+            .iter()
+            .map(|s| s.data.recv_timestamp)
+            .max()
+            .unwrap_or_else(Instant::now),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use std::time::Duration;
+
+    /// Creates DMXData on universe 1 from the given source with the given priority and values received at the given time.
+    fn dmx(cid: Uuid, priority: u8, values: &[u8], recv_timestamp: Instant) -> DMXData {
+        DMXData {
+            universe: 1,
+            values: values.to_vec(),
+            sync_uni: 0,
+            priority,
+            src_cid: Some(cid),
+            preview: false,
+            src_addr: None,
+            src_name: None,
+            recv_interface: None,
+            recv_timestamp,
+        }
+    }
+
+    /// Wraps the given data as MergeSources without per-address priorities.
+    fn srcs(data: &[DMXData]) -> Vec<MergeSource<'_>> {
+        data.iter()
+            .map(|d| MergeSource {
+                data: d,
+                per_address_priority: None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_htp_merger_ignores_priority() {
+        let now = Instant::now();
+        let data = [
+            dmx(Uuid::new_v4(), 200, &[0, 10, 50], now),
+            dmx(Uuid::new_v4(), 100, &[0, 40, 20, 5], now),
+        ];
+
+        let res = HtpMerger::new().merge(&srcs(&data)).unwrap();
+        assert_eq!(res.values, vec![0, 40, 50, 5]);
+        assert_eq!(res.src_cid, None);
+    }
+
+    #[test]
+    fn test_htp_merger_different_start_codes() {
+        let now = Instant::now();
+        let data = [
+            dmx(Uuid::new_v4(), 100, &[0, 10], now),
+            dmx(Uuid::new_v4(), 100, &[0x17, 40], now),
+        ];
+
+        match HtpMerger::new().merge(&srcs(&data)) {
+            Err(SacnError::DmxMergeError()) => {
+                // Expected error returned
+            }
+            x => panic!("Unexpected result, {:?}", x),
+        }
+    }
+
+    #[test]
+    fn test_priority_htp_merger() {
+        let now = Instant::now();
+        let data = [
+            dmx(Uuid::new_v4(), 100, &[0, 10, 50, 0], now),
+            dmx(Uuid::new_v4(), 150, &[0, 5, 20], now),
+            dmx(Uuid::new_v4(), 150, &[0, 1, 30], now),
+        ];
+
+        let res = PriorityHtpMerger::new().merge(&srcs(&data)).unwrap();
+        assert_eq!(res.values, vec![0, 5, 30, 0]);
+        assert_eq!(res.priority, 150);
+    }
+
+    #[test]
+    fn test_ltp_merger_latest_change_wins() {
+        let start = Instant::now();
+        let cid_a = Uuid::new_v4();
+        let cid_b = Uuid::new_v4();
+        let mut merger = LtpMerger::new();
+
+        let data = [
+            dmx(cid_a, 100, &[0, 10, 10], start),
+            dmx(cid_b, 100, &[0, 20, 20], start + Duration::from_millis(1)),
+        ];
+        assert_eq!(merger.merge(&srcs(&data)).unwrap().values, vec![0, 20, 20]);
+
+        // Source A changes only slot 2, that slot should now follow A while slot 1 stays with B.
+        let data = [
+            dmx(cid_b, 100, &[0, 20, 20], start + Duration::from_millis(1)),
+            dmx(cid_a, 100, &[0, 10, 5], start + Duration::from_millis(2)),
+        ];
+        assert_eq!(merger.merge(&srcs(&data)).unwrap().values, vec![0, 20, 5]);
+
+        // Source B sends again without changing anything so nothing should change.
+        let data = [
+            dmx(cid_a, 100, &[0, 10, 5], start + Duration::from_millis(2)),
+            dmx(cid_b, 100, &[0, 20, 20], start + Duration::from_millis(3)),
+        ];
+        assert_eq!(merger.merge(&srcs(&data)).unwrap().values, vec![0, 20, 5]);
+    }
+
+    #[test]
+    fn test_ltp_merger_forget_universe() {
+        let start = Instant::now();
+        let mut merger = LtpMerger::new();
+        let data = [
+            dmx(Uuid::new_v4(), 100, &[0, 10], start),
+            dmx(Uuid::new_v4(), 100, &[0, 20], start),
+        ];
+        merger.merge(&srcs(&data)).unwrap();
+        assert!(merger.sources.contains_key(&1));
+
+        merger.forget_universe(2);
+        assert!(merger.sources.contains_key(&1));
+
+        merger.forget_universe(1);
+        assert!(merger.sources.is_empty());
+    }
+
+    #[test]
+    fn test_follow_cid_merger() {
+        let now = Instant::now();
+        let cid = Uuid::new_v4();
+        let data = [
+            dmx(Uuid::new_v4(), 200, &[0, 255, 255], now),
+            dmx(cid, 50, &[0, 1, 2], now),
+        ];
+
+        let mut merger = FollowCidMerger::new(cid);
+        let res = merger.merge(&srcs(&data)).unwrap();
+        assert_eq!(res.values, vec![0, 1, 2]);
+        assert_eq!(res.src_cid, Some(cid));
+
+        let res = merger.merge(&srcs(&data[..1])).unwrap();
+        assert_eq!(res.values, vec![0, 255, 255]);
+    }
+
+    #[test]
+    fn test_closure_merger() {
+        let now = Instant::now();
+        let data = [
+            dmx(Uuid::new_v4(), 100, &[0, 1], now),
+            dmx(Uuid::new_v4(), 100, &[0, 2], now),
+        ];
+
+        let mut calls = 0;
+        let mut merger = |sources: &[MergeSource]| {
+            calls += 1;
+            Ok(sources[0].data.clone())
+        };
+
+        assert_eq!(merger.merge(&srcs(&data)).unwrap().values, vec![0, 1]);
+        assert_eq!(calls, 1);
+    }
+}
@@ -263,6 +263,29 @@ const E131_SYNC_FRAMING_LAYER_RESERVE_FIELD_LENGTH: usize = 2;
 // Length as per ANSI E1.31-2018 Section 4, Table 4-3.
 const E131_DISCOVERY_FRAMING_LAYER_RESERVE_FIELD_LENGTH: usize = 4;
 
+/// The index of the sequence number field within a packed data packet, used by the source to update the sequence number of a packet
+/// without packing it again. As per ANSI E1.31-2018 Section 4, Table 4-1.
+pub(crate) const E131_DATA_PACKET_SEQ_NUM_INDEX: usize = E131_PREAMBLE_SIZE as usize
+    + E131_CID_END_INDEX
+    + E131_PDU_LENGTH_FLAGS_LENGTH
+    + E131_FRAMING_LAYER_VECTOR_LENGTH
+    + E131_SOURCE_NAME_FIELD_LENGTH
+    + E131_PRIORITY_FIELD_LENGTH
+    + E131_SYNC_ADDR_FIELD_LENGTH;
+
+/// The index of the property values (starting with the start code) within a packed data packet, used by the source to update the
+/// levels of a packet without packing it again. As per ANSI E1.31-2018 Section 4, Table 4-1.
+pub(crate) const E131_DATA_PACKET_PROPERTY_VALUES_INDEX: usize = E131_DATA_PACKET_SEQ_NUM_INDEX
+    + E131_SEQ_NUM_FIELD_LENGTH
+    + E131_OPTIONS_FIELD_LENGTH
+    + E131_UNIVERSE_FIELD_LENGTH
+    + E131_PDU_LENGTH_FLAGS_LENGTH
+    + E131_DATA_PACKET_DMP_LAYER_VECTOR_FIELD_LENGTH
+    + E131_DATA_PACKET_DMP_LAYER_ADDRESS_DATA_FIELD_LENGTH
+    + E131_DATA_PACKET_DMP_LAYER_FIRST_PROPERTY_ADDRESS_FIELD_LENGTH
+    + E131_DATA_PACKET_DMP_LAYER_ADDRESS_INCREMENT_FIELD_LENGTH
+    + E131_DATA_PACKET_DMP_LAYER_PROPERTY_VALUE_COUNT_FIELD_LENGTH;
+
 /// The initial/starting sequence number used.
 pub const STARTING_SEQUENCE_NUMBER: u8 = 0;
 
@@ -310,12 +333,37 @@ pub const ACN_SDT_MULTICAST_PORT: u16 = 5568;
 /// The payload capacity for a sacn packet, for DMX data this would translate to 512 frames + a startcode byte.
 pub const UNIVERSE_CHANNEL_CAPACITY: usize = 513;
 
+/// The start code used for standard DMX512-A level data (the NULL START Code) as per ANSI E1.31-2018 Section 7.4.
+pub const E131_NULL_START_CODE: u8 = 0x00;
+
+/// The start code used for per-address priority data, each slot holds the priority (0-200) of the corresponding DMX slot with 0
+/// indicating that the source isn't sending that slot. This is the widely used ETC convention registered with ESTA for DMX512-A.
+pub const E131_PER_ADDRESS_PRIORITY_START_CODE: u8 = 0xDD;
+
 /// The synchronisation universe/address of packets which do not require synchronisation as specified in section 6.2.4.1 of ANSI E1.31-2018.
 pub const NO_SYNC_UNIVERSE: u16 = 0;
 
 /// The timeout before data loss is assumed for an E131 source, as defined in Appendix A of ANSI E1.31-2018.
 pub const E131_NETWORK_DATA_LOSS_TIMEOUT: Duration = Duration::from_millis(2500);
 
+/// The sampling period after a receiver starts listening to a universe, during which it learns the active sources before acting on data,
+/// as described in section 6.2.3.1 of ANSI E1.31-2018.
+pub const E131_SAMPLING_PERIOD: Duration = Duration::from_millis(1500);
+
+/// The maximum refresh rate (frames per second) of a full DMX512-A universe as specified in ANSI E1.11, sources shouldn't send data faster
+/// than this as per ANSI E1.31-2018 Section 6.6.1.
+pub const DMX512_MAX_REFRESH_RATE: u32 = 44;
+
+/// The number of additional copies of the last packet a source sends once the data stops changing before reducing to the keep-alive rate,
+/// as per ANSI E1.31-2018 Section 6.6.2.
+pub const E131_KEEP_ALIVE_REPEAT_COUNT: usize = 3;
+
+/// The shortest interval between packets that a source sending unchanged data may use, as per ANSI E1.31-2018 Section 6.6.2.
+pub const E131_KEEP_ALIVE_MIN_INTERVAL: Duration = Duration::from_millis(800);
+
+/// The longest interval between packets that a source sending unchanged data may use, as per ANSI E1.31-2018 Section 6.6.2.
+pub const E131_KEEP_ALIVE_MAX_INTERVAL: Duration = Duration::from_millis(1000);
+
 /// The timeout before a discovered source is assumed to be lost as defined in section 12.2 of ANSI E1.31-2018.
 pub const UNIVERSE_DISCOVERY_SOURCE_TIMEOUT: Duration = E131_NETWORK_DATA_LOSS_TIMEOUT;
 
@@ -731,7 +779,8 @@ macro_rules! impl_data_packet_framing_layer {
             /// If transmission on this universe is terminated.
             pub stream_terminated: bool,
 
-            /// Force synchronization if no synchronization packets are received.
+            /// Force synchronization if no synchronization packets are received, if set a receiver which has lost synchronisation may act
+            /// on this data without waiting for synchronisation to resume as per ANSI E1.31-2018 Section 6.2.6.
             pub force_synchronization: bool,
 
             /// The universe DMX data is transmitted for.
@@ -1574,13 +1623,10 @@ mod test {
     #[test]
     fn test_universe_to_ip_ipv4_out_range_low() {
         match universe_to_ipv4_multicast_addr(0) {
-            Ok(_) => assert!(
-                false,
-                "Universe to ipv4 multicast allowed below minimum allowed universe"
-            ),
+            Ok(_) => panic!("Universe to ipv4 multicast allowed below minimum allowed universe"),
             Err(e) => match e {
-                SacnError::IllegalUniverse(_) => assert!(true),
-                _ => assert!(false, "Unexpected error type returned"),
+                SacnError::IllegalUniverse(_) => {}
+                _ => panic!("Unexpected error type returned"),
             },
         }
     }
@@ -1588,13 +1634,10 @@ mod test {
     #[test]
     fn test_universe_to_ip_ipv4_out_range_high() {
         match universe_to_ipv4_multicast_addr(E131_MAX_MULTICAST_UNIVERSE + 10) {
-            Ok(_) => assert!(
-                false,
-                "Universe to ipv4 multicast allowed above maximum allowed universe"
-            ),
+            Ok(_) => panic!("Universe to ipv4 multicast allowed above maximum allowed universe"),
             Err(e) => match e {
-                SacnError::IllegalUniverse(_) => assert!(true),
-                _ => assert!(false, "Unexpected error type returned"),
+                SacnError::IllegalUniverse(_) => {}
+                _ => panic!("Unexpected error type returned"),
             },
         }
     }
@@ -1606,7 +1649,7 @@ mod test {
 
         assert!(res.as_socket_ipv6().unwrap().ip().is_multicast());
 
-        let low_16: u16 = (((val / 256) as u16) << 8) | ((val % 256) as u16);
+        let low_16: u16 = ((val / 256) << 8) | (val % 256);
 
         assert_eq!(
             res.as_socket_ipv6().unwrap(),
@@ -1626,7 +1669,7 @@ mod test {
 
         assert!(res.as_socket_ipv6().unwrap().ip().is_multicast());
 
-        let low_16: u16 = (((val / 256) as u16) << 8) | ((val % 256) as u16);
+        let low_16: u16 = ((val / 256) << 8) | (val % 256);
 
         assert_eq!(
             res.as_socket_ipv6().unwrap(),
@@ -1645,8 +1688,8 @@ mod test {
 
         assert!(res.as_socket_ipv6().unwrap().ip().is_multicast());
 
-        let low_16: u16 = (((E131_MAX_MULTICAST_UNIVERSE / 256) as u16) << 8)
-            | ((E131_MAX_MULTICAST_UNIVERSE % 256) as u16);
+        let low_16: u16 =
+            ((E131_MAX_MULTICAST_UNIVERSE / 256) << 8) | (E131_MAX_MULTICAST_UNIVERSE % 256);
 
         assert_eq!(
             res.as_socket_ipv6().unwrap(),
@@ -1665,8 +1708,8 @@ mod test {
 
         assert!(res.as_socket_ipv6().unwrap().ip().is_multicast());
 
-        let low_16: u16 = (((E131_MIN_MULTICAST_UNIVERSE / 256) as u16) << 8)
-            | ((E131_MIN_MULTICAST_UNIVERSE % 256) as u16);
+        let low_16: u16 =
+            ((E131_MIN_MULTICAST_UNIVERSE / 256) << 8) | (E131_MIN_MULTICAST_UNIVERSE % 256);
 
         assert_eq!(
             res.as_socket_ipv6().unwrap(),
@@ -1682,13 +1725,10 @@ mod test {
     #[test]
     fn test_universe_to_ip_ipv6_out_range_low() {
         match universe_to_ipv6_multicast_addr(0) {
-            Ok(_) => assert!(
-                false,
-                "Universe to ipv4 multicast allowed below minimum allowed universe"
-            ),
+            Ok(_) => panic!("Universe to ipv4 multicast allowed below minimum allowed universe"),
             Err(e) => match e {
-                SacnError::IllegalUniverse(_) => assert!(true),
-                _ => assert!(false, "Unexpected error type returned"),
+                SacnError::IllegalUniverse(_) => {}
+                _ => panic!("Unexpected error type returned"),
             },
         }
     }
@@ -1696,13 +1736,10 @@ mod test {
     #[test]
     fn test_universe_to_ip_ipv6_out_range_high() {
         match universe_to_ipv6_multicast_addr(E131_MAX_MULTICAST_UNIVERSE + 10) {
-            Ok(_) => assert!(
-                false,
-                "Universe to ipv4 multicast allowed above maximum allowed universe"
-            ),
+            Ok(_) => panic!("Universe to ipv4 multicast allowed above maximum allowed universe"),
             Err(e) => match e {
-                SacnError::IllegalUniverse(_) => assert!(true),
-                _ => assert!(false, "Unexpected error type returned"),
+                SacnError::IllegalUniverse(_) => {}
+                _ => panic!("Unexpected error type returned"),
             },
         }
     }
@@ -1723,4 +1760,35 @@ mod test {
         assert_eq!(E131_DISCOVERY_UNIVERSE, 64214);
         assert_eq!(ACN_SDT_MULTICAST_PORT, 5568);
     }
+
+    /// The indices the source uses to update a packed data packet in place must point at the sequence number and property values.
+    #[test]
+    fn test_data_packet_field_indices() {
+        let property_values = [0, 1, 2, 3];
+        let packet = AcnRootLayerProtocol {
+            pdu: E131RootLayer {
+                cid: Uuid::new_v4(),
+                data: E131RootLayerData::DataPacket(DataPacketFramingLayer {
+                    source_name: "Source_A".into(),
+                    priority: 100,
+                    synchronization_address: 7962,
+                    sequence_number: 154,
+                    preview_data: false,
+                    stream_terminated: false,
+                    force_synchronization: false,
+                    universe: 1,
+                    data: DataPacketDmpLayer {
+                        property_values: property_values.to_vec().into(),
+                    },
+                }),
+            },
+        };
+
+        let buf = packet.pack_alloc().unwrap();
+        assert_eq!(buf[E131_DATA_PACKET_SEQ_NUM_INDEX], 154);
+        assert_eq!(
+            &buf[E131_DATA_PACKET_PROPERTY_VALUES_INDEX..],
+            &property_values
+        );
+    }
 }
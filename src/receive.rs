@@ -17,8 +17,10 @@
 // received, if a discovery packet is received but there are more pages the source won't be discovered until all the pages are received.
 // If a page is lost this therefore means the source update / discovery in its entirety will be lost - implementation detail.
 
+#[cfg(target_os = "linux")]
+use socket2::SockAddrStorage;
 /// Socket 2 used for the underlying UDP socket that sACN is sent over.
-use socket2::{Domain, Protocol, SockAddr, Socket, Type};
+use socket2::{Domain, InterfaceIndexOrAddress, Protocol, SockAddr, Socket, Type};
 
 /// Mass import as a very large amount of packet is used here (upwards of 20 items) and this is much cleaner.
 use crate::packet::{E131RootLayerData::*, *};
@@ -26,14 +28,24 @@ use crate::packet::{E131RootLayerData::*, *};
 /// Same reasoning as for packet meaning all sacn errors are imported.
 use crate::error::errors::*;
 
+/// Merging of the data from multiple sources sending the same universe.
+use crate::capture::CaptureTransport;
+use crate::clock::{Clock, SystemClock};
+use crate::interface::NetworkInterface;
+use crate::merge::{MergeSource, Merger, PairwiseMerger};
+use crate::transport::Transport;
+
 /// The uuid crate is used for working with/generating UUIDs which sACN uses as part of the cid field in the protocol.
 /// This is used for uniquely identifying sources when counting sequence numbers.
 use uuid::Uuid;
 
 use std::cmp::{Ordering, max};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet, VecDeque};
+#[cfg(not(any(target_os = "linux", target_os = "android")))]
 use std::io::Read;
 use std::net::{Ipv4Addr, SocketAddr};
+use std::ops::RangeInclusive;
+use std::sync::Arc;
 use std::time::{Duration, Instant};
 use std::{fmt, io};
 
@@ -57,6 +69,17 @@ const AF_INET6: i32 = 23;
 #[cfg(target_os = "windows")]
 use std::net::IpAddr;
 
+/// Raw file descriptor access required to receive the interface packets arrive on.
+#[cfg(not(target_os = "windows"))]
+use std::os::fd::AsRawFd;
+
+/// Asynchronous receiving built on tokio, only available with the async feature.
+#[cfg(feature = "async")]
+mod asynchronous;
+
+#[cfg(feature = "async")]
+pub use asynchronous::AsyncSacnReceiver;
+
 /// The default size of the buffer used to receive E1.31 packets.
 /// 1143 bytes is biggest packet required as per Section 8 of ANSI E1.31-2018, aligned to 64 bit that is 1144 bytes.
 pub const RCV_BUF_DEFAULT_SIZE: usize = 1144;
@@ -80,6 +103,15 @@ const ANNOUNCE_STREAM_TERMINATION_DEFAULT: bool = false;
 /// The default value of the announce_timeout flag.
 const ANNOUNCE_TIMEOUT_DEFAULT: bool = false;
 
+/// The minimum interval between checks of the timeouts of every source and universe while packets are being received, see
+/// (check_timeouts_if_due)[SacnReceiver::check_timeouts_if_due]. This keeps the cost of receiving a packet independent of the number of
+/// sources and universes being received.
+const TIMEOUT_CHECK_INTERVAL: Duration = Duration::from_millis(100);
+
+/// The interval between the frames produced while fading out the data of a lost source, see (DataLossPolicy::Fade)[DataLossPolicy::Fade].
+/// Approximately the maximum refresh rate of DMX512.
+const DATA_LOSS_FADE_FRAME_INTERVAL: Duration = Duration::from_millis(25);
+
 /// If a packet for a universe is waiting to be synchronised and then another packet is received with the same universe and synchronisation address
 /// this situation must be handled. By default the implementation discards the lowest priority packet and if equal priority it discards the oldest
 /// packet as per ANSI E1.31-2018 Section 6.2.3.
@@ -89,6 +121,14 @@ const ANNOUNCE_TIMEOUT_DEFAULT: bool = false;
 const DEFAULT_MERGE_FUNC: fn(&DMXData, &DMXData) -> Result<DMXData> =
     discard_lowest_priority_then_previous;
 
+/// The merger used by a receiver for unsynchronised data from multiple sources unless another is set, see (set_merger)[SacnReceiver::set_merger].
+/// Data waiting for synchronisation is merged by DEFAULT_MERGE_FUNC instead.
+///
+/// Only the sources with the highest priority are used and if several sources share it then each slot takes the highest value (HTP), so the
+/// output is stable rather than alternating between the sources as their packets arrive, as per ANSI E1.31-2018 Section 6.2.3. While any
+/// source sends per-address priority (start code 0xDD) data the sources are merged slot-by-slot as described by (PairwiseMerger)[PairwiseMerger].
+pub const DEFAULT_MERGER: PairwiseMerger = PairwiseMerger::new(htp_merge_any_sync);
+
 /// Holds a universes worth of DMX data.
 #[derive(Debug)]
 pub struct DMXData {
@@ -114,6 +154,19 @@ pub struct DMXData {
     /// Indicates if the data is marked as 'preview' data indicating it is for use by visualisers etc. as per ANSI E1.31-2018 Section 6.2.6.
     pub preview: bool,
 
+    /// The network address that the data was received from, this may be useful for identifying devices which share a CID.
+    /// As with src_cid a value of None indicates that there is no clear source, for example if the data of 2 or more sources has been merged.
+    pub src_addr: Option<SocketAddr>,
+
+    /// The human readable name of the source of the data from the framing layer of the packet, no protocol guarantee this will be unique.
+    /// As with src_cid a value of None indicates that there is no clear source, for example if the data of 2 or more sources has been merged.
+    pub src_name: Option<String>,
+
+    /// The index of the network interface that the data was received on, see (interface_name)[crate::interface::interface_name].
+    /// A value of None indicates that the interface isn't known, which is always the case on OSes other than Linux, or that there is
+    /// no clear source as with src_cid.
+    pub recv_interface: Option<u32>,
+
     /// The timestamp that the data was received.
     pub recv_timestamp: Instant,
 }
@@ -151,8 +204,8 @@ pub struct DMXData {
 /// }
 /// ```
 pub struct SacnReceiver {
-    /// The SacnNetworkReceiver used for handling communication with UDP / Network / Transport layer.
-    receiver: SacnNetworkReceiver,
+    /// The network layer used for handling communication with UDP / Network / Transport layer.
+    receiver: ReceiverNetwork,
 
     /// Data that hasn't been passed up yet as it is waiting e.g. due to universe synchronisation.
     /// Key is the universe. A receiver may not have more than one packet waiting per data_universe.
@@ -161,15 +214,29 @@ pub struct SacnReceiver {
     /// 1 or more bits of data wait for 1 sync.
     waiting_data: HashMap<u16, DMXData>,
 
+    /// The most recent unsynchronised DMX (NULL START Code) data received from each source on each universe.
+    /// Key is the universe and then the CID of the source. This is used to arbitrate / merge between multiple sources sending
+    /// the same universe as per ANSI E1.31-2018 Section 6.2.3.
+    source_data: HashMap<u16, HashMap<Uuid, DMXData>>,
+
+    /// The most recent per-address priority (start code 0xDD) data received from each source on each universe.
+    /// Key is the universe and then the CID of the source. When present this is used for slot-by-slot arbitration of the
+    /// DMX data in source_data.
+    source_priorities: HashMap<u16, HashMap<Uuid, DMXData>>,
+
     /// Universes that this receiver is currently listening for.
     universes: Vec<u16>,
 
     /// Sacn sources that have been discovered by this receiver through universe discovery packets.
     discovered_sources: Vec<DiscoveredSacnSource>,
 
-    /// The merge function used by this receiver if DMXData for the same universe and synchronisation universe is received while there
-    /// is already DMXData waiting for that universe and synchronisation address.
-    merge_func: fn(&DMXData, &DMXData) -> Result<DMXData>,
+    /// The merger used by this receiver if DMXData for the same universe and synchronisation universe is received while there
+    /// is already DMXData waiting for that universe and synchronisation address, or if multiple sources are sending unsynchronised
+    /// data for the same universe.
+    ///
+    /// None until a merger is set, in which case DEFAULT_MERGE_FUNC is used for the waiting data and DEFAULT_MERGER for the
+    /// unsynchronised data.
+    merger: Option<Box<dyn Merger>>,
 
     /// Sacn sources that have been partially discovered by only some of their universes being discovered so far with more pages to go.
     partially_discovered_sources: Vec<DiscoveredSacnSource>,
@@ -194,6 +261,101 @@ pub struct SacnReceiver {
 
     /// Flag which indicates if an UniverseTimeout error should be thrown if it is detected that a source has timed out.
     announce_timeout: bool,
+
+    /// Events which have occurred but not yet been returned by (recv_event)[recv_event], oldest first.
+    pending_events: VecDeque<ReceiveEvent>,
+
+    /// The sampling period applied to universes when they are first listened to, None means no sampling period is used.
+    sampling_period: Option<Duration>,
+
+    /// The universes which are currently in their sampling period and the time at which the sampling period of each ends.
+    sampling_universes: HashMap<u16, Instant>,
+
+    /// The data loss policy of each universe, universes without a policy silently stop using the data of lost sources.
+    data_loss_policies: HashMap<u16, DataLossPolicy>,
+
+    /// The data of sources which have stopped sending a universe but are still used as per the data loss policy of the universe.
+    /// Key is the universe and then the CID of the source.
+    lost_sources: HashMap<u16, HashMap<Uuid, LostSource>>,
+
+    /// The time that the last synchronisation packet was received for each synchronisation address.
+    /// Used to detect when synchronisation has been lost as per ANSI E1.31-2018 Section 6.2.6.
+    sync_last_recv: HashMap<u16, Instant>,
+
+    /// The latest state of each universe being listened to which has had data acted on, key is the universe.
+    universe_states: HashMap<u16, UniverseState>,
+
+    /// The network interfaces that multicast groups are joined on, empty to use the interface of the bound address.
+    interfaces: Vec<NetworkInterface>,
+
+    /// The clock that protocol timeouts are measured with, see (set_clock)[SacnReceiver::set_clock].
+    clock: Arc<dyn Clock>,
+
+    /// The time of the clock when the timeouts of every source and universe were last checked, None if they haven't been since the
+    /// clock was set.
+    last_timeout_check: Option<Instant>,
+}
+
+/// The current state of a universe being received, this is the latest data for the universe which was acted on.
+///
+/// See (universe_state)[SacnReceiver::universe_state].
+#[derive(Clone, Debug)]
+pub struct UniverseState {
+    /// The latest DMX (NULL START code) data for the universe, after synchronisation and merging.
+    pub data: DMXData,
+
+    /// The time the data was last updated.
+    pub last_updated: Instant,
+
+    /// The CIDs of the sources whose data took part in producing the data, for merged data this is every source arbitrated between.
+    /// This is empty if the data was released by a data loss policy.
+    pub sources: Vec<Uuid>,
+}
+
+/// What a receiver does with the data of a source which stops sending a universe for E131_NETWORK_DATA_LOSS_TIMEOUT, see
+/// (set_data_loss_policy)[SacnReceiver::set_data_loss_policy].
+///
+/// Each policy produces the resulting universe data itself as (Data)[ReceiveEvent::Data] so the output is always defined.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DataLossPolicy {
+    /// Keep using the last data received from the source forever, until the source starts sending again.
+    Hold,
+
+    /// Keep using the last data received from the source for the given time and then release it as per (Release)[DataLossPolicy::Release].
+    ///
+    /// # Arguments
+    /// Duration: How long to keep using the data of the lost source.
+    HoldFor(Duration),
+
+    /// Fade the last data received from the source linearly to zero over the given time, producing a frame approximately every 25ms,
+    /// and then release it as per (Release)[DataLossPolicy::Release].
+    ///
+    /// # Arguments
+    /// Duration: The length of the fade.
+    Fade(Duration),
+
+    /// Stop using the data of the source immediately, switching to the next highest priority source if there are other sources sending
+    /// the universe or releasing the universe to all zeros if not.
+    Release,
+}
+
+/// The last data of a source which has stopped sending a universe, kept as required by the data loss policy of the universe.
+#[derive(Clone, Debug)]
+struct LostSource {
+    /// The last DMX data received from the source.
+    data: DMXData,
+
+    /// The last per-address priority data received from the source, if any.
+    per_address_priority: Option<DMXData>,
+
+    /// The data loss policy of the universe when the source was lost.
+    policy: DataLossPolicy,
+
+    /// The time at which the source was detected as lost.
+    lost_at: Instant,
+
+    /// The time at which the last frame was produced for this lost source, used to pace fades.
+    last_frame: Instant,
 }
 
 /// Represents an sACN source/sender on the network that has been discovered by this sACN receiver by receiving universe discovery packets.
@@ -202,7 +364,7 @@ pub struct DiscoveredSacnSource {
     /// The name of the source, no protocol guarantee this will be unique but if it isn't then universe discovery may not work correctly.
     pub name: String,
 
-    /// The unique CID of the source. This should be unique across all devices on the network. 
+    /// The unique CID of the source. This should be unique across all devices on the network.
     pub cid: Uuid,
 
     /// The time at which the discovered source was last updated / a discovery packet was received by the source.
@@ -215,6 +377,88 @@ pub struct DiscoveredSacnSource {
     last_page: u8,
 }
 
+/// An event that occurred while receiving, returned by (recv_event)[SacnReceiver::recv_event].
+///
+/// Unlike (recv)[SacnReceiver::recv] every event is returned regardless of the announce flags of the receiver. Errors, for example
+/// a failure of the underlying socket, are still returned as an Err.
+#[derive(Clone, Debug)]
+pub enum ReceiveEvent {
+    /// Data that is ready to act on, this is what (recv)[SacnReceiver::recv] returns. Waiting e.g. for universe synchronisation and merging
+    /// of multiple sources is already handled.
+    Data(Vec<DMXData>),
+
+    /// A source was fully discovered through universe discovery packets.
+    ///
+    /// # Arguments
+    /// DiscoveredSacnSource: The source that was discovered including the universes it is sending.
+    SourceDiscovered(DiscoveredSacnSource),
+
+    /// A source is no longer sending on any universe to this receiver as all its universes have timed out or been terminated.
+    ///
+    /// # Arguments
+    /// Uuid: The CID of the source which was lost.
+    SourceLost(Uuid),
+
+    /// A source terminated a universe as per ANSI E1.31-2018 Section 6.2.6.
+    ///
+    /// # Arguments
+    /// Uuid: The CID of the source which sent the termination packet.
+    ///
+    /// u16: The universe that was terminated.
+    UniverseTerminated(Uuid, u16),
+
+    /// A source stopped sending a universe for E131_NETWORK_DATA_LOSS_TIMEOUT as per ANSI E1.31-2018 Section 6.7.1.
+    ///
+    /// # Arguments
+    /// Uuid: The CID of the source which timed out.
+    ///
+    /// u16: The universe that timed out.
+    UniverseTimedOut(Uuid, u16),
+
+    /// A packet was discarded as it was out of sequence as per ANSI E1.31-2018 Section 6.7.2.
+    ///
+    /// # Arguments
+    /// Uuid: The CID of the source which sent the packet.
+    ///
+    /// u16: The universe of the packet, this is the synchronisation address for synchronisation packets.
+    ///
+    /// u8: The sequence number of the packet received.
+    ///
+    /// u8: The last sequence number received.
+    ///
+    /// isize: The difference between the last and current sequence numbers.
+    SequenceError(Uuid, u16, u8, u8, isize),
+}
+
+/// The network layer that a SacnReceiver receives packets from.
+#[derive(Debug)]
+enum ReceiverNetwork {
+    /// UDP sockets created and bound by the receiver.
+    Sockets(SacnNetworkReceiver),
+
+    /// A transport given to the receiver, see (SacnReceiver::with_transport)[SacnReceiver::with_transport].
+    Transport(TransportReceiver),
+}
+
+/// Used for receiving packets from a transport, joining the multicast groups of universes through the transport.
+#[derive(Debug)]
+struct TransportReceiver {
+    /// The transport packets are received from.
+    transport: Box<dyn Transport>,
+
+    /// The address that the transport is bound to, the multicast groups joined are of the same IP version.
+    addr: SocketAddr,
+
+    /// If true then the multicast groups of universes are joined when they are listened to.
+    is_multicast_enabled: bool,
+
+    /// The universes whose multicast groups have been joined through the transport.
+    joined: HashSet<u16>,
+
+    /// The timeout used when waiting for a packet, None to wait forever.
+    timeout: Option<Duration>,
+}
+
 /// Used for receiving dmx or other data on a particular universe using multicast.
 #[derive(Debug)]
 struct SacnNetworkReceiver {
@@ -228,6 +472,78 @@ struct SacnNetworkReceiver {
     /// This flag is set when the receiver is created as not all environments currently support IP multicast.
     /// E.g. IPv6 Windows IP Multicast is currently unsupported.
     is_multicast_enabled: bool,
+
+    /// The interfaces that multicast groups are joined on, if empty then the groups are joined on the interface of the bound address.
+    interfaces: Vec<MulticastInterface>,
+
+    /// Additional sockets bound to the same address that multicast groups are joined on once the socket above can join no more groups,
+    /// e.g. on Linux a socket is limited to net.ipv4.igmp_max_memberships (20 by default) groups. Packets are received from all of them.
+    /// Always empty on Windows.
+    shards: Vec<Socket>,
+
+    /// The index of the socket that the multicast groups of each universe are joined on, 0 is the socket and i is shards[i - 1].
+    #[cfg(not(target_os = "windows"))]
+    memberships: HashMap<u16, usize>,
+
+    /// The indexes (as in memberships) of the sockets which have reached the limit of multicast groups they can join.
+    #[cfg(not(target_os = "windows"))]
+    full_sockets: HashSet<usize>,
+
+    /// The timeout used when waiting for a packet on any of the sockets, see (set_timeout)[SacnNetworkReceiver::set_timeout].
+    #[cfg(not(target_os = "windows"))]
+    timeout: Option<Duration>,
+
+    /// The index of the socket that is checked first for a packet next time, rotated so that no socket is starved.
+    #[cfg(not(target_os = "windows"))]
+    next_socket: usize,
+
+    /// The packets received together by the last recvmmsg call, only used if the batch size is greater than 1.
+    #[cfg(target_os = "linux")]
+    batch: RecvBatch,
+}
+
+/// Packets received together by a single recvmmsg call which are then handled one at a time, see
+/// (SacnReceiver::set_recv_batch_size)[SacnReceiver::set_recv_batch_size].
+#[cfg(target_os = "linux")]
+#[derive(Debug, Default)]
+struct RecvBatch {
+    /// The maximum number of packets received by one call, batching is disabled if this is 1 or less.
+    size: usize,
+
+    /// The buffers that packets are received into, one per packet in a batch. Reallocated once the batch is handled if the size changed.
+    bufs: Vec<[u8; RCV_BUF_DEFAULT_SIZE]>,
+
+    /// The addresses that the packets in a batch were sent from.
+    addrs: Vec<libc::sockaddr_storage>,
+
+    /// The control messages of the packets in a batch, aligned for cmsghdr and large enough for a packet info control message.
+    controls: Vec<[u64; 16]>,
+
+    /// The length and origin of each packet received by the last call.
+    received: Vec<(usize, Option<PacketOrigin>)>,
+
+    /// The index within received of the next packet to handle.
+    next: usize,
+}
+
+/// A network interface resolved to the form used to join multicast groups on it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum MulticastInterface {
+    /// The interface with the given address, for IPv6 this leaves the OS to choose the interface.
+    Addr(IpAddr),
+
+    /// The interface with the given index.
+    Index(u32),
+}
+
+/// Where a packet was received from.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct PacketOrigin {
+    /// The network address the packet was sent from.
+    addr: SocketAddr,
+
+    /// The index of the network interface the packet arrived on, if known.
+    interface: Option<u32>,
 }
 
 /// Universe discovery packets are broken down into pages to allow sending a large list of universes, each page contains a list of universes and
@@ -290,12 +606,85 @@ impl SacnReceiver {
         {
             return Err(SacnError::SourceLimitZero());
         };
+        SacnReceiver::with_network(
+            ReceiverNetwork::Sockets(SacnNetworkReceiver::new(ip)?),
+            source_limit,
+        )
+    }
+
+    /// Creates a new SacnReceiver which receives packets from the given transport instead of UDP sockets, e.g. a
+    /// (MemoryTransport)[crate::transport::MemoryTransport] so that sources and receivers can be tested without a network.
+    ///
+    /// The multicast groups of the universes listened to are joined through the transport, using the IP version of the address the
+    /// transport is bound to. Network interfaces, IPv6 only mode and batched receiving cannot be used with a transport and the receiver
+    /// cannot be made asynchronous.
+    ///
+    /// # Arguments
+    /// transport: The transport to receive from.
+    ///
+    /// source_limit: The limit to the number of sources, past this limit a new source will cause a SourcesExceededError as per ANSI E1.31-2018 Section 6.2.3.3.
+    ///
+    /// # Errors
+    /// Will return a SourceLimitZero error if the source_limit has a value of Some(0) which would indicate this receiver can never receive from any source.
+    ///
+    /// Will return an Io error if the address the transport is bound to cannot be determined or the multicast group of the
+    /// E1.31_DISCOVERY_UNIVERSE cannot be joined through the transport.
+    pub fn with_transport<T: Transport + 'static>(
+        transport: T,
+        source_limit: Option<usize>,
+    ) -> Result<SacnReceiver> {
+        if let Some(x) = source_limit
+            && x == 0
+        {
+            return Err(SacnError::SourceLimitZero());
+        };
+        SacnReceiver::with_network(
+            ReceiverNetwork::Transport(TransportReceiver::new(Box::new(transport))?),
+            source_limit,
+        )
+    }
+
+    /// Creates a new SacnReceiver which handles the packets of a packet capture as if they were received from the network, see
+    /// (CaptureTransport)[crate::capture::CaptureTransport].
+    ///
+    /// The clock of the receiver is set to the clock of the capture so that timeouts follow the capture timestamps, whether it is
+    /// replayed at its original speed or as fast as possible. Once every packet has been handled (recv)[recv] and
+    /// (recv_event)[recv_event] return an Io error of the kind UnexpectedEof.
+    ///
+    /// # Arguments
+    /// capture: The capture to replay.
+    ///
+    /// source_limit: The limit to the number of sources, past this limit a new source will cause a SourcesExceededError as per ANSI E1.31-2018 Section 6.2.3.3.
+    ///
+    /// # Errors
+    /// See (with_transport)[with_transport].
+    pub fn with_capture(
+        capture: CaptureTransport,
+        source_limit: Option<usize>,
+    ) -> Result<SacnReceiver> {
+        let clock = capture.clock();
+        let mut receiver = SacnReceiver::with_transport(capture, source_limit)?;
+        receiver.set_clock(clock);
+        Ok(receiver)
+    }
+
+    /// Creates a new SacnReceiver receiving from the given network layer and listening to the E131_DISCOVERY_UNIVERSE.
+    ///
+    /// # Errors
+    /// Will return an error if the created SacnReceiver fails to listen to the E1.31_DISCOVERY_UNIVERSE.
+    /// For more details see SacnReceiver::listen_universes().
+    fn with_network(
+        receiver: ReceiverNetwork,
+        source_limit: Option<usize>,
+    ) -> Result<SacnReceiver> {
         let mut sri = SacnReceiver {
-            receiver: SacnNetworkReceiver::new(ip)?,
+            receiver,
             waiting_data: HashMap::new(),
+            source_data: HashMap::new(),
+            source_priorities: HashMap::new(),
             universes: Vec::new(),
             discovered_sources: Vec::new(),
-            merge_func: DEFAULT_MERGE_FUNC,
+            merger: None,
             partially_discovered_sources: Vec::new(),
             process_preview_data: PROCESS_PREVIEW_DATA_DEFAULT,
             source_limit,
@@ -303,6 +692,16 @@ impl SacnReceiver {
             announce_source_discovery: ANNOUNCE_SOURCE_DISCOVERY_DEFAULT,
             announce_stream_termination: ANNOUNCE_STREAM_TERMINATION_DEFAULT,
             announce_timeout: ANNOUNCE_TIMEOUT_DEFAULT,
+            pending_events: VecDeque::new(),
+            sampling_period: None,
+            sampling_universes: HashMap::new(),
+            data_loss_policies: HashMap::new(),
+            lost_sources: HashMap::new(),
+            sync_last_recv: HashMap::new(),
+            universe_states: HashMap::new(),
+            interfaces: Vec::new(),
+            clock: Arc::new(SystemClock),
+            last_timeout_check: None,
         };
 
         sri.listen_universes(&[E131_DISCOVERY_UNIVERSE])?;
@@ -333,12 +732,84 @@ impl SacnReceiver {
         self.receiver.is_multicast_enabled()
     }
 
+    /// Sets the clock that the protocol timeouts of the receiver are measured with, such as E131_NETWORK_DATA_LOSS_TIMEOUT, sampling
+    /// periods, data loss policies and the timeout of discovered sources. By default this is the (SystemClock)[SystemClock].
+    ///
+    /// Setting a (ManualClock)[crate::clock::ManualClock] allows the timeouts to be tested without waiting for them. The clock should be
+    /// set before receiving as any times already recorded were taken from the previous clock. Timeouts given to (recv)[recv] and
+    /// (recv_event)[recv_event] are always measured in real time.
+    ///
+    /// # Arguments
+    /// clock: The clock to use.
+    pub fn set_clock<C: Clock + 'static>(&mut self, clock: C) {
+        self.clock = Arc::new(clock);
+        self.last_timeout_check = None;
+    }
+
+    /// Returns the current time of the clock of the receiver.
+    fn now(&self) -> Instant {
+        self.clock.now()
+    }
+
+    /// Sets the maximum number of packets that (recv)[recv] and (recv_event)[recv_event] read from the network with a single system call.
+    ///
+    /// By default packets are read one at a time. With a batch size greater than 1 all the packets waiting, up to the batch size, are
+    /// read at once using recvmmsg and then handled one by one, reducing the per-packet overhead when receiving many universes. This
+    /// doesn't change what is returned: the data completed by the packets of a batch is returned by subsequent calls without reading from
+    /// the network again. Batching is only supported on Linux and isn't used by the async receiver.
+    ///
+    /// # Arguments
+    /// batch_size: The maximum number of packets read at once, 1 (or 0) disables batching.
+    ///
+    /// # Errors
+    /// Will return an OsOperationUnsupported error if attempting to set a batch size greater than 1 on an OS other than Linux.
+    ///
+    /// Will return a TransportUnsupported error if attempting to set a batch size greater than 1 on a receiver using a transport.
+    pub fn set_recv_batch_size(&mut self, batch_size: usize) -> Result<()> {
+        if batch_size <= 1 {
+            #[cfg(target_os = "linux")]
+            if let ReceiverNetwork::Sockets(receiver) = &mut self.receiver {
+                receiver.batch.size = batch_size;
+            }
+            return Ok(());
+        }
+
+        match &mut self.receiver {
+            #[cfg(target_os = "linux")]
+            ReceiverNetwork::Sockets(receiver) => {
+                receiver.batch.size = batch_size;
+                Ok(())
+            }
+            #[cfg(not(target_os = "linux"))]
+            ReceiverNetwork::Sockets(_) => Err(SacnError::OsOperationUnsupported(
+                "Receiving packets in batches is only supported on Linux".to_string(),
+            )),
+            ReceiverNetwork::Transport(_) => Err(SacnError::TransportUnsupported(
+                "Receiving packets in batches is only supported on UDP sockets".to_string(),
+            )),
+        }
+    }
+
+    /// Returns the maximum number of packets read from the network with a single system call, see
+    /// (set_recv_batch_size)[set_recv_batch_size].
+    pub fn recv_batch_size(&self) -> usize {
+        match &self.receiver {
+            #[cfg(target_os = "linux")]
+            ReceiverNetwork::Sockets(receiver) => receiver.batch.size.max(1),
+            _ => 1,
+        }
+    }
+
     /// Wipes the record of discovered and sequence number tracked sources.
     /// This is one way to handle a sources exceeded condition.
     ///
     /// If you want to wipe data awaiting synchronisation then see (clear_all_waiting_data)[clear_all_waiting_data].
     pub fn reset_sources(&mut self) {
         self.sequences.clear();
+        self.source_data.clear();
+        self.source_priorities.clear();
+        self.lost_sources.clear();
+        self.sync_last_recv.clear();
         self.partially_discovered_sources.clear();
         self.discovered_sources.clear();
     }
@@ -368,16 +839,89 @@ impl SacnReceiver {
     /// This merge function is called if data is waiting for a universe e.g. for synchronisation and then further data for that universe with the same
     /// synchronisation address arrives.
     ///
+    /// It is also used to combine unsynchronised data from multiple sources sending the same universe at the same (highest) priority, see
+    /// (recv)[recv]. The data is passed to the merge function in the order it was received with the oldest first.
+    ///
     /// This merge function MUST return a DmxMergeError if there is a problem merging. This error can optionally encapsulate further errors using the Error-chain system
     ///     to provide a more informative backtrace.
     ///
+    /// This replaces any merger set using (set_merger)[set_merger], the function is used as described by (PairwiseMerger)[crate::merge::PairwiseMerger].
+    ///
     /// Arguments:
     /// func: The merge function to use. Should take 2 DMXData references as arguments and return a Result<DMXData>.
     pub fn set_merge_fn(&mut self, func: fn(&DMXData, &DMXData) -> Result<DMXData>) -> Result<()> {
-        self.merge_func = func;
+        self.merger = Some(Box::new(PairwiseMerger::new(func)));
+        Ok(())
+    }
+
+    /// Sets the merger to be used by this receiver.
+    ///
+    /// Unlike a merge function (see (set_merge_fn)[set_merge_fn]) a merger is given the data of every source contributing to a universe at once,
+    /// including their per-address priorities, and can keep state between merges. See the (merge)[crate::merge] module for the provided mergers,
+    /// a closure taking a `&[MergeSource]` can also be used.
+    ///
+    /// The merger is used for unsynchronised data from multiple sources sending the same universe, see (recv)[recv], and if data is waiting for a
+    /// universe e.g. for synchronisation and then further data for that universe with the same synchronisation address arrives, in which case it is
+    /// given the existing and then the new data.
+    ///
+    /// Arguments:
+    /// merger: The merger to use.
+    pub fn set_merger<M: Merger + 'static>(&mut self, merger: M) {
+        self.merger = Some(Box::new(merger));
+    }
+
+    /// Sets the network interfaces that the multicast groups of the universes being listened to are joined on.
+    ///
+    /// By default the groups are joined on the interface of the address the receiver was created with, for the unspecified address
+    /// (`0.0.0.0`) the OS chooses the interface. On machines connected to several networks this allows receiving on one or more
+    /// specific networks. The groups of universes already being listened to are left on the previous interfaces and joined on the new ones.
+    ///
+    /// On Linux the index of the interface each packet arrived on is included in the received data, see (DMXData)[DMXData].
+    ///
+    /// # Arguments
+    /// interfaces: The interfaces to join multicast groups on, chosen by name, index or address. An empty list returns to the default.
+    ///
+    /// # Errors
+    /// IpVersionError: Returned if an interface is given by an address of a different IP version to the receiver.
+    ///
+    /// InterfaceNotFound: Returned if there is no interface with a given name or address.
+    ///
+    /// OsOperationUnsupported: Returned if interfaces cannot be looked up by name or address on the current OS.
+    ///
+    /// TransportUnsupported: Returned if an interface is given to a receiver using a transport.
+    ///
+    /// Io: Returned if the multicast groups cannot be left or joined on the interfaces.
+    pub fn set_interfaces(&mut self, interfaces: &[NetworkInterface]) -> Result<()> {
+        // Resolve all the interfaces first so that an invalid interface doesn't leave the receiver part way between interfaces.
+        let resolved = interfaces
+            .iter()
+            .map(|i| self.receiver.resolve_interface(i))
+            .collect::<Result<Vec<_>>>()?;
+
+        let multicast = self.is_multicast_enabled();
+        if multicast {
+            for u in &self.universes {
+                self.receiver.mute_multicast_universe(*u)?;
+            }
+        }
+
+        self.receiver.set_multicast_interfaces(resolved);
+        self.interfaces = interfaces.to_vec();
+
+        if multicast {
+            for u in &self.universes {
+                self.receiver.listen_multicast_universe(*u)?;
+            }
+        }
+
         Ok(())
     }
 
+    /// Returns the network interfaces that multicast groups are joined on, empty if the interface of the bound address is used.
+    pub fn interfaces(&self) -> &[NetworkInterface] {
+        &self.interfaces
+    }
+
     /// Allow only receiving on Ipv6.
     pub fn set_ipv6_only(&mut self, val: bool) -> Result<()> {
         self.receiver.set_only_v6(val)
@@ -390,6 +934,9 @@ impl SacnReceiver {
     ///
     /// If 1 or more universes in the list are already being listened to this method will have no effect for those universes only.
     ///
+    /// If a sampling period is set, see (set_sampling_period)[set_sampling_period], then each newly listened to universe starts in its
+    /// sampling period.
+    ///
     /// # Errors
     /// Returns an SacnError::IllegalUniverse error if the given universe is outwith the allowed range of universes,
     /// see (is_universe_in_range)[fn.is_universe_in_range.packet].
@@ -406,6 +953,12 @@ impl SacnReceiver {
                     // Value not found, i is the position it should be inserted
                     self.universes.insert(i, *u);
 
+                    if let Some(period) = self.sampling_period
+                        && *u != E131_DISCOVERY_UNIVERSE
+                    {
+                        self.sampling_universes.insert(*u, self.now() + period);
+                    }
+
                     if self.is_multicast_enabled() {
                         self.receiver.listen_multicast_universe(*u)?;
                     }
@@ -418,6 +971,35 @@ impl SacnReceiver {
         Ok(())
     }
 
+    /// Allows receiving from every universe in the given range, see (listen_universes)[listen_universes].
+    ///
+    /// Large numbers of universes can be listened to, the multicast groups are spread over as many sockets as needed to stay within the
+    /// per-socket membership limit of the OS (on Linux net.ipv4.igmp_max_memberships, 20 by default) and packets are received from all
+    /// of them together. The membership limit is not worked around on Windows.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use sacn::receive::SacnReceiver;
+    /// use sacn::packet::ACN_SDT_MULTICAST_PORT;
+    ///
+    /// use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+    ///
+    /// let addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::UNSPECIFIED), ACN_SDT_MULTICAST_PORT);
+    ///
+    /// let mut dmx_rcv = SacnReceiver::with_ip(addr, None).unwrap();
+    /// dmx_rcv.listen_universe_range(1..=2000).unwrap();
+    /// ```
+    ///
+    /// # Errors
+    /// Returns an SacnError::IllegalUniverse error if any universe in the range is outwith the allowed range of universes,
+    /// see (is_universe_in_range)[fn.is_universe_in_range.packet], in which case no universes are listened to.
+    ///
+    /// Returns an Io error if a multicast group cannot be joined or a socket cannot be created to join it on.
+    pub fn listen_universe_range(&mut self, universes: RangeInclusive<u16>) -> Result<()> {
+        self.listen_universes(&universes.collect::<Vec<u16>>())
+    }
+
     /// Stops listening to the given universe.
     ///
     /// # Errors
@@ -436,11 +1018,101 @@ impl SacnReceiver {
             Ok(i) => {
                 // If value found then don't insert to avoid duplicates.
                 self.universes.remove(i);
+                self.sampling_universes.remove(&universe);
+                self.lost_sources.remove(&universe);
+                self.sync_last_recv.remove(&universe);
+                self.universe_states.remove(&universe);
+                self.source_data.remove(&universe);
+                self.source_priorities.remove(&universe);
+                if let Some(merger) = self.merger.as_mut() {
+                    merger.forget_universe(universe);
+                }
                 self.receiver.mute_multicast_universe(universe)
             }
         }
     }
 
+    /// Sets the sampling period used for universes when they are first listened to, see ANSI E1.31-2018 Section 6.2.3.1.
+    ///
+    /// During the sampling period of a universe the receiver learns the active sources sending that universe without acting on their data.
+    /// This avoids briefly outputting the data of a lower priority source which happened to be received first. When the sampling period
+    /// ends the arbitrated data of all the sources is returned by (recv)[recv] / (recv_event)[recv_event] and data is then returned as normal.
+    ///
+    /// Synchronised data released for a universe during its sampling period is discarded, data with an alternative start code isn't held.
+    ///
+    /// By default there is no sampling period. Changing the sampling period only affects universes listened to afterwards.
+    ///
+    /// # Arguments
+    /// period: The length of the sampling period, usually E131_SAMPLING_PERIOD, or None to disable sampling.
+    pub fn set_sampling_period(&mut self, period: Option<Duration>) {
+        self.sampling_period = period;
+    }
+
+    /// Returns the sampling period used for universes when they are first listened to, None indicates no sampling period is used.
+    /// See (set_sampling_period)[set_sampling_period].
+    pub fn get_sampling_period(&self) -> Option<Duration> {
+        self.sampling_period
+    }
+
+    /// Returns true if the given universe is currently in its sampling period, see (set_sampling_period)[set_sampling_period].
+    ///
+    /// Arguments:
+    /// universe: The sACN universe to check.
+    pub fn is_sampling(&self, universe: u16) -> bool {
+        self.sampling_universes.contains_key(&universe)
+    }
+
+    /// Returns the current state of the given universe, this is the latest DMX data for the universe that was acted on along with when it
+    /// was updated and the sources which contributed to it.
+    ///
+    /// The state is updated as packets are handled by (recv)[recv] / (recv_event)[recv_event] so it reflects all data received so far even
+    /// if the data returned by those methods isn't kept. The data of synchronised universes is updated together when their synchronisation
+    /// packet arrives. Only DMX (NULL START code) data is kept.
+    ///
+    /// Returns None if the universe isn't being listened to or no data has been acted on for it yet.
+    ///
+    /// # Arguments
+    /// universe: The universe to get the state of.
+    pub fn universe_state(&self, universe: u16) -> Option<&UniverseState> {
+        self.universe_states.get(&universe)
+    }
+
+    /// Returns the current state of every universe which has had data acted on, ordered by universe.
+    /// See (universe_state)[universe_state].
+    pub fn all_universe_states(&self) -> Vec<UniverseState> {
+        let mut states: Vec<UniverseState> = self.universe_states.values().cloned().collect();
+        states.sort_by_key(|s| s.data.universe);
+        states
+    }
+
+    /// Sets the data loss policy of the given universe, this decides what happens to the data of a source which stops sending the universe
+    /// for E131_NETWORK_DATA_LOSS_TIMEOUT (ANSI E1.31-2018 Section 6.7.1). See (DataLossPolicy)[DataLossPolicy] for the policies.
+    ///
+    /// The frames produced by the policy, e.g. while fading or when switching to another source, are returned by (recv)[recv] /
+    /// (recv_event)[recv_event] as data for the universe. If the source starts sending again its new data is used as normal.
+    ///
+    /// By default a universe has no policy in which case the data of a lost source is silently discarded and no frames are produced.
+    /// The policy applies to unsynchronised DMX data only and sources which have already been lost keep the policy they were lost with.
+    ///
+    /// # Arguments
+    /// universe: The universe to set the policy for.
+    ///
+    /// policy: The new data loss policy or None to remove the policy of the universe.
+    pub fn set_data_loss_policy(&mut self, universe: u16, policy: Option<DataLossPolicy>) {
+        match policy {
+            Some(p) => self.data_loss_policies.insert(universe, p),
+            None => self.data_loss_policies.remove(&universe),
+        };
+    }
+
+    /// Returns the data loss policy of the given universe, see (set_data_loss_policy)[set_data_loss_policy].
+    ///
+    /// # Arguments
+    /// universe: The universe to get the policy of.
+    pub fn get_data_loss_policy(&self, universe: u16) -> Option<DataLossPolicy> {
+        self.data_loss_policies.get(&universe).copied()
+    }
+
     /// Set the process_preview_data flag to the given value.
     ///
     /// This flag indicates if this receiver should process packets marked as preview_data or should ignore them.
@@ -470,6 +1142,20 @@ impl SacnReceiver {
     /// Any data returned will be ready to act on immediately i.e. waiting e.g. for universe synchronisation
     /// is already handled.
     ///
+    /// If multiple sources are sending unsynchronised DMX data (NULL START Code) to the same universe then the data returned is the
+    /// arbitrated result of all those sources rather than the packet that was just received. The sources are combined using the merger,
+    /// see (set_merger)[set_merger], as per ANSI E1.31-2018 Section 6.2.3. Data with any other start code is returned as received.
+    ///
+    /// By default only the sources with the highest priority are considered and if there is more than one of them each slot takes the
+    /// highest value of those sources (HTP), see (DEFAULT_MERGER)[DEFAULT_MERGER].
+    ///
+    /// Per-address priority data (start code 0xDD) is not returned. Instead it is tracked per source and universe and passed to the merger
+    /// along with the DMX data. By default, while any source sending the universe has per-address priorities, the DMX data is arbitrated
+    /// slot-by-slot: each slot takes the value from the source with the highest priority for that slot. Sources without per-address
+    /// priorities use their packet priority for every slot and a per-address priority of 0 means the source isn't sending that slot. Ties
+    /// are resolved highest takes precedence (HTP), see (PriorityHtpMerger)[crate::merge::PriorityHtpMerger].
+    /// Receiving new per-address priority data returns the re-arbitrated DMX data if there is any.
+    ///
     /// # Errors
     /// This method will return a WouldBlock (unix) or TimedOut (windows) error if there is no data ready within the given timeout.
     /// A timeout of duration 0 will do timeout checks but otherwise will return a WouldBlock/TimedOut error without checking for data.
@@ -492,6 +1178,11 @@ impl SacnReceiver {
     /// specified by E131_NETWORK_DATA_LOSS_TIMEOUT (ANSI E1.31-2018 Appendix A).  This may not be detected immediately unless data is received for the timed-out
     /// universe from the source. If it isn't detected immediately it will be detected within an interval of E131_NETWORK_DATA_LOSS_TIMEOUT (assuming code
     /// executes in zero time).
+    ///
+    /// If the announce_stream_termination flag is set then the recv will return a UniverseTerminated error if a source terminates a universe.
+    ///
+    /// These announcements are returned as errors for compatibility, (recv_event)[recv_event] returns them as events alongside the data instead.
+    /// Any data received alongside an announcement isn't lost and is returned by the next call.
     pub fn recv(&mut self, timeout: Option<Duration>) -> Result<Vec<DMXData>> {
         if self.universes.len() == 1
             && self.universes[0] == E131_DISCOVERY_UNIVERSE
@@ -504,32 +1195,67 @@ impl SacnReceiver {
             return Err(SacnError::NoDataUniversesRegistered());
         }
 
-        // if timeout is 0, then it's time to return
-        if timeout == Some(Duration::from_secs(0)) {
-            // always check timeouts
-            self.sequences.check_timeouts(self.announce_timeout)?;
-            self.check_waiting_data_timeouts();
-            return Err(io::Error::new(
-                // Use the right expected error for the operating system.
-                if cfg!(target_os = "windows") {
-                    io::ErrorKind::TimedOut
-                } else {
-                    io::ErrorKind::WouldBlock
-                },
-                "No data available in given timeout",
-            )
-            .into());
-        }
-
         // Fixed instant that should return the whole recv call
         let deadline = timeout.and_then(|t| Instant::now().checked_add(t));
 
+        loop {
+            let remaining = deadline.map(|dl| dl.saturating_duration_since(Instant::now()));
+            let event = self.recv_event(remaining)?;
+            if let Some(res) = self.announced_result(event) {
+                return res;
+            }
+        }
+    }
+
+    /// Attempt to receive the next event, this is either data from any of the registered universes or a change in the state of the sources
+    /// being received from.
+    ///
+    /// Data is handled in exactly the same way as by (recv)[recv], see (ReceiveEvent)[ReceiveEvent] for the other events. Every event is returned
+    /// regardless of the announce flags of this receiver. Events which occur together, for example when multiple sources time out at once,
+    /// are returned in order by subsequent calls.
+    ///
+    /// Arguments:
+    /// timeout: The maximum time to wait for an event, None means block until an event occurs. A timeout of 0 will do timeout checks and return
+    ///     any events already waiting but won't check for new packets.
+    ///
+    /// # Errors
+    /// This method will return a WouldBlock (unix) or TimedOut (windows) error if there is no event within the given timeout.
+    ///
+    /// The method may also return an error if there is an issue setting a timeout on the receiver. See
+    /// SacnNetworkReceiver::set_timeout for details.
+    ///
+    /// The method may also return an error if there is an issue receiving or handling a packet, for example a DmxMergeError if the data cannot be merged
+    /// or a SourcesExceededError if the source limit is reached. Out of sequence packets are returned as a
+    /// (SequenceError)[ReceiveEvent::SequenceError] event instead.
+    pub fn recv_event(&mut self, timeout: Option<Duration>) -> Result<ReceiveEvent> {
+        if let Some(event) = self.pending_events.pop_front() {
+            return Ok(event);
+        }
+
+        // if timeout is 0, then it's time to return
+        if timeout == Some(Duration::from_secs(0)) {
+            // always check timeouts
+            self.check_timeouts()?;
+            return self.pending_events.pop_front().ok_or_else(no_data_error);
+        }
+
+        // Fixed instant that should return the whole recv_event call
+        let deadline = timeout.and_then(|t| Instant::now().checked_add(t));
+
         // shared buf through loop iterations
         let mut buf: [u8; RCV_BUF_DEFAULT_SIZE] = [0; RCV_BUF_DEFAULT_SIZE];
 
         loop {
-            self.sequences.check_timeouts(self.announce_timeout)?;
-            self.check_waiting_data_timeouts();
+            self.check_timeouts_if_due()?;
+            if let Some(event) = self.pending_events.pop_front() {
+                return Ok(event);
+            }
+
+            // Packets already read as part of a batch are handled before waiting for more.
+            #[cfg(target_os = "linux")]
+            if self.handle_batched_packet()? {
+                continue;
+            }
 
             // In the case of `timeout` being longer than `E131_NETWORK_DATA_LOSS_TIMEOUT`:
             // Forces the actual timeout used for receiving from the underlying network to never exceed E131_NETWORK_DATA_LOSS_TIMEOUT.
@@ -541,58 +1267,50 @@ impl SacnReceiver {
                     let now = Instant::now();
                     if now >= dl {
                         // timeout expired
-                        return Err(io::Error::new(
-                            if cfg!(target_os = "windows") {
-                                io::ErrorKind::TimedOut
-                            } else {
-                                io::ErrorKind::WouldBlock
-                            },
-                            "No data available in given timeout",
-                        )
-                        .into());
+                        return Err(no_data_error());
                     }
                     Some(dl - now)
                 }
             };
 
-            let actual_timeout = if let Some(rem) = remaining {
+            let mut actual_timeout = if let Some(rem) = remaining {
                 rem.min(E131_NETWORK_DATA_LOSS_TIMEOUT)
             } else {
                 E131_NETWORK_DATA_LOSS_TIMEOUT
             };
 
-            self.receiver.set_timeout(Some(actual_timeout))?; // "Failed to set a timeout value for the receiver"
+            // Wake up when the next sampling period ends or data loss frame is due so the data is returned promptly.
+            if let Some(end) = self.next_deadline() {
+                actual_timeout = actual_timeout.min(end.saturating_duration_since(self.now()));
+            }
+            if actual_timeout.is_zero() {
+                continue;
+            }
 
-            // Zero out the buffer before receiving. This may be redundant since recv should pack the whole buffer.
-            buf.fill(0);
-
-            match self.receiver.recv(&mut buf) {
-                Ok(pkt) => {
-                    let pdu: E131RootLayer = pkt.pdu;
-                    let data: E131RootLayerData = pdu.data;
-                    let res = match data {
-                        DataPacket(d) => self.handle_data_packet(pdu.cid, d)?,
-                        SynchronizationPacket(s) => self.handle_sync_packet(pdu.cid, s)?,
-                        UniverseDiscoveryPacket(u) => {
-                            let discovered_src: Option<String> =
-                                self.handle_universe_discovery_packet(pdu.cid, u);
-                            if let Some(src) = discovered_src
-                                && self.announce_source_discovery
-                            {
-                                return Err(SacnError::SourceDiscovered(src));
-                            }
-                            None
-                        }
-                    };
+            self.receiver.set_timeout(Some(actual_timeout))?; // "Failed to set a timeout value for the receiver"
 
-                    // return the data, otherwise continue if no data is ready
-                    if let Some(r) = res {
-                        return Ok(r);
-                    } else {
-                        continue;
+            // Only the received bytes are parsed so the buffer doesn't need clearing between packets.
+            #[cfg(target_os = "linux")]
+            let received = if self.recv_batch_size() > 1 {
+                self.receiver.recv_batch().map(|_| None)
+            } else {
+                self.receiver.recv(&mut buf).map(Some)
+            };
+            #[cfg(not(target_os = "linux"))]
+            let received = self.receiver.recv(&mut buf).map(Some);
+
+            match received {
+                Ok(Some((pkt, origin))) => {
+                    // Queue the data behind any events caused by the same packet, otherwise continue if no data is ready
+                    if let Some(r) = self.handle_packet(pkt, origin)? {
+                        self.pending_events.push_back(ReceiveEvent::Data(r));
                     }
+                    continue;
                 }
 
+                // A batch of packets was read, these are handled at the start of the loop.
+                Ok(None) => continue,
+
                 Err(err) =>
                 // This could be the socket-level timeout error or other socket recv error.
                 {
@@ -617,6 +1335,28 @@ impl SacnReceiver {
         }
     }
 
+    /// Converts the given event into the result that (recv)[recv] returns for it based on the announce flags of this receiver.
+    ///
+    /// Returns None if the event isn't announced by recv.
+    fn announced_result(&self, event: ReceiveEvent) -> Option<Result<Vec<DMXData>>> {
+        match event {
+            ReceiveEvent::Data(data) => Some(Ok(data)),
+            ReceiveEvent::SourceDiscovered(src) => self
+                .announce_source_discovery
+                .then_some(Err(SacnError::SourceDiscovered(src.name))),
+            ReceiveEvent::UniverseTerminated(cid, universe) => self
+                .announce_stream_termination
+                .then_some(Err(SacnError::UniverseTerminated(cid, universe))),
+            ReceiveEvent::UniverseTimedOut(cid, universe) => self
+                .announce_timeout
+                .then_some(Err(SacnError::UniverseTimeout(cid, universe))),
+            ReceiveEvent::SourceLost(_) => None,
+            ReceiveEvent::SequenceError(_, _, sequence_number, last, diff) => {
+                Some(Err(SacnError::OutOfSequence(sequence_number, last, diff)))
+            }
+        }
+    }
+
     /// Returns the current value of the announce_source_discovery flag.
     /// See (set_announce_source_discovery)[receive::set_announce_source_discovery] for an explanation of the flag.
     pub fn get_announce_source_discovery(&self) -> bool {
@@ -643,6 +1383,8 @@ impl SacnReceiver {
     /// If set to true then it means that a SourceDiscovered error will be thrown whenever a source is discovered through a
     ///  complete universe discovery packet.
     ///
+    /// This flag only affects (recv)[recv], (recv_event)[recv_event] always returns a SourceDiscovered event.
+    ///
     /// # Arguments:
     /// new_val: The new value for the announce_source_discovery flag.
     pub fn set_announce_source_discovery(&mut self, new_val: bool) {
@@ -662,6 +1404,8 @@ impl SacnReceiver {
     /// If set to true then a UniverseTimeout error will be thrown when attempting to receive if it is detected that a source universe has
     /// timed out as per ANSI E1.31-2018 Section 6.7.1.
     ///
+    /// This flag only affects (recv)[recv], (recv_event)[recv_event] always returns a UniverseTimedOut event.
+    ///
     /// # Arguments:
     /// new_val: The new value for the announce_timeout flag.
     pub fn set_announce_timeout(&mut self, new_val: bool) {
@@ -679,32 +1423,253 @@ impl SacnReceiver {
     /// By default this flag is false. This indicates that if a source sends a stream termination packet it will be handled silently by the receiver.
     /// If set to true then a UniverseTermination error will be thrown when attempting to receive if a termination packet is received as per
     /// ANSI E1.31-2018 Section 6.2.6.
+    ///
+    /// This flag only affects (recv)[recv], (recv_event)[recv_event] always returns a UniverseTerminated event.
     pub fn set_announce_stream_termination(&mut self, new_val: bool) {
         self.announce_stream_termination = new_val;
     }
 
+    /// Handles the next packet read as part of a batch which hasn't been handled yet, queueing any data it completes.
+    ///
+    /// Returns true if a packet was handled or false if there are no packets left in the batch.
+    ///
+    /// # Errors
+    /// Returns an error if the packet cannot be parsed or handled, see (handle_packet)[handle_packet]. The rest of the batch is kept.
+    #[cfg(target_os = "linux")]
+    fn handle_batched_packet(&mut self) -> Result<bool> {
+        let ReceiverNetwork::Sockets(receiver) = &mut self.receiver else {
+            return Ok(false);
+        };
+        // Taken while the packet is handled as the packet borrows the batch's buffer.
+        let mut batch = std::mem::take(&mut receiver.batch);
+        let res = match batch.next_packet() {
+            None => Ok(false),
+            Some((buf, origin)) => AcnRootLayerProtocol::parse(buf)
+                .and_then(|pkt| self.handle_packet(pkt, origin))
+                .map(|data| {
+                    if let Some(r) = data {
+                        self.pending_events.push_back(ReceiveEvent::Data(r));
+                    }
+                    true
+                }),
+        };
+        if let ReceiverNetwork::Sockets(receiver) = &mut self.receiver {
+            receiver.batch = batch;
+        }
+        res
+    }
+
+    /// Handles a received packet, passing it to the handler for its type.
+    ///
+    /// Returns the data that is ready to act on as a result of the packet or None if there is none.
+    ///
+    /// # Arguments
+    /// pkt: The received and parsed packet.
+    ///
+    /// origin: Where the packet was received from, if known.
+    ///
+    /// Any events caused by the packet, such as a source being discovered or a packet being out of sequence, are queued to be returned by
+    /// (recv_event)[recv_event].
+    ///
+    /// # Errors
+    /// Returns any error other than OutOfSequence from handling the packet, see (handle_data_packet)[handle_data_packet] and
+    /// (handle_sync_packet)[handle_sync_packet].
+    fn handle_packet(
+        &mut self,
+        pkt: AcnRootLayerProtocol,
+        origin: Option<PacketOrigin>,
+    ) -> Result<Option<Vec<DMXData>>> {
+        // Timeouts which happened before the packet arrived are reported before the events it causes.
+        self.check_timeouts_if_due()?;
+
+        let pdu: E131RootLayer = pkt.pdu;
+        let cid: Uuid = pdu.cid;
+        let data: E131RootLayerData = pdu.data;
+        match data {
+            DataPacket(d) => {
+                let universe = d.universe;
+                self.handle_data_packet(cid, d, origin)
+                    .or_else(|e| self.queue_sequence_error(e, cid, universe))
+            }
+            SynchronizationPacket(s) => {
+                let universe = s.synchronization_address;
+                self.handle_sync_packet(cid, s)
+                    .or_else(|e| self.queue_sequence_error(e, cid, universe))
+            }
+            UniverseDiscoveryPacket(u) => {
+                let discovered_src: Option<String> = self.handle_universe_discovery_packet(cid, u);
+                if discovered_src.is_some()
+                    && let Some(index) = find_discovered_src(&self.discovered_sources, &cid)
+                {
+                    self.pending_events
+                        .push_back(ReceiveEvent::SourceDiscovered(
+                            self.discovered_sources[index].clone(),
+                        ));
+                }
+                Ok(None)
+            }
+        }
+    }
+
+    /// Converts an OutOfSequence error from handling a packet into a queued SequenceError event, any other error is returned unchanged.
+    ///
+    /// # Arguments
+    /// err: The error returned from handling the packet.
+    ///
+    /// cid: The CID of the source which sent the packet.
+    ///
+    /// universe: The universe of the packet.
+    fn queue_sequence_error(
+        &mut self,
+        err: SacnError,
+        cid: Uuid,
+        universe: u16,
+    ) -> Result<Option<Vec<DMXData>>> {
+        match err {
+            SacnError::OutOfSequence(sequence_number, last, diff) => {
+                self.pending_events.push_back(ReceiveEvent::SequenceError(
+                    cid,
+                    universe,
+                    sequence_number,
+                    last,
+                    diff,
+                ));
+                Ok(None)
+            }
+            e => Err(e),
+        }
+    }
+
+    /// Performs the periodic timeout checks on the sequence numbers, waiting data and per-source data of this receiver.
+    ///
+    /// A UniverseTimedOut event is queued for each source universe which has timed out followed by a SourceLost event for each source which
+    /// no longer has any universes.
+    ///
+    /// The sampling periods of universes and the lost sources are also checked, a Data event is queued with the arbitrated data of each
+    /// universe whose sampling period has ended or for which a frame is produced by its data loss policy.
+    ///
+    /// # Errors
+    /// Will return a DmxMergeError if there is an issue merging the data of the sources of a universe.
+    fn check_timeouts(&mut self) -> Result<()> {
+        let now = self.now();
+        self.last_timeout_check = Some(now);
+
+        let mut lost: Vec<Uuid> = Vec::new();
+        for (cid, universe) in self.sequences.check_timeouts(now) {
+            self.pending_events
+                .push_back(ReceiveEvent::UniverseTimedOut(cid, universe));
+            if !lost.contains(&cid) && !self.sequences.has_source(&cid) {
+                lost.push(cid);
+            }
+        }
+        for cid in lost {
+            self.pending_events.push_back(ReceiveEvent::SourceLost(cid));
+        }
+
+        self.check_waiting_data_timeouts();
+        self.check_source_data_timeouts();
+        self.check_lost_sources()?;
+        self.check_sampling_periods()
+    }
+
+    /// Performs the timeout checks, see (check_timeouts)[check_timeouts], if they haven't been performed within the TIMEOUT_CHECK_INTERVAL.
+    ///
+    /// Otherwise only the sampling periods and lost sources are checked, as the times that they produce data at are kept to. This is
+    /// called for every packet received so the timeouts of every source and universe aren't checked for each packet, a timeout is
+    /// detected within the TIMEOUT_CHECK_INTERVAL of the next packet or the next wake up of the receiver.
+    ///
+    /// # Errors
+    /// Will return a DmxMergeError if there is an issue merging the data of the sources of a universe.
+    fn check_timeouts_if_due(&mut self) -> Result<()> {
+        let due = match self.last_timeout_check {
+            // A clock which has gone backwards is treated as due so that a new clock doesn't stop the checks.
+            Some(last) => self
+                .now()
+                .checked_duration_since(last)
+                .is_none_or(|d| d >= TIMEOUT_CHECK_INTERVAL),
+            None => true,
+        };
+        if due {
+            return self.check_timeouts();
+        }
+        self.check_lost_sources()?;
+        self.check_sampling_periods()
+    }
+
+    /// Ends the sampling period of any universes for which it has elapsed, queueing a Data event with the arbitrated data of the
+    /// sources learnt during the sampling period if there is any.
+    ///
+    /// # Errors
+    /// Will return a DmxMergeError if there is an issue merging the data of the sources of a universe.
+    fn check_sampling_periods(&mut self) -> Result<()> {
+        let now = self.now();
+        let mut ended: Vec<u16> = self
+            .sampling_universes
+            .iter()
+            .filter(|(_uni, end)| **end <= now)
+            .map(|(uni, _end)| *uni)
+            .collect();
+        ended.sort_unstable();
+
+        for universe in ended {
+            self.sampling_universes.remove(&universe);
+            if let Some(data) = self.arbitrate_source_data(universe)? {
+                self.pending_events
+                    .push_back(ReceiveEvent::Data(vec![data]));
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the next time at which a sampling period ends or a lost source needs a frame, None if there is no such time.
+    fn next_deadline(&self) -> Option<Instant> {
+        let lost = self.lost_sources.values().flat_map(|srcs| srcs.values());
+        let lost_deadlines = lost.filter_map(|l| match l.policy {
+            DataLossPolicy::Hold => None,
+            DataLossPolicy::HoldFor(d) => Some(l.lost_at + d),
+            DataLossPolicy::Fade(d) => {
+                Some((l.lost_at + d).min(l.last_frame + DATA_LOSS_FADE_FRAME_INTERVAL))
+            }
+            DataLossPolicy::Release => Some(l.lost_at),
+        });
+        self.sampling_universes
+            .values()
+            .copied()
+            .chain(lost_deadlines)
+            .min()
+    }
+
     /// Handles the given data packet for this DMX receiver.
     ///
     /// Returns the universe data if successful.
     /// If the returned value is None it indicates that the data was received successfully but isn't ready to act on.
     ///
-    /// Synchronised data packets handled as per ANSI E1.31-2018 Section 6.2.4.1.
+    /// Synchronised data packets handled as per ANSI E1.31-2018 Section 6.2.4.1. If synchronisation packets have stopped arriving for the
+    /// synchronisation address of a packet with the Force_Synchronization option bit set then the packet is handled as unsynchronised data,
+    /// without the bit set the packet keeps waiting for synchronisation to resume as per ANSI E1.31-2018 Section 6.2.6.
     ///
     /// Arguments:
+    /// cid: The CID of the source of the packet.
+    ///
     /// data_pkt: The sACN data packet to handle.
     ///
+    /// origin: Where the packet was received from, if known. This is included in the returned data along with the source name from
+    ///     the packet.
+    ///
     /// # Errors
     /// Returns an OutOfSequence error if a packet is received out of order as detected by the different between
     /// the packets sequence number and the expected sequence number as specified in ANSI E1.31-2018 Section 6.7.2 Sequence Numbering.
     ///
-    /// Returns a UniversesTerminated error if a packet is received with the stream_terminated flag set indicating that the source is no longer
-    /// sending on that universe and the announce_stream_termination_flag is set to true.
+    /// A packet received with the stream_terminated flag set indicating that the source is no longer sending on that universe queues a
+    /// UniverseTerminated event.
     ///
-    /// Will return an DmxMergeError if there is an issue merging or replacing new and existing waiting data.
+    /// Will return an DmxMergeError if there is an issue merging or replacing new and existing waiting data or if there is an issue
+    /// merging the data from multiple sources sending the same universe.
     fn handle_data_packet(
         &mut self,
         cid: Uuid,
         data_pkt: DataPacketFramingLayer,
+        origin: Option<PacketOrigin>,
     ) -> Result<Option<Vec<DMXData>>> {
         if data_pkt.preview_data && !self.process_preview_data {
             // Don't process preview data unless receiver has process_preview_data flag set.
@@ -712,10 +1677,9 @@ impl SacnReceiver {
         }
 
         if data_pkt.stream_terminated {
+            self.pending_events
+                .push_back(ReceiveEvent::UniverseTerminated(cid, data_pkt.universe));
             self.terminate_stream(cid, data_pkt.universe);
-            if self.announce_stream_termination {
-                return Err(SacnError::UniverseTerminated(cid, data_pkt.universe));
-            }
             return Ok(None);
         }
 
@@ -727,15 +1691,62 @@ impl SacnReceiver {
         // This is as per ANSI E1.31-2018 Section 6.2.6, Stream_Terminated: Bit 6, 'Any property values
         // in an E1.31 Data Packet containing this bit shall be ignored'
 
+        // A source which has timed out on the universe is treated as new so the timeouts are checked first, which also reports it. The
+        // timeouts of the other sources and universes are checked periodically rather than for every packet.
+        if self
+            .sequences
+            .data_timed_out(&cid, data_pkt.universe, self.now())
+        {
+            self.check_timeouts()?;
+        }
         self.sequences.check_data_seq_number(
             self.source_limit,
             cid,
             data_pkt.sequence_number,
             data_pkt.universe,
-            self.announce_timeout,
+            self.now(),
         )?;
 
-        if data_pkt.synchronization_address == E131_NO_SYNC_ADDR {
+        if data_pkt.data.property_values.first() == Some(&E131_PER_ADDRESS_PRIORITY_START_CODE) {
+            // Per-address priorities affect how data is arbitrated rather than being output directly so they take effect immediately
+            // and are never held for synchronisation.
+            let dmx_data: DMXData = DMXData {
+                universe: data_pkt.universe,
+                values: data_pkt.data.property_values.into_owned(),
+                sync_uni: data_pkt.synchronization_address,
+                priority: data_pkt.priority,
+                src_cid: Some(cid),
+                preview: data_pkt.preview_data,
+                src_addr: origin.map(|o| o.addr),
+                src_name: Some(data_pkt.source_name.to_string()),
+                recv_interface: origin.and_then(|o| o.interface),
+                recv_timestamp: self.now(),
+            };
+
+            self.source_priorities
+                .entry(dmx_data.universe)
+                .or_default()
+                .insert(cid, dmx_data);
+
+            if self.is_sampling(data_pkt.universe) {
+                return Ok(None);
+            }
+
+            return Ok(self
+                .arbitrate_source_data(data_pkt.universe)?
+                .map(|d| vec![d]));
+        }
+
+        // As per ANSI E1.31-2018 Section 6.2.6 if synchronisation has been lost and the source has set the Force_Synchronization
+        // option bit then the data is acted on immediately, otherwise it waits until synchronisation resumes.
+        // This is the meaning given by the standard, a clear bit does not fall back to unsynchronised processing. Holding the data keeps
+        // the last synchronised look of every universe in the group rather than updating some universes without the others, a source which
+        // would rather its receivers carry on without synchronisation sets the bit, see (SacnSource::set_force_synchronization).
+        let synchronised = data_pkt.synchronization_address != E131_NO_SYNC_ADDR
+            && !(data_pkt.force_synchronization
+                && self.is_sync_lost(data_pkt.synchronization_address));
+
+        if !synchronised {
             self.clear_waiting_data(data_pkt.universe);
 
             let vals: Vec<u8> = data_pkt.data.property_values.into_owned();
@@ -746,10 +1757,31 @@ impl SacnReceiver {
                 priority: data_pkt.priority,
                 src_cid: Some(cid),
                 preview: data_pkt.preview_data,
-                recv_timestamp: Instant::now(),
+                src_addr: origin.map(|o| o.addr),
+                src_name: Some(data_pkt.source_name.to_string()),
+                recv_interface: origin.and_then(|o| o.interface),
+                recv_timestamp: self.now(),
             };
 
-            Ok(Some(vec![dmx_data]))
+            if dmx_data.values.first() != Some(&E131_NULL_START_CODE) {
+                // Only DMX level data is arbitrated between sources, alternate start code data is passed up as received.
+                return Ok(Some(vec![dmx_data]));
+            }
+
+            self.source_data
+                .entry(dmx_data.universe)
+                .or_default()
+                .insert(cid, dmx_data);
+            remove_source_universe_data(&mut self.lost_sources, cid, data_pkt.universe);
+
+            if self.is_sampling(data_pkt.universe) {
+                // Only learn the sources during the sampling period, the arbitrated data is returned once it ends.
+                return Ok(None);
+            }
+
+            Ok(self
+                .arbitrate_source_data(data_pkt.universe)?
+                .map(|d| vec![d]))
         } else {
             // As per ANSI E1.31-2018 Appendix B.2 the receiver should listen at the synchronisation address when a data packet is received with a non-zero
             // synchronisation address.
@@ -763,7 +1795,10 @@ impl SacnReceiver {
                 priority: data_pkt.priority,
                 src_cid: Some(cid),
                 preview: data_pkt.preview_data,
-                recv_timestamp: Instant::now(),
+                src_addr: origin.map(|o| o.addr),
+                src_name: Some(data_pkt.source_name.to_string()),
+                recv_interface: origin.and_then(|o| o.interface),
+                recv_timestamp: self.now(),
             };
 
             self.store_waiting_data(dmx_data)?;
@@ -790,11 +1825,20 @@ impl SacnReceiver {
     fn terminate_stream(&mut self, src_cid: Uuid, universe: u16) {
         // Will only return an error if the source/universe wasn't found which is acceptable because as it
         // comes to the same result.
+        let was_tracked = self.sequences.has_source(&src_cid);
         let _ = self.sequences.remove_seq_numbers(src_cid, universe);
+        if was_tracked && !self.sequences.has_source(&src_cid) {
+            self.pending_events
+                .push_back(ReceiveEvent::SourceLost(src_cid));
+        }
+
+        remove_source_universe_data(&mut self.source_data, src_cid, universe);
+        remove_source_universe_data(&mut self.source_priorities, src_cid, universe);
+        remove_source_universe_data(&mut self.lost_sources, src_cid, universe);
+        self.forget_universe_if_unused(universe);
 
         // As with sequence numbers the source might not be found which is acceptable.
-        if let Some(index) = find_discovered_src(&self.discovered_sources, &src_cid)
-        {
+        if let Some(index) = find_discovered_src(&self.discovered_sources, &src_cid) {
             self.discovered_sources[index].terminate_universe(universe);
         }
     }
@@ -804,7 +1848,7 @@ impl SacnReceiver {
     /// Note that a receiver will only store a single packet of data per data_universe at once.
     ///
     /// If there is waiting data for the same universe as the data then it will be merged as per the
-    /// merge_func which by default keeps the highest priority data, if the data has the same priority
+    /// merger which by default keeps the highest priority data, if the data has the same priority
     /// then the newest data is kept.
     ///
     /// # Errors
@@ -812,8 +1856,20 @@ impl SacnReceiver {
     fn store_waiting_data(&mut self, data: DMXData) -> Result<()> {
         match self.waiting_data.remove(&data.universe) {
             Some(existing) => {
-                self.waiting_data
-                    .insert(data.universe, ((self.merge_func)(&existing, &data))?);
+                let merged = match self.merger.as_mut() {
+                    Some(merger) => merger.merge(&[
+                        MergeSource {
+                            data: &existing,
+                            per_address_priority: None,
+                        },
+                        MergeSource {
+                            data: &data,
+                            per_address_priority: None,
+                        },
+                    ])?,
+                    None => DEFAULT_MERGE_FUNC(&existing, &data)?,
+                };
+                self.waiting_data.insert(data.universe, merged);
             }
             None => {
                 self.waiting_data.insert(data.universe, data);
@@ -822,6 +1878,79 @@ impl SacnReceiver {
         Ok(())
     }
 
+    /// Produces the arbitrated DMX data for the given universe from the latest unsynchronised data of every source sending that universe.
+    ///
+    /// Sources which haven't sent within the E131_NETWORK_DATA_LOSS_TIMEOUT are ignored unless they are still used as per the data loss
+    /// policy of the universe, see (set_data_loss_policy)[set_data_loss_policy]. The remaining sources, along with their
+    /// per-address priority (start code 0xDD) data if they have sent any within the timeout, are combined using the merger in the
+    /// order they were received (oldest first).
+    ///
+    /// Returns None if there is no data from any source for the universe. The state of the universe is updated with the produced data,
+    /// see (universe_state)[universe_state].
+    ///
+    /// # Arguments
+    /// universe: The universe to produce the arbitrated data for.
+    ///
+    /// # Errors
+    /// Will return a DmxMergeError if there is an issue merging the data of the sources.
+    fn arbitrate_source_data(&mut self, universe: u16) -> Result<Option<DMXData>> {
+        let now = self.now();
+        let priorities = self.source_priorities.get(&universe);
+
+        // The data of lost sources as it should currently be used, e.g. part way through a fade.
+        let lost: Vec<(DMXData, Option<&DMXData>)> = self
+            .lost_sources
+            .get(&universe)
+            .map(|srcs| {
+                srcs.values()
+                    .map(|l| (l.current_data(now), l.per_address_priority.as_ref()))
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let mut live: Vec<MergeSource> = self
+            .source_data
+            .get(&universe)
+            .into_iter()
+            .flat_map(|srcs| srcs.values())
+            .filter(|d| {
+                now.saturating_duration_since(d.recv_timestamp) < E131_NETWORK_DATA_LOSS_TIMEOUT
+            })
+            .map(|d| MergeSource {
+                data: d,
+                per_address_priority: d
+                    .src_cid
+                    .and_then(|cid| priorities.and_then(|p| p.get(&cid)))
+                    .filter(|p| {
+                        now.saturating_duration_since(p.recv_timestamp)
+                            < E131_NETWORK_DATA_LOSS_TIMEOUT
+                    })
+                    .map(|p| p.values.as_slice()),
+            })
+            .collect();
+
+        live.extend(lost.iter().map(|(data, pap)| MergeSource {
+            data,
+            per_address_priority: pap.map(|p| p.values.as_slice()),
+        }));
+
+        if live.is_empty() {
+            return Ok(None);
+        }
+        live.sort_by_key(|s| s.data.recv_timestamp);
+
+        let sources: Vec<Uuid> = live.iter().filter_map(|s| s.data.src_cid).collect();
+        let merged = match self.merger.as_mut() {
+            Some(merger) => merger.merge(&live)?,
+            None => {
+                let mut merger = DEFAULT_MERGER;
+                merger.merge(&live)?
+            }
+        };
+        self.update_universe_state(&merged, sources);
+        Ok(Some(merged))
+    }
+
     /// Handles the given synchronisation packet for this DMX receiver.
     ///
     /// Synchronisation packets handled as described by ANSI E1.31-2018 Section 6.2.4.1.
@@ -851,13 +1980,22 @@ impl SacnReceiver {
             return Ok(None); // If not listening for this universe then ignore the packet.
         }
 
+        // A source which has timed out on the synchronisation address is treated as new, see (handle_data_packet)[handle_data_packet].
+        if self
+            .sequences
+            .sync_timed_out(&cid, sync_pkt.synchronization_address, self.now())
+        {
+            self.check_timeouts()?;
+        }
         self.sequences.check_sync_seq_number(
             self.source_limit,
             cid,
             sync_pkt.sequence_number,
             sync_pkt.synchronization_address,
-            self.announce_timeout,
+            self.now(),
         )?;
+        self.sync_last_recv
+            .insert(sync_pkt.synchronization_address, self.now());
 
         let res = self.rtrv_waiting_data(sync_pkt.synchronization_address);
         if res.is_empty() {
@@ -867,9 +2005,46 @@ impl SacnReceiver {
         }
     }
 
+    /// Updates the state of the universe of the given data, see (universe_state)[universe_state].
+    ///
+    /// Data with an alternative START code or for a universe which isn't being listened to is ignored.
+    ///
+    /// Arguments:
+    /// data: The data which is being acted on.
+    ///
+    /// sources: The CIDs of the sources which contributed to the data.
+    fn update_universe_state(&mut self, data: &DMXData, sources: Vec<Uuid>) {
+        if data.values.first() != Some(&E131_NULL_START_CODE) || !self.is_listening(&data.universe)
+        {
+            return;
+        }
+        self.universe_states.insert(
+            data.universe,
+            UniverseState {
+                data: data.clone(),
+                last_updated: self.now(),
+                sources,
+            },
+        );
+    }
+
+    /// Returns true if synchronisation has been lost on the given synchronisation address, this is when synchronisation packets were being
+    /// received for the address but none have been received within E131_NETWORK_DATA_LOSS_TIMEOUT.
+    ///
+    /// Arguments:
+    /// sync_uni: The synchronisation address to check.
+    fn is_sync_lost(&self, sync_uni: u16) -> bool {
+        self.sync_last_recv.get(&sync_uni).is_some_and(|t| {
+            self.now().saturating_duration_since(*t) >= E131_NETWORK_DATA_LOSS_TIMEOUT
+        })
+    }
+
     /// Retrieves and removes the DMX data of all waiting data with a synchronisation address matching the one provided.
     /// Returns an empty Vec if there is no data waiting.
     ///
+    /// Data for universes which are in their sampling period is removed but not returned. The state of each universe returned is updated,
+    /// see (universe_state)[universe_state].
+    ///
     /// Arguments:
     /// sync_uni: The synchronisation universe of the data that should be retrieved.
     fn rtrv_waiting_data(&mut self, sync_uni: u16) -> Vec<DMXData> {
@@ -883,14 +2058,22 @@ impl SacnReceiver {
             }
         }
 
+        let now = self.now();
         let mut res: Vec<DMXData> = Vec::new();
         for k in keys {
             let data = self.waiting_data.remove(&k).unwrap();
-            if data.recv_timestamp.elapsed() < E131_NETWORK_DATA_LOSS_TIMEOUT {
+            if now.saturating_duration_since(data.recv_timestamp) < E131_NETWORK_DATA_LOSS_TIMEOUT
+                && !self.is_sampling(data.universe)
+            {
                 res.push(data);
             }
         }
 
+        // All the released universes are updated together.
+        for data in res.iter() {
+            self.update_universe_state(data, data.src_cid.into_iter().collect());
+        }
+
         res
     }
 
@@ -915,9 +2098,9 @@ impl SacnReceiver {
     /// Returns the source name if a source was fully discovered or None if the source was only partially discovered.
     ///
     /// Arguments:
-    /// 
+    ///
     /// cid: the source CID.
-    /// 
+    ///
     /// discovery_pkt: The universe discovery part of the universe discovery packet to handle.
     fn handle_universe_discovery_packet(
         &mut self,
@@ -937,16 +2120,14 @@ impl SacnReceiver {
         };
 
         // See if some pages that belong to the source that this page belongs to have already been received.
-        match find_discovered_src(
-            &self.partially_discovered_sources,
-            &cid
-        ) {
+        match find_discovered_src(&self.partially_discovered_sources, &cid) {
             Some(index) => {
-                // Some pages have already been received from this source.
-                self.partially_discovered_sources[index]
-                    .pages
-                    .push(uni_page);
-                self.partially_discovered_sources[index].last_updated = Instant::now();
+                // Some pages have already been received from this source, a page received again (e.g. duplicated by the network)
+                // replaces the earlier copy so it isn't counted twice.
+                let pages = &mut self.partially_discovered_sources[index].pages;
+                pages.retain(|p| p.page != page);
+                pages.push(uni_page);
+                self.partially_discovered_sources[index].last_updated = self.now();
                 if self.partially_discovered_sources[index].has_all_pages() {
                     let discovered_src: DiscoveredSacnSource =
                         self.partially_discovered_sources.remove(index);
@@ -961,7 +2142,7 @@ impl SacnReceiver {
                     cid,
                     last_page,
                     pages: vec![uni_page],
-                    last_updated: Instant::now(),
+                    last_updated: self.now(),
                 };
 
                 if page == 0 && page == last_page {
@@ -981,16 +2162,125 @@ impl SacnReceiver {
     /// Goes through all the waiting data and removes any which has timed out as a sync-packet for it hasn't been received within the E131_NETWORK_DATA_LOSS_TIMEOUT
     /// period as specified by ANSI E1.31-2018 Section 11.1.2.
     fn check_waiting_data_timeouts(&mut self) {
-        self.waiting_data
-            .retain(|_uni, data| data.recv_timestamp.elapsed() < E131_NETWORK_DATA_LOSS_TIMEOUT);
+        let now = self.now();
+        self.waiting_data.retain(|_uni, data| {
+            now.saturating_duration_since(data.recv_timestamp) < E131_NETWORK_DATA_LOSS_TIMEOUT
+        });
+    }
+
+    /// Goes through the latest data from each source and removes any which hasn't been refreshed within the E131_NETWORK_DATA_LOSS_TIMEOUT
+    /// period so that a source which has stopped sending no longer takes part in arbitration, as per ANSI E1.31-2018 Section 6.7.1.
+    ///
+    /// If the universe has a data loss policy the data of the source is instead kept as a lost source, see
+    /// (check_lost_sources)[check_lost_sources].
+    fn check_source_data_timeouts(&mut self) {
+        let now = self.now();
+        let timed_out = take_timed_out_source_data(&mut self.source_data, now);
+        let mut universes: Vec<u16> = timed_out.iter().map(|d| d.universe).collect();
+        universes.dedup();
+        for data in timed_out {
+            let (Some(policy), Some(cid)) =
+                (self.get_data_loss_policy(data.universe), data.src_cid)
+            else {
+                continue;
+            };
+            let per_address_priority = self
+                .source_priorities
+                .get_mut(&data.universe)
+                .and_then(|p| p.remove(&cid));
+            self.lost_sources.entry(data.universe).or_default().insert(
+                cid,
+                LostSource {
+                    data,
+                    per_address_priority,
+                    policy,
+                    lost_at: now,
+                    last_frame: now,
+                },
+            );
+        }
+        remove_timed_out_source_data(&mut self.source_priorities, now);
+        for universe in universes {
+            self.forget_universe_if_unused(universe);
+        }
+    }
+
+    /// Tells the merger to forget the given universe if the receiver has no sources left for it, live or lost, so that the state
+    /// a merger keeps per universe doesn't grow with every universe ever received, see (Merger::forget_universe)[Merger::forget_universe].
+    fn forget_universe_if_unused(&mut self, universe: u16) {
+        if self.source_data.contains_key(&universe) || self.lost_sources.contains_key(&universe) {
+            return;
+        }
+        if let Some(merger) = self.merger.as_mut() {
+            merger.forget_universe(universe);
+        }
+    }
+
+    /// Applies the data loss policy of each lost source, see (DataLossPolicy)[DataLossPolicy].
+    ///
+    /// Lost sources which should no longer be used are removed and a Data event is queued with the resulting data of each universe
+    /// which changed, this is the arbitrated data of the remaining sources or all zeros if there are none.
+    ///
+    /// # Errors
+    /// Will return a DmxMergeError if there is an issue merging the data of the sources of a universe.
+    fn check_lost_sources(&mut self) -> Result<()> {
+        let now = self.now();
+
+        // The universes which need a frame and the length of the data of the lost sources.
+        let mut frames: Vec<(u16, usize)> = Vec::new();
+        for (universe, srcs) in self.lost_sources.iter_mut() {
+            let mut frame_len: Option<usize> = None;
+            srcs.retain(|_cid, lost| {
+                let elapsed = now.saturating_duration_since(lost.lost_at);
+                let (released, fading) = match lost.policy {
+                    DataLossPolicy::Hold => (false, false),
+                    DataLossPolicy::HoldFor(d) => (elapsed >= d, false),
+                    DataLossPolicy::Fade(d) => (elapsed >= d, true),
+                    DataLossPolicy::Release => (true, false),
+                };
+                let frame_due = fading
+                    && now.saturating_duration_since(lost.last_frame)
+                        >= DATA_LOSS_FADE_FRAME_INTERVAL;
+                if released || frame_due {
+                    lost.last_frame = now;
+                    frame_len = frame_len.max(Some(lost.data.values.len()));
+                }
+                !released
+            });
+            if let Some(len) = frame_len {
+                frames.push((*universe, len));
+            }
+        }
+        self.lost_sources.retain(|_uni, srcs| !srcs.is_empty());
+        frames.sort_unstable();
+
+        for (universe, len) in frames {
+            self.forget_universe_if_unused(universe);
+            if self.is_sampling(universe) {
+                continue;
+            }
+            let data = match self.arbitrate_source_data(universe)? {
+                Some(d) => d,
+                None => {
+                    let d = released_data(universe, len, now);
+                    self.update_universe_state(&d, Vec::new());
+                    d
+                }
+            };
+            self.pending_events
+                .push_back(ReceiveEvent::Data(vec![data]));
+        }
+        Ok(())
     }
 
     /// Goes through all discovered sources and removes any that have timed out
     fn remove_expired_sources(&mut self) {
-        self.partially_discovered_sources
-            .retain(|s| s.last_updated.elapsed() < UNIVERSE_DISCOVERY_SOURCE_TIMEOUT);
-        self.discovered_sources
-            .retain(|s| s.last_updated.elapsed() < UNIVERSE_DISCOVERY_SOURCE_TIMEOUT);
+        let now = self.now();
+        let live = |s: &DiscoveredSacnSource| {
+            now.saturating_duration_since(s.last_updated) < UNIVERSE_DISCOVERY_SOURCE_TIMEOUT
+        };
+        self.partially_discovered_sources.retain(live);
+        self.discovered_sources.retain(live);
     }
 }
 
@@ -1015,69 +2305,332 @@ impl Drop for SacnReceiver {
     }
 }
 
+/// Returns the error used to indicate that no data was available within the timeout when receiving.
+///
+/// Windows and Unix use different error kinds (TimedOut/WouldBlock) for this so the kind for the current operating system is used.
+fn no_data_error() -> SacnError {
+    io::Error::new(
+        if cfg!(target_os = "windows") {
+            io::ErrorKind::TimedOut
+        } else {
+            io::ErrorKind::WouldBlock
+        },
+        "No data available in given timeout",
+    )
+    .into()
+}
+
 /// Searches for the discovered source with the given name in the given vector of discovered sources and
 /// returns the index of the src in the Vec or None if not found.
 ///
 /// Arguments:
-/// 
+///
 /// srcs: The Vec of DiscoveredSacnSources to search.
-/// 
+///
 /// cid: The CID (uuid) of the source to find.
 fn find_discovered_src(srcs: &[DiscoveredSacnSource], cid: &Uuid) -> Option<usize> {
     (0..srcs.len()).find(|&i| srcs[i].cid == *cid)
 }
 
-/// In general the lower level transport layer is handled by SacnNetworkReceiver (which itself wraps a Socket).
-/// Windows and linux handle multicast sockets differently.
-/// This is built for / tested with Windows 10 1909.
-#[cfg(target_os = "windows")]
-impl SacnNetworkReceiver {
-    /// Creates a new DMX receiver on the interface specified by the given address.
-    ///
-    /// If the given address is an IPv4 address then communication will only work between IPv4 devices, if the given address is IPv6 then communication
-    /// will only work between IPv6 devices by default but IPv4 receiving can be enabled using set_ipv6_only(false).
-    ///
-    /// # Errors
-    /// Will return an error if the SacnReceiver fails to bind to a socket with the given ip.
-    /// For more details see socket2::Socket::new().
-    fn new(ip: SocketAddr) -> Result<SacnNetworkReceiver> {
-        Ok(SacnNetworkReceiver {
-            socket: create_win_socket(ip)?,
-            addr: ip,
-            is_multicast_enabled: !(ip.is_ipv6()), // IPv6 Windows IP Multicast is currently unsupported.
-        })
+/// Passes each operation on to the UDP sockets or the transport that the receiver receives from.
+impl ReceiverNetwork {
+    /// Returns the sockets that packets are received on, empty if a transport is used.
+    #[cfg(feature = "async")]
+    fn sockets(&self) -> impl Iterator<Item = &Socket> {
+        match self {
+            ReceiverNetwork::Sockets(receiver) => Some(receiver),
+            ReceiverNetwork::Transport(_) => None,
+        }
+        .into_iter()
+        .flat_map(SacnNetworkReceiver::sockets)
     }
 
-    /// Connects this SacnNetworkReceiver to the multicast address which corresponds to the given universe to allow receiving packets for that universe.
+    /// Returns the UDP socket receiver, used by tests to check its internal state.
+    #[cfg(test)]
+    fn sockets_receiver(&self) -> &SacnNetworkReceiver {
+        match self {
+            ReceiverNetwork::Sockets(receiver) => receiver,
+            ReceiverNetwork::Transport(_) => panic!("Receiver uses a transport"),
+        }
+    }
+
+    /// Resolves the given interface to the form used to join multicast groups on it, see
+    /// (SacnNetworkReceiver::resolve_interface)[SacnNetworkReceiver::resolve_interface].
     ///
     /// # Errors
-    /// Will return an Error if the given universe cannot be converted to an Ipv4 or Ipv6 multicast_addr depending on if the Receiver is bound to an
-    /// IPv4 or IPv6 address. See packet::universe_to_ipv4_multicast_addr and packet::universe_to_ipv6_multicast_addr.
-    ///
-    /// Will return an Io error if cannot join the universes corresponding multicast group address.
-    fn listen_multicast_universe(&self, universe: u16) -> Result<()> {
-        let multicast_addr = if self.addr.is_ipv4() {
-            universe_to_ipv4_multicast_addr(universe)? // "Failed to convert universe to IPv4 multicast addr"
-        } else {
-            universe_to_ipv6_multicast_addr(universe)? // "Failed to convert universe to IPv6 multicast addr"
-        };
+    /// TransportUnsupported: Returned if a transport is used as interfaces cannot be chosen for it.
+    fn resolve_interface(&self, interface: &NetworkInterface) -> Result<MulticastInterface> {
+        match self {
+            ReceiverNetwork::Sockets(receiver) => receiver.resolve_interface(interface),
+            ReceiverNetwork::Transport(_) => Err(SacnError::TransportUnsupported(
+                "Network interfaces cannot be chosen for a receiver using a transport".to_string(),
+            )),
+        }
+    }
 
-        join_win_multicast(&self.socket, multicast_addr, self.addr.ip())
+    /// Sets the interfaces that multicast groups are joined on, these are only used by UDP sockets.
+    fn set_multicast_interfaces(&mut self, interfaces: Vec<MulticastInterface>) {
+        if let ReceiverNetwork::Sockets(receiver) = self {
+            receiver.interfaces = interfaces;
+        }
     }
 
-    /// Removes this SacnNetworkReceiver from the multicast group which corresponds to the given universe.
-    ///
-    /// # Errors
-    /// Will return an Error if the given universe cannot be converted to an Ipv4 or Ipv6 multicast_addr depending on if the Receiver is bound to an
-    /// IPv4 or IPv6 address. See packet::universe_to_ipv4_multicast_addr and packet::universe_to_ipv6_multicast_addr.
-    fn mute_multicast_universe(&mut self, universe: u16) -> Result<()> {
-        let multicast_addr = if self.addr.is_ipv4() {
+    /// Joins the multicast group which corresponds to the given universe.
+    fn listen_multicast_universe(&mut self, universe: u16) -> Result<()> {
+        match self {
+            ReceiverNetwork::Sockets(receiver) => receiver.listen_multicast_universe(universe),
+            ReceiverNetwork::Transport(receiver) => receiver.listen_multicast_universe(universe),
+        }
+    }
+
+    /// Leaves the multicast group which corresponds to the given universe.
+    fn mute_multicast_universe(&mut self, universe: u16) -> Result<()> {
+        match self {
+            ReceiverNetwork::Sockets(receiver) => receiver.mute_multicast_universe(universe),
+            ReceiverNetwork::Transport(receiver) => receiver.mute_multicast_universe(universe),
+        }
+    }
+
+    /// Sets the value of the is_multicast_enabled flag, see (SacnReceiver::set_is_multicast_enabled)[SacnReceiver::set_is_multicast_enabled].
+    fn set_is_multicast_enabled(&mut self, val: bool) -> Result<()> {
+        match self {
+            ReceiverNetwork::Sockets(receiver) => receiver.set_is_multicast_enabled(val),
+            ReceiverNetwork::Transport(receiver) => {
+                receiver.is_multicast_enabled = val;
+                Ok(())
+            }
+        }
+    }
+
+    /// Returns true if multicast is enabled on this receiver and false if not.
+    fn is_multicast_enabled(&self) -> bool {
+        match self {
+            ReceiverNetwork::Sockets(receiver) => receiver.is_multicast_enabled(),
+            ReceiverNetwork::Transport(receiver) => receiver.is_multicast_enabled,
+        }
+    }
+
+    /// If set to true then only receive over IPv6, see (SacnNetworkReceiver::set_only_v6)[SacnNetworkReceiver::set_only_v6].
+    ///
+    /// # Errors
+    /// TransportUnsupported: Returned if a transport is used.
+    fn set_only_v6(&mut self, val: bool) -> Result<()> {
+        match self {
+            ReceiverNetwork::Sockets(receiver) => receiver.set_only_v6(val),
+            ReceiverNetwork::Transport(_) => Err(SacnError::TransportUnsupported(
+                "IPv6 only mode cannot be set on a receiver using a transport".to_string(),
+            )),
+        }
+    }
+
+    /// Sets the timeout for the recv operation, a value of None means the recv operation will become blocking.
+    fn set_timeout(&mut self, timeout: Option<Duration>) -> Result<()> {
+        match self {
+            ReceiverNetwork::Sockets(receiver) => receiver.set_timeout(timeout),
+            ReceiverNetwork::Transport(receiver) => {
+                receiver.timeout = timeout;
+                Ok(())
+            }
+        }
+    }
+
+    /// Returns a packet, along with where it was received from if known, if there is one available within the timeout.
+    ///
+    /// # Errors
+    /// See (SacnNetworkReceiver::recv)[SacnNetworkReceiver::recv].
+    fn recv<'a>(
+        &mut self,
+        buf: &'a mut [u8; RCV_BUF_DEFAULT_SIZE],
+    ) -> Result<(AcnRootLayerProtocol<'a>, Option<PacketOrigin>)> {
+        match self {
+            ReceiverNetwork::Sockets(receiver) => receiver.recv(buf),
+            ReceiverNetwork::Transport(receiver) => receiver.recv(buf),
+        }
+    }
+
+    /// Receives a batch of packets, only used with UDP sockets as batching cannot be enabled on a transport.
+    #[cfg(target_os = "linux")]
+    fn recv_batch(&mut self) -> Result<()> {
+        match self {
+            ReceiverNetwork::Sockets(receiver) => receiver.recv_batch(),
+            ReceiverNetwork::Transport(_) => Err(SacnError::TransportUnsupported(
+                "Receiving packets in batches is only supported on UDP sockets".to_string(),
+            )),
+        }
+    }
+}
+
+impl TransportReceiver {
+    /// Creates a new TransportReceiver receiving from the given transport, multicast is enabled and the receive operation blocks.
+    ///
+    /// # Errors
+    /// Io: Returned if the address the transport is bound to cannot be determined.
+    fn new(transport: Box<dyn Transport>) -> Result<TransportReceiver> {
+        Ok(TransportReceiver {
+            addr: transport.local_addr()?,
+            transport,
+            is_multicast_enabled: true,
+            joined: HashSet::new(),
+            timeout: None,
+        })
+    }
+
+    /// Returns the multicast group which corresponds to the given universe, of the IP version of the bound address.
+    ///
+    /// # Errors
+    /// IllegalUniverse: Returned if the universe is outwith the allowed range of universes.
+    fn multicast_group(&self, universe: u16) -> Result<IpAddr> {
+        let addr = if self.addr.is_ipv6() {
+            universe_to_ipv6_multicast_addr(universe)?
+        } else {
+            universe_to_ipv4_multicast_addr(universe)?
+        };
+        // The multicast addresses of universes are always IPv4 or IPv6 addresses.
+        Ok(addr.as_socket().unwrap().ip())
+    }
+
+    /// Joins the multicast group which corresponds to the given universe through the transport, if not already joined.
+    ///
+    /// # Errors
+    /// Io: Returned if the transport fails to join the group.
+    fn listen_multicast_universe(&mut self, universe: u16) -> Result<()> {
+        if !self.joined.contains(&universe) {
+            self.transport
+                .join_multicast(self.multicast_group(universe)?)?;
+            self.joined.insert(universe);
+        }
+        Ok(())
+    }
+
+    /// Leaves the multicast group which corresponds to the given universe through the transport, if it was joined.
+    ///
+    /// # Errors
+    /// Io: Returned if the transport fails to leave the group.
+    fn mute_multicast_universe(&mut self, universe: u16) -> Result<()> {
+        if self.joined.remove(&universe) {
+            self.transport
+                .leave_multicast(self.multicast_group(universe)?)?;
+        }
+        Ok(())
+    }
+
+    /// Returns a packet, along with where it was sent from, if one is received from the transport within the timeout.
+    ///
+    /// # Errors
+    /// Io: Returned if no packet is received within the timeout or the transport fails to receive.
+    ///
+    /// May return an error if the received packet cannot be parsed, see (parse)[fn.AcnRootLayerProtocol::parse.packet].
+    fn recv<'a>(
+        &mut self,
+        buf: &'a mut [u8; RCV_BUF_DEFAULT_SIZE],
+    ) -> Result<(AcnRootLayerProtocol<'a>, Option<PacketOrigin>)> {
+        let (n, addr) = self.transport.recv_from(buf, self.timeout)?;
+        let origin = PacketOrigin {
+            addr,
+            interface: None,
+        };
+        Ok((AcnRootLayerProtocol::parse(&buf[..n])?, Some(origin)))
+    }
+}
+
+/// In general the lower level transport layer is handled by SacnNetworkReceiver (which itself wraps a Socket).
+/// Windows and linux handle multicast sockets differently.
+/// This is built for / tested with Windows 10 1909.
+impl SacnNetworkReceiver {
+    /// Returns the sockets that packets are received on, the socket followed by the shards.
+    fn sockets(&self) -> impl Iterator<Item = &Socket> {
+        std::iter::once(&self.socket).chain(&self.shards)
+    }
+
+    /// Returns the interfaces that multicast groups are joined on, the interface of the bound address if none have been set.
+    fn multicast_interfaces(&self) -> Vec<MulticastInterface> {
+        if self.interfaces.is_empty() {
+            vec![MulticastInterface::Addr(self.addr.ip())]
+        } else {
+            self.interfaces.clone()
+        }
+    }
+
+    /// Resolves the given interface to the form used to join multicast groups on it.
+    ///
+    /// IPv4 groups can be joined using an interface address directly, otherwise the index of the interface is looked up.
+    ///
+    /// # Errors
+    /// IpVersionError: Returned if the interface is given by an address of a different IP version to the bound address.
+    ///
+    /// InterfaceNotFound: Returned if there is no interface with the given name or address.
+    ///
+    /// OsOperationUnsupported: Returned if interfaces cannot be looked up by name or address on the current OS.
+    fn resolve_interface(&self, interface: &NetworkInterface) -> Result<MulticastInterface> {
+        match (self.addr, interface) {
+            (SocketAddr::V4(_), NetworkInterface::Addr(IpAddr::V4(a))) => {
+                Ok(MulticastInterface::Addr(IpAddr::V4(*a)))
+            }
+            (SocketAddr::V4(_), NetworkInterface::Addr(IpAddr::V6(_)))
+            | (SocketAddr::V6(_), NetworkInterface::Addr(IpAddr::V4(_))) => {
+                Err(SacnError::IpVersionError())
+            }
+            _ => Ok(MulticastInterface::Index(interface.index()?)),
+        }
+    }
+}
+
+#[cfg(target_os = "windows")]
+impl SacnNetworkReceiver {
+    /// Creates a new DMX receiver on the interface specified by the given address.
+    ///
+    /// If the given address is an IPv4 address then communication will only work between IPv4 devices, if the given address is IPv6 then communication
+    /// will only work between IPv6 devices by default but IPv4 receiving can be enabled using set_ipv6_only(false).
+    ///
+    /// # Errors
+    /// Will return an error if the SacnReceiver fails to bind to a socket with the given ip.
+    /// For more details see socket2::Socket::new().
+    fn new(ip: SocketAddr) -> Result<SacnNetworkReceiver> {
+        Ok(SacnNetworkReceiver {
+            socket: create_win_socket(ip)?,
+            addr: ip,
+            is_multicast_enabled: !(ip.is_ipv6()), // IPv6 Windows IP Multicast is currently unsupported.
+            interfaces: Vec::new(),
+            shards: Vec::new(),
+        })
+    }
+
+    /// Connects this SacnNetworkReceiver to the multicast address which corresponds to the given universe to allow receiving packets for that universe.
+    ///
+    /// # Errors
+    /// Will return an Error if the given universe cannot be converted to an Ipv4 or Ipv6 multicast_addr depending on if the Receiver is bound to an
+    /// IPv4 or IPv6 address. See packet::universe_to_ipv4_multicast_addr and packet::universe_to_ipv6_multicast_addr.
+    ///
+    /// Will return an Io error if cannot join the universes corresponding multicast group address on each of the interfaces.
+    fn listen_multicast_universe(&self, universe: u16) -> Result<()> {
+        let multicast_addr = if self.addr.is_ipv4() {
+            universe_to_ipv4_multicast_addr(universe)? // "Failed to convert universe to IPv4 multicast addr"
+        } else {
+            universe_to_ipv6_multicast_addr(universe)? // "Failed to convert universe to IPv6 multicast addr"
+        };
+
+        for interface in self.multicast_interfaces() {
+            join_win_multicast(&self.socket, multicast_addr.clone(), interface)?;
+        }
+        Ok(())
+    }
+
+    /// Removes this SacnNetworkReceiver from the multicast group which corresponds to the given universe.
+    ///
+    /// # Errors
+    /// Will return an Error if the given universe cannot be converted to an Ipv4 or Ipv6 multicast_addr depending on if the Receiver is bound to an
+    /// IPv4 or IPv6 address. See packet::universe_to_ipv4_multicast_addr and packet::universe_to_ipv6_multicast_addr.
+    fn mute_multicast_universe(&mut self, universe: u16) -> Result<()> {
+        let multicast_addr = if self.addr.is_ipv4() {
             universe_to_ipv4_multicast_addr(universe)? // "Failed to convert universe to IPv4 multicast addr"
         } else {
             universe_to_ipv6_multicast_addr(universe)? // "Failed to convert universe to IPv6 multicast addr"
         };
 
-        leave_win_multicast(&self.socket, multicast_addr)
+        for interface in self.multicast_interfaces() {
+            leave_win_multicast(&self.socket, multicast_addr.clone(), interface)?;
+        }
+        Ok(())
     }
 
     /// Sets the value of the is_multicast_enabled flag to the given value.
@@ -1119,7 +2672,7 @@ impl SacnNetworkReceiver {
         }
     }
 
-    /// Returns a packet if there is one available.
+    /// Returns a packet, along with where it was received from if known, if there is one available.
     ///
     /// The packet may not be ready to transmit if it is awaiting synchronisation.
     /// Will only block if set_timeout was called with a timeout of None so otherwise (and by default) it won't
@@ -1141,13 +2694,12 @@ impl SacnNetworkReceiver {
     fn recv<'a>(
         &mut self,
         buf: &'a mut [u8; RCV_BUF_DEFAULT_SIZE],
-    ) -> Result<AcnRootLayerProtocol<'a>> {
-        // use read() for the windows impl, since windows does not like using read_exact()
-        let n = self.socket.read(buf)?;
+    ) -> Result<(AcnRootLayerProtocol<'a>, Option<PacketOrigin>)> {
+        let (n, origin) = recv_with_origin(&self.socket, buf)?;
         if n > RCV_BUF_DEFAULT_SIZE {
             return Err(SacnError::TooManyBytesRead(n, buf.len()));
         }
-        AcnRootLayerProtocol::parse(buf)
+        Ok((AcnRootLayerProtocol::parse(&buf[..n])?, origin))
     }
 
     /// Set the timeout for the recv operation.
@@ -1184,24 +2736,76 @@ impl SacnNetworkReceiver {
             socket: create_unix_socket(ip)?,
             addr: ip,
             is_multicast_enabled: true, // Linux IP Multicast is supported for Ipv4 and Ipv6.
+            interfaces: Vec::new(),
+            shards: Vec::new(),
+            memberships: HashMap::new(),
+            full_sockets: HashSet::new(),
+            timeout: None,
+            next_socket: 0,
+            #[cfg(target_os = "linux")]
+            batch: RecvBatch::default(),
         })
     }
 
     /// Connects this SacnNetworkReceiver to the multicast address which corresponds to the given universe to allow receiving packets for that universe.
     ///
+    /// The group is joined on the first socket which hasn't reached its membership limit, creating a new shard socket if they all have.
+    ///
     /// # Errors
     /// Will return an Error if the given universe cannot be converted to an IPv4 or IPv6 multicast_addr depending on if the Receiver is bound to an
     /// IPv4 or IPv6 address. See packet::universe_to_ipv4_multicast_addr and packet::universe_to_ipv6_multicast_addr.
     ///
-    /// Will return an Io error if cannot join the universes corresponding multicast group address.
-    fn listen_multicast_universe(&self, universe: u16) -> Result<()> {
+    /// Will return an Io error if cannot join the universes corresponding multicast group address on each of the interfaces, including if
+    /// a newly created socket cannot join it, or if a new shard socket cannot be created.
+    fn listen_multicast_universe(&mut self, universe: u16) -> Result<()> {
         let multicast_addr = if self.addr.is_ipv4() {
             universe_to_ipv4_multicast_addr(universe)? // "Failed to convert universe to IPv4 multicast addr"
         } else {
             universe_to_ipv6_multicast_addr(universe)? // "Failed to convert universe to IPv6 multicast addr"
         };
 
-        join_unix_multicast(&self.socket, multicast_addr, self.addr.ip())
+        let mut i = 0;
+        loop {
+            let is_new = i > self.shards.len();
+            if is_new {
+                self.shards.push(create_unix_socket(self.addr)?);
+            } else if self.full_sockets.contains(&i) {
+                i += 1;
+                continue;
+            }
+
+            match self.join_multicast_on(i, &multicast_addr) {
+                Ok(()) => {
+                    self.memberships.insert(universe, i);
+                    return Ok(());
+                }
+                Err(SacnError::Io(e)) if !is_new && is_membership_limit(&e) => {
+                    self.full_sockets.insert(i);
+                    i += 1;
+                }
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
+    /// Joins the given multicast group on each of the interfaces using the socket with the given index (as in memberships).
+    ///
+    /// If the group cannot be joined on one of the interfaces then it is left on the interfaces already joined.
+    ///
+    /// # Errors
+    /// Will return an Io error if the group cannot be joined on one of the interfaces, see (join_unix_multicast)[join_unix_multicast].
+    fn join_multicast_on(&self, i: usize, multicast_addr: &SockAddr) -> Result<()> {
+        let socket = self.sockets().nth(i).unwrap();
+        let interfaces = self.multicast_interfaces();
+        for (joined, interface) in interfaces.iter().enumerate() {
+            if let Err(e) = join_unix_multicast(socket, multicast_addr.clone(), *interface) {
+                for interface in &interfaces[..joined] {
+                    let _ = leave_unix_multicast(socket, multicast_addr.clone(), *interface);
+                }
+                return Err(e);
+            }
+        }
+        Ok(())
     }
 
     /// Removes this SacnNetworkReceiver from the multicast group which corresponds to the given universe.
@@ -1216,7 +2820,14 @@ impl SacnNetworkReceiver {
             universe_to_ipv6_multicast_addr(universe)?
         };
 
-        leave_unix_multicast(&self.socket, multicast_addr, self.addr.ip())
+        let i = self.memberships.remove(&universe).unwrap_or(0);
+        self.full_sockets.remove(&i);
+
+        let socket = self.sockets().nth(i).unwrap();
+        for interface in self.multicast_interfaces() {
+            leave_unix_multicast(socket, multicast_addr.clone(), interface)?;
+        }
+        Ok(())
     }
 
     /// Sets the value of the is_multicast_enabled flag to the given value.
@@ -1253,7 +2864,7 @@ impl SacnNetworkReceiver {
         }
     }
 
-    /// Returns a packet if there is one available.
+    /// Returns a packet, along with where it was received from if known, if there is one available.
     ///
     /// The packet may not be ready to transmit if it is awaiting synchronisation.
     /// Will only block if set_timeout was called with a timeout of None so otherwise (and by default) it won't
@@ -1274,13 +2885,18 @@ impl SacnNetworkReceiver {
     fn recv<'a>(
         &mut self,
         buf: &'a mut [u8; RCV_BUF_DEFAULT_SIZE],
-    ) -> Result<AcnRootLayerProtocol<'a>> {
-        // use read() since read_exact() was not passing the tests.
-        let n = self.socket.read(buf)?;
+    ) -> Result<(AcnRootLayerProtocol<'a>, Option<PacketOrigin>)> {
+        let socket = if self.shards.is_empty() {
+            &self.socket
+        } else {
+            let i = self.poll_sockets()?;
+            self.sockets().nth(i).unwrap()
+        };
+        let (n, origin) = recv_with_origin(socket, buf)?;
         if n > RCV_BUF_DEFAULT_SIZE {
             return Err(SacnError::TooManyBytesRead(n, buf.len()));
         }
-        AcnRootLayerProtocol::parse(buf)
+        Ok((AcnRootLayerProtocol::parse(&buf[..n])?, origin))
     }
 
     /// Set the timeout for the recv operation.
@@ -1291,8 +2907,162 @@ impl SacnNetworkReceiver {
     /// Errors:
     /// A timeout with Duration 0 will cause an error. See (set_read_timeout)[fn.set_read_timeout.Socket].
     fn set_timeout(&mut self, timeout: Option<Duration>) -> Result<()> {
+        self.timeout = timeout;
         Ok(self.socket.set_read_timeout(timeout)?)
     }
+
+    /// Waits for a packet to be ready on any of the sockets for up to the timeout, returning the index of a socket with a packet ready.
+    ///
+    /// The sockets are checked starting from a different socket each time so that a busy socket can't starve the others.
+    ///
+    /// # Errors
+    /// Will return a WouldBlock error if no packet is ready within the timeout or the wait is interrupted.
+    ///
+    /// Will return an Io error if the sockets cannot be polled, see poll(2).
+    fn poll_sockets(&mut self) -> Result<usize> {
+        let mut fds: Vec<libc::pollfd> = self
+            .sockets()
+            .map(|s| libc::pollfd {
+                fd: s.as_raw_fd(),
+                events: libc::POLLIN,
+                revents: 0,
+            })
+            .collect();
+
+        let timeout_ms = match self.timeout {
+            // Rounded up so that a short timeout doesn't become a non-blocking check.
+            Some(t) => t
+                .as_nanos()
+                .div_ceil(1_000_000)
+                .min(libc::c_int::MAX as u128) as libc::c_int,
+            None => -1,
+        };
+
+        // SAFETY: fds is a valid array of pollfd structures of the given length for the duration of the call.
+        let res = unsafe { libc::poll(fds.as_mut_ptr(), fds.len() as libc::nfds_t, timeout_ms) };
+        if res < 0 {
+            let e = io::Error::last_os_error();
+            return Err(if e.kind() == io::ErrorKind::Interrupted {
+                no_data_error()
+            } else {
+                e.into()
+            });
+        }
+
+        let start = self.next_socket % fds.len();
+        let ready = (start..fds.len())
+            .chain(0..start)
+            .find(|i| fds[*i].revents != 0)
+            .ok_or_else(no_data_error)?;
+        self.next_socket = ready + 1;
+        Ok(ready)
+    }
+
+    /// Reads a batch of packets with a single recvmmsg call, waiting for up to the timeout for the first packet.
+    ///
+    /// # Errors
+    /// Will return a WouldBlock error if no packet is ready within the timeout.
+    ///
+    /// Will return an Io error if the packets cannot be received, see recvmmsg(2).
+    #[cfg(target_os = "linux")]
+    fn recv_batch(&mut self) -> Result<()> {
+        let i = if self.shards.is_empty() {
+            0
+        } else {
+            self.poll_sockets()?
+        };
+        let socket = if i == 0 {
+            &self.socket
+        } else {
+            &self.shards[i - 1]
+        };
+        Ok(self.batch.recv(socket)?)
+    }
+}
+
+#[cfg(target_os = "linux")]
+impl RecvBatch {
+    /// Returns the next received packet which hasn't been handled yet along with where it came from, None if all have been handled.
+    fn next_packet(&mut self) -> Option<(&[u8], Option<PacketOrigin>)> {
+        let (len, origin) = *self.received.get(self.next)?;
+        let buf = &self.bufs[self.next][..len];
+        self.next += 1;
+        Some((buf, origin))
+    }
+
+    /// Receives up to size packets from the given socket with a single recvmmsg call, replacing the previous batch.
+    ///
+    /// # Errors
+    /// Will return an error if no packets can be received from the socket, see recvmmsg(2).
+    fn recv(&mut self, socket: &Socket) -> io::Result<()> {
+        if self.bufs.len() != self.size {
+            self.bufs = vec![[0; RCV_BUF_DEFAULT_SIZE]; self.size];
+            // SAFETY: all zeros is a valid sockaddr_storage.
+            self.addrs = vec![unsafe { std::mem::zeroed() }; self.size];
+            self.controls = vec![[0; 16]; self.size];
+        }
+        self.received.clear();
+        self.next = 0;
+
+        let mut iovs: Vec<libc::iovec> = self
+            .bufs
+            .iter_mut()
+            .map(|buf| libc::iovec {
+                iov_base: buf.as_mut_ptr().cast(),
+                iov_len: buf.len(),
+            })
+            .collect();
+
+        let mut msgs: Vec<libc::mmsghdr> = iovs
+            .iter_mut()
+            .zip(self.addrs.iter_mut().zip(self.controls.iter_mut()))
+            .map(|(iov, (addr, control))| {
+                // SAFETY: all zeros is a valid mmsghdr, the pointers are then set to the buffers above.
+                let mut msg: libc::mmsghdr = unsafe { std::mem::zeroed() };
+                msg.msg_hdr.msg_name = (addr as *mut libc::sockaddr_storage).cast();
+                msg.msg_hdr.msg_namelen = std::mem::size_of::<libc::sockaddr_storage>() as _;
+                msg.msg_hdr.msg_iov = iov;
+                msg.msg_hdr.msg_iovlen = 1;
+                msg.msg_hdr.msg_control = control.as_mut_ptr().cast();
+                msg.msg_hdr.msg_controllen = std::mem::size_of_val(control) as _;
+                msg
+            })
+            .collect();
+
+        // SAFETY: msgs holds msgs.len() headers which point to buffers, addresses and control buffers which are valid for the duration of
+        // the call. MSG_WAITFORONE only waits (up to the socket timeout) for the first packet.
+        let n = unsafe {
+            libc::recvmmsg(
+                socket.as_raw_fd(),
+                msgs.as_mut_ptr(),
+                msgs.len() as _,
+                libc::MSG_WAITFORONE as _,
+                std::ptr::null_mut(),
+            )
+        };
+        if n < 0 {
+            return Err(io::Error::last_os_error());
+        }
+
+        for (msg, addr) in msgs.iter().zip(&self.addrs).take(n as usize) {
+            let mut storage = SockAddrStorage::zeroed();
+            // SAFETY: the kernel wrote a valid address of msg_namelen bytes into addr, which is copied into the storage. The control messages
+            // are only read within the length the kernel reported.
+            let (addr, interface) = unsafe {
+                *storage.view_as::<libc::sockaddr_storage>() = *addr;
+                (
+                    SockAddr::new(storage, msg.msg_hdr.msg_namelen),
+                    pktinfo_interface(&msg.msg_hdr),
+                )
+            };
+            self.received.push((
+                msg.msg_len as usize,
+                addr.as_socket()
+                    .map(|addr| PacketOrigin { addr, interface }),
+            ));
+        }
+        Ok(())
+    }
 }
 
 impl Clone for DMXData {
@@ -1305,6 +3075,9 @@ impl Clone for DMXData {
             priority: self.priority,
             src_cid: self.src_cid,
             preview: self.preview,
+            src_addr: self.src_addr,
+            src_name: self.src_name.clone(),
+            recv_interface: self.recv_interface,
             recv_timestamp: self.recv_timestamp,
         }
     }
@@ -1342,13 +3115,37 @@ impl PartialEq for DMXData {
 /// See PartialEq trait implementation for DMXData.
 impl Eq for DMXData {}
 
+impl LostSource {
+    /// Returns the data of the lost source as it should currently be used, for a fade this is the last data scaled by the remaining
+    /// proportion of the fade.
+    ///
+    /// # Arguments
+    /// now: The current time of the clock of the receiver.
+    fn current_data(&self, now: Instant) -> DMXData {
+        let mut data = self.data.clone();
+        if let DataLossPolicy::Fade(d) = self.policy
+            && !d.is_zero()
+        {
+            let remaining = d
+                .saturating_sub(now.saturating_duration_since(self.lost_at))
+                .as_secs_f64()
+                / d.as_secs_f64();
+            // The start code is kept as is.
+            for v in data.values.iter_mut().skip(1) {
+                *v = (f64::from(*v) * remaining).round() as u8;
+            }
+        }
+        data
+    }
+}
+
 impl DiscoveredSacnSource {
     /// Returns true if all the pages sent by this DiscoveredSacnSource have been received.
     ///
     /// This is based on each page containing a last-page value which indicates the number of the last page expected.
     pub fn has_all_pages(&mut self) -> bool {
         // https://rust-lang-nursery.github.io/rust-cookbook/algorithms/sorting.html (31/12/2019)
-        self.pages.sort_by(|a, b| a.page.cmp(&b.page));
+        self.pages.sort_by_key(|p| p.page);
         for i in 0..=self.last_page {
             if self
                 .pages
@@ -1402,6 +3199,13 @@ fn create_unix_socket(addr: SocketAddr) -> Result<Socket> {
         socket.set_reuse_port(true)?;
         socket.set_reuse_address(true)?;
 
+        #[cfg(any(target_os = "linux", target_os = "android"))]
+        set_recv_pktinfo(&socket, false)?;
+
+        // Only receive the groups joined on this socket, otherwise every shard socket would receive every group joined by any socket.
+        #[cfg(target_os = "linux")]
+        socket.set_multicast_all_v4(false)?;
+
         let socket_addr =
             SocketAddr::new(IpAddr::V4(Ipv4Addr::UNSPECIFIED), ACN_SDT_MULTICAST_PORT);
         socket.bind(&socket_addr.into())?;
@@ -1413,6 +3217,14 @@ fn create_unix_socket(addr: SocketAddr) -> Result<Socket> {
         socket.set_reuse_port(true)?;
         socket.set_reuse_address(true)?;
 
+        #[cfg(any(target_os = "linux", target_os = "android"))]
+        set_recv_pktinfo(&socket, true)?;
+
+        // Only receive the groups joined on this socket, see above. This option was added in Linux 4.20 so a failure is ignored, shards
+        // may then receive the same packet.
+        #[cfg(target_os = "linux")]
+        let _ = socket.set_multicast_all_v6(false);
+
         let socket_addr =
             SocketAddr::new(IpAddr::V6(Ipv6Addr::UNSPECIFIED), ACN_SDT_MULTICAST_PORT);
         socket.bind(&socket_addr.into())?;
@@ -1423,8 +3235,9 @@ fn create_unix_socket(addr: SocketAddr) -> Result<Socket> {
 /// Joins the multicast group with the given address using the given socket.
 ///
 /// Arguments:
-/// socket: The socket to join to the multicast group.
-/// addr:   The address of the multicast group to join.
+/// socket:    The socket to join to the multicast group.
+/// addr:      The address of the multicast group to join.
+/// interface: The interface to join the multicast group on.
 ///
 /// # Errors
 /// Will return an error if the given socket cannot be joined to the given multicast group address.
@@ -1432,37 +3245,37 @@ fn create_unix_socket(addr: SocketAddr) -> Result<Socket> {
 ///
 /// Will return an IpVersionError if addr and interface_addr are not the same IP version.
 #[cfg(not(target_os = "windows"))]
-fn join_unix_multicast(socket: &Socket, addr: SockAddr, interface_addr: IpAddr) -> Result<()> {
+fn join_unix_multicast(
+    socket: &Socket,
+    addr: SockAddr,
+    interface: MulticastInterface,
+) -> Result<()> {
     match addr.family() as i32 {
         // Cast required because AF_INET is defined in libc in terms of a c_int (i32) but addr.family returns using u16.
         AF_INET => match addr.as_socket_ipv4() {
-            Some(a) => match interface_addr {
-                IpAddr::V4(ref interface_v4) => {
-                    socket
-                        .join_multicast_v4(a.ip(), interface_v4)
-                        .map_err(|e| {
-                            SacnError::Io(std::io::Error::new(
-                                e.kind(),
-                                "Failed to join IPv4 multicast",
-                            ))
-                        })?;
-                }
-                IpAddr::V6(ref _interface_v6) => {
-                    return Err(SacnError::IpVersionError());
+            Some(a) => {
+                match interface {
+                    MulticastInterface::Addr(IpAddr::V4(ref interface_v4)) => {
+                        socket.join_multicast_v4(a.ip(), interface_v4)
+                    }
+                    MulticastInterface::Addr(IpAddr::V6(_)) => {
+                        return Err(SacnError::IpVersionError());
+                    }
+                    MulticastInterface::Index(index) => {
+                        socket.join_multicast_v4_n(a.ip(), &InterfaceIndexOrAddress::Index(index))
+                    }
                 }
-            },
+                .map_err(|e| join_error(e, "Failed to join IPv4 multicast"))?;
+            }
             None => {
                 return Err(SacnError::UnsupportedIpVersion("IP version recognised as AF_INET but not actually usable as AF_INET so must be unknown type".to_string()));
             }
         },
         AF_INET6 => match addr.as_socket_ipv6() {
             Some(a) => {
-                socket.join_multicast_v6(a.ip(), 0).map_err(|e| {
-                    SacnError::Io(std::io::Error::new(
-                        e.kind(),
-                        "Failed to join IPv6 multicast",
-                    ))
-                })?;
+                socket
+                    .join_multicast_v6(a.ip(), interface_index_v6(interface))
+                    .map_err(|e| join_error(e, "Failed to join IPv6 multicast"))?;
             }
             None => {
                 return Err(SacnError::UnsupportedIpVersion("IP version recognised as AF_INET6 but not actually usable as AF_INET6 so must be unknown type".to_string()));
@@ -1476,11 +3289,29 @@ fn join_unix_multicast(socket: &Socket, addr: SockAddr, interface_addr: IpAddr)
     Ok(())
 }
 
+/// Returns the error used when joining a multicast group fails, the error is given the message as context unless it indicates that the
+/// socket has reached its membership limit which is returned as is so that it can be recognised, see (is_membership_limit)[is_membership_limit].
+#[cfg(not(target_os = "windows"))]
+fn join_error(e: io::Error, msg: &str) -> SacnError {
+    if is_membership_limit(&e) {
+        SacnError::Io(e)
+    } else {
+        SacnError::Io(io::Error::new(e.kind(), msg))
+    }
+}
+
+/// Returns true if the given error is returned because a socket cannot join any more multicast groups.
+#[cfg(not(target_os = "windows"))]
+fn is_membership_limit(e: &io::Error) -> bool {
+    matches!(e.raw_os_error(), Some(libc::ENOBUFS | libc::ETOOMANYREFS))
+}
+
 /// Leaves the multicast group with the given address using the given socket.
 ///
 /// Arguments:
-/// socket: The socket to leave the multicast group.
-/// addr:   The address of the multicast group to leave.
+/// socket:    The socket to leave the multicast group.
+/// addr:      The address of the multicast group to leave.
+/// interface: The interface to leave the multicast group on.
 ///
 /// # Errors
 /// Will return an error if the given socket cannot leave the given multicast group address.
@@ -1488,37 +3319,47 @@ fn join_unix_multicast(socket: &Socket, addr: SockAddr, interface_addr: IpAddr)
 ///
 /// Will return an IpVersionError if addr and interface_addr are not the same IP version.
 #[cfg(not(target_os = "windows"))]
-fn leave_unix_multicast(socket: &Socket, addr: SockAddr, interface_addr: IpAddr) -> Result<()> {
+fn leave_unix_multicast(
+    socket: &Socket,
+    addr: SockAddr,
+    interface: MulticastInterface,
+) -> Result<()> {
     match addr.family() as i32 {
         // Cast required because AF_INET is defined in libc in terms of a c_int (i32) but addr.family returns using u16.
         AF_INET => match addr.as_socket_ipv4() {
-            Some(a) => match interface_addr {
-                IpAddr::V4(ref interface_v4) => {
-                    socket
-                        .leave_multicast_v4(a.ip(), interface_v4)
-                        .map_err(|e| {
-                            SacnError::Io(std::io::Error::new(
-                                e.kind(),
-                                "Failed to leave IPv4 multicast",
-                            ))
-                        })?;
-                }
-                IpAddr::V6(ref _interface_v6) => {
-                    return Err(SacnError::IpVersionError());
+            Some(a) => {
+                match interface {
+                    MulticastInterface::Addr(IpAddr::V4(ref interface_v4)) => {
+                        socket.leave_multicast_v4(a.ip(), interface_v4)
+                    }
+                    MulticastInterface::Addr(IpAddr::V6(_)) => {
+                        return Err(SacnError::IpVersionError());
+                    }
+                    MulticastInterface::Index(index) => {
+                        socket.leave_multicast_v4_n(a.ip(), &InterfaceIndexOrAddress::Index(index))
+                    }
                 }
-            },
+                .map_err(|e| {
+                    SacnError::Io(std::io::Error::new(
+                        e.kind(),
+                        "Failed to leave IPv4 multicast",
+                    ))
+                })?;
+            }
             None => {
                 return Err(SacnError::UnsupportedIpVersion("IP version recognised as AF_INET but not actually usable as AF_INET so must be unknown type".to_string()));
             }
         },
         AF_INET6 => match addr.as_socket_ipv6() {
             Some(a) => {
-                socket.leave_multicast_v6(a.ip(), 0).map_err(|e| {
-                    SacnError::Io(std::io::Error::new(
-                        e.kind(),
-                        "Failed to leave IPv6 multicast",
-                    ))
-                })?;
+                socket
+                    .leave_multicast_v6(a.ip(), interface_index_v6(interface))
+                    .map_err(|e| {
+                        SacnError::Io(std::io::Error::new(
+                            e.kind(),
+                            "Failed to leave IPv6 multicast",
+                        ))
+                    })?;
             }
             None => {
                 return Err(SacnError::UnsupportedIpVersion("IP version recognised as AF_INET6 but not actually usable as AF_INET6 so must be unknown type".to_string()));
@@ -1565,8 +3406,9 @@ fn create_win_socket(addr: SocketAddr) -> Result<Socket> {
 /// Note that Ipv6 is currently unsupported.
 ///
 /// Arguments:
-/// socket: The socket to join to the multicast group.
-/// addr:   The address of the multicast group to join.
+/// socket:    The socket to join to the multicast group.
+/// addr:      The address of the multicast group to join.
+/// interface: The interface to join the multicast group on.
 ///
 /// # Errors
 /// Will return an error if the given socket cannot be joined to the given multicast group address.
@@ -1574,37 +3416,47 @@ fn create_win_socket(addr: SocketAddr) -> Result<Socket> {
 ///
 /// Will return OsOperationUnsupported error if attempt to leave an Ipv6 multicast group as all Ipv6 multicast operations are currently unsupported in Rust on Windows.
 #[cfg(target_os = "windows")]
-fn join_win_multicast(socket: &Socket, addr: SockAddr, interface_addr: IpAddr) -> Result<()> {
+fn join_win_multicast(
+    socket: &Socket,
+    addr: SockAddr,
+    interface: MulticastInterface,
+) -> Result<()> {
     match addr.family() as i32 {
         // Cast required because AF_INET is defined in libc in terms of a c_int (i32) but addr.family returns using u16.
         AF_INET => match addr.as_socket_ipv4() {
-            Some(a) => match interface_addr {
-                IpAddr::V4(ref interface_v4) => {
-                    socket
-                        .join_multicast_v4(a.ip(), interface_v4)
-                        .map_err(|e| {
-                            SacnError::Io(std::io::Error::new(
-                                e.kind(),
-                                "Failed to join IPv4 multicast",
-                            ))
-                        })?;
-                }
-                IpAddr::V6(ref _interface_v6) => {
-                    return Err(SacnError::IpVersionError());
+            Some(a) => {
+                match interface {
+                    MulticastInterface::Addr(IpAddr::V4(ref interface_v4)) => {
+                        socket.join_multicast_v4(a.ip(), interface_v4)
+                    }
+                    MulticastInterface::Addr(IpAddr::V6(_)) => {
+                        return Err(SacnError::IpVersionError());
+                    }
+                    MulticastInterface::Index(index) => {
+                        socket.join_multicast_v4_n(a.ip(), &InterfaceIndexOrAddress::Index(index))
+                    }
                 }
-            },
+                .map_err(|e| {
+                    SacnError::Io(std::io::Error::new(
+                        e.kind(),
+                        "Failed to join IPv4 multicast",
+                    ))
+                })?;
+            }
             None => {
                 return Err(SacnError::UnsupportedIpVersion("IP version recognised as AF_INET but not actually usable as AF_INET so must be unknown type".to_string()));
             }
         },
         AF_INET6 => match addr.as_socket_ipv6() {
             Some(a) => {
-                socket.join_multicast_v6(a.ip(), 0).map_err(|e| {
-                    SacnError::Io(std::io::Error::new(
-                        e.kind(),
-                        "Failed to join IPv6 multicast",
-                    ))
-                })?;
+                socket
+                    .join_multicast_v6(a.ip(), interface_index_v6(interface))
+                    .map_err(|e| {
+                        SacnError::Io(std::io::Error::new(
+                            e.kind(),
+                            "Failed to join IPv6 multicast",
+                        ))
+                    })?;
             }
             None => {
                 return Err(SacnError::UnsupportedIpVersion("IP version recognised as AF_INET6 but not actually usable as AF_INET6 so must be unknown type".to_string()));
@@ -1623,8 +3475,9 @@ fn join_win_multicast(socket: &Socket, addr: SockAddr, interface_addr: IpAddr) -
 /// Note that Ipv6 is currently unsupported.
 ///
 /// Arguments:
-/// socket: The socket to leave the multicast group.
-/// addr:   The address of the multicast group to leave.
+/// socket:    The socket to leave the multicast group.
+/// addr:      The address of the multicast group to leave.
+/// interface: The interface to leave the multicast group on.
 ///
 /// # Errors
 /// Will return an error if the given socket cannot leave the given multicast group address.
@@ -1632,13 +3485,23 @@ fn join_win_multicast(socket: &Socket, addr: SockAddr, interface_addr: IpAddr) -
 ///
 /// Will return OsOperationUnsupported error if attempt to leave an Ipv6 multicast group as all Ipv6 multicast operations are currently unsupported in Rust on Windows.
 #[cfg(target_os = "windows")]
-fn leave_win_multicast(socket: &Socket, addr: SockAddr) -> Result<()> {
+fn leave_win_multicast(
+    socket: &Socket,
+    addr: SockAddr,
+    interface: MulticastInterface,
+) -> Result<()> {
     match addr.family() as i32 {
         // Cast required because AF_INET is defined in libc in terms of a c_int (i32) but addr.family returns using u16.
         AF_INET => match addr.as_socket_ipv4() {
             Some(a) => {
+                let interface = match interface {
+                    MulticastInterface::Addr(_) => {
+                        InterfaceIndexOrAddress::Address(Ipv4Addr::new(0, 0, 0, 0))
+                    }
+                    MulticastInterface::Index(index) => InterfaceIndexOrAddress::Index(index),
+                };
                 socket
-                    .leave_multicast_v4(a.ip(), &Ipv4Addr::new(0, 0, 0, 0))
+                    .leave_multicast_v4_n(a.ip(), &interface)
                     .map_err(|e| {
                         SacnError::Io(std::io::Error::new(
                             e.kind(),
@@ -1668,6 +3531,149 @@ fn leave_win_multicast(socket: &Socket, addr: SockAddr) -> Result<()> {
     Ok(())
 }
 
+/// Returns the interface index used to join an IPv6 multicast group on the given interface, 0 lets the OS choose the interface.
+fn interface_index_v6(interface: MulticastInterface) -> u32 {
+    match interface {
+        MulticastInterface::Addr(_) => 0,
+        MulticastInterface::Index(index) => index,
+    }
+}
+
+/// Enables the IP_PKTINFO / IPV6_RECVPKTINFO socket option so that the interface each packet arrives on is reported by
+/// (recv_with_origin)[recv_with_origin].
+///
+/// # Errors
+/// Will return an Io error if the socket option cannot be set.
+#[cfg(any(target_os = "linux", target_os = "android"))]
+fn set_recv_pktinfo(socket: &Socket, ipv6: bool) -> Result<()> {
+    let set = |level: libc::c_int, name: libc::c_int| {
+        let enable: libc::c_int = 1;
+        // SAFETY: the option value points to a c_int which is valid for the duration of the call along with its size.
+        let res = unsafe {
+            libc::setsockopt(
+                socket.as_raw_fd(),
+                level,
+                name,
+                (&enable as *const libc::c_int).cast(),
+                std::mem::size_of::<libc::c_int>() as libc::socklen_t,
+            )
+        };
+        if res == 0 {
+            Ok(())
+        } else {
+            Err(io::Error::last_os_error())
+        }
+    };
+
+    if ipv6 {
+        set(libc::IPPROTO_IPV6, libc::IPV6_RECVPKTINFO)?;
+        // Also report the interface of IPv4 packets received if the socket isn't IPv6 only, a failure only affects the tagging.
+        let _ = set(libc::IPPROTO_IP, libc::IP_PKTINFO);
+    } else {
+        set(libc::IPPROTO_IP, libc::IP_PKTINFO)?;
+    }
+    Ok(())
+}
+
+/// Receives a packet into the given buffer, returning the number of bytes received and where the packet was received from if known.
+///
+/// The interface the packet arrived on is read from the packet info control message enabled by (set_recv_pktinfo)[set_recv_pktinfo].
+///
+/// # Errors
+/// Will return an error if the packet cannot be received from the socket, see recvmsg(2).
+#[cfg(any(target_os = "linux", target_os = "android"))]
+fn recv_with_origin(socket: &Socket, buf: &mut [u8]) -> io::Result<(usize, Option<PacketOrigin>)> {
+    // Aligned for cmsghdr and large enough for either an in_pktinfo or in6_pktinfo control message.
+    let mut control = [0u64; 16];
+    let mut interface = None;
+
+    // SAFETY: recvmsg is given the buffer, control buffer and address storage along with their lengths which are all valid for the
+    // duration of the call, the control messages are then only read within the length recvmsg reports.
+    let (n, addr) = unsafe {
+        SockAddr::try_init(|storage, storage_len| {
+            let mut iov = libc::iovec {
+                iov_base: buf.as_mut_ptr().cast(),
+                iov_len: buf.len(),
+            };
+            let mut msg: libc::msghdr = std::mem::zeroed();
+            msg.msg_name = storage.cast();
+            msg.msg_namelen = *storage_len;
+            msg.msg_iov = &mut iov;
+            msg.msg_iovlen = 1;
+            msg.msg_control = control.as_mut_ptr().cast();
+            msg.msg_controllen = std::mem::size_of_val(&control) as _;
+
+            let n = libc::recvmsg(socket.as_raw_fd(), &mut msg, 0);
+            if n < 0 {
+                return Err(io::Error::last_os_error());
+            }
+            *storage_len = msg.msg_namelen;
+            interface = pktinfo_interface(&msg);
+
+            Ok(n as usize)
+        })?
+    };
+
+    Ok((
+        n,
+        addr.as_socket()
+            .map(|addr| PacketOrigin { addr, interface }),
+    ))
+}
+
+/// Returns the index of the interface a received message arrived on from its packet info control message, if it has one.
+///
+/// # Safety
+/// The control buffer of msg must hold the control messages written by the kernel when the message was received, of the length given.
+#[cfg(any(target_os = "linux", target_os = "android"))]
+unsafe fn pktinfo_interface(msg: &libc::msghdr) -> Option<u32> {
+    let mut interface = None;
+    // SAFETY: the caller guarantees the control messages are valid, they are only read through the CMSG macros which stay within them.
+    unsafe {
+        let mut cmsg = libc::CMSG_FIRSTHDR(msg);
+        while !cmsg.is_null() {
+            match ((*cmsg).cmsg_level, (*cmsg).cmsg_type) {
+                (libc::IPPROTO_IP, libc::IP_PKTINFO) => {
+                    let info = libc::CMSG_DATA(cmsg)
+                        .cast::<libc::in_pktinfo>()
+                        .read_unaligned();
+                    interface = Some(info.ipi_ifindex as u32);
+                }
+                (libc::IPPROTO_IPV6, libc::IPV6_PKTINFO) => {
+                    let info = libc::CMSG_DATA(cmsg)
+                        .cast::<libc::in6_pktinfo>()
+                        .read_unaligned();
+                    interface = Some(info.ipi6_ifindex);
+                }
+                _ => {}
+            }
+            cmsg = libc::CMSG_NXTHDR(msg, cmsg);
+        }
+    }
+    interface
+}
+
+/// Receives a packet into the given buffer, returning the number of bytes received and where the packet was received from if known.
+///
+/// The interface the packet arrived on is only reported on Linux so is always None.
+///
+/// # Errors
+/// Will return an error if the packet cannot be received from the socket, see (read)[fn.read.Socket].
+#[cfg(not(any(target_os = "linux", target_os = "android")))]
+fn recv_with_origin(socket: &Socket, buf: &mut [u8]) -> io::Result<(usize, Option<PacketOrigin>)> {
+    // Peek at the sender first so that the packet can still be read directly into the buffer.
+    let addr = socket.peek_sender()?.as_socket();
+    // use read() since read_exact() was not passing the tests and windows does not like using read_exact().
+    let n = (&*socket).read(buf)?;
+    Ok((
+        n,
+        addr.map(|addr| PacketOrigin {
+            addr,
+            interface: None,
+        }),
+    ))
+}
+
 /// Stores a sequence number and a timestamp.
 ///
 /// Used internally within SequenceNumbering for tracking the last received timestamps of each packet-type, source, universe combination.
@@ -1725,23 +3731,54 @@ impl SequenceNumbering {
     }
 
     /// Checks the timeouts for all packet types, sources and universes with sequence numbers registed.
-    /// Removes any universes for which the last_recv time was at least the given timeout amount of time ago.
+    /// Removes any universes for which the last_recv time was at least E131_NETWORK_DATA_LOSS_TIMEOUT ago.
     /// Any sources which have no universes after this operation are also removed.
     ///
-    /// #Arguments
+    /// Returns the source CID and universe of each universe that timed out, a universe which timed out for both data and sync packets
+    /// is only included once.
     ///
-    /// announce_timeout: A flag, if true it indicates than a UniverseTimeout error should be thrown if a universe times out on a source.
-    fn check_timeouts(&mut self, announce_timeout: bool) -> Result<()> {
-        check_timeouts(
+    /// # Arguments
+    /// now: The current time of the clock of the receiver.
+    fn check_timeouts(&mut self, now: Instant) -> Vec<(Uuid, u16)> {
+        let mut timed_out = check_timeouts(
             &mut self.data_sequences,
             E131_NETWORK_DATA_LOSS_TIMEOUT,
-            announce_timeout,
-        )?;
-        check_timeouts(
+            now,
+        );
+        for t in check_timeouts(
             &mut self.sync_sequences,
             E131_NETWORK_DATA_LOSS_TIMEOUT,
-            announce_timeout,
-        )
+            now,
+        ) {
+            if !timed_out.contains(&t) {
+                timed_out.push(t);
+            }
+        }
+        timed_out
+    }
+
+    /// Returns true if the data packet sequence number of the given source on the given universe was received at least
+    /// E131_NETWORK_DATA_LOSS_TIMEOUT ago but hasn't been removed by (check_timeouts)[SequenceNumbering::check_timeouts] yet.
+    ///
+    /// # Arguments
+    /// cid: The CID of the source.
+    ///
+    /// universe: The data universe.
+    ///
+    /// now: The current time of the clock of the receiver.
+    fn data_timed_out(&self, cid: &Uuid, universe: u16, now: Instant) -> bool {
+        seq_timed_out(&self.data_sequences, cid, universe, now)
+    }
+
+    /// Returns true if the sync packet sequence number of the given source on the given synchronisation address has timed out, see
+    /// (data_timed_out)[SequenceNumbering::data_timed_out].
+    fn sync_timed_out(&self, cid: &Uuid, sync_uni: u16, now: Instant) -> bool {
+        seq_timed_out(&self.sync_sequences, cid, sync_uni, now)
+    }
+
+    /// Returns true if sequence numbers are being tracked for any universe, for any packet type, from the source with the given CID.
+    fn has_source(&self, cid: &Uuid) -> bool {
+        self.data_sequences.contains_key(cid) || self.sync_sequences.contains_key(cid)
     }
 
     /// Checks the sequence number is correct for a data packet with the given sequence_number and universe from the given source with given cid.
@@ -1758,6 +3795,8 @@ impl SequenceNumbering {
     ///
     /// universe: The data universe of the packet.
     ///
+    /// now: The current time of the clock of the receiver, recorded as when the packet was received.
+    ///
     /// # Errors
     /// Returns an OutOfSequence error if a packet is received out of order as detected by the different between
     /// the packets sequence number and the expected sequence number as specified in ANSI E1.31-2018 Section 6.7.2 Sequence Numbering.
@@ -1769,7 +3808,7 @@ impl SequenceNumbering {
         cid: Uuid,
         sequence_number: u8,
         universe: u16,
-        announce_timeout: bool,
+        now: Instant,
     ) -> Result<()> {
         check_seq_number(
             &mut self.data_sequences,
@@ -1777,7 +3816,7 @@ impl SequenceNumbering {
             cid,
             sequence_number,
             universe,
-            announce_timeout,
+            now,
         )
     }
 
@@ -1795,6 +3834,8 @@ impl SequenceNumbering {
     ///
     /// universe: The sync universe of the packet
     ///
+    /// now: The current time of the clock of the receiver, recorded as when the packet was received.
+    ///
     /// # Errors
     /// Returns an OutOfSequence error if a packet is received out of order as detected by the different between
     /// the packets sequence number and the expected sequence number as specified in ANSI E1.31-2018 Section 6.7.2 Sequence Numbering.
@@ -1806,7 +3847,7 @@ impl SequenceNumbering {
         cid: Uuid,
         sequence_number: u8,
         sync_uni: u16,
-        announce_timeout: bool,
+        now: Instant,
     ) -> Result<()> {
         check_seq_number(
             &mut self.sync_sequences,
@@ -1814,7 +3855,7 @@ impl SequenceNumbering {
             cid,
             sequence_number,
             sync_uni,
-            announce_timeout,
+            now,
         )
     }
 
@@ -1843,6 +3884,7 @@ impl SequenceNumbering {
 /// cid:    The Uuid of the source that send the packet.
 /// sequence_number: The sequence number of the packet to check.
 /// universe: The universe of the packet (this is the data universe for data packets and the sync universe for synchronisation packets).
+/// now: The current time of the clock of the receiver, recorded as when the packet was received.
 ///
 /// # Errors
 /// Returns an OutOfSequence error if a packet is received out of order as detected by the different between
@@ -1855,17 +3897,8 @@ fn check_seq_number(
     cid: Uuid,
     sequence_number: u8,
     universe: u16,
-    announce_timeout: bool,
+    now: Instant,
 ) -> Result<()> {
-    // Check all the timeouts at the start.
-    // This is done for all sources/universes rather than just the source that sent the packet because a completely dead (no packets being sent) universe
-    // would not be removed otherwise and would continue to take up space. This comes at the cost of increased processing time complexity as each
-    // source is checked every time.
-    check_timeouts(
-        src_sequences,
-        E131_NETWORK_DATA_LOSS_TIMEOUT,
-        announce_timeout,
-    )?;
     if src_sequences.get(&cid).is_none() {
         // New source not previously received from.
         if source_limit.is_none() || src_sequences.len() < source_limit.unwrap() {
@@ -1886,7 +3919,7 @@ fn check_seq_number(
                 None => {
                     // Indicates that this is the first time (or the first time since it timed out) the universe has been received from this source.
                     let initial_seq_num = sequence_number.wrapping_sub(1);
-                    TimedStampedSeqNo::new(initial_seq_num, Instant::now())
+                    TimedStampedSeqNo::new(initial_seq_num, now)
                 }
             }
         }
@@ -1922,10 +3955,7 @@ fn check_seq_number(
     match src_sequences.get_mut(&cid) {
         Some(src) => {
             // Replace the old sequence number with the new and reset the timeout.
-            src.insert(
-                universe,
-                TimedStampedSeqNo::new(sequence_number, Instant::now()),
-            );
+            src.insert(universe, TimedStampedSeqNo::new(sequence_number, now));
         }
         None => {
             // See previous node regarding panic previously in this method.
@@ -1940,58 +3970,59 @@ fn check_seq_number(
 /// Removes any universes for which the last_recv time was at least the given timeout amount of time ago.
 /// Any sources which have no universes after this operation are also removed.
 ///
+/// Returns the source CID and universe of each universe that was removed.
+///
 /// #Arguments
 ///
 /// src_sequences: The source sequence numbers to check the timeout of.
 ///
 /// timeout: The exclusive length of time permitted since a source last sent on a universe.
 ///     If the time elapsed since the last received data that is equal to or great than the timeout then the source is said to have timed out.
+///
+/// now: The current time of the clock of the receiver.
 fn check_timeouts(
     src_sequences: &mut HashMap<Uuid, HashMap<u16, TimedStampedSeqNo>>,
     timeout: Duration,
-    announce_timeout: bool,
-) -> Result<()> {
-    if announce_timeout {
-        let mut timedout_src_id: Option<Uuid> = None;
-        let mut timedout_uni: Option<u16> = None;
-        for (src_id, universes) in src_sequences.iter_mut() {
-            for (uni, seq_num) in universes.iter() {
-                if seq_num.last_recv.elapsed() >= timeout {
-                    timedout_src_id = Some(*src_id);
-                    timedout_uni = Some(*uni);
-                    break;
-                }
+    now: Instant,
+) -> Vec<(Uuid, u16)> {
+    let mut timed_out: Vec<(Uuid, u16)> = Vec::new();
+    for (src_id, universes) in src_sequences.iter_mut() {
+        universes.retain(|uni, seq_num| {
+            let live = now.saturating_duration_since(seq_num.last_recv) < timeout;
+            if !live {
+                timed_out.push((*src_id, *uni));
             }
-            if timedout_uni.is_none() {
-                break;
-            }
-        }
-        if let Some(uni_to_remove) = timedout_uni {
-            // If None then it indicates nothing timed out.
-            let src_universes = src_sequences.get_mut(&timedout_src_id.unwrap());
-
-            if let Some(universes) = src_universes {
-                universes.remove(&uni_to_remove);
-                if universes.is_empty() {
-                    // Remove source if all its universes have timed out
-                    src_sequences.remove(&timedout_src_id.unwrap());
-                }
-                return Err(SacnError::UniverseTimeout(
-                    timedout_src_id.unwrap(),
-                    uni_to_remove,
-                ));
-            }
-        }
-
-        Ok(())
-    } else {
-        for (_src_id, universes) in src_sequences.iter_mut() {
-            universes.retain(|_uni, seq_num| seq_num.last_recv.elapsed() < timeout);
-        }
-        // Remove all empty sources.
-        src_sequences.retain(|_src_id, universes| !universes.is_empty());
-        Ok(())
+            live
+        });
     }
+    // Remove all empty sources.
+    src_sequences.retain(|_src_id, universes| !universes.is_empty());
+    timed_out
+}
+
+/// Returns true if the sequence number of the given source on the given universe was last received at least E131_NETWORK_DATA_LOSS_TIMEOUT
+/// ago, false if it was received more recently or isn't in the given sequences.
+///
+/// # Arguments
+/// src_sequences: The sequence numbers for each source and universe.
+///
+/// cid: The CID of the source.
+///
+/// universe: The universe of the sequence number.
+///
+/// now: The current time of the clock of the receiver.
+fn seq_timed_out(
+    src_sequences: &HashMap<Uuid, HashMap<u16, TimedStampedSeqNo>>,
+    cid: &Uuid,
+    universe: u16,
+    now: Instant,
+) -> bool {
+    src_sequences
+        .get(cid)
+        .and_then(|universes| universes.get(&universe))
+        .is_some_and(|s| {
+            now.saturating_duration_since(s.last_recv) >= E131_NETWORK_DATA_LOSS_TIMEOUT
+        })
 }
 
 /// Removes the sequence number entry from the given sequences for the given source cid and universe.
@@ -2035,10 +4066,106 @@ fn remove_source_universe_seq(
     }
 }
 
-/// The default merge action for the receiver.
+/// Removes the data stored for the given source on the given universe, removing the universe entirely if no sources are left.
+///
+/// If there is no data for the source / universe then this has no effect.
+///
+/// # Arguments
+/// src_data: The latest data for each universe and source.
+///
+/// src_cid:  The CID of the source to remove the data of.
+///
+/// universe: The universe to remove the source's data from.
+fn remove_source_universe_data<T>(
+    src_data: &mut HashMap<u16, HashMap<Uuid, T>>,
+    src_cid: Uuid,
+    universe: u16,
+) {
+    if let Some(srcs) = src_data.get_mut(&universe) {
+        srcs.remove(&src_cid);
+        if srcs.is_empty() {
+            src_data.remove(&universe);
+        }
+    }
+}
+
+/// Removes and returns any source data which was received at least E131_NETWORK_DATA_LOSS_TIMEOUT ago, ordered by universe.
+/// Any universes which have no sources left after this are also removed.
+///
+/// # Arguments
+/// src_data: The latest data for each universe and source.
+///
+/// now: The current time of the clock of the receiver.
+fn take_timed_out_source_data(
+    src_data: &mut HashMap<u16, HashMap<Uuid, DMXData>>,
+    now: Instant,
+) -> Vec<DMXData> {
+    let mut timed_out: Vec<DMXData> = Vec::new();
+    for srcs in src_data.values_mut() {
+        let cids: Vec<Uuid> = srcs
+            .iter()
+            .filter(|(_cid, data)| {
+                now.saturating_duration_since(data.recv_timestamp) >= E131_NETWORK_DATA_LOSS_TIMEOUT
+            })
+            .map(|(cid, _data)| *cid)
+            .collect();
+        for cid in cids {
+            timed_out.extend(srcs.remove(&cid));
+        }
+    }
+    src_data.retain(|_uni, srcs| !srcs.is_empty());
+    timed_out.sort_by_key(|d| d.universe);
+    timed_out
+}
+
+/// Returns the data of a universe released to all zeros, used when the last source of a universe with a data loss policy is released.
+///
+/// # Arguments
+/// universe: The universe of the data.
+///
+/// len: The length of the data including the start code.
+///
+/// now: The current time of the clock of the receiver, used as the time the data was received.
+fn released_data(universe: u16, len: usize, now: Instant) -> DMXData {
+    let mut values = vec![0; len.max(1)];
+    values[0] = E131_NULL_START_CODE;
+    DMXData {
+        universe,
+        values,
+        sync_uni: E131_NO_SYNC_ADDR,
+        priority: 0,
+        src_cid: None,
+        preview: false,
+        src_addr: None,
+        src_name: None,
+        recv_interface: None,
+        recv_timestamp: now,
+    }
+}
+
+/// Removes any source data which was received at least E131_NETWORK_DATA_LOSS_TIMEOUT ago.
+/// Any universes which have no sources left after this are also removed.
+///
+/// # Arguments
+/// src_data: The latest data for each universe and source.
+///
+/// now: The current time of the clock of the receiver.
+fn remove_timed_out_source_data(src_data: &mut HashMap<u16, HashMap<Uuid, DMXData>>, now: Instant) {
+    for srcs in src_data.values_mut() {
+        srcs.retain(|_cid, data| {
+            now.saturating_duration_since(data.recv_timestamp) < E131_NETWORK_DATA_LOSS_TIMEOUT
+        });
+    }
+    src_data.retain(|_uni, srcs| !srcs.is_empty());
+}
+
+/// The default merge action for the receiver for data waiting for synchronisation.
 ///
 /// This discarding of the old data is the default action for compliance as specified in ANSI E1.31-2018, Section 11.2.1.
 ///
+/// Note that if used for unsynchronised data from multiple sources at the same priority the result alternates between them as their packets
+/// arrive, unlike the default merger for unsynchronised data, see (DEFAULT_MERGER)[DEFAULT_MERGER].
+///
 /// This can be changed if required as part of the mechanism described in ANSI E1.31-2018, Section 6.2.3.4 Requirements for Merging and Arbitrating.
 ///
 /// The first argument (i) is the existing data, n is the new data.
@@ -2052,6 +4179,22 @@ pub fn discard_lowest_priority_then_previous(i: &DMXData, n: &DMXData) -> Result
     Ok(n.clone())
 }
 
+/// The merge function of the (DEFAULT_MERGER)[DEFAULT_MERGER], this is (htp_dmx_merge)[htp_dmx_merge] except that data with different
+/// synchronisation addresses is also merged, as data acted on without waiting for synchronisation (Force_Synchronization) is arbitrated along
+/// with unsynchronised data. The result has the synchronisation address of the existing data.
+fn htp_merge_any_sync(i: &DMXData, n: &DMXData) -> Result<DMXData> {
+    if i.sync_uni == n.sync_uni {
+        return htp_dmx_merge(i, n);
+    }
+    htp_dmx_merge(
+        i,
+        &DMXData {
+            sync_uni: i.sync_uni,
+            ..n.clone()
+        },
+    )
+}
+
 /// Performs a highest takes priority (HTP) (per byte) DMX merge of data.
 ///
 /// Note this merge is done within the explicit priority, if i or n has an explicitly higher priority it will always take precedence before this HTP merge is attempted.
@@ -2088,6 +4231,9 @@ pub fn htp_dmx_merge(i: &DMXData, n: &DMXData) -> Result<DMXData> {
         priority: i.priority,
         src_cid: None,
         preview: i.preview || n.preview, // If either data is preview then mark the result as preview.
+        src_addr: None,
+        src_name: None,
+        recv_interface: None,
         recv_timestamp: i.recv_timestamp,
     };
 
@@ -2121,6 +4267,8 @@ pub fn htp_dmx_merge(i: &DMXData, n: &DMXData) -> Result<DMXData> {
 mod test {
     use super::*;
 
+    use crate::clock::ManualClock;
+
     use std::borrow::Cow;
     use std::net::{IpAddr, Ipv4Addr, SocketAddr};
     use std::time::Instant;
@@ -2169,10 +4317,10 @@ mod test {
 
                 // Universe discovery layer.
                 data: UniverseDiscoveryPacketUniverseDiscoveryLayer {
-                    page: page,
+                    page,
 
                     // The number of the final page.
-                    last_page: last_page,
+                    last_page,
 
                     // List of universes.
                     universes: universes.clone().into(),
@@ -2247,11 +4395,13 @@ mod test {
                     universes: universes_page_2.clone().into(),
                 },
             };
-        let res: Option<String> = dmx_rcv.handle_universe_discovery_packet(src_cid, discovery_pkt_1);
+        let res: Option<String> =
+            dmx_rcv.handle_universe_discovery_packet(src_cid, discovery_pkt_1);
 
         assert!(res.is_none()); // Should be none because first packet isn't complete as its only the first page.
 
-        let res2: Option<String> = dmx_rcv.handle_universe_discovery_packet(src_cid, discovery_pkt_2);
+        let res2: Option<String> =
+            dmx_rcv.handle_universe_discovery_packet(src_cid, discovery_pkt_2);
 
         assert!(res2.is_some()); // Source should be discovered because the second and last page is now received.
         assert_eq!(res2.unwrap(), name);
@@ -2274,6 +4424,49 @@ mod test {
         );
     }
 
+    /// A page received twice, e.g. duplicated by the network, shouldn't stop the source being discovered once the other pages arrive.
+    #[test]
+    fn test_handle_duplicate_discovery_page() {
+        let addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), ACN_SDT_MULTICAST_PORT);
+
+        let mut dmx_rcv = SacnReceiver::with_ip(addr, None).unwrap();
+
+        let name = "Test Src 1";
+        let src_cid: Uuid = Uuid::from_bytes([
+            0xef, 0x07, 0xc8, 0xdd, 0x00, 0x64, 0x44, 0x01, 0xa3, 0xa2, 0x45, 0x9e, 0xf8, 0xe6,
+            0x14, 0x3e,
+        ]);
+        let page = |page: u8, universes: Vec<u16>| UniverseDiscoveryPacketFramingLayer {
+            source_name: name.into(),
+            data: UniverseDiscoveryPacketUniverseDiscoveryLayer {
+                page,
+                last_page: 1,
+                universes: universes.into(),
+            },
+        };
+
+        assert!(
+            dmx_rcv
+                .handle_universe_discovery_packet(src_cid, page(0, vec![1, 2]))
+                .is_none()
+        );
+        assert!(
+            dmx_rcv
+                .handle_universe_discovery_packet(src_cid, page(0, vec![1, 2]))
+                .is_none()
+        );
+        assert_eq!(
+            dmx_rcv.handle_universe_discovery_packet(src_cid, page(1, vec![3])),
+            Some(name.to_string())
+        );
+
+        assert_eq!(dmx_rcv.discovered_sources.len(), 1);
+        assert_eq!(
+            dmx_rcv.discovered_sources[0].get_all_universes(),
+            vec![1, 2, 3]
+        );
+    }
+
     #[test]
     fn test_store_retrieve_waiting_data() {
         let addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), ACN_SDT_MULTICAST_PORT);
@@ -2285,12 +4478,15 @@ mod test {
         let vals: Vec<u8> = vec![0, 1, 2, 3, 4, 5, 6, 7, 8, 9];
 
         let dmx_data = DMXData {
-            universe: universe,
+            universe,
             values: vals.clone(),
-            sync_uni: sync_uni,
+            sync_uni,
             priority: 100,
             src_cid: None,
             preview: false,
+            src_addr: None,
+            src_name: None,
+            recv_interface: None,
             recv_timestamp: Instant::now(),
         };
 
@@ -2315,12 +4511,15 @@ mod test {
         let vals: Vec<u8> = vec![0, 1, 2, 3, 4, 5, 6, 7, 8, 9];
 
         let dmx_data = DMXData {
-            universe: universe,
+            universe,
             values: vals.clone(),
-            sync_uni: sync_uni,
+            sync_uni,
             priority: 100,
             src_cid: None,
             preview: false,
+            src_addr: None,
+            src_name: None,
+            recv_interface: None,
             recv_timestamp: Instant::now(),
         };
 
@@ -2331,6 +4530,9 @@ mod test {
             priority: 100,
             src_cid: None,
             preview: false,
+            src_addr: None,
+            src_name: None,
+            recv_interface: None,
             recv_timestamp: Instant::now(),
         };
 
@@ -2356,12 +4558,15 @@ mod test {
         let vals: Vec<u8> = vec![0, 1, 2, 3, 4, 5, 6, 7, 8, 9];
 
         let dmx_data = DMXData {
-            universe: universe,
+            universe,
             values: vals.clone(),
-            sync_uni: sync_uni,
+            sync_uni,
             priority: 100,
             src_cid: None,
             preview: false,
+            src_addr: None,
+            src_name: None,
+            recv_interface: None,
             recv_timestamp: Instant::now(),
         };
 
@@ -2374,6 +4579,9 @@ mod test {
             priority: 100,
             src_cid: None,
             preview: false,
+            src_addr: None,
+            src_name: None,
+            recv_interface: None,
             recv_timestamp: Instant::now(),
         };
 
@@ -2406,24 +4614,30 @@ mod test {
         let vals: Vec<u8> = vec![0, 1, 2, 3, 4, 5, 6, 7, 8, 9];
 
         let dmx_data = DMXData {
-            universe: universe,
+            universe,
             values: vals.clone(),
-            sync_uni: sync_uni,
+            sync_uni,
             priority: 100,
             src_cid: None,
             preview: false,
+            src_addr: None,
+            src_name: None,
+            recv_interface: None,
             recv_timestamp: Instant::now(),
         };
 
         let vals2: Vec<u8> = vec![0, 9, 7, 3, 2, 4, 5, 6, 5, 1, 2, 3];
 
         let dmx_data2 = DMXData {
-            universe: universe,
+            universe,
             values: vals2.clone(),
-            sync_uni: sync_uni,
+            sync_uni,
             priority: 100,
             src_cid: None,
             preview: false,
+            src_addr: None,
+            src_name: None,
+            recv_interface: None,
             recv_timestamp: Instant::now(),
         };
 
@@ -2451,24 +4665,30 @@ mod test {
         let vals: Vec<u8> = vec![0, 1, 2, 3, 4, 5, 6, 7, 8, 9];
 
         let dmx_data = DMXData {
-            universe: universe,
+            universe,
             values: vals.clone(),
-            sync_uni: sync_uni,
+            sync_uni,
             priority: 120,
             src_cid: None,
             preview: false,
+            src_addr: None,
+            src_name: None,
+            recv_interface: None,
             recv_timestamp: Instant::now(),
         };
 
         let vals2: Vec<u8> = vec![0, 9, 7, 3, 2, 4, 5, 6, 5, 1, 2, 3];
 
         let dmx_data2 = DMXData {
-            universe: universe,
+            universe,
             values: vals2.clone(),
-            sync_uni: sync_uni,
+            sync_uni,
             priority: 100,
             src_cid: None,
             preview: false,
+            src_addr: None,
+            src_name: None,
+            recv_interface: None,
             recv_timestamp: Instant::now(),
         };
 
@@ -2509,11 +4729,11 @@ mod test {
             source_name: "Source_A".into(),
             priority: 100,
             synchronization_address: 0,
-            sequence_number: sequence_number,
+            sequence_number,
             preview_data: false,
             stream_terminated: false,
             force_synchronization: false,
-            universe: universe,
+            universe,
             data: DataPacketDmpLayer {
                 property_values: Cow::from(&TEST_DATA_SINGLE_UNIVERSE[0..]),
             },
@@ -2527,12 +4747,12 @@ mod test {
     ///     sequence_number: <given sequence number>,
     ///     synchronization_address: <given synchronisation address>
     /// }
-    fn generate_sync_packet_framing_layer_seq_num<'a>(
+    fn generate_sync_packet_framing_layer_seq_num(
         sync_address: u16,
         sequence_number: u8,
     ) -> SynchronizationPacketFramingLayer {
         SynchronizationPacketFramingLayer {
-            sequence_number: sequence_number,
+            sequence_number,
             synchronization_address: sync_address,
         }
     }
@@ -2562,33 +4782,29 @@ mod test {
         // Not interested in specific return values from this test, just assert the data is processed successfully.
         assert!(
             dmx_rcv
-                .handle_data_packet(src_cid, data_packet)
+                .handle_data_packet(src_cid, data_packet, None)
                 .unwrap()
                 .is_some(),
             "Receiver incorrectly rejected first data packet"
         );
         assert!(
             dmx_rcv
-                .handle_data_packet(src_cid, data_packet2)
+                .handle_data_packet(src_cid, data_packet2, None)
                 .unwrap()
                 .is_some(),
             "Receiver incorrectly rejected second data packet"
         );
 
         // Check that the third data packet with the low sequence number is rejected correctly with the expected OutOfSequence error.
-        match dmx_rcv.handle_data_packet(src_cid, data_packet3) {
+        match dmx_rcv.handle_data_packet(src_cid, data_packet3, None) {
             Err(SacnError::OutOfSequence(..)) => {
-                assert!(
-                    true,
-                    "Receiver correctly rejected third data packet with correct error"
-                );
+                // Receiver correctly rejected third data packet with correct error
             }
             Ok(_) => {
-                assert!(false, "Receiver incorrectly accepted third data packet");
+                panic!("Receiver incorrectly accepted third data packet");
             }
             Err(e) => {
-                assert!(
-                    false,
+                panic!(
                     "Receiver correctly rejected third data packet but with unexpected error: {}",
                     e
                 );
@@ -2641,14 +4857,14 @@ mod test {
             // Not interested in specific return values from this test, just assert the data is processed successfully.
             assert!(
                 dmx_rcv
-                    .handle_data_packet(src_cid, data_packet)
+                    .handle_data_packet(src_cid, data_packet, None)
                     .unwrap()
                     .is_some(),
                 "Receiver incorrectly rejected first data packet"
             );
             assert!(
                 dmx_rcv
-                    .handle_data_packet(src_cid, data_packet2)
+                    .handle_data_packet(src_cid, data_packet2, None)
                     .unwrap()
                     .is_some(),
                 "Receiver incorrectly rejected second data packet"
@@ -2660,6 +4876,7 @@ mod test {
             let res = dmx_rcv.handle_data_packet(
                 src_cid,
                 generate_data_packet_framing_layer_seq_num(UNIVERSE1, i),
+                None,
             );
 
             // Cannot do straight 8 bit arithmetic that relies on underflows/overflows as this is undefined behaviour in rust forbidden by the compiler.
@@ -2670,13 +4887,9 @@ mod test {
                 Err(SacnError::OutOfSequence(..)) => {
                     // Data packet was rejected due to sequence number.
                     if (diff <= REJECT_RANGE_UPPER_BOUND) && (diff > REJECT_RANGE_LOWER_BOUND) {
-                        assert!(
-                            true,
-                            "Rejection is correct as per ANSI E1.31-2018 Section 6.7.2"
-                        );
+                        // Rejection is correct as per ANSI E1.31-2018 Section 6.7.2
                     } else {
-                        assert!(
-                            false,
+                        panic!(
                             "Data packet with sequence number: {} was rejected incorrectly",
                             i
                         );
@@ -2685,21 +4898,17 @@ mod test {
                 Ok(_p) => {
                     // Data packet and therefore sequence number was accepted.
                     if (diff <= REJECT_RANGE_UPPER_BOUND) && (diff > REJECT_RANGE_LOWER_BOUND) {
-                        assert!(
-                            false,
+                        panic!(
                             "Data packet with sequence number: {} was accepted incorrectly",
                             1
                         );
                     } else {
-                        assert!(
-                            true,
-                            "Acceptance is correct as per ANSI E1.31-2018 Section 6.7.2"
-                        );
+                        // Acceptance is correct as per ANSI E1.31-2018 Section 6.7.2
                     }
                 }
                 Err(e) => {
                     // This is never expected and always means test failure.
-                    assert!(false, "Receiver produced unexpected error: {}", e);
+                    panic!("Receiver produced unexpected error: {}", e);
                 }
             }
         }
@@ -2781,13 +4990,9 @@ mod test {
                 Err(SacnError::OutOfSequence(..)) => {
                     // Sync packet was rejected due to sequence number.
                     if (diff <= REJECT_RANGE_UPPER_BOUND) && (diff > REJECT_RANGE_LOWER_BOUND) {
-                        assert!(
-                            true,
-                            "Rejection is correct as per ANSI E1.31-2018 Section 6.7.2"
-                        );
+                        // Rejection is correct as per ANSI E1.31-2018 Section 6.7.2
                     } else {
-                        assert!(
-                            false,
+                        panic!(
                             "Sync packet with sequence number: {} was rejected incorrectly",
                             i
                         );
@@ -2796,21 +5001,17 @@ mod test {
                 Ok(_p) => {
                     // Sync packet and therefore sequence number was accepted.
                     if (diff <= REJECT_RANGE_UPPER_BOUND) && (diff > REJECT_RANGE_LOWER_BOUND) {
-                        assert!(
-                            false,
+                        panic!(
                             "Sync packet with sequence number: {} was accepted incorrectly",
                             i
                         );
                     } else {
-                        assert!(
-                            true,
-                            "Acceptance is correct as per ANSI E1.31-2018 Section 6.7.2"
-                        );
+                        // Acceptance is correct as per ANSI E1.31-2018 Section 6.7.2
                     }
                 }
                 Err(e) => {
                     // This is never expected and always means test failure.
-                    assert!(false, "Receiver produced unexpected error: {}", e);
+                    panic!("Receiver produced unexpected error: {}", e);
                 }
             }
         }
@@ -2857,17 +5058,13 @@ mod test {
         // Check that the third sync packet with the low sequence number is rejected correctly with the expected OutOfSequence error.
         match dmx_rcv.handle_sync_packet(src_cid, sync_packet3) {
             Err(SacnError::OutOfSequence(..)) => {
-                assert!(
-                    true,
-                    "Receiver correctly rejected third sync packet with correct error"
-                );
+                // Receiver correctly rejected third sync packet with correct error
             }
             Ok(_) => {
-                assert!(false, "Receiver incorrectly accepted third sync packet");
+                panic!("Receiver incorrectly accepted third sync packet");
             }
             Err(e) => {
-                assert!(
-                    false,
+                panic!(
                     "Receiver correctly rejected third sync packet but with unexpected error: {}",
                     e
                 );
@@ -2952,14 +5149,14 @@ mod test {
         // Not interested in specific return values from this test, just assert the data is processed successfully.
         assert!(
             dmx_rcv
-                .handle_data_packet(src_cid, data_packet)
+                .handle_data_packet(src_cid, data_packet, None)
                 .unwrap()
                 .is_some(),
             "Receiver incorrectly rejected first data packet"
         );
         assert!(
             dmx_rcv
-                .handle_data_packet(src_cid, data_packet2)
+                .handle_data_packet(src_cid, data_packet2, None)
                 .unwrap()
                 .is_some(),
             "Receiver incorrectly rejected second data packet"
@@ -2970,7 +5167,7 @@ mod test {
         // Packet shouldn't be rejected.
         assert!(
             dmx_rcv
-                .handle_data_packet(src_cid, data_packet3)
+                .handle_data_packet(src_cid, data_packet3, None)
                 .unwrap()
                 .is_some(),
             "Receiver incorrectly rejected third data packet"
@@ -3005,14 +5202,14 @@ mod test {
         // Not interested in specific return values from this test, just assert the data is processed successfully.
         assert!(
             dmx_rcv
-                .handle_data_packet(src_cid, data_packet)
+                .handle_data_packet(src_cid, data_packet, None)
                 .unwrap()
                 .is_some(),
             "Receiver incorrectly rejected first data packet"
         );
         assert!(
             dmx_rcv
-                .handle_data_packet(src_cid, data_packet2)
+                .handle_data_packet(src_cid, data_packet2, None)
                 .unwrap()
                 .is_some(),
             "Receiver incorrectly rejected second data packet"
@@ -3057,14 +5254,14 @@ mod test {
         // Not interested in specific return values from this test, just assert the data is processed successfully.
         assert!(
             dmx_rcv
-                .handle_data_packet(src_cid, data_packet)
+                .handle_data_packet(src_cid, data_packet, None)
                 .unwrap()
                 .is_some(),
             "Receiver incorrectly rejected first data packet"
         );
         assert!(
             dmx_rcv
-                .handle_data_packet(src_cid, data_packet2)
+                .handle_data_packet(src_cid, data_packet2, None)
                 .unwrap()
                 .is_some(),
             "Receiver incorrectly rejected second data packet"
@@ -3074,7 +5271,7 @@ mod test {
         // however this data packet is for UNIVERSE2 and so therefore should be accepted.
         assert!(
             dmx_rcv
-                .handle_data_packet(src_cid, data_packet3)
+                .handle_data_packet(src_cid, data_packet3, None)
                 .unwrap()
                 .is_some(),
             "Receiver incorrectly rejected third data packet"
@@ -3143,17 +5340,14 @@ mod test {
         match SacnReceiver::with_ip(addr, source_limit) {
             Err(e) => match e {
                 SacnError::SourceLimitZero() => {
-                    assert!(true, "Correct error returned");
+                    // Correct error returned
                 }
                 _ => {
-                    assert!(false, "Unexpected error type returned");
+                    panic!("Unexpected error type returned");
                 }
             },
             _ => {
-                assert!(
-                    false,
-                    "SacnReceiver accepted 0 source limit when it shouldn't"
-                );
+                panic!("SacnReceiver accepted 0 source limit when it shouldn't");
             }
         }
     }
@@ -3182,6 +5376,94 @@ mod test {
         );
     }
 
+    /// Listening to more universes than a socket can join multicast groups for should spread the groups over several sockets, which
+    /// are all received from, and muting the universes should leave the groups on the sockets they were joined on.
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_listen_universe_range_shards() {
+        const LAST_UNIVERSE: u16 = 100;
+
+        let addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), ACN_SDT_MULTICAST_PORT);
+        let mut dmx_rcv = SacnReceiver::with_ip(addr, None).unwrap();
+        dmx_rcv.listen_universe_range(1..=LAST_UNIVERSE).unwrap();
+
+        for u in 1..=LAST_UNIVERSE {
+            assert!(dmx_rcv.is_listening(&u));
+        }
+
+        let limit: usize = std::fs::read_to_string("/proc/sys/net/ipv4/igmp_max_memberships")
+            .unwrap()
+            .trim()
+            .parse()
+            .unwrap();
+        // The discovery universe is also joined.
+        if usize::from(LAST_UNIVERSE) + 1 > limit {
+            assert!(!dmx_rcv.receiver.sockets_receiver().shards.is_empty());
+        }
+
+        let mut src = crate::source::SacnSource::with_ip(
+            "Shard Source",
+            SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), 0),
+        )
+        .unwrap();
+        src.set_is_sending_discovery(false);
+        src.set_interfaces(&[NetworkInterface::Addr(IpAddr::V4(Ipv4Addr::LOCALHOST))])
+            .unwrap();
+        src.register_universes(&[1, LAST_UNIVERSE]).unwrap();
+
+        for u in [1, LAST_UNIVERSE] {
+            src.send(&[u], &[0, 1, 2], None, None, None).unwrap();
+            let data = dmx_rcv.recv(Some(Duration::from_secs(2))).unwrap();
+            assert_eq!(data[0].universe, u);
+            assert_eq!(data[0].values, vec![0, 1, 2]);
+        }
+
+        for u in 1..=LAST_UNIVERSE {
+            dmx_rcv.mute_universe(u).unwrap();
+        }
+        assert_eq!(dmx_rcv.receiver.sockets_receiver().memberships.len(), 1);
+        assert!(dmx_rcv.receiver.sockets_receiver().full_sockets.is_empty());
+    }
+
+    /// Packets waiting when receiving with a batch size should all be read by one call and then returned in order by the following calls.
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_recv_batch() {
+        const UNIVERSES: [u16; 5] = [1, 2, 3, 4, 5];
+
+        let addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), ACN_SDT_MULTICAST_PORT);
+        let mut dmx_rcv = SacnReceiver::with_ip(addr, None).unwrap();
+        assert_eq!(dmx_rcv.recv_batch_size(), 1);
+        dmx_rcv.set_recv_batch_size(16).unwrap();
+        assert_eq!(dmx_rcv.recv_batch_size(), 16);
+        dmx_rcv.listen_universes(&UNIVERSES).unwrap();
+
+        let mut src = crate::source::SacnSource::with_ip(
+            "Batch Source",
+            SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), 0),
+        )
+        .unwrap();
+        src.set_is_sending_discovery(false);
+        src.set_interfaces(&[NetworkInterface::Addr(IpAddr::V4(Ipv4Addr::LOCALHOST))])
+            .unwrap();
+        src.register_universes(&UNIVERSES).unwrap();
+        for u in UNIVERSES {
+            src.send(&[u], &[0, u as u8], None, None, None).unwrap();
+        }
+
+        for (i, u) in UNIVERSES.into_iter().enumerate() {
+            let data = dmx_rcv.recv(Some(Duration::from_secs(2))).unwrap();
+            assert_eq!(data[0].universe, u);
+            assert_eq!(data[0].values, vec![0, u as u8]);
+            if i == 0 {
+                assert_eq!(
+                    dmx_rcv.receiver.sockets_receiver().batch.received.len(),
+                    UNIVERSES.len()
+                );
+            }
+        }
+    }
+
     #[test]
     fn test_clear_waiting_data() {
         let addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), ACN_SDT_MULTICAST_PORT);
@@ -3196,6 +5478,9 @@ mod test {
             priority: 100,
             src_cid: Some(Uuid::new_v4()),
             preview: false,
+            src_addr: None,
+            src_name: None,
+            recv_interface: None,
             recv_timestamp: Instant::now(),
         };
 
@@ -3283,18 +5568,24 @@ mod test {
             priority: PRIORITY,
             src_cid: Some(Uuid::new_v4()),
             preview: PREVIEW,
+            src_addr: None,
+            src_name: None,
+            recv_interface: None,
             recv_timestamp: Instant::now(),
         };
 
         let data2 = DMXData {
             universe: UNIVERSE,
-            values: values,
+            values,
             sync_uni: SYNC_ADDR,
 
             // The below values can be different for 2 DMXData to be taken as equivalent.
             priority: PRIORITY + 50,
             src_cid: None,
             preview: !PREVIEW,
+            src_addr: None,
+            src_name: None,
+            recv_interface: None,
             recv_timestamp: Instant::now(),
         };
 
@@ -3326,12 +5617,12 @@ mod test {
 
         // Initial sequence number of new universe is 255 so send a valid new sequnce number to start.
         let pkt = generate_data_packet_framing_layer_seq_num(UNIVERSE, 21u8);
-        let _ = rcv.handle_data_packet(src_cid, pkt);
+        let _ = rcv.handle_data_packet(src_cid, pkt, None);
 
         // Send a run up to wrap.
         for seq in 250u8..=255u8 {
             let pkt = generate_data_packet_framing_layer_seq_num(UNIVERSE, seq);
-            let res = rcv.handle_data_packet(src_cid, pkt);
+            let res = rcv.handle_data_packet(src_cid, pkt, None);
             assert!(
                 res.is_ok(),
                 "sequence {} should be accepted (got {:?})",
@@ -3342,7 +5633,7 @@ mod test {
 
         // Now wrap to 0. This should be accepted as the next in-sequence packet.
         let pkt0 = generate_data_packet_framing_layer_seq_num(UNIVERSE, 0);
-        let res0 = rcv.handle_data_packet(src_cid, pkt0);
+        let res0 = rcv.handle_data_packet(src_cid, pkt0, None);
         assert!(
             res0.is_ok(),
             "sequence wrap 255->0 should be accepted (got {:?})",
@@ -3351,11 +5642,1077 @@ mod test {
 
         // And 1 should also be accepted.
         let pkt1 = generate_data_packet_framing_layer_seq_num(UNIVERSE, 1);
-        let res1 = rcv.handle_data_packet(src_cid, pkt1);
+        let res1 = rcv.handle_data_packet(src_cid, pkt1, None);
         assert!(
             res1.is_ok(),
             "sequence 1 after wrap should be accepted (got {:?})",
             res1
         );
     }
+
+    /// Generates an unsynchronised data packet framing layer carrying DMX (NULL START Code) data with the given priority, sequence number and values.
+    fn generate_dmx_data_packet_framing_layer<'a>(
+        universe: u16,
+        priority: u8,
+        sequence_number: u8,
+        values: &'a [u8],
+    ) -> DataPacketFramingLayer<'a> {
+        DataPacketFramingLayer {
+            source_name: "Source_A".into(),
+            priority,
+            synchronization_address: E131_NO_SYNC_ADDR,
+            sequence_number,
+            preview_data: false,
+            stream_terminated: false,
+            force_synchronization: false,
+            universe,
+            data: DataPacketDmpLayer {
+                property_values: Cow::from(values),
+            },
+        }
+    }
+
+    /// Two sources send unsynchronised data to the same universe at the same priority with a HTP merge function set.
+    /// Every packet received should produce the HTP merge of both sources rather than alternating between them.
+    #[test]
+    fn test_unsync_data_two_sources_same_priority_merged() {
+        const UNIVERSE: u16 = 1;
+        let addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), ACN_SDT_MULTICAST_PORT);
+        let mut dmx_rcv = SacnReceiver::with_ip(addr, None).unwrap();
+        dmx_rcv.listen_universes(&[UNIVERSE]).unwrap();
+        dmx_rcv.set_merge_fn(htp_dmx_merge).unwrap();
+
+        let src_a = Uuid::new_v4();
+        let src_b = Uuid::new_v4();
+
+        let res = dmx_rcv
+            .handle_data_packet(
+                src_a,
+                generate_dmx_data_packet_framing_layer(UNIVERSE, 100, 0, &[0, 255, 0, 10]),
+                None,
+            )
+            .unwrap()
+            .unwrap();
+        assert_eq!(res.len(), 1);
+        assert_eq!(res[0].values, vec![0, 255, 0, 10]);
+        assert_eq!(res[0].src_cid, Some(src_a));
+
+        let res = dmx_rcv
+            .handle_data_packet(
+                src_b,
+                generate_dmx_data_packet_framing_layer(UNIVERSE, 100, 0, &[0, 0, 255, 5]),
+                None,
+            )
+            .unwrap()
+            .unwrap();
+        assert_eq!(res.len(), 1);
+        assert_eq!(res[0].values, vec![0, 255, 255, 10]);
+        assert_eq!(res[0].src_cid, None);
+
+        let res = dmx_rcv
+            .handle_data_packet(
+                src_a,
+                generate_dmx_data_packet_framing_layer(UNIVERSE, 100, 1, &[0, 1, 0, 1]),
+                None,
+            )
+            .unwrap()
+            .unwrap();
+        assert_eq!(res[0].values, vec![0, 1, 255, 5]);
+    }
+
+    /// Two sources send the same unchanging unsynchronised data to the same universe at the same priority with the default merger.
+    /// Every packet received after both are sending should produce the same HTP merge rather than flipping between the sources.
+    #[test]
+    fn test_unsync_data_two_sources_same_priority_default_merger_stable() {
+        const UNIVERSE: u16 = 1;
+        let addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), ACN_SDT_MULTICAST_PORT);
+        let mut dmx_rcv = SacnReceiver::with_ip(addr, None).unwrap();
+        dmx_rcv.listen_universes(&[UNIVERSE]).unwrap();
+
+        let src_a = Uuid::new_v4();
+        let src_b = Uuid::new_v4();
+
+        dmx_rcv
+            .handle_data_packet(
+                src_a,
+                generate_dmx_data_packet_framing_layer(UNIVERSE, 100, 0, &[0, 255, 0, 10]),
+                None,
+            )
+            .unwrap();
+
+        for seq in 0..4 {
+            for (src, values) in [(src_b, [0, 0, 255, 5]), (src_a, [0, 255, 0, 10])] {
+                let res = dmx_rcv
+                    .handle_data_packet(
+                        src,
+                        generate_dmx_data_packet_framing_layer(UNIVERSE, 100, seq + 1, &values),
+                        None,
+                    )
+                    .unwrap()
+                    .unwrap();
+                assert_eq!(res.len(), 1);
+                assert_eq!(res[0].values, vec![0, 255, 255, 10]);
+                assert_eq!(res[0].priority, 100);
+            }
+        }
+    }
+
+    /// Lower priority data waiting for synchronisation from the same source shouldn't replace its higher priority waiting data while the
+    /// unsynchronised data of the universe is HTP merged by default.
+    #[test]
+    fn test_store_waiting_data_same_source_lower_priority_kept() {
+        let addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), ACN_SDT_MULTICAST_PORT);
+        let mut dmx_rcv = SacnReceiver::with_ip(addr, None).unwrap();
+        let src = Some(Uuid::new_v4());
+
+        let data = |priority: u8, values: Vec<u8>| DMXData {
+            universe: 1,
+            values,
+            sync_uni: 2,
+            priority,
+            src_cid: src,
+            preview: false,
+            src_addr: None,
+            src_name: None,
+            recv_interface: None,
+            recv_timestamp: Instant::now(),
+        };
+
+        dmx_rcv
+            .store_waiting_data(data(120, vec![0, 255, 255]))
+            .unwrap();
+        dmx_rcv.store_waiting_data(data(100, vec![0, 10])).unwrap();
+
+        let res = dmx_rcv.rtrv_waiting_data(2);
+        assert_eq!(res.len(), 1);
+        assert_eq!(res[0].values, vec![0, 255, 255]);
+        assert_eq!(res[0].priority, 120);
+    }
+
+    /// A higher priority source should be output exclusively while it is sending, data from the lower priority source
+    /// should not be passed up until the higher priority source terminates.
+    #[test]
+    fn test_unsync_data_highest_priority_wins() {
+        const UNIVERSE: u16 = 1;
+        let addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), ACN_SDT_MULTICAST_PORT);
+        let mut dmx_rcv = SacnReceiver::with_ip(addr, None).unwrap();
+        dmx_rcv.listen_universes(&[UNIVERSE]).unwrap();
+        dmx_rcv.set_merge_fn(htp_dmx_merge).unwrap();
+
+        let src_high = Uuid::new_v4();
+        let src_low = Uuid::new_v4();
+
+        dmx_rcv
+            .handle_data_packet(
+                src_high,
+                generate_dmx_data_packet_framing_layer(UNIVERSE, 150, 0, &[0, 10, 20]),
+                None,
+            )
+            .unwrap();
+
+        let res = dmx_rcv
+            .handle_data_packet(
+                src_low,
+                generate_dmx_data_packet_framing_layer(UNIVERSE, 100, 0, &[0, 255, 255]),
+                None,
+            )
+            .unwrap()
+            .unwrap();
+        assert_eq!(res[0].values, vec![0, 10, 20]);
+        assert_eq!(res[0].priority, 150);
+        assert_eq!(res[0].src_cid, Some(src_high));
+
+        let mut terminate = generate_dmx_data_packet_framing_layer(UNIVERSE, 150, 1, &[0, 10, 20]);
+        terminate.stream_terminated = true;
+        assert!(
+            dmx_rcv
+                .handle_data_packet(src_high, terminate, None)
+                .unwrap()
+                .is_none()
+        );
+
+        let res = dmx_rcv
+            .handle_data_packet(
+                src_low,
+                generate_dmx_data_packet_framing_layer(UNIVERSE, 100, 1, &[0, 255, 255]),
+                None,
+            )
+            .unwrap()
+            .unwrap();
+        assert_eq!(res[0].values, vec![0, 255, 255]);
+        assert_eq!(res[0].src_cid, Some(src_low));
+    }
+
+    /// A merger set on the receiver should be given every live source, ignoring priority if the merger chooses to.
+    #[test]
+    fn test_unsync_data_custom_merger_sees_all_sources() {
+        const UNIVERSE: u16 = 1;
+        let addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), ACN_SDT_MULTICAST_PORT);
+        let mut dmx_rcv = SacnReceiver::with_ip(addr, None).unwrap();
+        dmx_rcv.listen_universes(&[UNIVERSE]).unwrap();
+        dmx_rcv.set_merger(crate::merge::HtpMerger::new());
+
+        let src_high = Uuid::new_v4();
+        let src_low = Uuid::new_v4();
+
+        dmx_rcv
+            .handle_data_packet(
+                src_high,
+                generate_dmx_data_packet_framing_layer(UNIVERSE, 150, 0, &[0, 10, 20]),
+                None,
+            )
+            .unwrap();
+
+        let res = dmx_rcv
+            .handle_data_packet(
+                src_low,
+                generate_dmx_data_packet_framing_layer(UNIVERSE, 100, 0, &[0, 255, 5]),
+                None,
+            )
+            .unwrap()
+            .unwrap();
+        assert_eq!(res[0].values, vec![0, 255, 20]);
+        assert_eq!(res[0].priority, 150);
+        assert_eq!(res[0].src_cid, None);
+    }
+
+    /// A merger should be told to forget a universe once the receiver has no sources left for it, whether the universe was muted or
+    /// its last source timed out, but not while another source is still sending it.
+    #[test]
+    fn test_merger_forgets_universes_without_sources() {
+        /// Records the universes it is told to forget.
+        struct ForgetRecorder(std::sync::Arc<std::sync::Mutex<Vec<u16>>>);
+
+        impl Merger for ForgetRecorder {
+            fn merge(&mut self, sources: &[MergeSource]) -> Result<DMXData> {
+                Ok(sources[0].data.clone())
+            }
+
+            fn forget_universe(&mut self, universe: u16) {
+                self.0.lock().unwrap().push(universe);
+            }
+        }
+
+        let addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), ACN_SDT_MULTICAST_PORT);
+        let mut dmx_rcv = SacnReceiver::with_ip(addr, None).unwrap();
+        let clock = ManualClock::new();
+        dmx_rcv.set_clock(clock.clone());
+        dmx_rcv.listen_universes(&[1, 2]).unwrap();
+        let forgotten = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+        dmx_rcv.set_merger(ForgetRecorder(forgotten.clone()));
+
+        let src_a = Uuid::new_v4();
+        let src_b = Uuid::new_v4();
+        for (cid, universe) in [(src_a, 1), (src_b, 1), (src_b, 2)] {
+            dmx_rcv
+                .handle_data_packet(
+                    cid,
+                    generate_dmx_data_packet_framing_layer(universe, 100, 0, &[0, 1]),
+                    None,
+                )
+                .unwrap();
+        }
+
+        dmx_rcv.mute_universe(2).unwrap();
+        assert_eq!(*forgotten.lock().unwrap(), vec![2]);
+
+        // Source A keeps sending universe 1 so it shouldn't be forgotten when source B times out.
+        clock.advance(E131_NETWORK_DATA_LOSS_TIMEOUT / 2);
+        dmx_rcv
+            .handle_data_packet(
+                src_a,
+                generate_dmx_data_packet_framing_layer(1, 100, 1, &[0, 1]),
+                None,
+            )
+            .unwrap();
+        clock.advance(E131_NETWORK_DATA_LOSS_TIMEOUT / 2);
+        dmx_rcv.check_timeouts().unwrap();
+        assert_eq!(*forgotten.lock().unwrap(), vec![2]);
+
+        clock.advance(E131_NETWORK_DATA_LOSS_TIMEOUT / 2);
+        dmx_rcv.check_timeouts().unwrap();
+        assert_eq!(*forgotten.lock().unwrap(), vec![2, 1]);
+    }
+
+    /// Data with an alternate start code isn't arbitrated between sources and should be passed up exactly as received.
+    #[test]
+    fn test_unsync_alternate_start_code_not_merged() {
+        const UNIVERSE: u16 = 1;
+        let addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), ACN_SDT_MULTICAST_PORT);
+        let mut dmx_rcv = SacnReceiver::with_ip(addr, None).unwrap();
+        dmx_rcv.listen_universes(&[UNIVERSE]).unwrap();
+        dmx_rcv.set_merge_fn(htp_dmx_merge).unwrap();
+
+        let src_a = Uuid::new_v4();
+        let src_b = Uuid::new_v4();
+
+        dmx_rcv
+            .handle_data_packet(
+                src_a,
+                generate_dmx_data_packet_framing_layer(UNIVERSE, 100, 0, &[0, 255, 0]),
+                None,
+            )
+            .unwrap();
+
+        let res = dmx_rcv
+            .handle_data_packet(
+                src_b,
+                generate_dmx_data_packet_framing_layer(UNIVERSE, 100, 0, &[0x17, 1, 2]),
+                None,
+            )
+            .unwrap()
+            .unwrap();
+        assert_eq!(res[0].values, vec![0x17, 1, 2]);
+        assert_eq!(res[0].src_cid, Some(src_b));
+    }
+
+    /// Generates an unsynchronised per-address priority (start code 0xDD) data packet framing layer with the given priorities.
+    fn generate_per_address_priority_packet_framing_layer<'a>(
+        universe: u16,
+        sequence_number: u8,
+        priorities: &'a [u8],
+    ) -> DataPacketFramingLayer<'a> {
+        let mut pkt =
+            generate_dmx_data_packet_framing_layer(universe, 100, sequence_number, priorities);
+        pkt.data.property_values =
+            Cow::from([&[E131_PER_ADDRESS_PRIORITY_START_CODE], priorities].concat());
+        pkt
+    }
+
+    /// One source sends per-address priorities which claim slot 1 at a higher priority than a second source without per-address
+    /// priorities and doesn't send slot 2 at all. Slot 1 should come from the first source and slot 2 from the second source.
+    #[test]
+    fn test_per_address_priority_slot_arbitration() {
+        const UNIVERSE: u16 = 1;
+        let addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), ACN_SDT_MULTICAST_PORT);
+        let mut dmx_rcv = SacnReceiver::with_ip(addr, None).unwrap();
+        dmx_rcv.listen_universes(&[UNIVERSE]).unwrap();
+
+        let src_a = Uuid::new_v4();
+        let src_b = Uuid::new_v4();
+
+        dmx_rcv
+            .handle_data_packet(
+                src_a,
+                generate_dmx_data_packet_framing_layer(UNIVERSE, 100, 0, &[0, 10, 20, 30]),
+                None,
+            )
+            .unwrap();
+
+        let res = dmx_rcv
+            .handle_data_packet(
+                src_a,
+                generate_per_address_priority_packet_framing_layer(UNIVERSE, 1, &[150, 0, 100]),
+                None,
+            )
+            .unwrap()
+            .unwrap();
+        assert_eq!(res[0].values, vec![0, 10, 0, 30]);
+        assert_eq!(res[0].src_cid, Some(src_a));
+
+        let res = dmx_rcv
+            .handle_data_packet(
+                src_b,
+                generate_dmx_data_packet_framing_layer(UNIVERSE, 100, 0, &[0, 200, 5, 20]),
+                None,
+            )
+            .unwrap()
+            .unwrap();
+        assert_eq!(res.len(), 1);
+        assert_eq!(res[0].values, vec![0, 10, 5, 30]);
+        assert_eq!(res[0].src_cid, None);
+    }
+
+    /// Per-address priority data should never be passed up itself, if there is no DMX data for the universe nothing is returned.
+    #[test]
+    fn test_per_address_priority_without_levels_not_returned() {
+        const UNIVERSE: u16 = 1;
+        let addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), ACN_SDT_MULTICAST_PORT);
+        let mut dmx_rcv = SacnReceiver::with_ip(addr, None).unwrap();
+        dmx_rcv.listen_universes(&[UNIVERSE]).unwrap();
+
+        let res = dmx_rcv
+            .handle_data_packet(
+                Uuid::new_v4(),
+                generate_per_address_priority_packet_framing_layer(UNIVERSE, 0, &[100, 100]),
+                None,
+            )
+            .unwrap();
+        assert!(res.is_none());
+    }
+
+    /// Once the source sending per-address priorities terminates the universe should return to normal whole universe arbitration.
+    #[test]
+    fn test_per_address_priority_removed_on_termination() {
+        const UNIVERSE: u16 = 1;
+        let addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), ACN_SDT_MULTICAST_PORT);
+        let mut dmx_rcv = SacnReceiver::with_ip(addr, None).unwrap();
+        dmx_rcv.listen_universes(&[UNIVERSE]).unwrap();
+
+        let src_a = Uuid::new_v4();
+        let src_b = Uuid::new_v4();
+
+        dmx_rcv
+            .handle_data_packet(
+                src_a,
+                generate_dmx_data_packet_framing_layer(UNIVERSE, 100, 0, &[0, 10, 20]),
+                None,
+            )
+            .unwrap();
+        dmx_rcv
+            .handle_data_packet(
+                src_a,
+                generate_per_address_priority_packet_framing_layer(UNIVERSE, 1, &[200, 200]),
+                None,
+            )
+            .unwrap();
+
+        let res = dmx_rcv
+            .handle_data_packet(
+                src_b,
+                generate_dmx_data_packet_framing_layer(UNIVERSE, 150, 0, &[0, 99, 99]),
+                None,
+            )
+            .unwrap()
+            .unwrap();
+        assert_eq!(res[0].values, vec![0, 10, 20]);
+
+        let mut terminate = generate_dmx_data_packet_framing_layer(UNIVERSE, 100, 2, &[0]);
+        terminate.stream_terminated = true;
+        dmx_rcv.handle_data_packet(src_a, terminate, None).unwrap();
+
+        let res = dmx_rcv
+            .handle_data_packet(
+                src_b,
+                generate_dmx_data_packet_framing_layer(UNIVERSE, 150, 1, &[0, 99, 99]),
+                None,
+            )
+            .unwrap()
+            .unwrap();
+        assert_eq!(res[0].values, vec![0, 99, 99]);
+        assert_eq!(res[0].src_cid, Some(src_b));
+    }
+
+    /// Wraps the given data packet framing layer in the root layer sent from the source with the given cid.
+    fn generate_root_layer_data_packet(
+        cid: Uuid,
+        data_pkt: DataPacketFramingLayer<'_>,
+    ) -> AcnRootLayerProtocol<'_> {
+        AcnRootLayerProtocol {
+            pdu: E131RootLayer {
+                cid,
+                data: DataPacket(data_pkt),
+            },
+        }
+    }
+
+    /// A stream termination packet should produce a UniverseTerminated event followed by a SourceLost event once the source has no
+    /// universes left, regardless of the announce_stream_termination flag. The recv compatibility mapping only reports the termination
+    /// when the flag is set.
+    #[test]
+    fn test_recv_event_universe_terminated_source_lost() {
+        const UNIVERSE: u16 = 1;
+        let addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), ACN_SDT_MULTICAST_PORT);
+        let mut dmx_rcv = SacnReceiver::with_ip(addr, None).unwrap();
+        dmx_rcv.listen_universes(&[UNIVERSE]).unwrap();
+
+        let src_cid = Uuid::new_v4();
+
+        dmx_rcv
+            .handle_packet(
+                generate_root_layer_data_packet(
+                    src_cid,
+                    generate_dmx_data_packet_framing_layer(UNIVERSE, 100, 0, &[0, 10]),
+                ),
+                None,
+            )
+            .unwrap();
+
+        let mut terminate = generate_dmx_data_packet_framing_layer(UNIVERSE, 100, 1, &[0]);
+        terminate.stream_terminated = true;
+        let res = dmx_rcv
+            .handle_packet(generate_root_layer_data_packet(src_cid, terminate), None)
+            .unwrap();
+        assert!(res.is_none());
+
+        match dmx_rcv.recv_event(Some(Duration::from_secs(0))).unwrap() {
+            ReceiveEvent::UniverseTerminated(cid, universe) => {
+                assert_eq!(cid, src_cid);
+                assert_eq!(universe, UNIVERSE);
+            }
+            e => panic!("Unexpected event {e:?}"),
+        }
+        let lost = dmx_rcv.recv_event(Some(Duration::from_secs(0))).unwrap();
+        assert!(matches!(lost, ReceiveEvent::SourceLost(cid) if cid == src_cid));
+        assert!(dmx_rcv.recv_event(Some(Duration::from_secs(0))).is_err());
+
+        let event = ReceiveEvent::UniverseTerminated(src_cid, UNIVERSE);
+        assert!(dmx_rcv.announced_result(event.clone()).is_none());
+        dmx_rcv.set_announce_stream_termination(true);
+        assert!(matches!(
+            dmx_rcv.announced_result(event),
+            Some(Err(SacnError::UniverseTerminated(..)))
+        ));
+    }
+
+    /// An out of sequence data packet should be reported as a SequenceError event rather than an error.
+    #[test]
+    fn test_recv_event_sequence_error() {
+        const UNIVERSE: u16 = 1;
+        let addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), ACN_SDT_MULTICAST_PORT);
+        let mut dmx_rcv = SacnReceiver::with_ip(addr, None).unwrap();
+        dmx_rcv.listen_universes(&[UNIVERSE]).unwrap();
+
+        let src_cid = Uuid::new_v4();
+
+        for seq in [5, 4] {
+            dmx_rcv
+                .handle_packet(
+                    generate_root_layer_data_packet(
+                        src_cid,
+                        generate_dmx_data_packet_framing_layer(UNIVERSE, 100, seq, &[0, 10]),
+                    ),
+                    None,
+                )
+                .unwrap();
+        }
+
+        match dmx_rcv.recv_event(Some(Duration::from_secs(0))).unwrap() {
+            ReceiveEvent::SequenceError(cid, universe, seq, last, _) => {
+                assert_eq!(cid, src_cid);
+                assert_eq!(universe, UNIVERSE);
+                assert_eq!(seq, 4);
+                assert_eq!(last, 5);
+            }
+            e => panic!("Unexpected event {e:?}"),
+        }
+    }
+
+    /// Completing the discovery of a source should produce a SourceDiscovered event containing the source even though the
+    /// announce_source_discovery flag isn't set.
+    #[test]
+    fn test_recv_event_source_discovered() {
+        let addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), ACN_SDT_MULTICAST_PORT);
+        let mut dmx_rcv = SacnReceiver::with_ip(addr, None).unwrap();
+
+        let name = "Test Src 1";
+        let src_cid = Uuid::new_v4();
+        let universes: Vec<u16> = vec![1, 2, 3];
+
+        let pkt = AcnRootLayerProtocol {
+            pdu: E131RootLayer {
+                cid: src_cid,
+                data: UniverseDiscoveryPacket(UniverseDiscoveryPacketFramingLayer {
+                    source_name: name.into(),
+                    data: UniverseDiscoveryPacketUniverseDiscoveryLayer {
+                        page: 0,
+                        last_page: 0,
+                        universes: universes.clone().into(),
+                    },
+                }),
+            },
+        };
+        assert!(dmx_rcv.handle_packet(pkt, None).unwrap().is_none());
+
+        match dmx_rcv.recv_event(Some(Duration::from_secs(0))).unwrap() {
+            ReceiveEvent::SourceDiscovered(src) => {
+                assert_eq!(src.name, name);
+                assert_eq!(src.cid, src_cid);
+                assert_eq!(src.get_all_universes(), universes);
+            }
+            e => panic!("Unexpected event {e:?}"),
+        }
+    }
+
+    /// During the sampling period of a universe data from the sources should be learnt but not returned, once it ends the arbitrated
+    /// data of all the sources is returned so the lower priority source which was received first is never output.
+    #[test]
+    fn test_sampling_period_holds_then_delivers_arbitrated_data() {
+        const UNIVERSE: u16 = 1;
+        const SAMPLING_PERIOD: Duration = Duration::from_millis(50);
+        let addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), ACN_SDT_MULTICAST_PORT);
+        let mut dmx_rcv = SacnReceiver::with_ip(addr, None).unwrap();
+        let clock = ManualClock::new();
+        dmx_rcv.set_clock(clock.clone());
+        dmx_rcv.set_sampling_period(Some(SAMPLING_PERIOD));
+        dmx_rcv.listen_universes(&[UNIVERSE]).unwrap();
+
+        assert!(dmx_rcv.is_sampling(UNIVERSE));
+        assert!(!dmx_rcv.is_sampling(E131_DISCOVERY_UNIVERSE));
+
+        let low_src = Uuid::new_v4();
+        let high_src = Uuid::new_v4();
+
+        let res = dmx_rcv
+            .handle_data_packet(
+                low_src,
+                generate_dmx_data_packet_framing_layer(UNIVERSE, 50, 0, &[0, 1, 1]),
+                None,
+            )
+            .unwrap();
+        assert!(res.is_none(), "Data returned during the sampling period");
+        let res = dmx_rcv
+            .handle_data_packet(
+                high_src,
+                generate_dmx_data_packet_framing_layer(UNIVERSE, 150, 0, &[0, 2, 2]),
+                None,
+            )
+            .unwrap();
+        assert!(res.is_none(), "Data returned during the sampling period");
+
+        assert!(dmx_rcv.recv_event(Some(Duration::from_secs(0))).is_err());
+
+        clock.advance(SAMPLING_PERIOD / 2);
+        assert!(dmx_rcv.recv_event(Some(Duration::from_secs(0))).is_err());
+        assert!(dmx_rcv.is_sampling(UNIVERSE));
+
+        clock.advance(SAMPLING_PERIOD / 2);
+
+        match dmx_rcv.recv_event(Some(Duration::from_secs(0))).unwrap() {
+            ReceiveEvent::Data(data) => {
+                assert_eq!(data.len(), 1);
+                assert_eq!(data[0].values, vec![0, 2, 2]);
+                assert_eq!(data[0].src_cid, Some(high_src));
+            }
+            e => panic!("Unexpected event {e:?}"),
+        }
+        assert!(!dmx_rcv.is_sampling(UNIVERSE));
+
+        // After the sampling period data is returned immediately.
+        let res = dmx_rcv
+            .handle_data_packet(
+                low_src,
+                generate_dmx_data_packet_framing_layer(UNIVERSE, 50, 1, &[0, 1, 1]),
+                None,
+            )
+            .unwrap();
+        assert_eq!(res.unwrap()[0].values, vec![0, 2, 2]);
+    }
+
+    /// Checks that the next events of the receiver report the given source as lost after timing out on the given universe, which
+    /// is the only universe it was sending.
+    fn assert_source_lost(dmx_rcv: &mut SacnReceiver, universe: u16, cid: Uuid) {
+        match dmx_rcv.recv_event(Some(Duration::from_secs(0))).unwrap() {
+            ReceiveEvent::UniverseTimedOut(c, u) => assert_eq!((c, u), (cid, universe)),
+            e => panic!("Unexpected event {e:?}"),
+        }
+        match dmx_rcv.recv_event(Some(Duration::from_secs(0))).unwrap() {
+            ReceiveEvent::SourceLost(c) => assert_eq!(c, cid),
+            e => panic!("Unexpected event {e:?}"),
+        }
+    }
+
+    /// The timeouts of every source and universe should only be checked once per TIMEOUT_CHECK_INTERVAL while packets are received, apart
+    /// from the timeout of the source and universe of the packet which is always checked so that a returning source is treated as new.
+    #[test]
+    fn test_timeouts_checked_periodically() {
+        let addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), ACN_SDT_MULTICAST_PORT);
+        let mut dmx_rcv = SacnReceiver::with_ip(addr, None).unwrap();
+        let clock = ManualClock::new();
+        dmx_rcv.set_clock(clock.clone());
+        dmx_rcv.listen_universes(&[1, 2, 3]).unwrap();
+
+        let src_a = Uuid::new_v4();
+        let src_b = Uuid::new_v4();
+        let src_c = Uuid::new_v4();
+        let send = |dmx_rcv: &mut SacnReceiver, cid: Uuid, universe: u16, seq: u8| {
+            dmx_rcv
+                .handle_packet(
+                    generate_root_layer_data_packet(
+                        cid,
+                        generate_dmx_data_packet_framing_layer(universe, 100, seq, &[0, 1]),
+                    ),
+                    None,
+                )
+                .unwrap()
+        };
+        send(&mut dmx_rcv, src_a, 1, 0);
+        send(&mut dmx_rcv, src_b, 2, 0);
+
+        // The timeouts are checked just before the sources time out.
+        clock.advance(E131_NETWORK_DATA_LOSS_TIMEOUT - TIMEOUT_CHECK_INTERVAL / 2);
+        assert!(dmx_rcv.recv_event(Some(Duration::from_secs(0))).is_err());
+        clock.advance(TIMEOUT_CHECK_INTERVAL / 2);
+
+        // Within the interval a packet from another source doesn't check the timeouts.
+        assert!(send(&mut dmx_rcv, src_c, 3, 0).is_some());
+        assert!(dmx_rcv.pending_events.is_empty());
+        assert!(dmx_rcv.sequences.has_source(&src_a));
+
+        // A packet from a source which has timed out does, reporting every timeout.
+        assert!(send(&mut dmx_rcv, src_a, 1, 100).is_some());
+        let mut events: Vec<String> = dmx_rcv
+            .pending_events
+            .drain(..)
+            .map(|e| format!("{e:?}"))
+            .collect();
+        let mut expected = vec![
+            format!("SourceLost({src_a})"),
+            format!("SourceLost({src_b})"),
+            format!("UniverseTimedOut({src_a}, 1)"),
+            format!("UniverseTimedOut({src_b}, 2)"),
+        ];
+        events.sort();
+        expected.sort();
+        assert_eq!(events, expected);
+
+        // Once the interval has passed the next packet checks the timeouts.
+        clock.advance(E131_NETWORK_DATA_LOSS_TIMEOUT / 2);
+        send(&mut dmx_rcv, src_a, 1, 101);
+        assert!(dmx_rcv.pending_events.is_empty());
+        clock.advance(E131_NETWORK_DATA_LOSS_TIMEOUT / 2);
+        send(&mut dmx_rcv, src_a, 1, 102);
+        assert_source_lost(&mut dmx_rcv, 3, src_c);
+    }
+
+    /// With the Release policy losing the highest priority source should immediately produce the data of the next source and
+    /// losing the last source should release the universe to zero.
+    #[test]
+    fn test_data_loss_release() {
+        const UNIVERSE: u16 = 1;
+        let addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), ACN_SDT_MULTICAST_PORT);
+        let mut dmx_rcv = SacnReceiver::with_ip(addr, None).unwrap();
+        let clock = ManualClock::new();
+        dmx_rcv.set_clock(clock.clone());
+        dmx_rcv.listen_universes(&[UNIVERSE]).unwrap();
+        dmx_rcv.set_data_loss_policy(UNIVERSE, Some(DataLossPolicy::Release));
+        assert_eq!(
+            dmx_rcv.get_data_loss_policy(UNIVERSE),
+            Some(DataLossPolicy::Release)
+        );
+
+        let low_src = Uuid::new_v4();
+        let high_src = Uuid::new_v4();
+        dmx_rcv
+            .handle_data_packet(
+                high_src,
+                generate_dmx_data_packet_framing_layer(UNIVERSE, 150, 0, &[0, 2, 2]),
+                None,
+            )
+            .unwrap();
+        clock.advance(E131_NETWORK_DATA_LOSS_TIMEOUT / 2);
+        dmx_rcv
+            .handle_data_packet(
+                low_src,
+                generate_dmx_data_packet_framing_layer(UNIVERSE, 50, 0, &[0, 1, 1]),
+                None,
+            )
+            .unwrap();
+        assert!(dmx_rcv.recv_event(Some(Duration::from_secs(0))).is_err());
+
+        clock.advance(E131_NETWORK_DATA_LOSS_TIMEOUT / 2);
+        assert_source_lost(&mut dmx_rcv, UNIVERSE, high_src);
+        match dmx_rcv.recv_event(Some(Duration::from_secs(0))).unwrap() {
+            ReceiveEvent::Data(data) => assert_eq!(data[0].values, vec![0, 1, 1]),
+            e => panic!("Unexpected event {e:?}"),
+        }
+
+        clock.advance(E131_NETWORK_DATA_LOSS_TIMEOUT / 2);
+        assert_source_lost(&mut dmx_rcv, UNIVERSE, low_src);
+        match dmx_rcv.recv_event(Some(Duration::from_secs(0))).unwrap() {
+            ReceiveEvent::Data(data) => {
+                assert_eq!(data[0].values, vec![0, 0, 0]);
+                assert_eq!(data[0].src_cid, None);
+            }
+            e => panic!("Unexpected event {e:?}"),
+        }
+        assert!(dmx_rcv.recv_event(Some(Duration::from_secs(0))).is_err());
+    }
+
+    /// With the Hold policy the data of a lost source should keep being used in arbitration until the source returns.
+    #[test]
+    fn test_data_loss_hold() {
+        const UNIVERSE: u16 = 1;
+        let addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), ACN_SDT_MULTICAST_PORT);
+        let mut dmx_rcv = SacnReceiver::with_ip(addr, None).unwrap();
+        let clock = ManualClock::new();
+        dmx_rcv.set_clock(clock.clone());
+        dmx_rcv.listen_universes(&[UNIVERSE]).unwrap();
+        dmx_rcv.set_data_loss_policy(UNIVERSE, Some(DataLossPolicy::Hold));
+
+        let low_src = Uuid::new_v4();
+        let high_src = Uuid::new_v4();
+        dmx_rcv
+            .handle_data_packet(
+                high_src,
+                generate_dmx_data_packet_framing_layer(UNIVERSE, 150, 0, &[0, 2, 2]),
+                None,
+            )
+            .unwrap();
+
+        clock.advance(E131_NETWORK_DATA_LOSS_TIMEOUT);
+        assert_source_lost(&mut dmx_rcv, UNIVERSE, high_src);
+        assert!(dmx_rcv.recv_event(Some(Duration::from_secs(0))).is_err());
+
+        let res = dmx_rcv
+            .handle_data_packet(
+                low_src,
+                generate_dmx_data_packet_framing_layer(UNIVERSE, 50, 0, &[0, 1, 1]),
+                None,
+            )
+            .unwrap()
+            .unwrap();
+        assert_eq!(res[0].values, vec![0, 2, 2]);
+
+        // Once the source returns its new data is used.
+        let res = dmx_rcv
+            .handle_data_packet(
+                high_src,
+                generate_dmx_data_packet_framing_layer(UNIVERSE, 150, 1, &[0, 3, 3]),
+                None,
+            )
+            .unwrap()
+            .unwrap();
+        assert_eq!(res[0].values, vec![0, 3, 3]);
+        assert!(dmx_rcv.lost_sources.is_empty());
+    }
+
+    /// With the Fade policy the data of a lost source should be scaled down over the fade and then released.
+    #[test]
+    fn test_data_loss_fade() {
+        const UNIVERSE: u16 = 1;
+        const FADE: Duration = Duration::from_secs(10);
+        let addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), ACN_SDT_MULTICAST_PORT);
+        let mut dmx_rcv = SacnReceiver::with_ip(addr, None).unwrap();
+        let clock = ManualClock::new();
+        dmx_rcv.set_clock(clock.clone());
+        dmx_rcv.listen_universes(&[UNIVERSE]).unwrap();
+        dmx_rcv.set_data_loss_policy(UNIVERSE, Some(DataLossPolicy::Fade(FADE)));
+
+        let src = Uuid::new_v4();
+        dmx_rcv
+            .handle_data_packet(
+                src,
+                generate_dmx_data_packet_framing_layer(UNIVERSE, 100, 0, &[0, 200, 100]),
+                None,
+            )
+            .unwrap();
+
+        clock.advance(E131_NETWORK_DATA_LOSS_TIMEOUT);
+        assert_source_lost(&mut dmx_rcv, UNIVERSE, src);
+        assert!(dmx_rcv.recv_event(Some(Duration::from_secs(0))).is_err());
+
+        // Move the fade to half way through.
+        clock.advance(FADE / 2);
+
+        match dmx_rcv.recv_event(Some(Duration::from_secs(0))).unwrap() {
+            ReceiveEvent::Data(data) => assert_eq!(data[0].values, vec![0, 100, 50]),
+            e => panic!("Unexpected event {e:?}"),
+        }
+
+        // Move to the end of the fade.
+        clock.advance(FADE / 2);
+
+        match dmx_rcv.recv_event(Some(Duration::from_secs(0))).unwrap() {
+            ReceiveEvent::Data(data) => assert_eq!(data[0].values, vec![0, 0, 0]),
+            e => panic!("Unexpected event {e:?}"),
+        }
+        assert!(dmx_rcv.lost_sources.is_empty());
+    }
+
+    /// Generates a DMX data packet framing layer for the given universe with the given synchronisation address and force synchronisation
+    /// option bit.
+    fn generate_force_sync_data_packet_framing_layer(
+        universe: u16,
+        sync_uni: u16,
+        sequence_number: u8,
+        force_synchronization: bool,
+    ) -> DataPacketFramingLayer<'static> {
+        let mut pkt =
+            generate_dmx_data_packet_framing_layer(universe, 100, sequence_number, &[0, 1, 2]);
+        pkt.synchronization_address = sync_uni;
+        pkt.force_synchronization = force_synchronization;
+        pkt
+    }
+
+    /// Once synchronisation has been lost data with the force synchronisation option bit set should be acted on immediately, while data
+    /// without the bit should keep waiting for synchronisation as per ANSI E1.31-2018 Section 6.2.6.
+    #[test]
+    fn test_force_synchronization_after_sync_lost() {
+        const UNIVERSE: u16 = 1;
+        const SYNC_UNI: u16 = 2;
+        let addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), ACN_SDT_MULTICAST_PORT);
+        let mut dmx_rcv = SacnReceiver::with_ip(addr, None).unwrap();
+        let clock = ManualClock::new();
+        dmx_rcv.set_clock(clock.clone());
+        dmx_rcv.listen_universes(&[UNIVERSE, SYNC_UNI]).unwrap();
+
+        let src_cid = Uuid::new_v4();
+
+        // Before any synchronisation packets are received the data waits regardless of the option bit.
+        let res = dmx_rcv
+            .handle_data_packet(
+                src_cid,
+                generate_force_sync_data_packet_framing_layer(UNIVERSE, SYNC_UNI, 0, true),
+                None,
+            )
+            .unwrap();
+        assert!(res.is_none());
+
+        let res = dmx_rcv
+            .handle_sync_packet(
+                src_cid,
+                generate_sync_packet_framing_layer_seq_num(SYNC_UNI, 0),
+            )
+            .unwrap();
+        assert_eq!(res.unwrap().len(), 1);
+
+        // Synchronisation is then lost.
+        clock.advance(E131_NETWORK_DATA_LOSS_TIMEOUT);
+
+        let res = dmx_rcv
+            .handle_data_packet(
+                src_cid,
+                generate_force_sync_data_packet_framing_layer(UNIVERSE, SYNC_UNI, 1, false),
+                None,
+            )
+            .unwrap();
+        assert!(res.is_none(), "Data without force synchronisation acted on");
+
+        let res = dmx_rcv
+            .handle_data_packet(
+                src_cid,
+                generate_force_sync_data_packet_framing_layer(UNIVERSE, SYNC_UNI, 2, true),
+                None,
+            )
+            .unwrap()
+            .expect("Data with force synchronisation should be acted on");
+        assert_eq!(res[0].values, vec![0, 1, 2]);
+        assert!(dmx_rcv.waiting_data.is_empty());
+    }
+
+    /// The universe state should hold the latest arbitrated data along with every source that took part and be removed when the universe
+    /// is muted.
+    #[test]
+    fn test_universe_state_unsync_sources() {
+        const UNIVERSE: u16 = 1;
+        let addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), ACN_SDT_MULTICAST_PORT);
+        let mut dmx_rcv = SacnReceiver::with_ip(addr, None).unwrap();
+        dmx_rcv.listen_universes(&[UNIVERSE]).unwrap();
+
+        assert!(dmx_rcv.universe_state(UNIVERSE).is_none());
+
+        let src_a = Uuid::new_v4();
+        let src_b = Uuid::new_v4();
+        dmx_rcv
+            .handle_data_packet(
+                src_a,
+                generate_dmx_data_packet_framing_layer(UNIVERSE, 100, 0, &[0, 1, 1]),
+                None,
+            )
+            .unwrap();
+        dmx_rcv
+            .handle_data_packet(
+                src_b,
+                generate_dmx_data_packet_framing_layer(UNIVERSE, 150, 0, &[0, 2, 2]),
+                None,
+            )
+            .unwrap();
+
+        let state = dmx_rcv.universe_state(UNIVERSE).unwrap();
+        assert_eq!(state.data.values, vec![0, 2, 2]);
+        assert_eq!(state.sources.len(), 2);
+        assert!(state.sources.contains(&src_a));
+        assert!(state.sources.contains(&src_b));
+        assert!(state.last_updated.elapsed() < E131_NETWORK_DATA_LOSS_TIMEOUT);
+
+        // Alternative start code data doesn't change the state.
+        dmx_rcv
+            .handle_data_packet(
+                src_b,
+                generate_dmx_data_packet_framing_layer(UNIVERSE, 150, 1, &[0x17, 9, 9]),
+                None,
+            )
+            .unwrap();
+        assert_eq!(
+            dmx_rcv.universe_state(UNIVERSE).unwrap().data.values,
+            vec![0, 2, 2]
+        );
+
+        dmx_rcv.mute_universe(UNIVERSE).unwrap();
+        assert!(dmx_rcv.universe_state(UNIVERSE).is_none());
+    }
+
+    /// Synchronised universes should only update their state when the synchronisation packet arrives and then all update together.
+    #[test]
+    fn test_universe_state_sync() {
+        const UNIVERSE1: u16 = 1;
+        const UNIVERSE2: u16 = 2;
+        const SYNC_UNI: u16 = 3;
+        let addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), ACN_SDT_MULTICAST_PORT);
+        let mut dmx_rcv = SacnReceiver::with_ip(addr, None).unwrap();
+        dmx_rcv
+            .listen_universes(&[UNIVERSE1, UNIVERSE2, SYNC_UNI])
+            .unwrap();
+
+        let src_cid = Uuid::new_v4();
+        for universe in [UNIVERSE1, UNIVERSE2] {
+            dmx_rcv
+                .handle_data_packet(
+                    src_cid,
+                    generate_force_sync_data_packet_framing_layer(universe, SYNC_UNI, 0, false),
+                    None,
+                )
+                .unwrap();
+        }
+        assert!(dmx_rcv.all_universe_states().is_empty());
+
+        dmx_rcv
+            .handle_sync_packet(
+                src_cid,
+                generate_sync_packet_framing_layer_seq_num(SYNC_UNI, 0),
+            )
+            .unwrap();
+
+        let states = dmx_rcv.all_universe_states();
+        assert_eq!(states.len(), 2);
+        assert_eq!(states[0].data.universe, UNIVERSE1);
+        assert_eq!(states[1].data.universe, UNIVERSE2);
+        for state in states {
+            assert_eq!(state.data.values, vec![0, 1, 2]);
+            assert_eq!(state.sources, vec![src_cid]);
+        }
+    }
+
+    /// Data from a single source should carry the address and interface it was received from and the source name, once merged with another source at
+    /// the same priority there is no clear source so both are cleared.
+    #[test]
+    fn test_data_src_addr_and_name() {
+        const UNIVERSE: u16 = 1;
+        let addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), ACN_SDT_MULTICAST_PORT);
+        let mut dmx_rcv = SacnReceiver::with_ip(addr, None).unwrap();
+        dmx_rcv.listen_universes(&[UNIVERSE]).unwrap();
+        dmx_rcv.set_merge_fn(htp_dmx_merge).unwrap();
+
+        let src_a = Uuid::new_v4();
+        let src_a_addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(10, 0, 0, 12)), 5568);
+
+        let res = dmx_rcv
+            .handle_data_packet(
+                src_a,
+                generate_dmx_data_packet_framing_layer(UNIVERSE, 100, 0, &[0, 1, 1]),
+                Some(PacketOrigin {
+                    addr: src_a_addr,
+                    interface: Some(3),
+                }),
+            )
+            .unwrap()
+            .unwrap();
+        assert_eq!(res[0].src_addr, Some(src_a_addr));
+        assert_eq!(res[0].src_name.as_deref(), Some("Source_A"));
+        assert_eq!(res[0].recv_interface, Some(3));
+
+        let res = dmx_rcv
+            .handle_data_packet(
+                Uuid::new_v4(),
+                generate_dmx_data_packet_framing_layer(UNIVERSE, 100, 0, &[0, 2, 2]),
+                None,
+            )
+            .unwrap()
+            .unwrap();
+        assert_eq!(res[0].src_cid, None);
+        assert_eq!(res[0].src_addr, None);
+        assert_eq!(res[0].src_name, None);
+        assert_eq!(res[0].recv_interface, None);
+    }
 }
@@ -0,0 +1,266 @@
+// Copyright 2020 sacn Developers
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! Asynchronous sACN receiving built on the tokio runtime, enabled by the `async` feature.
+
+use super::{
+    DMXData, RCV_BUF_DEFAULT_SIZE, ReceiveEvent, ReceiverNetwork, SacnReceiver, recv_with_origin,
+};
+
+use crate::error::errors::*;
+use crate::packet::{AcnRootLayerProtocol, E131_NETWORK_DATA_LOSS_TIMEOUT};
+
+use std::future::{Future, poll_fn};
+use std::io;
+use std::net::SocketAddr;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use futures_core::Stream;
+use tokio::io::Interest;
+use tokio::net::UdpSocket;
+use tokio::time::{Instant, Interval, MissedTickBehavior, Sleep, interval_at, sleep_until};
+
+/// An asynchronous ANSI E1.31-2018 sACN receiver.
+///
+/// This wraps a [`SacnReceiver`], which performs all the sequence numbering, synchronisation, merging and universe discovery
+/// handling, with a tokio UdpSocket so that receiving doesn't block a thread. The receiver is configured through
+/// (receiver_mut)[AsyncSacnReceiver::receiver_mut] in the same way as a SacnReceiver and data is received using
+/// (recv)[AsyncSacnReceiver::recv], (recv_event)[AsyncSacnReceiver::recv_event] or by using the receiver as a [`Stream`] of events.
+///
+/// The receiver must be created from within a tokio runtime.
+///
+/// # Examples
+///
+/// ```no_run
+/// use sacn::receive::AsyncSacnReceiver;
+/// use sacn::packet::ACN_SDT_MULTICAST_PORT;
+///
+/// use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+///
+/// # async fn run() {
+/// let addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::UNSPECIFIED), ACN_SDT_MULTICAST_PORT);
+///
+/// let mut dmx_rcv = AsyncSacnReceiver::with_ip(addr, None).unwrap();
+/// dmx_rcv.receiver_mut().listen_universes(&[1]).unwrap();
+///
+/// loop {
+///     match dmx_rcv.recv().await {
+///         Ok(data) => println!("{:?}", data),
+///         Err(e) => println!("{:?}", e),
+///     }
+/// }
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct AsyncSacnReceiver {
+    /// The receiver which handles the received packets.
+    receiver: SacnReceiver,
+
+    /// The tokio sockets used for receiving, these share the underlying sockets of the receiver so multicast groups joined through
+    /// the receiver apply. Sockets added by the receiver to spread multicast groups over are added here when next polled.
+    sockets: Vec<UdpSocket>,
+
+    /// The index of the socket that is checked first for a packet next time, rotated so that no socket is starved.
+    next_socket: usize,
+
+    /// The buffer packets are received into.
+    buf: Box<[u8; RCV_BUF_DEFAULT_SIZE]>,
+
+    /// Wakes the receiver every E131_NETWORK_DATA_LOSS_TIMEOUT so that timeouts are checked even if no packets arrive.
+    timeout_check: Interval,
+
+    /// Wakes the receiver when the next sampling period of a universe ends or data loss frame is due so that the data is returned promptly.
+    deadline_wake: Pin<Box<Sleep>>,
+}
+
+impl AsyncSacnReceiver {
+    /// Creates a new AsyncSacnReceiver bound to the given address.
+    ///
+    /// This must be called from within a tokio runtime.
+    ///
+    /// Arguments:
+    ///     ip: The address of the interface for this receiver to join, see (SacnReceiver::with_ip)[SacnReceiver::with_ip].
+    ///     source_limit: The limit to the number of sources, past this limit a new source will cause a SourcesExceededError as per ANSI E1.31-2018 Section 6.2.3.3.
+    ///
+    /// # Errors
+    /// Will return any error from creating the underlying SacnReceiver, see (SacnReceiver::with_ip)[SacnReceiver::with_ip].
+    ///
+    /// Will return an Io error if the socket cannot be registered with the tokio runtime.
+    pub fn with_ip(ip: SocketAddr, source_limit: Option<usize>) -> Result<AsyncSacnReceiver> {
+        AsyncSacnReceiver::from_receiver(SacnReceiver::with_ip(ip, source_limit)?)
+    }
+
+    /// Creates a new AsyncSacnReceiver from an existing SacnReceiver, keeping its configuration and the universes it is listening to.
+    ///
+    /// The SacnReceiver's socket is switched to non-blocking mode so its blocking (recv)[SacnReceiver::recv] is no longer usable.
+    /// This must be called from within a tokio runtime.
+    ///
+    /// # Errors
+    /// Will return an Io error if the socket cannot be cloned or registered with the tokio runtime.
+    ///
+    /// Will return a TransportUnsupported error if the receiver uses a transport, see (SacnReceiver::with_transport)[SacnReceiver::with_transport].
+    pub fn from_receiver(receiver: SacnReceiver) -> Result<AsyncSacnReceiver> {
+        if let ReceiverNetwork::Transport(_) = receiver.receiver {
+            return Err(SacnError::TransportUnsupported(
+                "The async receiver can only receive on UDP sockets".to_string(),
+            ));
+        }
+
+        let mut timeout_check = interval_at(
+            Instant::now() + E131_NETWORK_DATA_LOSS_TIMEOUT,
+            E131_NETWORK_DATA_LOSS_TIMEOUT,
+        );
+        timeout_check.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+        let mut async_receiver = AsyncSacnReceiver {
+            receiver,
+            sockets: Vec::new(),
+            next_socket: 0,
+            buf: Box::new([0; RCV_BUF_DEFAULT_SIZE]),
+            timeout_check,
+            deadline_wake: Box::pin(sleep_until(Instant::now())),
+        };
+        async_receiver.add_new_sockets()?;
+        Ok(async_receiver)
+    }
+
+    /// Creates tokio sockets for any sockets of the receiver which don't have one yet, the receiver only ever adds sockets.
+    ///
+    /// # Errors
+    /// Will return an Io error if a socket cannot be cloned or registered with the tokio runtime.
+    fn add_new_sockets(&mut self) -> Result<()> {
+        for s in self.receiver.receiver.sockets().skip(self.sockets.len()) {
+            let socket: std::net::UdpSocket = s.try_clone()?.into();
+            socket.set_nonblocking(true)?;
+            self.sockets.push(UdpSocket::from_std(socket)?);
+        }
+        Ok(())
+    }
+
+    /// Returns a reference to the SacnReceiver which handles the received packets.
+    pub fn receiver(&self) -> &SacnReceiver {
+        &self.receiver
+    }
+
+    /// Returns a mutable reference to the SacnReceiver which handles the received packets, used to configure the receiver
+    /// e.g. to listen to universes.
+    ///
+    /// The blocking (recv)[SacnReceiver::recv] of the returned receiver shouldn't be used, use (recv)[AsyncSacnReceiver::recv] instead.
+    pub fn receiver_mut(&mut self) -> &mut SacnReceiver {
+        &mut self.receiver
+    }
+
+    /// Attempts to receive data from any of the registered universes, waiting until there is data ready to act on.
+    ///
+    /// Received packets are handled exactly as by (SacnReceiver::recv)[SacnReceiver::recv] so the returned data has already been
+    /// synchronised and merged as required.
+    ///
+    /// # Errors
+    /// See (SacnReceiver::recv)[SacnReceiver::recv], timeout errors are never returned.
+    pub async fn recv(&mut self) -> Result<Vec<DMXData>> {
+        loop {
+            let event = self.recv_event().await?;
+            if let Some(res) = self.receiver.announced_result(event) {
+                return res;
+            }
+        }
+    }
+
+    /// Attempts to receive the next event, waiting until one occurs.
+    ///
+    /// Events are produced exactly as by (SacnReceiver::recv_event)[SacnReceiver::recv_event].
+    ///
+    /// # Errors
+    /// See (SacnReceiver::recv_event)[SacnReceiver::recv_event], timeout errors are never returned.
+    pub async fn recv_event(&mut self) -> Result<ReceiveEvent> {
+        poll_fn(|cx| self.poll_recv_event(cx)).await
+    }
+
+    /// Polls for the next event, see (recv_event)[AsyncSacnReceiver::recv_event].
+    fn poll_recv_event(&mut self, cx: &mut Context<'_>) -> Poll<Result<ReceiveEvent>> {
+        loop {
+            // Registers the waker so that timeouts are checked periodically while waiting for packets.
+            while self.timeout_check.poll_tick(cx).is_ready() {}
+
+            if let Err(e) = self.receiver.check_timeouts_if_due() {
+                return Poll::Ready(Err(e));
+            }
+
+            if let Some(end) = self.receiver.next_deadline() {
+                // The deadline is a time of the clock of the receiver, which needn't be the system clock, so the wake up is set from the
+                // time remaining until it.
+                let end = Instant::now() + end.saturating_duration_since(self.receiver.now());
+                if self.deadline_wake.deadline() != end {
+                    self.deadline_wake.as_mut().reset(end);
+                }
+                // Registers the waker, if the deadline passed since the timeouts were checked then check again straight away.
+                if self.deadline_wake.as_mut().poll(cx).is_ready() {
+                    continue;
+                }
+            }
+
+            if let Some(event) = self.receiver.pending_events.pop_front() {
+                return Poll::Ready(Ok(event));
+            }
+
+            if let Err(e) = self.add_new_sockets() {
+                return Poll::Ready(Err(e));
+            }
+
+            // Polls every socket, starting from a different one each time, so that the waker is registered with all of them.
+            let start = self.next_socket % self.sockets.len();
+            let mut ready = None;
+            for i in (start..self.sockets.len()).chain(0..start) {
+                match self.sockets[i].poll_recv_ready(cx) {
+                    Poll::Pending => {}
+                    Poll::Ready(Err(e)) => return Poll::Ready(Err(e.into())),
+                    Poll::Ready(Ok(())) => {
+                        ready = Some(i);
+                        break;
+                    }
+                }
+            }
+            let Some(i) = ready else {
+                return Poll::Pending;
+            };
+            self.next_socket = i + 1;
+
+            // Received on the receiver's socket, which shares the underlying socket, so that where the packet came from is known.
+            let socket = self.receiver.receiver.sockets().nth(i).unwrap();
+            let (len, origin) = match self.sockets[i].try_io(Interest::READABLE, || {
+                recv_with_origin(socket, &mut self.buf[..])
+            }) {
+                Err(e) if e.kind() == io::ErrorKind::WouldBlock => continue,
+                Err(e) => return Poll::Ready(Err(e.into())),
+                Ok(res) => res,
+            };
+
+            let res = AcnRootLayerProtocol::parse(&self.buf[..len])
+                .and_then(|pkt| self.receiver.handle_packet(pkt, origin));
+
+            match res {
+                // Queued behind any events caused by the same packet.
+                Ok(Some(data)) => self
+                    .receiver
+                    .pending_events
+                    .push_back(ReceiveEvent::Data(data)),
+                Ok(None) => {}
+                Err(e) => return Poll::Ready(Err(e)),
+            }
+        }
+    }
+}
+
+/// Allows the receiver to be used as a never ending stream of receive events.
+impl Stream for AsyncSacnReceiver {
+    type Item = Result<ReceiveEvent>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.get_mut().poll_recv_event(cx).map(Some)
+    }
+}
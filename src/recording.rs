@@ -0,0 +1,909 @@
+// Copyright 2020 sacn Developers
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! Recording the DMX data received by a receiver to a file and playing it back out through a source.
+//!
+//! A [`Recorder`] writes each frame of data returned by (SacnReceiver::recv)[crate::receive::SacnReceiver::recv] along with when it
+//! was received, its universe, start code, priority, source CID and synchronisation universe. A [`Recording`] reads the file back and a
+//! [`Player`] streams it out through a (SacnSource)[crate::source::SacnSource] with the original timing, optionally looping, seeking,
+//! remapping universes or changing speed.
+//!
+//! Data received together, e.g. a set of universes released by the same synchronisation packet, is recorded with the same timestamp and
+//! played back together, followed by a synchronisation packet for each synchronisation universe it was waiting for.
+//!
+//! # File format
+//! All values are big endian. The file starts with the 8 byte magic `sACNshow` followed by a 1 byte version, currently 1. This is followed
+//! by records, each starting with a 1 byte tag:
+//!
+//! - Frame (1): timestamp in microseconds since the start of the recording (u64), universe (u16), synchronisation universe (u16),
+//!   priority (u8), flags (u8, bit 0 preview, bit 1 CID present), start code (u8), slot count (u16), the CID (16 bytes) if present and
+//!   then the slot values.
+//! - End (2): the length of the recording in microseconds (u64), written when the recorder is finished.
+//!
+//! A recording without an end record, e.g. because the recorder was never finished, is still read and ends with its last frame.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use sacn::receive::SacnReceiver;
+//! use sacn::recording::{Player, Recorder, Recording};
+//! use sacn::source::SacnSource;
+//!
+//! use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+//! use std::time::Duration;
+//!
+//! // Record 10 seconds of universe 1.
+//! let addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::UNSPECIFIED), sacn::packet::ACN_SDT_MULTICAST_PORT);
+//! let mut dmx_rcv = SacnReceiver::with_ip(addr, None).unwrap();
+//! dmx_rcv.listen_universes(&[1]).unwrap();
+//!
+//! let mut recorder = Recorder::create("show.sacn").unwrap();
+//! while recorder.elapsed() < Duration::from_secs(10) {
+//!     recorder.record_from(&mut dmx_rcv, Some(Duration::from_millis(100))).ok();
+//! }
+//! recorder.finish().unwrap();
+//!
+//! // Play it back twice as fast on universe 5, forever.
+//! let mut player = Player::new(Recording::open("show.sacn").unwrap());
+//! player.remap_universe(1, 5).unwrap();
+//! player.set_speed(2.0).unwrap();
+//! player.set_looping(true);
+//!
+//! let mut src = SacnSource::new_v4("Show Player").unwrap();
+//! player.play(&mut src).unwrap();
+//! ```
+
+use crate::clock::{Clock, SystemClock};
+use crate::error::errors::*;
+use crate::packet::{NO_SYNC_UNIVERSE, UNIVERSE_CHANNEL_CAPACITY, is_universe_in_range};
+use crate::receive::{DMXData, SacnReceiver};
+use crate::source::SacnSource;
+
+use byteorder::{BigEndian, ReadBytesExt, WriteBytesExt};
+use uuid::Uuid;
+
+use std::collections::HashMap;
+use std::fmt;
+use std::fs::File;
+use std::io::{self, BufReader, BufWriter, Read, Write};
+use std::path::Path;
+use std::sync::Arc;
+use std::thread::sleep;
+use std::time::{Duration, Instant};
+
+/// Magic bytes at the start of a recording.
+const RECORDING_MAGIC: [u8; 8] = *b"sACNshow";
+
+/// Version of the recording format written.
+const RECORDING_VERSION: u8 = 1;
+
+/// Tag of a record holding a frame of data.
+const RECORD_FRAME: u8 = 1;
+
+/// Tag of the record which ends a recording.
+const RECORD_END: u8 = 2;
+
+/// Bit of the flags of a frame record set if the data was preview data.
+const FRAME_FLAG_PREVIEW: u8 = 0x01;
+
+/// Bit of the flags of a frame record set if the record contains the CID of the source.
+const FRAME_FLAG_CID: u8 = 0x02;
+
+/// A frame of data for a single universe within a recording.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RecordedFrame {
+    /// When the data was received, relative to the start of the recording.
+    pub timestamp: Duration,
+
+    /// The universe the data was sent to.
+    pub universe: u16,
+
+    /// The start code of the data, e.g. E131_NULL_START_CODE for DMX levels.
+    pub start_code: u8,
+
+    /// The slot values of the data, not including the start code.
+    pub values: Vec<u8>,
+
+    /// The priority the data was sent with.
+    pub priority: u8,
+
+    /// The CID of the source of the data, None if the data was merged from several sources.
+    pub src_cid: Option<Uuid>,
+
+    /// The universe the data was synchronised by, 0 (NO_SYNC_UNIVERSE) if it wasn't synchronised.
+    pub sync_uni: u16,
+
+    /// True if the data was marked as preview data.
+    pub preview: bool,
+}
+
+impl RecordedFrame {
+    /// Creates a frame from data returned by a receiver.
+    ///
+    /// # Arguments
+    /// data: The received data, the first value of which is the start code.
+    ///
+    /// timestamp: When the data was received, relative to the start of the recording.
+    pub fn from_dmx_data(data: &DMXData, timestamp: Duration) -> RecordedFrame {
+        let (start_code, values) = match data.values.split_first() {
+            Some((start_code, values)) => (*start_code, values.to_vec()),
+            None => (0, Vec::new()),
+        };
+        RecordedFrame {
+            timestamp,
+            universe: data.universe,
+            start_code,
+            values,
+            priority: data.priority,
+            src_cid: data.src_cid,
+            sync_uni: data.sync_uni,
+            preview: data.preview,
+        }
+    }
+}
+
+/// Writes the data received by a receiver to a recording, see the (module documentation)[crate::recording].
+///
+/// Timestamps are measured with the recorder's clock from the first data recorded.
+#[derive(Debug)]
+pub struct Recorder<W: Write> {
+    /// Where the recording is written.
+    writer: W,
+
+    /// The clock timestamps are measured with.
+    clock: Arc<dyn Clock>,
+
+    /// When the first data was recorded.
+    start: Option<Instant>,
+
+    /// The latest timestamp of the frames recorded.
+    last_timestamp: Duration,
+
+    /// The number of frames recorded.
+    frames: usize,
+}
+
+impl Recorder<BufWriter<File>> {
+    /// Creates a recorder which writes to a new file at the given path, replacing any existing file.
+    ///
+    /// # Errors
+    /// Io: Returned if the file cannot be created or written to.
+    pub fn create<P: AsRef<Path>>(path: P) -> Result<Recorder<BufWriter<File>>> {
+        Recorder::new(BufWriter::new(File::create(path)?))
+    }
+}
+
+impl<W: Write> Recorder<W> {
+    /// Creates a recorder which writes to the given writer, writing the header of the recording immediately.
+    ///
+    /// # Errors
+    /// Io: Returned if the header cannot be written.
+    pub fn new(mut writer: W) -> Result<Recorder<W>> {
+        writer.write_all(&RECORDING_MAGIC)?;
+        writer.write_u8(RECORDING_VERSION)?;
+        Ok(Recorder {
+            writer,
+            clock: Arc::new(SystemClock),
+            start: None,
+            last_timestamp: Duration::ZERO,
+            frames: 0,
+        })
+    }
+
+    /// Sets the clock that timestamps are measured with. By default this is the (SystemClock)[SystemClock].
+    ///
+    /// The clock should be set before recording, e.g. to the clock of a receiver replaying a capture so the recording follows the capture
+    /// timestamps.
+    ///
+    /// # Arguments
+    /// clock: The clock to use.
+    pub fn set_clock<C: Clock + 'static>(&mut self, clock: C) {
+        self.clock = Arc::new(clock);
+    }
+
+    /// Records the given data, as returned together by a receiver, with the time since the first data was recorded.
+    ///
+    /// # Arguments
+    /// data: The data to record, each element is recorded as a frame with the same timestamp.
+    ///
+    /// # Errors
+    /// ExceedUniverseCapacity: Returned if the data of a universe has more values than fit in a universe.
+    ///
+    /// Io: Returned if the data cannot be written.
+    pub fn record(&mut self, data: &[DMXData]) -> Result<()> {
+        if data.is_empty() {
+            return Ok(());
+        }
+
+        let now = self.clock.now();
+        let timestamp = now.saturating_duration_since(*self.start.get_or_insert(now));
+        for d in data {
+            self.record_frame(&RecordedFrame::from_dmx_data(d, timestamp))?;
+        }
+        Ok(())
+    }
+
+    /// Receives data from the given receiver, records it and returns it.
+    ///
+    /// # Arguments
+    /// receiver: The receiver to receive from.
+    ///
+    /// timeout: The timeout passed to (SacnReceiver::recv)[crate::receive::SacnReceiver::recv].
+    ///
+    /// # Errors
+    /// Any error returned by (SacnReceiver::recv)[crate::receive::SacnReceiver::recv], or by (record)[Recorder::record].
+    pub fn record_from(
+        &mut self,
+        receiver: &mut SacnReceiver,
+        timeout: Option<Duration>,
+    ) -> Result<Vec<DMXData>> {
+        let data = receiver.recv(timeout)?;
+        self.record(&data)?;
+        Ok(data)
+    }
+
+    /// Records a frame with its own timestamp, e.g. when converting another recording format.
+    ///
+    /// Frames are sorted by timestamp when the recording is read so they don't have to be recorded in order.
+    ///
+    /// # Errors
+    /// ExceedUniverseCapacity: Returned if the frame has more values than fit in a universe.
+    ///
+    /// Io: Returned if the frame cannot be written.
+    pub fn record_frame(&mut self, frame: &RecordedFrame) -> Result<()> {
+        if frame.values.len() >= UNIVERSE_CHANNEL_CAPACITY {
+            return Err(SacnError::ExceedUniverseCapacity(frame.values.len() + 1));
+        }
+
+        let mut flags = 0;
+        if frame.preview {
+            flags |= FRAME_FLAG_PREVIEW;
+        }
+        if frame.src_cid.is_some() {
+            flags |= FRAME_FLAG_CID;
+        }
+
+        self.writer.write_u8(RECORD_FRAME)?;
+        self.writer
+            .write_u64::<BigEndian>(frame.timestamp.as_micros() as u64)?;
+        self.writer.write_u16::<BigEndian>(frame.universe)?;
+        self.writer.write_u16::<BigEndian>(frame.sync_uni)?;
+        self.writer.write_u8(frame.priority)?;
+        self.writer.write_u8(flags)?;
+        self.writer.write_u8(frame.start_code)?;
+        self.writer
+            .write_u16::<BigEndian>(frame.values.len() as u16)?;
+        if let Some(cid) = frame.src_cid {
+            self.writer.write_all(cid.as_bytes())?;
+        }
+        self.writer.write_all(&frame.values)?;
+
+        self.last_timestamp = self.last_timestamp.max(frame.timestamp);
+        self.frames += 1;
+        Ok(())
+    }
+
+    /// Returns the time since the first data was recorded, zero if nothing has been recorded yet.
+    pub fn elapsed(&self) -> Duration {
+        self.start.map_or(Duration::ZERO, |s| {
+            self.clock.now().saturating_duration_since(s)
+        })
+    }
+
+    /// Returns the number of frames recorded.
+    pub fn frames_recorded(&self) -> usize {
+        self.frames
+    }
+
+    /// Ends the recording, recording its length as the time since the first data was recorded, and returns the writer once flushed.
+    ///
+    /// # Errors
+    /// Io: Returned if the end of the recording cannot be written.
+    pub fn finish(mut self) -> Result<W> {
+        let length = self.elapsed().max(self.last_timestamp);
+        self.writer.write_u8(RECORD_END)?;
+        self.writer
+            .write_u64::<BigEndian>(length.as_micros() as u64)?;
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+}
+
+/// A recording read into memory, sorted by timestamp.
+#[derive(Clone, Default, PartialEq, Eq)]
+pub struct Recording {
+    /// The frames of the recording in timestamp order.
+    frames: Vec<RecordedFrame>,
+
+    /// The length of the recording, at least the timestamp of the last frame.
+    length: Duration,
+}
+
+impl Recording {
+    /// Reads the recording at the given path.
+    ///
+    /// # Errors
+    /// See (read)[Recording::read].
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Recording> {
+        Recording::read(BufReader::new(File::open(path)?))
+    }
+
+    /// Reads a recording from the given reader.
+    ///
+    /// # Errors
+    /// InvalidRecording: Returned if the reader doesn't contain a recording, the recording is a newer version or a record is corrupt.
+    ///
+    /// Io: Returned if reading fails.
+    pub fn read<R: Read>(mut reader: R) -> Result<Recording> {
+        let mut magic = [0; 8];
+        reader
+            .read_exact(&mut magic)
+            .map_err(|e| truncated(e, "header"))?;
+        if magic != RECORDING_MAGIC {
+            return Err(SacnError::InvalidRecording(
+                "Not an sACN recording".to_string(),
+            ));
+        }
+        let version = reader.read_u8().map_err(|e| truncated(e, "header"))?;
+        if version != RECORDING_VERSION {
+            return Err(SacnError::InvalidRecording(format!(
+                "Unsupported version {version}"
+            )));
+        }
+
+        let mut frames = Vec::new();
+        let mut length = Duration::ZERO;
+        loop {
+            let tag = match reader.read_u8() {
+                Ok(tag) => tag,
+                // A recording which was never finished.
+                Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => break,
+                Err(e) => return Err(e.into()),
+            };
+            match tag {
+                RECORD_FRAME => {
+                    frames.push(read_frame(&mut reader).map_err(|e| truncated(e, "frame"))?)
+                }
+                RECORD_END => {
+                    let micros = reader
+                        .read_u64::<BigEndian>()
+                        .map_err(|e| truncated(e, "end"))?;
+                    length = Duration::from_micros(micros);
+                    break;
+                }
+                _ => {
+                    return Err(SacnError::InvalidRecording(format!(
+                        "Unknown record tag {tag}"
+                    )));
+                }
+            }
+        }
+
+        Ok(Recording::from_frames(frames).with_length(length))
+    }
+
+    /// Creates a recording of the given frames, sorting them by timestamp. The length is the timestamp of the last frame.
+    pub fn from_frames(mut frames: Vec<RecordedFrame>) -> Recording {
+        frames.sort_by_key(|f| f.timestamp);
+        let length = frames.last().map_or(Duration::ZERO, |f| f.timestamp);
+        Recording { frames, length }
+    }
+
+    /// Returns the recording with the given length, or the timestamp of the last frame if that is later.
+    fn with_length(mut self, length: Duration) -> Recording {
+        self.length = self.length.max(length);
+        self
+    }
+
+    /// Returns the frames of the recording in timestamp order.
+    pub fn frames(&self) -> &[RecordedFrame] {
+        &self.frames
+    }
+
+    /// Returns the length of the recording.
+    pub fn duration(&self) -> Duration {
+        self.length
+    }
+
+    /// Returns the universes which have data in the recording, in ascending order.
+    pub fn universes(&self) -> Vec<u16> {
+        let mut universes: Vec<u16> = self.frames.iter().map(|f| f.universe).collect();
+        universes.sort_unstable();
+        universes.dedup();
+        universes
+    }
+}
+
+impl fmt::Debug for Recording {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Recording")
+            .field("frames", &self.frames.len())
+            .field("length", &self.length)
+            .finish()
+    }
+}
+
+/// Plays a recording out through a source, see the (module documentation)[crate::recording].
+///
+/// Frames are sent at the time after the start of playback that they were recorded at, divided by the speed. The universes sent on
+/// are registered on the source as they are needed. The preview mode of the source is changed to match each frame.
+pub struct Player {
+    /// The recording being played.
+    recording: Recording,
+
+    /// The index of the next frame to send.
+    next: usize,
+
+    /// The position in the recording when playback was last anchored, used while not playing.
+    position: Duration,
+
+    /// The real time and position in the recording that playback is measured from, None until playback starts.
+    anchor: Option<(Instant, Duration)>,
+
+    /// True if playback restarts at the beginning once the end of the recording is reached.
+    looping: bool,
+
+    /// How many times faster than recorded the recording is played.
+    speed: f64,
+
+    /// The universe each recorded universe is sent on instead, if it is remapped.
+    remap: HashMap<u16, u16>,
+
+    /// The CID of the only source whose frames are played, None to play the frames of every source.
+    source_filter: Option<Uuid>,
+
+    /// The clock playback is timed with.
+    clock: Arc<dyn Clock>,
+
+    /// Reused buffer holding the start code and values of the frame being sent.
+    buf: Vec<u8>,
+}
+
+impl Player {
+    /// Creates a player which plays the given recording from the start at its original speed, once.
+    pub fn new(recording: Recording) -> Player {
+        Player {
+            recording,
+            next: 0,
+            position: Duration::ZERO,
+            anchor: None,
+            looping: false,
+            speed: 1.0,
+            remap: HashMap::new(),
+            source_filter: None,
+            clock: Arc::new(SystemClock),
+            buf: Vec::with_capacity(UNIVERSE_CHANNEL_CAPACITY),
+        }
+    }
+
+    /// Returns the recording being played.
+    pub fn recording(&self) -> &Recording {
+        &self.recording
+    }
+
+    /// Sets if playback restarts at the beginning once the end of the recording is reached.
+    pub fn set_looping(&mut self, looping: bool) {
+        self.looping = looping;
+    }
+
+    /// Returns true if playback restarts at the beginning once the end of the recording is reached.
+    pub fn looping(&self) -> bool {
+        self.looping
+    }
+
+    /// Sets how many times faster than recorded the recording is played, e.g. 0.5 plays at half speed. Takes effect from the current
+    /// position.
+    ///
+    /// # Errors
+    /// InvalidPlaybackSpeed: Returned if the speed isn't a finite number greater than 0.
+    pub fn set_speed(&mut self, speed: f64) -> Result<()> {
+        if !(speed.is_finite() && speed > 0.0) {
+            return Err(SacnError::InvalidPlaybackSpeed(speed));
+        }
+        self.reanchor(self.position());
+        self.speed = speed;
+        Ok(())
+    }
+
+    /// Returns how many times faster than recorded the recording is played.
+    pub fn speed(&self) -> f64 {
+        self.speed
+    }
+
+    /// Sends the data recorded on one universe on another instead. Synchronisation universes are remapped in the same way.
+    ///
+    /// # Arguments
+    /// from: The universe in the recording.
+    ///
+    /// to: The universe to send its data on.
+    ///
+    /// # Errors
+    /// IllegalUniverse: Returned if either universe is outwith the allowed range of sACN universes as defined in ANSI E1.31-2018 Section 6.2.7.
+    pub fn remap_universe(&mut self, from: u16, to: u16) -> Result<()> {
+        is_universe_in_range(from)?;
+        is_universe_in_range(to)?;
+        self.remap.insert(from, to);
+        Ok(())
+    }
+
+    /// Removes all universe remapping, so data is sent on the universes it was recorded on.
+    pub fn clear_remapping(&mut self) {
+        self.remap.clear();
+    }
+
+    /// Only plays the frames recorded from the source with the given CID, as the player sends every frame with the CID of its source.
+    ///
+    /// # Arguments
+    /// cid: The CID of the source to play, None plays every frame.
+    pub fn set_source_filter(&mut self, cid: Option<Uuid>) {
+        self.source_filter = cid;
+    }
+
+    /// Sets the clock that playback is timed with. By default this is the (SystemClock)[SystemClock].
+    ///
+    /// # Arguments
+    /// clock: The clock to use.
+    pub fn set_clock<C: Clock + 'static>(&mut self, clock: C) {
+        let position = self.position();
+        self.clock = Arc::new(clock);
+        self.reanchor(position);
+    }
+
+    /// Moves playback to the given position in the recording, the next frame sent is the first recorded at or after it.
+    ///
+    /// # Arguments
+    /// position: The time from the start of the recording, positions after the end move to the end.
+    pub fn seek(&mut self, position: Duration) {
+        let position = position.min(self.recording.duration());
+        self.next = self
+            .recording
+            .frames
+            .partition_point(|f| f.timestamp < position);
+        self.reanchor(position);
+    }
+
+    /// Returns the current position of playback in the recording.
+    pub fn position(&self) -> Duration {
+        match self.anchor {
+            Some((anchor, position)) => (position
+                + self
+                    .clock
+                    .now()
+                    .saturating_duration_since(anchor)
+                    .mul_f64(self.speed))
+            .min(self.recording.duration()),
+            None => self.position,
+        }
+    }
+
+    /// Returns true if every frame has been played and playback isn't looping.
+    pub fn is_finished(&self) -> bool {
+        !self.looping && self.next >= self.recording.frames.len()
+    }
+
+    /// Sends every frame which is due, starting playback if this is the first call since creation or seeking.
+    ///
+    /// Frames recorded with the same timestamp are sent together, followed by a synchronisation packet for each synchronisation universe
+    /// they were waiting for.
+    ///
+    /// # Arguments
+    /// source: The source to send the frames with.
+    ///
+    /// # Errors
+    /// Any error returned by the source when registering universes or sending, see (SacnSource::send)[crate::source::SacnSource::send].
+    ///
+    /// # Returns
+    /// The real time until this should next be called, or None once playback has finished.
+    pub fn update(&mut self, source: &mut SacnSource) -> Result<Option<Duration>> {
+        let now = self.clock.now();
+        let (mut anchor, mut anchor_position) = *self.anchor.get_or_insert((now, self.position));
+        let mut position =
+            anchor_position + now.saturating_duration_since(anchor).mul_f64(self.speed);
+        let length = self.recording.duration();
+        let mut wrapped = false;
+
+        loop {
+            while let Some(frame) = self.recording.frames.get(self.next)
+                && frame.timestamp <= position
+            {
+                self.send_batch(source, frame.timestamp)?;
+            }
+
+            if self.next < self.recording.frames.len() {
+                break;
+            }
+            if !self.looping || length.is_zero() {
+                self.anchor = None;
+                self.position = length;
+                return Ok(None);
+            }
+            // Only loop once per call so a call made long after the last always returns.
+            if position < length || wrapped {
+                break;
+            }
+
+            anchor += (length - anchor_position).div_f64(self.speed);
+            anchor_position = Duration::ZERO;
+            self.anchor = Some((anchor, anchor_position));
+            position = now.saturating_duration_since(anchor).mul_f64(self.speed);
+            self.next = 0;
+            wrapped = true;
+        }
+
+        let due = self
+            .recording
+            .frames
+            .get(self.next)
+            .map_or(length, |f| f.timestamp);
+        Ok(Some(due.saturating_sub(position).div_f64(self.speed)))
+    }
+
+    /// Plays the recording, blocking until playback has finished. If looping this only returns on error.
+    ///
+    /// # Errors
+    /// See (update)[Player::update].
+    pub fn play(&mut self, source: &mut SacnSource) -> Result<()> {
+        while let Some(wait) = self.update(source)? {
+            sleep(wait);
+        }
+        Ok(())
+    }
+
+    /// Restarts the timing of playback from the given position, keeping playback stopped if it hasn't started.
+    fn reanchor(&mut self, position: Duration) {
+        self.position = position;
+        if self.anchor.is_some() {
+            self.anchor = Some((self.clock.now(), position));
+        }
+    }
+
+    /// Returns the universe that data recorded on the given universe is sent on.
+    fn remapped(&self, universe: u16) -> u16 {
+        self.remap.get(&universe).copied().unwrap_or(universe)
+    }
+
+    /// Sends the frames with the given timestamp starting from the next frame, followed by their synchronisation packets.
+    fn send_batch(&mut self, source: &mut SacnSource, timestamp: Duration) -> Result<()> {
+        let mut sync_universes: Vec<u16> = Vec::new();
+        while let Some(frame) = self.recording.frames.get(self.next)
+            && frame.timestamp == timestamp
+        {
+            self.next += 1;
+            if self
+                .source_filter
+                .is_some_and(|cid| frame.src_cid != Some(cid))
+            {
+                continue;
+            }
+
+            let universe = self.remapped(frame.universe);
+            let sync_uni =
+                (frame.sync_uni != NO_SYNC_UNIVERSE).then(|| self.remapped(frame.sync_uni));
+            source.register_universe(universe)?;
+            if let Some(sync_uni) = sync_uni {
+                source.register_universe(sync_uni)?;
+                if !sync_universes.contains(&sync_uni) {
+                    sync_universes.push(sync_uni);
+                }
+            }
+            if source.preview_mode()? != frame.preview {
+                source.set_preview_mode(frame.preview)?;
+            }
+
+            self.buf.clear();
+            self.buf.push(frame.start_code);
+            self.buf.extend_from_slice(&frame.values);
+            source.send(&[universe], &self.buf, Some(frame.priority), None, sync_uni)?;
+        }
+
+        for sync_uni in sync_universes {
+            source.send_sync_packet(sync_uni, None)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Debug for Player {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Player")
+            .field("recording", &self.recording)
+            .field("position", &self.position())
+            .field("looping", &self.looping)
+            .field("speed", &self.speed)
+            .field("remap", &self.remap)
+            .field("source_filter", &self.source_filter)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Reads the rest of a frame record after its tag.
+fn read_frame<R: Read>(reader: &mut R) -> io::Result<RecordedFrame> {
+    let timestamp = Duration::from_micros(reader.read_u64::<BigEndian>()?);
+    let universe = reader.read_u16::<BigEndian>()?;
+    let sync_uni = reader.read_u16::<BigEndian>()?;
+    let priority = reader.read_u8()?;
+    let flags = reader.read_u8()?;
+    let start_code = reader.read_u8()?;
+    let slots = reader.read_u16::<BigEndian>()? as usize;
+    if slots >= UNIVERSE_CHANNEL_CAPACITY {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("Frame has {slots} slots"),
+        ));
+    }
+
+    let src_cid = if flags & FRAME_FLAG_CID != 0 {
+        let mut cid = [0; 16];
+        reader.read_exact(&mut cid)?;
+        Some(Uuid::from_bytes(cid))
+    } else {
+        None
+    };
+    let mut values = vec![0; slots];
+    reader.read_exact(&mut values)?;
+
+    Ok(RecordedFrame {
+        timestamp,
+        universe,
+        start_code,
+        values,
+        priority,
+        src_cid,
+        sync_uni,
+        preview: flags & FRAME_FLAG_PREVIEW != 0,
+    })
+}
+
+/// Converts an error reading the given part of a recording into an InvalidRecording error if the recording was cut short or corrupt.
+fn truncated(e: io::Error, part: &str) -> SacnError {
+    match e.kind() {
+        io::ErrorKind::UnexpectedEof => {
+            SacnError::InvalidRecording(format!("Recording ends part way through a {part}"))
+        }
+        io::ErrorKind::InvalidData => SacnError::InvalidRecording(format!("Corrupt {part}: {e}")),
+        _ => e.into(),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::clock::ManualClock;
+
+    fn frame(timestamp_ms: u64, universe: u16, values: &[u8]) -> RecordedFrame {
+        RecordedFrame {
+            timestamp: Duration::from_millis(timestamp_ms),
+            universe,
+            start_code: 0,
+            values: values.to_vec(),
+            priority: 100,
+            src_cid: None,
+            sync_uni: 0,
+            preview: false,
+        }
+    }
+
+    /// Frames written by a recorder should be read back the same, sorted by timestamp, with the length of the recording.
+    #[test]
+    fn test_recording_round_trip() {
+        let clock = ManualClock::new();
+        let mut recorder = Recorder::new(Vec::new()).unwrap();
+        recorder.set_clock(clock.clone());
+
+        let first = RecordedFrame {
+            start_code: 0xDD,
+            src_cid: Some(Uuid::from_bytes([3; 16])),
+            sync_uni: 4,
+            preview: true,
+            ..frame(20, 2, &[100; 512])
+        };
+        recorder.record_frame(&frame(500, 1, &[])).unwrap();
+        recorder.record_frame(&first).unwrap();
+        assert!(matches!(
+            recorder.record_frame(&frame(0, 1, &[0; 513])),
+            Err(SacnError::ExceedUniverseCapacity(514))
+        ));
+        assert_eq!(recorder.frames_recorded(), 2);
+
+        let recording = Recording::read(recorder.finish().unwrap().as_slice()).unwrap();
+        assert_eq!(recording.frames(), &[first, frame(500, 1, &[])]);
+        assert_eq!(recording.duration(), Duration::from_millis(500));
+        assert_eq!(recording.universes(), vec![1, 2]);
+    }
+
+    /// Recorded data should be timestamped from the first data recorded and the length should run until the recording was finished.
+    #[test]
+    fn test_recorder_timestamps() {
+        let clock = ManualClock::new();
+        let mut recorder = Recorder::new(Vec::new()).unwrap();
+        recorder.set_clock(clock.clone());
+
+        let data = DMXData {
+            universe: 1,
+            values: vec![0, 1, 2],
+            sync_uni: 0,
+            priority: 100,
+            src_cid: Some(Uuid::from_bytes([1; 16])),
+            preview: false,
+            src_addr: None,
+            src_name: None,
+            recv_interface: None,
+            recv_timestamp: clock.now(),
+        };
+        clock.advance(Duration::from_secs(5));
+        recorder.record(&[data.clone(), data.clone()]).unwrap();
+        clock.advance(Duration::from_millis(250));
+        recorder.record(std::slice::from_ref(&data)).unwrap();
+        clock.advance(Duration::from_millis(750));
+        assert_eq!(recorder.elapsed(), Duration::from_secs(1));
+
+        let recording = Recording::read(recorder.finish().unwrap().as_slice()).unwrap();
+        let timestamps: Vec<Duration> = recording.frames().iter().map(|f| f.timestamp).collect();
+        assert_eq!(
+            timestamps,
+            vec![Duration::ZERO, Duration::ZERO, Duration::from_millis(250)]
+        );
+        assert_eq!(recording.frames()[0].values, vec![1, 2]);
+        assert_eq!(recording.frames()[0].src_cid, data.src_cid);
+        assert_eq!(recording.duration(), Duration::from_secs(1));
+    }
+
+    /// Unfinished recordings should be read up to their last frame while other files and corrupt recordings should give an InvalidRecording error.
+    #[test]
+    fn test_read_invalid_recordings() {
+        let mut recorder = Recorder::new(Vec::new()).unwrap();
+        recorder.record_frame(&frame(10, 1, &[1, 2, 3])).unwrap();
+        let unfinished = recorder.writer.clone();
+        let recording = Recording::read(unfinished.as_slice()).unwrap();
+        assert_eq!(recording.frames().len(), 1);
+        assert_eq!(recording.duration(), Duration::from_millis(10));
+
+        let truncated = &unfinished[..unfinished.len() - 1];
+        let mut too_many_slots = unfinished.clone();
+        too_many_slots[24..26].copy_from_slice(&513u16.to_be_bytes());
+        let mut unknown_record = unfinished.clone();
+        unknown_record.push(9);
+        for invalid in [
+            &b"sACNshox\x01"[..],
+            &b"sACNshow\x02"[..],
+            &b"sACN"[..],
+            truncated,
+            &too_many_slots,
+            &unknown_record,
+        ] {
+            assert!(matches!(
+                Recording::read(invalid),
+                Err(SacnError::InvalidRecording(_))
+            ));
+        }
+    }
+
+    /// Invalid speeds should be rejected and seeking should move to the first frame at or after the position.
+    #[test]
+    fn test_player_speed_and_seek() {
+        let recording = Recording::from_frames(vec![frame(2000, 1, &[2]), frame(1000, 1, &[1])]);
+        let mut player = Player::new(recording);
+        for speed in [0.0, -1.0, f64::NAN, f64::INFINITY] {
+            assert!(matches!(
+                player.set_speed(speed),
+                Err(SacnError::InvalidPlaybackSpeed(_))
+            ));
+        }
+        player.set_speed(0.5).unwrap();
+        assert_eq!(player.speed(), 0.5);
+
+        player.seek(Duration::from_millis(1500));
+        assert_eq!(player.next, 1);
+        assert_eq!(player.position(), Duration::from_millis(1500));
+        player.seek(Duration::from_secs(10));
+        assert_eq!(player.position(), Duration::from_secs(2));
+        // The last frame is at the end so is still sent.
+        assert_eq!(player.next, 1);
+        assert!(!player.is_finished());
+        assert!(player.remap_universe(1, 0).is_err());
+    }
+}
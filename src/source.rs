@@ -14,25 +14,35 @@
 // of each public item without relying on referring to private items.
 //
 
+use crate::clock::{Clock, SystemClock};
 use crate::error::errors::*;
+use crate::interface::NetworkInterface;
 use crate::packet::*;
+use crate::transport::Transport;
 
 use std::cell::RefCell;
 use std::cmp;
 use std::cmp::min;
-use std::collections::HashMap;
-use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV6};
 use std::sync::{Arc, Mutex, MutexGuard};
 use std::thread;
 use std::thread::JoinHandle;
 use std::time::{Duration, Instant};
 
 /// Socket2 used to create the underlying UDP socket that sACN is sent on.
-use socket2::{Domain, Socket, Type};
+use socket2::{Domain, SockAddr, Socket, Type};
 
 /// UUID library used to handle the UUID's used in the CID fields.
 use uuid::Uuid;
 
+/// Asynchronous sending built on tokio, only available with the async feature.
+#[cfg(feature = "async")]
+mod asynchronous;
+
+#[cfg(feature = "async")]
+pub use asynchronous::AsyncSacnSource;
+
 /// The name of the thread which runs periodically to perform various actions such as universe discovery adverts for the source.
 const SND_UPDATE_THREAD_NAME: &str = "rust_sacn_snd_update_thread";
 
@@ -44,6 +54,9 @@ const DEFAULT_TERMINATE_START_CODE: u8 = 0;
 // const DEFAULT_POLL_PERIOD: Duration = E131_UNIVERSE_DISCOVERY_INTERVAL;
 const DEFAULT_POLL_PERIOD: Duration = Duration::from_secs(1);
 
+/// The default rate (frames per second) at which the universe levels set on a source are sent, see (SacnSource::set_universe_levels).
+const DEFAULT_REFRESH_RATE: u32 = DMX512_MAX_REFRESH_RATE;
+
 /// A DMX over sACN sender.
 ///
 /// SacnSource is used for sending sACN packets over an IP network.
@@ -82,20 +95,107 @@ pub struct SacnSource {
     internal: Arc<Mutex<SacnSourceInternal>>,
 
     /// Update thread which performs actions every DEFAULT_POLL_PERIOD such as checking if a universe
-    /// discovery packet should be sent.
+    /// discovery packet should be sent, and sends the universe levels every refresh period.
     update_thread: Option<JoinHandle<()>>,
 }
 
+/// Packed packets along with the address to send them to, queued by a SacnSourceInternal to be sent later.
+type QueuedPackets = Vec<(Vec<u8>, SockAddr)>;
+
+/// The universe and start code that a packed data packet is kept for by a SacnSourceInternal.
+type DataPacketKey = (u16, u8);
+
+/// A packed data packet kept by a SacnSourceInternal so that following packets for the same universe and start code only need their
+/// sequence number and levels updating rather than the whole packet packing again.
+#[derive(Debug)]
+struct DataPacketBuffer {
+    /// The priority the packet was packed with.
+    priority: u8,
+
+    /// The synchronisation address the packet was packed with.
+    sync_address: u16,
+
+    /// The packed packet.
+    packet: Vec<u8>,
+}
+
+/// How often the levels of a universe are sent by a source, see (SacnSource::set_transmission_policy)[SacnSource::set_transmission_policy].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TransmissionPolicy {
+    /// Send the levels every refresh period whether or not they have changed.
+    #[default]
+    Continuous,
+
+    /// Once the levels stop changing send E131_KEEP_ALIVE_REPEAT_COUNT more copies of the last packet every refresh period and then reduce
+    /// to sending one packet per interval until the levels change, as per ANSI E1.31-2018 Section 6.6.2.
+    ///
+    /// # Arguments
+    /// Duration: The keep-alive interval, must be within [E131_KEEP_ALIVE_MIN_INTERVAL, E131_KEEP_ALIVE_MAX_INTERVAL].
+    KeepAlive(Duration),
+}
+
+/// A universe whose levels are sent periodically by the update thread, see (SacnSource::set_universe_levels)[SacnSource::set_universe_levels].
+#[derive(Debug, Default)]
+struct RefreshUniverse {
+    /// The data (including the NULL START code) sent every refresh period, None if the levels haven't been set.
+    data: Option<Vec<u8>>,
+
+    /// The priority the data is sent with, None to use E131_DEFAULT_PRIORITY.
+    priority: Option<u8>,
+
+    /// The destination the data is sent to, None to use multicast.
+    dst_ip: Option<SocketAddr>,
+
+    /// The synchronisation address the data is sent with, None for no synchronisation.
+    synchronisation_addr: Option<u16>,
+
+    /// How often the data is sent.
+    policy: TransmissionPolicy,
+
+    /// The number of times the data has been sent since it last changed.
+    unchanged_sends: usize,
+
+    /// The time the data was last sent, None if it hasn't been sent.
+    last_sent: Option<Instant>,
+}
+
+impl RefreshUniverse {
+    /// Returns true if the data should be sent in the frame at the given time.
+    fn is_due(&self, now: Instant) -> bool {
+        if self.data.is_none() {
+            return false;
+        }
+
+        match (self.policy, self.last_sent) {
+            (TransmissionPolicy::KeepAlive(interval), Some(last_sent)) => {
+                // The first send of the data and the required number of copies are sent every frame.
+                self.unchanged_sends <= E131_KEEP_ALIVE_REPEAT_COUNT
+                    || now.duration_since(last_sent) >= interval
+            }
+            _ => true,
+        }
+    }
+}
+
 /// Internal sACN sender, this does most of the work however is encapsulated within SacnSource
 /// to allow access by the update_thread which is used to manage sending periodic universe discovery packets.
 #[derive(Debug)]
 struct SacnSourceInternal {
-    /// Underlying UDP socket used for sending sACN packets on the network.
-    socket: Socket,
+    /// Underlying UDP socket used for sending sACN packets on the network, None if the source sends using a transport instead.
+    socket: Option<Socket>,
+
+    /// If Some then every packet is sent using this transport rather than a socket, see (SacnSource::with_transport)[SacnSource::with_transport].
+    transport: Option<Box<dyn Transport>>,
 
     /// The address of this SacnSourceInternal on the network.
     addr: SocketAddr,
 
+    /// The network interfaces that packets are sent on, empty if the operating system chooses the interface.
+    interfaces: Vec<NetworkInterface>,
+
+    /// A socket bound to each of the interfaces above, if not empty then packets are sent on every one of these instead of on the socket.
+    interface_sockets: Vec<Socket>,
+
     /// The unique ID of this SacnSourceInternal.
     /// It is the job of the user of the library to ensure that the cid is given on creation of the SacnSourceInternal is unique.
     cid: Uuid,
@@ -116,6 +216,14 @@ struct SacnSourceInternal {
     /// Sequence numbers are always in the range [0, 255].
     sync_sequences: RefCell<HashMap<u16, u8>>,
 
+    /// The most recently sent data packet for each universe and start code, reused for the next packet unless a field other than the
+    /// sequence number or levels has changed. Cleared whenever a field shared by all packets, such as the source name, changes.
+    data_packets: RefCell<HashMap<DataPacketKey, DataPacketBuffer>>,
+
+    /// The data packets waiting to be sent along with their destination, these are sent together by flush_data_packets so that on Linux
+    /// the packets for many universes are sent with a single system call.
+    pending_data_packets: RefCell<Vec<(DataPacketKey, SockAddr)>>,
+
     /// A list of the universes registered to send by this source, used for universe discovery.
     /// Always sorted with lowest universe first to allow quicker usage.
     /// This may never contain duplicate universe values.
@@ -129,6 +237,29 @@ struct SacnSourceInternal {
 
     /// Flag that is set to True to indicate that the source is sending periodic universe discovery packets.
     is_sending_discovery: bool,
+
+    /// The per-address priorities (including the 0xDD start code) that are sent alongside the DMX data for each universe.
+    /// Universes without an entry don't send per-address priority packets.
+    per_address_priorities: HashMap<u16, Vec<u8>>,
+
+    /// The universes for which data packets are sent with the Force_Synchronization option bit set, see ANSI E1.31-2018 Section 6.2.6.
+    force_synchronization: HashSet<u16>,
+
+    /// If Some then packets are queued here with their destination instead of being sent on the socket, this is used by the async source
+    /// which sends the queued packets on its own socket.
+    queued_packets: RefCell<Option<QueuedPackets>>,
+
+    /// The universes which are sent periodically by the update thread, ordered by universe.
+    refresh_universes: BTreeMap<u16, RefreshUniverse>,
+
+    /// The time between sending the refresh universes.
+    refresh_period: Duration,
+
+    /// The time that the refresh universes were last sent.
+    last_refresh_timestamp: Instant,
+
+    /// The clock that the discovery interval and refresh period are measured with, see (SacnSource::set_clock)[SacnSource::set_clock].
+    clock: Arc<dyn Clock>,
 }
 
 impl SacnSource {
@@ -200,17 +331,59 @@ impl SacnSource {
             ));
         }
 
+        SacnSource::from_internal(SacnSourceInternal::with_cid_ip(name, cid, ip)?)
+    }
+
+    /// Constructs a new SacnSource with the given name which sends every packet using the given transport instead of a UDP socket,
+    /// e.g. a (MemoryTransport)[crate::transport::MemoryTransport] so that sources and receivers can be tested without a network.
+    /// This generates a new CID automatically using random values.
+    ///
+    /// The source uses the IP version of the address the transport is bound to. Options of the UDP socket, such as the time to live,
+    /// and network interfaces cannot be set on a source using a transport.
+    ///
+    /// # Errors
+    /// Io: Returned if the address the transport is bound to cannot be determined or if the thread used for sending periodic
+    ///     discovery adverts fails to be created.
+    ///
+    /// MalformedSourceName: Returned if the given source name is longer than the maximum allowed size of E131_SOURCE_NAME_FIELD_LENGTH.
+    pub fn with_transport<T: Transport + 'static>(name: &str, transport: T) -> Result<SacnSource> {
+        if name.len() > E131_SOURCE_NAME_FIELD_LENGTH {
+            return Err(SacnError::MalformedSourceName(
+                "Source name provided is longer than maximum allowed".to_string(),
+            ));
+        }
+
+        SacnSource::from_internal(SacnSourceInternal::with_cid_transport(
+            name,
+            Uuid::new_v4(),
+            Box::new(transport),
+        )?)
+    }
+
+    /// Constructs a new SacnSource around the given internal source, starting the thread which performs its periodic updates.
+    ///
+    /// # Errors
+    /// Io: Returned if the thread fails to be created.
+    fn from_internal(internal: SacnSourceInternal) -> Result<SacnSource> {
         let trd_builder = thread::Builder::new().name(SND_UPDATE_THREAD_NAME.into());
 
-        let internal_src = Arc::new(Mutex::new(SacnSourceInternal::with_cid_ip(name, cid, ip)?));
+        let internal_src = Arc::new(Mutex::new(internal));
 
         let mut trd_src = internal_src.clone();
 
         let src = SacnSource {
             internal: internal_src,
             update_thread: Some(trd_builder.spawn(move || {
-                while trd_src.lock().unwrap().running {
-                    thread::sleep(DEFAULT_POLL_PERIOD);
+                loop {
+                    let delay = {
+                        let src = trd_src.lock().unwrap();
+                        if !src.running {
+                            break;
+                        }
+                        src.time_until_next_update()
+                    };
+                    // Parked rather than slept so that the thread can be woken early when levels are first set or the source is dropped.
+                    thread::park_timeout(delay);
                     match perform_periodic_update(&mut trd_src) {
                         Err(e) => {
                             println!("Periodic error: {e:?}");
@@ -292,6 +465,9 @@ impl SacnSource {
     ///
     /// Note as per ANSI-E1.31-2018 Appendix B.1 it is recommended to have a small delay before sending the follow up sync packet.
     ///
+    /// If per-address priorities have been set for a universe, see (set_per_address_priority)[set_per_address_priority], then a per-address
+    /// priority packet is sent after the DMX data for that universe.
+    ///
     /// # Errors
     /// SenderAlreadyTerminated: Returned if this method is called on an SacnReceiverInternal that has already terminated.
     ///
@@ -326,6 +502,207 @@ impl SacnSource {
         )
     }
 
+    /// Sets the per-address priorities (start code 0xDD) to send alongside the DMX data for the given universe.
+    ///
+    /// While set, every time DMX data (NULL START Code) is sent on the universe a per-address priority packet is sent immediately after it
+    /// using the same priority, destination and synchronisation address. Each value is the priority (0-E131_MAX_PRIORITY) of the
+    /// corresponding DMX slot, starting at slot 1, with 0 indicating that this source isn't sending that slot. Receivers that support
+    /// per-address priority will use these to arbitrate between sources slot-by-slot.
+    ///
+    /// # Arguments
+    /// universe:   The universe to set the per-address priorities for.
+    ///
+    /// priorities: The priority of each DMX slot not including a start code, at most UNIVERSE_CHANNEL_CAPACITY - 1 values. None stops sending
+    ///                 per-address priorities for the universe.
+    ///
+    /// # Errors
+    /// IllegalUniverse: Returned if the universe is outwith the allowed range of sACN universes as defined in ANSI E1.31-2018 Section 6.2.7.
+    ///
+    /// UniverseNotRegistered: Returned if the universe is not registered on this source.
+    ///
+    /// InvalidPriority: Returned if any of the priorities is greater than the allowed maximum priority of E131_MAX_PRIORITY.
+    ///
+    /// ExceedUniverseCapacity: Returned if there are more priorities than there are slots in a universe.
+    ///
+    /// SourceCorrupt: Returned if the Mutex used to control access to the internal sender is poisoned by a thread encountering
+    /// a panic while accessing causing the source to be left in a potentially inconsistent state.
+    pub fn set_per_address_priority(
+        &mut self,
+        universe: u16,
+        priorities: Option<&[u8]>,
+    ) -> Result<()> {
+        unlock_internal_mut(&mut self.internal)?.set_per_address_priority(universe, priorities)
+    }
+
+    /// Sets if data packets for the given universe are sent with the Force_Synchronization option bit set, as per ANSI E1.31-2018 Section 6.2.6.
+    ///
+    /// When the bit is clear (the default) a receiver which was synchronised on the universe and stops receiving synchronisation packets
+    /// won't act on new data until synchronisation resumes. When the bit is set the receiver may act on the next data packet without waiting
+    /// for a synchronisation packet.
+    ///
+    /// Note that this follows the meaning given by the standard rather than treating a clear bit as a request to fall back to unsynchronised
+    /// output. The standard defaults to holding the last synchronised look as acting on data from only some of the universes of a
+    /// synchronised group would break the outputs apart, so a source has to opt in to its receivers continuing without synchronisation.
+    ///
+    /// # Arguments
+    /// universe: The universe to set the option for.
+    ///
+    /// force_synchronization: The new value of the option bit for the universe.
+    ///
+    /// # Errors
+    /// IllegalUniverse: Returned if the universe is outwith the allowed range of sACN universes as defined in ANSI E1.31-2018 Section 6.2.7.
+    ///
+    /// UniverseNotRegistered: Returned if the universe is not registered on this source.
+    ///
+    /// SourceCorrupt: Returned if the Mutex used to control access to the internal sender is poisoned by a thread encountering
+    /// a panic while accessing causing the source to be left in a potentially inconsistent state.
+    pub fn set_force_synchronization(
+        &mut self,
+        universe: u16,
+        force_synchronization: bool,
+    ) -> Result<()> {
+        unlock_internal_mut(&mut self.internal)?
+            .set_force_synchronization(universe, force_synchronization)
+    }
+
+    /// Sets the levels of the given universe which are then sent by the source every refresh period until cleared or the universe is
+    /// terminated, see (set_refresh_rate)[SacnSource::set_refresh_rate].
+    ///
+    /// This is an alternative to repeatedly calling (send)[SacnSource::send], the levels can be updated at any time and the latest levels
+    /// are sent in the next frame. The priority, destination and synchronisation address the levels are sent with are set using
+    /// (set_refresh_options)[SacnSource::set_refresh_options]. Any per-address priorities set for the universe are sent alongside the levels.
+    ///
+    /// # Arguments
+    /// universe: The universe to set the levels of.
+    ///
+    /// levels:   The DMX slot values not including a START code, at most UNIVERSE_CHANNEL_CAPACITY - 1 values. The levels are sent with
+    ///             the NULL START code.
+    ///
+    /// # Errors
+    /// IllegalUniverse: Returned if the universe is outwith the allowed range of sACN universes as defined in ANSI E1.31-2018 Section 6.2.7.
+    ///
+    /// UniverseNotRegistered: Returned if the universe is not registered on this source.
+    ///
+    /// DataArrayEmpty: Returned if no levels are given.
+    ///
+    /// ExceedUniverseCapacity: Returned if there are more levels than there are slots in a universe.
+    ///
+    /// SourceCorrupt: Returned if the Mutex used to control access to the internal sender is poisoned by a thread encountering
+    /// a panic while accessing causing the source to be left in a potentially inconsistent state.
+    pub fn set_universe_levels(&mut self, universe: u16, levels: &[u8]) -> Result<()> {
+        unlock_internal_mut(&mut self.internal)?.set_universe_levels(universe, levels)?;
+
+        // Wakes the update thread so the levels are sent without waiting for the next poll.
+        if let Some(thread) = &self.update_thread {
+            thread.thread().unpark();
+        }
+        Ok(())
+    }
+
+    /// Stops sending the levels of the given universe, no termination packets are sent, see (terminate_stream)[SacnSource::terminate_stream].
+    ///
+    /// # Errors
+    /// IllegalUniverse: Returned if the universe is outwith the allowed range of sACN universes as defined in ANSI E1.31-2018 Section 6.2.7.
+    ///
+    /// UniverseNotRegistered: Returned if the universe is not registered on this source.
+    ///
+    /// SourceCorrupt: Returned if the Mutex used to control access to the internal sender is poisoned by a thread encountering
+    /// a panic while accessing causing the source to be left in a potentially inconsistent state.
+    pub fn clear_universe_levels(&mut self, universe: u16) -> Result<()> {
+        unlock_internal_mut(&mut self.internal)?.clear_universe_levels(universe)
+    }
+
+    /// Sets the priority, destination ip and synchronisation address that the levels of the given universe are sent with,
+    /// see (set_universe_levels)[SacnSource::set_universe_levels]. This can be called before or after the levels are set.
+    ///
+    /// After sending the levels of all universes each refresh period a synchronisation packet is sent for each synchronisation address
+    /// used so that the receivers act on the levels together.
+    ///
+    /// # Arguments
+    /// universe:             The universe to set the options for.
+    ///
+    /// priority:             The E131 priority that the levels should be sent with, None to use E131_DEFAULT_PRIORITY.
+    ///
+    /// dst_ip:               The destination IP, can be Ipv4 or Ipv6, None if the levels should be sent using ip multicast.
+    ///
+    /// synchronisation_addr: The address to use for synchronisation, must be a registered universe, None indicates no synchronisation.
+    ///
+    /// # Errors
+    /// IllegalUniverse: Returned if the universe is outwith the allowed range of sACN universes as defined in ANSI E1.31-2018 Section 6.2.7.
+    ///
+    /// UniverseNotRegistered: Returned if the universe is not registered on this source.
+    ///
+    /// InvalidPriority: Returned if the priority is greater than the allowed maximum priority of E131_MAX_PRIORITY.
+    ///
+    /// IllegalSyncUniverse: Returned if the synchronisation address is not a registered universe.
+    ///
+    /// SourceCorrupt: Returned if the Mutex used to control access to the internal sender is poisoned by a thread encountering
+    /// a panic while accessing causing the source to be left in a potentially inconsistent state.
+    pub fn set_refresh_options(
+        &mut self,
+        universe: u16,
+        priority: Option<u8>,
+        dst_ip: Option<SocketAddr>,
+        synchronisation_addr: Option<u16>,
+    ) -> Result<()> {
+        unlock_internal_mut(&mut self.internal)?.set_refresh_options(
+            universe,
+            priority,
+            dst_ip,
+            synchronisation_addr,
+        )
+    }
+
+    /// Sets how often the levels of the given universe are sent, by default (Continuous)[TransmissionPolicy::Continuous].
+    ///
+    /// With a (KeepAlive)[TransmissionPolicy::KeepAlive] policy the source reduces to sending a packet per keep-alive interval once the levels
+    /// stop changing, as allowed by ANSI E1.31-2018 Section 6.6.2, and returns to the full refresh rate as soon as they change. This reduces
+    /// the network traffic of mostly static universes while still keeping receivers from timing out the source.
+    ///
+    /// # Arguments
+    /// universe: The universe to set the transmission policy of.
+    ///
+    /// policy:   The new transmission policy of the universe.
+    ///
+    /// # Errors
+    /// IllegalUniverse: Returned if the universe is outwith the allowed range of sACN universes as defined in ANSI E1.31-2018 Section 6.2.7.
+    ///
+    /// UniverseNotRegistered: Returned if the universe is not registered on this source.
+    ///
+    /// InvalidKeepAliveInterval: Returned if the keep-alive interval is outwith [E131_KEEP_ALIVE_MIN_INTERVAL, E131_KEEP_ALIVE_MAX_INTERVAL].
+    ///
+    /// SourceCorrupt: Returned if the Mutex used to control access to the internal sender is poisoned by a thread encountering
+    /// a panic while accessing causing the source to be left in a potentially inconsistent state.
+    pub fn set_transmission_policy(
+        &mut self,
+        universe: u16,
+        policy: TransmissionPolicy,
+    ) -> Result<()> {
+        unlock_internal_mut(&mut self.internal)?.set_transmission_policy(universe, policy)
+    }
+
+    /// Sets the rate (frames per second) at which the universe levels are sent, by default DMX512_MAX_REFRESH_RATE.
+    ///
+    /// As per ANSI E1.31-2018 Section 6.6.1 the rate is capped at the maximum refresh rate of DMX512-A, DMX512_MAX_REFRESH_RATE.
+    ///
+    /// # Errors
+    /// InvalidRefreshRate: Returned if the rate is 0.
+    ///
+    /// SourceCorrupt: Returned if the Mutex used to control access to the internal sender is poisoned by a thread encountering
+    /// a panic while accessing causing the source to be left in a potentially inconsistent state.
+    pub fn set_refresh_rate(&mut self, rate: u32) -> Result<()> {
+        unlock_internal_mut(&mut self.internal)?.set_refresh_rate(rate)
+    }
+
+    /// Returns the rate (frames per second) at which the universe levels are sent.
+    ///
+    /// # Errors
+    /// SourceCorrupt: Returned if the Mutex used to control access to the internal sender is poisoned by a thread encountering
+    /// a panic while accessing causing the source to be left in a potentially inconsistent state.
+    pub fn refresh_rate(&self) -> Result<u32> {
+        Ok(unlock_internal(&self.internal)?.refresh_rate())
+    }
+
     /// Sends a synchronisation packet to trigger the sending of packets waiting to be sent together.
     ///
     /// A common pattern would be to use the send method to send data to all the universes that should be synchronised using a
@@ -445,7 +822,67 @@ impl SacnSource {
         self.internal.lock().unwrap().set_is_sending_discovery(val);
     }
 
+    /// Sets the clock that the E131_UNIVERSE_DISCOVERY_INTERVAL between universe discovery packets and the refresh period of the
+    /// universe levels are measured with. By default this is the (SystemClock)[crate::clock::SystemClock].
+    ///
+    /// Setting a (ManualClock)[crate::clock::ManualClock] allows the periodic sending to be tested without waiting for it, the update
+    /// thread checks what is due on the clock at least once a second. The discovery interval and refresh period restart
+    /// from the current time of the new clock.
+    ///
+    /// # Arguments
+    /// clock: The clock to use.
+    ///
+    /// # Errors
+    /// SourceCorrupt: Returned if the Mutex used to control access to the internal sender is poisoned by a thread encountering
+    /// a panic while accessing causing the source to be left in a potentially inconsistent state.
+    pub fn set_clock<C: Clock + 'static>(&mut self, clock: C) -> Result<()> {
+        unlock_internal_mut(&mut self.internal)?.set_clock(Arc::new(clock));
+        Ok(())
+    }
+
+    /// Sets the network interfaces that this source sends on, replacing any previously set.
+    ///
+    /// Every data, synchronisation, termination and discovery packet is sent on each of the interfaces with the same sequence number so
+    /// receivers see a single consistent stream, e.g. when driving redundant primary and backup networks. Multicast packets leave through
+    /// the given interface and unicast packets are sent from its address. If no interfaces are given (the default) then packets are sent
+    /// once and the operating system chooses the interface.
+    ///
+    /// The interfaces must have an address of the same IP version as the address the source was created with.
+    ///
+    /// # Arguments
+    /// interfaces: The network interfaces to send on.
+    ///
+    /// # Errors
+    /// InterfaceNotFound: Returned if an interface doesn't exist or has no address of the IP version used by the source.
+    ///
+    /// OsOperationUnsupported: Returned if an interface cannot be looked up on the current OS, see (NetworkInterface::addr)[NetworkInterface::addr].
+    ///
+    /// Io: Returned if a socket cannot be created and bound for an interface.
+    ///
+    /// TransportUnsupported: Returned if the source sends using a transport, see (with_transport)[SacnSource::with_transport].
+    ///
+    /// SourceCorrupt: Returned if the Mutex used to control access to the internal sender is poisoned by a thread encountering
+    /// a panic while accessing causing the source to be left in a potentially inconsistent state.
+    pub fn set_interfaces(&mut self, interfaces: &[NetworkInterface]) -> Result<()> {
+        unlock_internal_mut(&mut self.internal)?.set_interfaces(interfaces)
+    }
+
+    /// Returns the network interfaces that this source sends on, empty if the operating system chooses the interface.
+    ///
+    /// # Errors
+    /// SourceCorrupt: Returned if the Mutex used to control access to the internal sender is poisoned by a thread encountering
+    /// a panic while accessing causing the source to be left in a potentially inconsistent state.
+    pub fn interfaces(&self) -> Result<Vec<NetworkInterface>> {
+        Ok(unlock_internal(&self.internal)?.interfaces.clone())
+    }
+
     /// Returns the multicast time to live of the socket.
+    ///
+    /// # Errors
+    /// TransportUnsupported: Returned if the source sends using a transport, see (with_transport)[SacnSource::with_transport].
+    ///
+    /// SourceCorrupt: Returned if the Mutex used to control access to the internal sender is poisoned by a thread encountering
+    /// a panic while accessing causing the source to be left in a potentially inconsistent state.
     pub fn multicast_ttl(&self) -> Result<u32> {
         unlock_internal(&self.internal)?.multicast_ttl()
     }
@@ -458,6 +895,8 @@ impl SacnSource {
     /// # Errors
     /// Io: Returned if the multicast TTL fails to be set on the underlying socket.
     ///
+    /// TransportUnsupported: Returned if the source sends using a transport, see (with_transport)[SacnSource::with_transport].
+    ///
     /// SourceCorrupt: Returned if the Mutex used to control access to the internal sender is poisoned by a thread encountering
     /// a panic while accessing causing the source to be left in a potentially inconsistent state.
     pub fn set_multicast_ttl(&mut self, multicast_ttl: u32) -> Result<()> {
@@ -469,6 +908,8 @@ impl SacnSource {
     /// # Errors
     /// Io: Returned if the TTL cannot be retrieved from the underlying socket.
     ///
+    /// TransportUnsupported: Returned if the source sends using a transport, see (with_transport)[SacnSource::with_transport].
+    ///
     /// SourceCorrupt: Returned if the Mutex used to control access to the internal sender is poisoned by a thread encountering
     /// a panic while accessing causing the source to be left in a potentially inconsistent state.
     pub fn ttl(&self) -> Result<u32> {
@@ -483,6 +924,8 @@ impl SacnSource {
     /// # Errors
     /// Io: Returned if the TTL value cannot be changed.
     ///
+    /// TransportUnsupported: Returned if the source sends using a transport, see (with_transport)[SacnSource::with_transport].
+    ///
     /// SourceCorrupt: Returned if the Mutex used to control access to the internal sender is poisoned by a thread encountering
     /// a panic while accessing causing the source to be left in a potentially inconsistent state.
     pub fn set_ttl(&mut self, ttl: u32) -> Result<()> {
@@ -497,6 +940,8 @@ impl SacnSource {
     /// # Errors
     /// Io: Returned if the set_multicast_loop option fails to be set on the socket.
     ///
+    /// TransportUnsupported: Returned if the source sends using a transport, see (with_transport)[SacnSource::with_transport].
+    ///
     /// SourceCorrupt: Returned if the Mutex used to control access to the internal sender is poisoned by a thread encountering
     /// a panic while accessing causing the source to be left in a potentially inconsistent state.
     pub fn set_multicast_loop_v4(&mut self, multicast_loop: bool) -> Result<()> {
@@ -506,6 +951,8 @@ impl SacnSource {
     /// Returns true if multicast loop is enabled, false if not.
     ///
     /// # Errors
+    /// TransportUnsupported: Returned if the source sends using a transport, see (with_transport)[SacnSource::with_transport].
+    ///
     /// SourceCorrupt: Returned if the Mutex used to control access to the internal sender is poisoned by a thread encountering
     /// a panic while accessing causing the source to be left in a potentially inconsistent state.
     pub fn multicast_loop(&self) -> Result<bool> {
@@ -544,6 +991,7 @@ impl Drop for SacnSource {
                 {} // As drop isn't always explicitly called and cannot return an error the error is ignored. Memory safety is maintain and this prevents causing a panic!.
             };
 
+            thread.thread().unpark();
             thread.join().unwrap();
         }
     }
@@ -565,59 +1013,160 @@ impl SacnSourceInternal {
     ///
     /// UnsupportedIpVersion: Returned if the SockAddr is not IPv4 or IPv6.
     fn with_cid_ip(name: &str, cid: Uuid, ip: SocketAddr) -> Result<SacnSourceInternal> {
-        let socket = if ip.is_ipv4() {
-            Socket::new(Domain::IPV4, Type::DGRAM, None).unwrap()
-        } else if ip.is_ipv6() {
-            Socket::new(Domain::IPV6, Type::DGRAM, None).unwrap()
-        } else {
-            return Err(SacnError::UnsupportedIpVersion(
-                "Address to create SacnSource is not IPv4 or IPv6".to_string(),
-            ));
-        };
-
-        // Multiple different processes might want to send to the sACN stream so therefore need to allow re-using the ACN port.
-        // Set reuse port is only supported on linux.
-        #[cfg(target_os = "linux")]
-        socket.set_reuse_port(true)?;
+        let socket = create_socket(ip)?;
+        Ok(SacnSourceInternal::new(name, cid, ip, Some(socket), None))
+    }
 
-        // Set reuse address supported on linux and windows.
-        socket.set_reuse_address(true)?;
-        socket.bind(&ip.into())?;
+    /// Constructs a new SacnSourceInternal with the given name and cid which sends using the given transport.
+    ///
+    /// # Errors
+    /// Io: Returned if the address the transport is bound to cannot be determined.
+    fn with_cid_transport(
+        name: &str,
+        cid: Uuid,
+        transport: Box<dyn Transport>,
+    ) -> Result<SacnSourceInternal> {
+        let addr = transport.local_addr()?;
+        Ok(SacnSourceInternal::new(
+            name,
+            cid,
+            addr,
+            None,
+            Some(transport),
+        ))
+    }
 
-        let ds = SacnSourceInternal {
+    /// Constructs a new SacnSourceInternal at the given address which sends on the given socket or transport.
+    fn new(
+        name: &str,
+        cid: Uuid,
+        addr: SocketAddr,
+        socket: Option<Socket>,
+        transport: Option<Box<dyn Transport>>,
+    ) -> SacnSourceInternal {
+        SacnSourceInternal {
             socket,
-            addr: ip,
+            transport,
+            addr,
+            interfaces: Vec::new(),
+            interface_sockets: Vec::new(),
             cid,
             name: name.to_string(),
             preview_data: false,
             data_sequences: RefCell::new(HashMap::new()),
             sync_sequences: RefCell::new(HashMap::new()),
+            data_packets: RefCell::new(HashMap::new()),
+            pending_data_packets: RefCell::new(Vec::new()),
             universes: Vec::new(),
             running: true,
-            last_discovery_advert_timestamp: Instant::now(),
+            last_discovery_advert_timestamp: SystemClock.now(),
             is_sending_discovery: true,
-        };
-
-        Ok(ds)
+            per_address_priorities: HashMap::new(),
+            force_synchronization: HashSet::new(),
+            queued_packets: RefCell::new(None),
+            refresh_universes: BTreeMap::new(),
+            refresh_period: refresh_period(DEFAULT_REFRESH_RATE),
+            last_refresh_timestamp: SystemClock.now(),
+            clock: Arc::new(SystemClock),
+        }
     }
 
-    /// Sets the is_sending_discovery flag to the given value.
+    /// Sets the network interfaces that packets are sent on, see (SacnSource::set_interfaces)[SacnSource::set_interfaces].
     ///
-    /// If is_sending_discovery is set to false then no discovery adverts for this source
-    /// will be sent otherwise (and by default) they will be sent every UNIVERSE_DISCOVERY_INTERVAL.
+    /// The sockets for all the interfaces are created before any are replaced so on error the previous interfaces are kept.
     ///
-    /// # Arguments:
-    /// val: The new value of the is_sending_discovery flag.
-    fn set_is_sending_discovery(&mut self, val: bool) {
-        self.is_sending_discovery = val;
-    }
-
-    /// Registers the given array of universes with this source.
+    /// # Errors
+    /// InterfaceNotFound: Returned if an interface doesn't exist or has no address of the IP version used by the source.
     ///
-    /// Any universes already registered won't be re-registered and will have no effect.
+    /// OsOperationUnsupported: Returned if an interface cannot be looked up on the current OS.
     ///
-    /// # Arguments:
-    /// universes: The sACN universe to register. Note that sACN universes start at 1 not 0.
+    /// Io: Returned if a socket cannot be created and bound for an interface.
+    ///
+    /// TransportUnsupported: Returned if the source sends using a transport.
+    fn set_interfaces(&mut self, interfaces: &[NetworkInterface]) -> Result<()> {
+        self.socket()?;
+
+        let sockets = interfaces
+            .iter()
+            .map(|i| self.create_interface_socket(i))
+            .collect::<Result<Vec<Socket>>>()?;
+
+        self.interfaces = interfaces.to_vec();
+        self.interface_sockets = sockets;
+        Ok(())
+    }
+
+    /// Creates a socket which sends on the given interface, bound to the interface's address and the port of this source.
+    ///
+    /// For IPv4 the time to live and multicast settings of the source's socket are copied to the new socket.
+    ///
+    /// # Errors
+    /// See (set_interfaces)[SacnSourceInternal::set_interfaces].
+    fn create_interface_socket(&self, interface: &NetworkInterface) -> Result<Socket> {
+        match interface.addr(self.addr.is_ipv6())? {
+            IpAddr::V4(addr) => {
+                let socket = create_socket(SocketAddr::new(IpAddr::V4(addr), self.addr.port()))?;
+                socket.set_multicast_if_v4(&addr)?;
+                let src_socket = self.socket()?;
+                socket.set_ttl_v4(src_socket.ttl_v4()?)?;
+                socket.set_multicast_ttl_v4(src_socket.multicast_ttl_v4()?)?;
+                socket.set_multicast_loop_v4(src_socket.multicast_loop_v4()?)?;
+                Ok(socket)
+            }
+            IpAddr::V6(addr) => {
+                // The index is used as the scope so that link-local addresses can be bound.
+                let index = interface.index()?;
+                let socket = create_socket(SocketAddr::V6(SocketAddrV6::new(
+                    addr,
+                    self.addr.port(),
+                    0,
+                    index,
+                )))?;
+                socket.set_multicast_if_v6(index)?;
+                Ok(socket)
+            }
+        }
+    }
+
+    /// Returns the sockets that each packet is sent on, the sockets of the interfaces if any are set otherwise just the socket.
+    /// Empty if the source sends using a transport.
+    fn send_sockets(&self) -> &[Socket] {
+        if self.interface_sockets.is_empty() {
+            self.socket.as_slice()
+        } else {
+            &self.interface_sockets
+        }
+    }
+
+    /// Sets the is_sending_discovery flag to the given value.
+    ///
+    /// If is_sending_discovery is set to false then no discovery adverts for this source
+    /// will be sent otherwise (and by default) they will be sent every UNIVERSE_DISCOVERY_INTERVAL.
+    ///
+    /// # Arguments:
+    /// val: The new value of the is_sending_discovery flag.
+    fn set_is_sending_discovery(&mut self, val: bool) {
+        self.is_sending_discovery = val;
+    }
+
+    /// Sets the clock used for periodic sending, restarting the discovery interval and refresh period from its current time, see
+    /// (SacnSource::set_clock)[SacnSource::set_clock].
+    fn set_clock(&mut self, clock: Arc<dyn Clock>) {
+        let now = clock.now();
+        self.last_discovery_advert_timestamp = now;
+        self.last_refresh_timestamp = now;
+        for refresh in self.refresh_universes.values_mut() {
+            refresh.last_sent = None;
+        }
+        self.clock = clock;
+    }
+
+    /// Registers the given array of universes with this source.
+    ///
+    /// Any universes already registered won't be re-registered and will have no effect.
+    ///
+    /// # Arguments:
+    /// universes: The sACN universe to register. Note that sACN universes start at 1 not 0.
     ///
     /// # Errors
     /// See register_universe(fn.register_universe.source) for more details.
@@ -753,9 +1302,9 @@ impl SacnSourceInternal {
         }
 
         // Check that the synchronisation universe is also valid.
-        if synchronisation_addr.is_some() {
-            self.universe_allowed(&synchronisation_addr.unwrap())
-                .map_err(|_| SacnError::IllegalSyncUniverse(synchronisation_addr.unwrap()))?;
+        if let Some(sync_addr) = synchronisation_addr {
+            self.universe_allowed(&sync_addr)
+                .map_err(|_| SacnError::IllegalSyncUniverse(sync_addr))?;
         }
 
         // + 1 as there must be at least 1 universe required as the data isn't empty then additional universes for any more.
@@ -766,21 +1315,27 @@ impl SacnSourceInternal {
             return Err(SacnError::UniverseListEmpty());
         }
 
-        for (i, &universe) in universes.iter().enumerate().take(required_universes) {
-            let start_index = i * UNIVERSE_CHANNEL_CAPACITY;
-            // Safety check to make sure that the end index doesn't exceed the data length
-            let end_index = cmp::min((i + 1) * UNIVERSE_CHANNEL_CAPACITY, data.len());
-
-            self.send_universe(
-                universe,
-                &data[start_index..end_index],
-                priority.unwrap_or(E131_DEFAULT_PRIORITY),
-                &dst_ip,
-                synchronisation_addr.unwrap_or(NO_SYNC_UNIVERSE),
-            )?;
-        }
+        let res = universes
+            .iter()
+            .enumerate()
+            .take(required_universes)
+            .try_for_each(|(i, &universe)| {
+                let start_index = i * UNIVERSE_CHANNEL_CAPACITY;
+                // Safety check to make sure that the end index doesn't exceed the data length
+                let end_index = cmp::min((i + 1) * UNIVERSE_CHANNEL_CAPACITY, data.len());
 
-        Ok(())
+                self.send_universe(
+                    universe,
+                    &data[start_index..end_index],
+                    priority.unwrap_or(E131_DEFAULT_PRIORITY),
+                    &dst_ip,
+                    synchronisation_addr.unwrap_or(NO_SYNC_UNIVERSE),
+                )
+            });
+
+        // The packets prepared before any error are still sent, as they would have been if each was sent as it was prepared.
+        let flushed = self.flush_data_packets();
+        res.and(flushed)
     }
 
     /// Sends the given data to the given universe with the given priority, synchronisation address (universe) and destination ip.
@@ -821,53 +1376,113 @@ impl SacnSourceInternal {
             return Err(SacnError::ExceedUniverseCapacity(data.len()));
         }
 
+        self.send_data_packet(universe, data, priority, dst_ip, sync_address)?;
+
+        if data.first() == Some(&E131_NULL_START_CODE)
+            && let Some(priorities) = self.per_address_priorities.get(&universe)
+        {
+            self.send_data_packet(universe, priorities, priority, dst_ip, sync_address)?;
+        }
+
+        Ok(())
+    }
+
+    /// Sends a single data packet containing the given property values (including start code) to the given universe, using and then
+    /// incrementing the data sequence number for that universe.
+    ///
+    /// # Arguments
+    /// universe:     The sACN universe that the data should be set on.
+    ///
+    /// data:         The property values to send, the first value is the start code.
+    ///
+    /// priority:     The E131 priority that the packet should be sent with.
+    ///
+    /// dst_ip:       The destination IP, can be Ipv4 or Ipv6, None if should be sent using ip multicast.
+    ///
+    /// sync_address: The address to use for synchronisation, must be a valid universe, 0 indicates no synchronisation.
+    ///
+    /// # Errors
+    /// IllegalUniverse: Returned if the given universe is outwith the allowed range of universes,
+    ///                     see (universe_to_ipv4_multicast_addr)[fn.universe_to_ipv4_multicast_addr.packet] and (universe_to_ipv6_multicast_addr)[fn.universe_to_ipv6_multicast_addr.packet].
+    ///
+    /// Io: Returned if the data fails to be sent on the socket, see send_to(fn.send_to.Socket).
+    fn send_data_packet(
+        &self,
+        universe: u16,
+        data: &[u8],
+        priority: u8,
+        dst_ip: &Option<SocketAddr>,
+        sync_address: u16,
+    ) -> Result<()> {
+        let dst: SockAddr = match dst_ip {
+            Some(ip) => (*ip).into(),
+            None if self.addr.is_ipv6() => universe_to_ipv6_multicast_addr(universe)?,
+            None => universe_to_ipv4_multicast_addr(universe)?,
+        };
+
+        let key = (
+            universe,
+            data.first().copied().unwrap_or(E131_NULL_START_CODE),
+        );
+
+        // The buffer for this universe and start code is about to be updated so a packet still waiting to be sent from it is sent first.
+        if self
+            .pending_data_packets
+            .borrow()
+            .iter()
+            .any(|(k, _)| *k == key)
+        {
+            self.flush_data_packets()?;
+        }
+
         let mut sequence = match self.data_sequences.borrow().get(&universe) {
             Some(s) => *s,
             None => STARTING_SEQUENCE_NUMBER,
         };
 
-        let packet = AcnRootLayerProtocol {
-            pdu: E131RootLayer {
-                cid: self.cid,
-                data: E131RootLayerData::DataPacket(DataPacketFramingLayer {
-                    source_name: self.name.as_str().into(),
-                    priority,
-                    synchronization_address: sync_address,
-                    sequence_number: sequence,
-                    preview_data: self.preview_data,
-                    stream_terminated: false,
-                    force_synchronization: false,
-                    universe,
-                    data: DataPacketDmpLayer {
-                        property_values: {
-                            let mut property_values = Vec::with_capacity(data.len());
-                            property_values.extend(data);
-                            property_values.into()
-                        },
+        let mut data_packets = self.data_packets.borrow_mut();
+        match data_packets.get_mut(&key) {
+            Some(buf)
+                if buf.priority == priority
+                    && buf.sync_address == sync_address
+                    && buf.packet.len() == E131_DATA_PACKET_PROPERTY_VALUES_INDEX + data.len() =>
+            {
+                buf.packet[E131_DATA_PACKET_SEQ_NUM_INDEX] = sequence;
+                buf.packet[E131_DATA_PACKET_PROPERTY_VALUES_INDEX..].copy_from_slice(data);
+            }
+            _ => {
+                let packet = AcnRootLayerProtocol {
+                    pdu: E131RootLayer {
+                        cid: self.cid,
+                        data: E131RootLayerData::DataPacket(DataPacketFramingLayer {
+                            source_name: self.name.as_str().into(),
+                            priority,
+                            synchronization_address: sync_address,
+                            sequence_number: sequence,
+                            preview_data: self.preview_data,
+                            stream_terminated: false,
+                            force_synchronization: self.force_synchronization.contains(&universe),
+                            universe,
+                            data: DataPacketDmpLayer {
+                                property_values: data.into(),
+                            },
+                        }),
                     },
-                }),
-            },
-        };
+                };
 
-        if dst_ip.is_some() {
-            self.socket
-                .send_to(&packet.pack_alloc().unwrap(), &dst_ip.unwrap().into())
-                .map_err(|e| {
-                    std::io::Error::new(e.kind(), "Failed to send data unicast on socket")
-                })?;
-        } else {
-            let dst = if self.addr.is_ipv6() {
-                universe_to_ipv6_multicast_addr(universe)?
-            } else {
-                universe_to_ipv4_multicast_addr(universe)?
-            };
-
-            self.socket
-                .send_to(&packet.pack_alloc().unwrap(), &dst)
-                .map_err(|e| {
-                    std::io::Error::new(e.kind(), "Failed to send data multicast on socket")
-                })?;
+                let buf = data_packets.entry(key).or_insert(DataPacketBuffer {
+                    priority,
+                    sync_address,
+                    packet: Vec::new(),
+                });
+                buf.priority = priority;
+                buf.sync_address = sync_address;
+                packet.pack_vec(&mut buf.packet)?;
+            }
         }
+        drop(data_packets);
+
+        self.pending_data_packets.borrow_mut().push((key, dst));
 
         if sequence == 255 {
             sequence = 0;
@@ -878,6 +1493,308 @@ impl SacnSourceInternal {
         Ok(())
     }
 
+    /// Sends the data packets prepared by send_data_packet which are waiting to be sent, in the order they were prepared.
+    ///
+    /// On Linux the packets are sent using a single sendmmsg system call per socket rather than a call per packet.
+    ///
+    /// # Errors
+    /// Io: Returned if the packets fail to be sent on the socket, all the packets are attempted even if an earlier one fails.
+    fn flush_data_packets(&self) -> Result<()> {
+        let pending = std::mem::take(&mut *self.pending_data_packets.borrow_mut());
+        if pending.is_empty() {
+            return Ok(());
+        }
+
+        let data_packets = self.data_packets.borrow();
+        let packets: Vec<(&[u8], &SockAddr)> = pending
+            .iter()
+            .map(|(key, dst)| (data_packets[key].packet.as_slice(), dst))
+            .collect();
+
+        self.send_all(&packets)
+            .map_err(|e| std::io::Error::new(e.kind(), "Failed to send data on socket").into())
+    }
+
+    /// Sets the per-address priorities (start code 0xDD) to send alongside the DMX data for the given universe.
+    ///
+    /// # Arguments
+    /// universe:   The universe to set the per-address priorities for.
+    ///
+    /// priorities: The priority of each DMX slot not including a start code. None stops sending per-address priorities for the universe.
+    ///
+    /// # Errors
+    /// IllegalUniverse: Returned if the universe is outwith the allowed range of sACN universes as defined in ANSI E1.31-2018 Section 6.2.7.
+    ///
+    /// UniverseNotRegistered: Returned if the universe is not registered on this source.
+    ///
+    /// InvalidPriority: Returned if any of the priorities is greater than the allowed maximum priority of E131_MAX_PRIORITY.
+    ///
+    /// ExceedUniverseCapacity: Returned if there are more priorities than there are slots in a universe.
+    fn set_per_address_priority(&mut self, universe: u16, priorities: Option<&[u8]>) -> Result<()> {
+        self.universe_allowed(&universe)?;
+
+        match priorities {
+            Some(p) => {
+                if p.len() >= UNIVERSE_CHANNEL_CAPACITY {
+                    return Err(SacnError::ExceedUniverseCapacity(p.len() + 1));
+                }
+
+                if let Some(invalid) = p.iter().find(|x| **x > E131_MAX_PRIORITY) {
+                    return Err(SacnError::InvalidPriority(*invalid));
+                }
+
+                let mut values = Vec::with_capacity(p.len() + 1);
+                values.push(E131_PER_ADDRESS_PRIORITY_START_CODE);
+                values.extend(p);
+                self.per_address_priorities.insert(universe, values);
+            }
+            None => {
+                self.per_address_priorities.remove(&universe);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Sets if data packets for the given universe are sent with the Force_Synchronization option bit set.
+    ///
+    /// # Arguments
+    /// universe: The universe to set the option for.
+    ///
+    /// force_synchronization: The new value of the option bit for the universe.
+    ///
+    /// # Errors
+    /// IllegalUniverse: Returned if the universe is outwith the allowed range of sACN universes as defined in ANSI E1.31-2018 Section 6.2.7.
+    ///
+    /// UniverseNotRegistered: Returned if the universe is not registered on this source.
+    fn set_force_synchronization(
+        &mut self,
+        universe: u16,
+        force_synchronization: bool,
+    ) -> Result<()> {
+        self.universe_allowed(&universe)?;
+
+        if force_synchronization {
+            self.force_synchronization.insert(universe);
+        } else {
+            self.force_synchronization.remove(&universe);
+        }
+        self.data_packets.borrow_mut().clear();
+
+        Ok(())
+    }
+
+    /// Sets the levels of the given universe which are sent every refresh period.
+    ///
+    /// # Arguments
+    /// universe: The universe to set the levels of.
+    ///
+    /// levels:   The DMX slot values not including a START code.
+    ///
+    /// # Errors
+    /// IllegalUniverse: Returned if the universe is outwith the allowed range of sACN universes as defined in ANSI E1.31-2018 Section 6.2.7.
+    ///
+    /// UniverseNotRegistered: Returned if the universe is not registered on this source.
+    ///
+    /// DataArrayEmpty: Returned if no levels are given.
+    ///
+    /// ExceedUniverseCapacity: Returned if there are more levels than there are slots in a universe.
+    fn set_universe_levels(&mut self, universe: u16, levels: &[u8]) -> Result<()> {
+        self.universe_allowed(&universe)?;
+
+        if levels.is_empty() {
+            return Err(SacnError::DataArrayEmpty());
+        }
+
+        if levels.len() >= UNIVERSE_CHANNEL_CAPACITY {
+            return Err(SacnError::ExceedUniverseCapacity(levels.len() + 1));
+        }
+
+        let refresh = self.refresh_universes.entry(universe).or_default();
+        if refresh.data.as_ref().map(|d| &d[1..]) == Some(levels) {
+            // The levels haven't changed so don't restart sending at the full rate.
+            return Ok(());
+        }
+
+        let data = refresh
+            .data
+            .get_or_insert_with(|| Vec::with_capacity(UNIVERSE_CHANNEL_CAPACITY));
+        data.clear();
+        data.push(E131_NULL_START_CODE);
+        data.extend(levels);
+        refresh.unchanged_sends = 0;
+
+        Ok(())
+    }
+
+    /// Stops sending the levels of the given universe.
+    ///
+    /// # Errors
+    /// IllegalUniverse: Returned if the universe is outwith the allowed range of sACN universes as defined in ANSI E1.31-2018 Section 6.2.7.
+    ///
+    /// UniverseNotRegistered: Returned if the universe is not registered on this source.
+    fn clear_universe_levels(&mut self, universe: u16) -> Result<()> {
+        self.universe_allowed(&universe)?;
+
+        if let Some(refresh) = self.refresh_universes.get_mut(&universe) {
+            refresh.data = None;
+            refresh.unchanged_sends = 0;
+        }
+
+        Ok(())
+    }
+
+    /// Sets the priority, destination ip and synchronisation address that the levels of the given universe are sent with.
+    ///
+    /// # Errors
+    /// IllegalUniverse: Returned if the universe is outwith the allowed range of sACN universes as defined in ANSI E1.31-2018 Section 6.2.7.
+    ///
+    /// UniverseNotRegistered: Returned if the universe is not registered on this source.
+    ///
+    /// InvalidPriority: Returned if the priority is greater than the allowed maximum priority of E131_MAX_PRIORITY.
+    ///
+    /// IllegalSyncUniverse: Returned if the synchronisation address is not a registered universe.
+    fn set_refresh_options(
+        &mut self,
+        universe: u16,
+        priority: Option<u8>,
+        dst_ip: Option<SocketAddr>,
+        synchronisation_addr: Option<u16>,
+    ) -> Result<()> {
+        self.universe_allowed(&universe)?;
+
+        if let Some(p) = priority
+            && p > E131_MAX_PRIORITY
+        {
+            return Err(SacnError::InvalidPriority(p));
+        }
+
+        if let Some(sync_addr) = synchronisation_addr {
+            self.universe_allowed(&sync_addr)
+                .map_err(|_| SacnError::IllegalSyncUniverse(sync_addr))?;
+        }
+
+        let refresh = self.refresh_universes.entry(universe).or_default();
+        refresh.priority = priority;
+        refresh.dst_ip = dst_ip;
+        refresh.synchronisation_addr = synchronisation_addr;
+
+        Ok(())
+    }
+
+    /// Sets how often the levels of the given universe are sent.
+    ///
+    /// # Errors
+    /// IllegalUniverse: Returned if the universe is outwith the allowed range of sACN universes as defined in ANSI E1.31-2018 Section 6.2.7.
+    ///
+    /// UniverseNotRegistered: Returned if the universe is not registered on this source.
+    ///
+    /// InvalidKeepAliveInterval: Returned if the keep-alive interval is outwith [E131_KEEP_ALIVE_MIN_INTERVAL, E131_KEEP_ALIVE_MAX_INTERVAL].
+    fn set_transmission_policy(&mut self, universe: u16, policy: TransmissionPolicy) -> Result<()> {
+        self.universe_allowed(&universe)?;
+
+        if let TransmissionPolicy::KeepAlive(interval) = policy
+            && !(E131_KEEP_ALIVE_MIN_INTERVAL..=E131_KEEP_ALIVE_MAX_INTERVAL).contains(&interval)
+        {
+            return Err(SacnError::InvalidKeepAliveInterval(interval));
+        }
+
+        self.refresh_universes.entry(universe).or_default().policy = policy;
+        Ok(())
+    }
+
+    /// Sets the rate (frames per second) at which the universe levels are sent, capped at DMX512_MAX_REFRESH_RATE.
+    ///
+    /// # Errors
+    /// InvalidRefreshRate: Returned if the rate is 0.
+    fn set_refresh_rate(&mut self, rate: u32) -> Result<()> {
+        if rate == 0 {
+            return Err(SacnError::InvalidRefreshRate(rate));
+        }
+
+        self.refresh_period = refresh_period(min(rate, DMX512_MAX_REFRESH_RATE));
+        Ok(())
+    }
+
+    /// Returns the rate (frames per second) at which the universe levels are sent.
+    fn refresh_rate(&self) -> u32 {
+        (Duration::from_secs(1).as_nanos() / self.refresh_period.as_nanos()) as u32
+    }
+
+    /// Sends the levels of every universe which has them, and is due to be sent as per its transmission policy, followed by a
+    /// synchronisation packet for each synchronisation address (and destination) used.
+    ///
+    /// # Errors
+    /// Returns an error if any of the packets cannot be sent, see (send_universe)[fn.send_universe.source] and
+    /// (send_sync_packet)[fn.send_sync_packet.source].
+    fn send_refresh_universes(&mut self) -> Result<()> {
+        let now = self.clock.now();
+        let due: Vec<u16> = self
+            .refresh_universes
+            .iter()
+            .filter(|(_, r)| r.is_due(now))
+            .map(|(u, _)| *u)
+            .collect();
+
+        let mut syncs: Vec<(u16, Option<SocketAddr>)> = Vec::new();
+
+        for universe in due {
+            let refresh = &self.refresh_universes[&universe];
+            let Some(data) = &refresh.data else {
+                continue;
+            };
+
+            if let Err(e) = self.send_universe(
+                universe,
+                data,
+                refresh.priority.unwrap_or(E131_DEFAULT_PRIORITY),
+                &refresh.dst_ip,
+                refresh.synchronisation_addr.unwrap_or(NO_SYNC_UNIVERSE),
+            ) {
+                // The universes already prepared are still sent, the error which stopped the refresh is the one returned.
+                let _ = self.flush_data_packets();
+                return Err(e);
+            }
+
+            if let Some(sync_addr) = refresh.synchronisation_addr
+                && !syncs.contains(&(sync_addr, refresh.dst_ip))
+            {
+                syncs.push((sync_addr, refresh.dst_ip));
+            }
+
+            if let Some(refresh) = self.refresh_universes.get_mut(&universe) {
+                refresh.unchanged_sends = refresh.unchanged_sends.saturating_add(1);
+                refresh.last_sent = Some(now);
+            }
+        }
+
+        self.flush_data_packets()?;
+
+        for (sync_addr, dst_ip) in syncs {
+            self.send_sync_packet(sync_addr, dst_ip)?;
+        }
+
+        Ok(())
+    }
+
+    /// Returns true if any universe has levels to send every refresh period.
+    fn is_refreshing(&self) -> bool {
+        self.refresh_universes.values().any(|r| r.data.is_some())
+    }
+
+    /// Returns the time until the update thread should next call (perform_periodic_update)[fn.perform_periodic_update.source].
+    fn time_until_next_update(&self) -> Duration {
+        if self.is_refreshing() {
+            min(
+                DEFAULT_POLL_PERIOD,
+                (self.last_refresh_timestamp + self.refresh_period)
+                    .saturating_duration_since(self.clock.now()),
+            )
+        } else {
+            DEFAULT_POLL_PERIOD
+        }
+    }
+
     /// Sends a synchronisation packet to trigger the sending of packets waiting to be sent together.
     ///
     /// A common pattern would be to use the send method to send data to all the universes that should be synchronised using a
@@ -921,8 +1838,7 @@ impl SacnSourceInternal {
                 }),
             },
         };
-        self.socket
-            .send_to(&packet.pack_alloc()?, &ip)
+        self.send_to(&packet.pack_alloc()?, &ip)
             .map_err(|e| std::io::Error::new(e.kind(), "Failed to send sync packet on socket"))?;
 
         if sequence == 255 {
@@ -983,7 +1899,7 @@ impl SacnSourceInternal {
                     sequence_number: sequence,
                     preview_data: self.preview_data,
                     stream_terminated: true,
-                    force_synchronization: false,
+                    force_synchronization: self.force_synchronization.contains(&universe),
                     universe,
                     data: DataPacketDmpLayer {
                         property_values: vec![start_code].into(),
@@ -993,7 +1909,7 @@ impl SacnSourceInternal {
         };
         let res = &packet.pack_alloc().unwrap();
 
-        self.socket.send_to(res, &ip)?;
+        self.send_to(res, &ip)?;
 
         if sequence == 255 {
             sequence = 0;
@@ -1025,6 +1941,16 @@ impl SacnSourceInternal {
             self.send_terminate_stream_pkt(universe, None, start_code)?;
         }
 
+        self.per_address_priorities.remove(&universe);
+        self.refresh_universes.remove(&universe);
+
+        // The universe can no longer be used for synchronisation once it is deregistered.
+        for refresh in self.refresh_universes.values_mut() {
+            if refresh.synchronisation_addr == Some(universe) {
+                refresh.synchronisation_addr = None;
+            }
+        }
+
         self.deregister_universe(universe)?;
         Ok(())
     }
@@ -1117,8 +2043,102 @@ impl SacnSourceInternal {
             universe_to_ipv4_multicast_addr(E131_DISCOVERY_UNIVERSE)?
         };
 
-        self.socket.send_to(&packet.pack_alloc()?, &ip)?;
+        self.send_to(&packet.pack_alloc()?, &ip)?;
+
+        Ok(())
+    }
+
+    /// Sends the given packed packet to the given address on the socket or, if packets are being queued, adds it to the queue.
+    ///
+    /// # Errors
+    /// Io: Returned if the packet fails to be sent on the socket.
+    fn send_to(&self, packet: &[u8], dst: &SockAddr) -> std::io::Result<usize> {
+        match self.queued_packets.borrow_mut().as_mut() {
+            Some(queue) => {
+                queue.push((packet.to_vec(), dst.clone()));
+                Ok(packet.len())
+            }
+            None if self.transport.is_some() => {
+                let dst = dst.as_socket().ok_or_else(|| {
+                    std::io::Error::new(
+                        std::io::ErrorKind::InvalidInput,
+                        "Transports only send to IPv4 or IPv6 addresses",
+                    )
+                })?;
+                self.transport.as_ref().unwrap().send_to(packet, dst)
+            }
+            None => {
+                // Sent on every socket even if one fails so that an error on one network doesn't stop the others.
+                let mut res = Ok(packet.len());
+                for socket in self.send_sockets() {
+                    let sent = socket.send_to(packet, dst);
+                    if res.is_ok() {
+                        res = sent;
+                    }
+                }
+                res
+            }
+        }
+    }
+
+    /// Sends the given packed packets to their addresses on the socket or, if packets are being queued, adds them to the queue.
+    ///
+    /// On Linux the packets are sent using sendmmsg so that many packets, e.g. one for each universe of a large fixture, are sent with a
+    /// single system call per socket.
+    ///
+    /// # Errors
+    /// Io: Returned if any of the packets fail to be sent on the socket.
+    fn send_all(&self, packets: &[(&[u8], &SockAddr)]) -> std::io::Result<()> {
+        #[cfg(target_os = "linux")]
+        if self.queued_packets.borrow().is_none() && self.transport.is_none() {
+            // Sent on every socket even if one fails so that an error on one network doesn't stop the others.
+            let mut res = Ok(());
+            for socket in self.send_sockets() {
+                let sent = send_mmsg(socket, packets);
+                if res.is_ok() {
+                    res = sent;
+                }
+            }
+            return res;
+        }
+
+        let mut res = Ok(());
+        for (packet, dst) in packets {
+            let sent = self.send_to(packet, dst);
+            if res.is_ok() {
+                res = sent.map(|_| ());
+            }
+        }
+        res
+    }
+
+    /// Sends a universe discovery packet if this source is sending discovery and E131_UNIVERSE_DISCOVERY_INTERVAL has passed since
+    /// the last one was sent, then sends the universe levels if the refresh period has passed since they were last sent.
+    ///
+    /// # Errors
+    /// Returns an error if a discovery packet cannot be sent, see (send_universe_discovery)[fn.send_universe_discovery.source].
+    ///
+    /// Returns an error if the universe levels cannot be sent, see (send_refresh_universes)[fn.send_refresh_universes.source].
+    fn perform_periodic_update(&mut self) -> Result<()> {
+        let now = self.clock.now();
+        if self.is_sending_discovery
+            && now.saturating_duration_since(self.last_discovery_advert_timestamp)
+                > E131_UNIVERSE_DISCOVERY_INTERVAL
+        {
+            self.send_universe_discovery()?;
+            self.last_discovery_advert_timestamp = now;
+        }
 
+        let next_refresh = self.last_refresh_timestamp + self.refresh_period;
+        if self.is_refreshing() && now >= next_refresh {
+            // Frames are kept at a steady rate unless more than a whole frame behind, e.g. after being idle, when it restarts from now.
+            self.last_refresh_timestamp = if now - next_refresh < self.refresh_period {
+                next_refresh
+            } else {
+                now
+            };
+            self.send_refresh_universes()?;
+        }
         Ok(())
     }
 
@@ -1133,6 +2153,7 @@ impl SacnSourceInternal {
     /// cid: The new CID identifier for this source. It is left to the user to ensure that this is always unique within the network the source is in.
     fn set_cid(&mut self, cid: Uuid) {
         self.cid = cid;
+        self.data_packets.borrow_mut().clear();
     }
 
     /// Returns the ACN source name.
@@ -1154,6 +2175,7 @@ impl SacnSourceInternal {
             ));
         }
         self.name = name.to_string();
+        self.data_packets.borrow_mut().clear();
 
         Ok(())
     }
@@ -1170,6 +2192,7 @@ impl SacnSourceInternal {
     ///     for live output. If false then the flag will be set to false.
     fn set_preview_mode(&mut self, preview_mode: bool) {
         self.preview_data = preview_mode;
+        self.data_packets.borrow_mut().clear();
     }
 
     /// Sets the multicast time to live.
@@ -1180,7 +2203,10 @@ impl SacnSourceInternal {
     /// # Errors
     /// Io: Returned if the multicast TTL fails to be set on the underlying socket.
     fn set_multicast_ttl(&self, multicast_ttl: u32) -> Result<()> {
-        Ok(self.socket.set_multicast_ttl_v4(multicast_ttl)?)
+        for socket in self.sockets()? {
+            socket.set_multicast_ttl_v4(multicast_ttl)?;
+        }
+        Ok(())
     }
 
     /// Returns the current Time To Live for unicast packets send by this source.
@@ -1188,7 +2214,7 @@ impl SacnSourceInternal {
     /// # Errors
     /// Io: Returned if the TTL cannot be retrieved from the underlying socket.
     fn ttl(&self) -> Result<u32> {
-        Ok(self.socket.ttl_v4()?)
+        Ok(self.socket()?.ttl_v4()?)
     }
 
     /// Sets the Time To Live for unicast packets sent by this source.
@@ -1199,12 +2225,15 @@ impl SacnSourceInternal {
     /// # Errors
     /// Io: Returned if the TTL fails to be set on the underlying socket.
     fn set_ttl(&mut self, ttl: u32) -> Result<()> {
-        Ok(self.socket.set_ttl_v4(ttl)?)
+        for socket in self.sockets()? {
+            socket.set_ttl_v4(ttl)?;
+        }
+        Ok(())
     }
 
     /// Returns the multicast time to live of the socket.
     fn multicast_ttl(&self) -> Result<u32> {
-        Ok(self.socket.multicast_ttl_v4()?)
+        Ok(self.socket()?.multicast_ttl_v4()?)
     }
 
     /// Sets if multicast loop is enabled.
@@ -1215,12 +2244,35 @@ impl SacnSourceInternal {
     /// # Errors
     /// Io: Returned if the set_multicast_loop option fails to be set on the socket.
     fn set_multicast_loop_v4(&self, multicast_loop: bool) -> Result<()> {
-        Ok(self.socket.set_multicast_loop_v4(multicast_loop)?)
+        for socket in self.sockets()? {
+            socket.set_multicast_loop_v4(multicast_loop)?;
+        }
+        Ok(())
     }
 
     /// Returns true if multicast loop is enabled, false if not.
     fn multicast_loop(&self) -> Result<bool> {
-        Ok(self.socket.multicast_loop_v4()?)
+        Ok(self.socket()?.multicast_loop_v4()?)
+    }
+
+    /// Returns the socket followed by the sockets of the interfaces, used to apply socket options to all of them.
+    ///
+    /// # Errors
+    /// TransportUnsupported: Returned if the source sends using a transport so has no sockets.
+    fn sockets(&self) -> Result<impl Iterator<Item = &Socket>> {
+        Ok(std::iter::once(self.socket()?).chain(&self.interface_sockets))
+    }
+
+    /// Returns the socket of this source.
+    ///
+    /// # Errors
+    /// TransportUnsupported: Returned if the source sends using a transport so has no socket.
+    fn socket(&self) -> Result<&Socket> {
+        self.socket.as_ref().ok_or_else(|| {
+            SacnError::TransportUnsupported(
+                "The source sends using a transport so has no socket to configure".to_string(),
+            )
+        })
     }
 
     /// Returns the universes currently registered on this source.
@@ -1283,9 +2335,99 @@ fn unlock_internal_mut(
     }
 }
 
+/// Creates a UDP socket bound to the given address which allows the address to be reused by other sockets.
+///
+/// # Errors
+/// Io: Returned if the socket cannot be created or bound.
+///
+/// UnsupportedIpVersion: Returned if the address is not IPv4 or IPv6.
+fn create_socket(ip: SocketAddr) -> Result<Socket> {
+    let socket = if ip.is_ipv4() {
+        Socket::new(Domain::IPV4, Type::DGRAM, None).unwrap()
+    } else if ip.is_ipv6() {
+        Socket::new(Domain::IPV6, Type::DGRAM, None).unwrap()
+    } else {
+        return Err(SacnError::UnsupportedIpVersion(
+            "Address to create SacnSource is not IPv4 or IPv6".to_string(),
+        ));
+    };
+
+    // Multiple different processes might want to send to the sACN stream so therefore need to allow re-using the ACN port.
+    // Set reuse port is only supported on linux.
+    #[cfg(target_os = "linux")]
+    socket.set_reuse_port(true)?;
+
+    // Set reuse address supported on linux and windows.
+    socket.set_reuse_address(true)?;
+    socket.bind(&ip.into())?;
+
+    Ok(socket)
+}
+
+/// Sends each of the given packed packets to its address on the socket using sendmmsg, calling it again with the remaining packets
+/// if only some are sent.
+///
+/// # Errors
+/// Io: Returned if sendmmsg fails before all the packets are sent.
+#[cfg(target_os = "linux")]
+fn send_mmsg(socket: &Socket, packets: &[(&[u8], &SockAddr)]) -> std::io::Result<()> {
+    use std::os::fd::AsRawFd;
+
+    let mut iovecs: Vec<libc::iovec> = packets
+        .iter()
+        .map(|(packet, _)| libc::iovec {
+            iov_base: packet.as_ptr() as *mut libc::c_void,
+            iov_len: packet.len(),
+        })
+        .collect();
+
+    let mut msgs: Vec<libc::mmsghdr> = iovecs
+        .iter_mut()
+        .zip(packets)
+        .map(|(iovec, (_, dst))| {
+            // SAFETY: mmsghdr is a plain C struct for which all zeroes is a valid value.
+            let mut msg: libc::mmsghdr = unsafe { std::mem::zeroed() };
+            msg.msg_hdr.msg_name = dst.as_ptr() as *mut libc::c_void;
+            msg.msg_hdr.msg_namelen = dst.len();
+            msg.msg_hdr.msg_iov = iovec;
+            msg.msg_hdr.msg_iovlen = 1;
+            msg
+        })
+        .collect();
+
+    let mut sent = 0;
+    while sent < msgs.len() {
+        // SAFETY: each message points to an address and a single iovec which point to the packets, all of which outlive the call.
+        // sendmmsg only reads these and writes the number of bytes sent into msg_len of each message.
+        let res = unsafe {
+            libc::sendmmsg(
+                socket.as_raw_fd(),
+                msgs[sent..].as_mut_ptr(),
+                (msgs.len() - sent) as libc::c_uint,
+                0,
+            )
+        };
+        if res < 0 {
+            let e = std::io::Error::last_os_error();
+            if e.kind() == std::io::ErrorKind::Interrupted {
+                continue;
+            }
+            return Err(e);
+        }
+        sent += res as usize;
+    }
+
+    Ok(())
+}
+
+/// Returns the time between frames sent at the given refresh rate (frames per second), which must be non-zero.
+fn refresh_period(rate: u32) -> Duration {
+    Duration::from_secs(1) / rate
+}
+
 /// Called periodically by the source update thread.
 ///
-/// Is responsible for sending the periodic universe discovery packets.
+/// Is responsible for sending the periodic universe discovery packets and the universe levels.
 ///
 /// # Arguments:
 /// src: A reference to the SacnSourceInternal for which to send the universe discovery packet with/from.
@@ -1295,13 +2437,5 @@ fn unlock_internal_mut(
 ///
 /// Returns an error if a discovery packet cannot be sent, see (send_universe_discovery)[fn.send_universe_discovery.source].
 fn perform_periodic_update(src: &mut Arc<Mutex<SacnSourceInternal>>) -> Result<()> {
-    let mut unwrap_src = unlock_internal_mut(src)?;
-    if unwrap_src.is_sending_discovery
-        && Instant::now().duration_since(unwrap_src.last_discovery_advert_timestamp)
-            > E131_UNIVERSE_DISCOVERY_INTERVAL
-    {
-        unwrap_src.send_universe_discovery()?;
-        unwrap_src.last_discovery_advert_timestamp = Instant::now();
-    }
-    Ok(())
+    unlock_internal_mut(src)?.perform_periodic_update()
 }
@@ -0,0 +1,451 @@
+// Copyright 2020 sacn Developers
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! Asynchronous sACN sending built on the tokio runtime, enabled by the `async` feature.
+
+use super::{DEFAULT_TERMINATE_START_CODE, QueuedPackets, SacnSourceInternal, TransmissionPolicy};
+
+use crate::clock::Clock;
+use crate::error::errors::*;
+use crate::interface::NetworkInterface;
+use crate::packet::*;
+
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::sync::Arc;
+
+use tokio::net::UdpSocket;
+use tokio::sync::{Mutex, Notify};
+use tokio::task::JoinHandle;
+use tokio::time::timeout;
+
+/// UUID library used to handle the UUID's used in the CID fields.
+use uuid::Uuid;
+
+/// An asynchronous ANSI E1.31-2018 sACN source.
+///
+/// Behaves the same as a [`SacnSource`](crate::source::SacnSource), sharing its sequence numbering, validation and universe discovery
+/// logic, but sends on a tokio UdpSocket and sends the periodic universe discovery packets and universe levels from a tokio task rather
+/// than a thread.
+///
+/// The source must be created from within a tokio runtime. When dropped the discovery task is stopped and a best attempt is made to
+/// terminate the registered universes.
+///
+/// # Examples
+///
+/// ```no_run
+/// use sacn::source::AsyncSacnSource;
+///
+/// # async fn run() {
+/// let mut src = AsyncSacnSource::new_v4("Source").unwrap();
+///
+/// src.register_universe(1).await.unwrap();
+///
+/// src.send(&[1], &[0, 255, 128], Some(100), None, None).await.unwrap();
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct AsyncSacnSource {
+    /// The source which builds the packets to send, its packets are queued rather than sent and then sent on the socket.
+    internal: Arc<Mutex<SacnSourceInternal>>,
+
+    /// The tokio sockets used for sending, these share the underlying sockets that the internal source sends on. Each packet is sent on
+    /// all of them.
+    sockets: Arc<Mutex<Vec<UdpSocket>>>,
+
+    /// Task which performs actions every DEFAULT_POLL_PERIOD such as checking if a universe discovery packet should be sent, and sends the
+    /// universe levels every refresh period.
+    update_task: JoinHandle<()>,
+
+    /// Wakes the update task early, used when universe levels are set so they are sent without waiting for the next poll.
+    update_wake: Arc<Notify>,
+
+    /// The error of the most recent failed periodic update, kept until taken by (take_periodic_error)[AsyncSacnSource::take_periodic_error].
+    periodic_error: Arc<Mutex<Option<SacnError>>>,
+}
+
+impl AsyncSacnSource {
+    /// Constructs a new AsyncSacnSource with the given name, binding to an IPv4 address.
+    /// This generates a new CID automatically using random values.
+    ///
+    /// # Errors
+    /// See (with_cid_ip)[with_cid_ip]
+    pub fn new_v4(name: &str) -> Result<AsyncSacnSource> {
+        let ip = SocketAddr::new(IpAddr::V4(Ipv4Addr::UNSPECIFIED), ACN_SDT_MULTICAST_PORT);
+        AsyncSacnSource::with_cid_ip(name, Uuid::new_v4(), ip)
+    }
+
+    /// Constructs a new AsyncSacnSource with the given name, binding to an IPv6 address.
+    /// This generates a new CID automatically using random values.
+    ///
+    /// # Errors
+    /// See (with_cid_ip)[with_cid_ip]
+    pub fn new_v6(name: &str) -> Result<AsyncSacnSource> {
+        let ip = SocketAddr::new(IpAddr::V6(Ipv6Addr::UNSPECIFIED), ACN_SDT_MULTICAST_PORT);
+        AsyncSacnSource::with_cid_ip(name, Uuid::new_v4(), ip)
+    }
+
+    /// Constructs a new AsyncSacnSource with the given name and binding to the supplied ip.
+    ///
+    /// # Errors
+    /// See (with_cid_ip)[with_cid_ip]
+    pub fn with_ip(name: &str, ip: SocketAddr) -> Result<AsyncSacnSource> {
+        AsyncSacnSource::with_cid_ip(name, Uuid::new_v4(), ip)
+    }
+
+    /// Constructs a new AsyncSacnSource with the given name, specified CID and binding to the supplied ip.
+    ///
+    /// This must be called from within a tokio runtime as it spawns the task which sends universe discovery packets.
+    ///
+    /// # Errors
+    /// MalformedSourceName: Returned if the source name is longer than the maximum allowed.
+    ///
+    /// Io: Returned if the underlying socket cannot be created, bound or registered with the tokio runtime.
+    ///
+    /// UnsupportedIpVersion: Returned if the SockAddr is not IPv4 or IPv6.
+    pub fn with_cid_ip(name: &str, cid: Uuid, ip: SocketAddr) -> Result<AsyncSacnSource> {
+        if name.len() > E131_SOURCE_NAME_FIELD_LENGTH {
+            return Err(SacnError::MalformedSourceName(
+                "Source name provided is longer than maximum allowed".to_string(),
+            ));
+        }
+
+        let internal = SacnSourceInternal::with_cid_ip(name, cid, ip)?;
+        internal.queued_packets.replace(Some(Vec::new()));
+
+        let sockets = Arc::new(Mutex::new(tokio_sockets(&internal)?));
+
+        let internal = Arc::new(Mutex::new(internal));
+
+        let update_wake = Arc::new(Notify::new());
+
+        let periodic_error = Arc::new(Mutex::new(None));
+
+        let task_src = internal.clone();
+        let task_sockets = sockets.clone();
+        let task_wake = update_wake.clone();
+        let task_error = periodic_error.clone();
+        let update_task = tokio::spawn(async move {
+            loop {
+                let delay = task_src.lock().await.time_until_next_update();
+                let _ = timeout(delay, task_wake.notified()).await;
+                let mut src = task_src.lock().await;
+                if !src.running {
+                    break;
+                }
+                let res = src.perform_periodic_update();
+                let sent = send_queued(&task_sockets.lock().await, take_queued(&src)).await;
+                if let Err(e) = sent.and(res) {
+                    // In-case of an error on the discovery task the source continues to operate and tries again, the error is kept so
+                    // that it can be taken through the source.
+                    *task_error.lock().await = Some(e);
+                }
+            }
+        });
+
+        Ok(AsyncSacnSource {
+            internal,
+            sockets,
+            update_task,
+            update_wake,
+            periodic_error,
+        })
+    }
+
+    /// Registers the given universes on this source in addition to already registered universes.
+    ///
+    /// # Errors
+    /// IllegalUniverse: Returned if a universe is outwith the range permitted by ANSI E1.31-2018.
+    pub async fn register_universes(&mut self, universes: &[u16]) -> Result<()> {
+        self.internal.lock().await.register_universes(universes)
+    }
+
+    /// Registers a single universe on this source, see (register_universes)[AsyncSacnSource::register_universes].
+    ///
+    /// # Errors
+    /// IllegalUniverse: Returned if the universe is outwith the range permitted by ANSI E1.31-2018.
+    pub async fn register_universe(&mut self, universe: u16) -> Result<()> {
+        self.internal.lock().await.register_universe(universe)
+    }
+
+    /// Sends the given data to the given universes with the given priority, synchronisation address (universe) and destination ip.
+    ///
+    /// See (SacnSource::send)[crate::source::SacnSource::send] for details of the arguments.
+    ///
+    /// # Errors
+    /// See (SacnSource::send)[crate::source::SacnSource::send].
+    pub async fn send(
+        &mut self,
+        universes: &[u16],
+        data: &[u8],
+        priority: Option<u8>,
+        dst_ip: Option<SocketAddr>,
+        synchronisation_addr: Option<u16>,
+    ) -> Result<()> {
+        let src = self.internal.lock().await;
+        let res = src.send(universes, data, priority, dst_ip, synchronisation_addr);
+        send_queued(&self.sockets.lock().await, take_queued(&src)).await?;
+        res
+    }
+
+    /// Sets the per-address priorities (start code 0xDD) to send alongside the DMX data for the given universe.
+    ///
+    /// See (SacnSource::set_per_address_priority)[crate::source::SacnSource::set_per_address_priority].
+    ///
+    /// # Errors
+    /// See (SacnSource::set_per_address_priority)[crate::source::SacnSource::set_per_address_priority].
+    pub async fn set_per_address_priority(
+        &mut self,
+        universe: u16,
+        priorities: Option<&[u8]>,
+    ) -> Result<()> {
+        self.internal
+            .lock()
+            .await
+            .set_per_address_priority(universe, priorities)
+    }
+
+    /// Sets if data packets for the given universe are sent with the Force_Synchronization option bit set.
+    ///
+    /// See (SacnSource::set_force_synchronization)[crate::source::SacnSource::set_force_synchronization].
+    ///
+    /// # Errors
+    /// See (SacnSource::set_force_synchronization)[crate::source::SacnSource::set_force_synchronization].
+    pub async fn set_force_synchronization(
+        &mut self,
+        universe: u16,
+        force_synchronization: bool,
+    ) -> Result<()> {
+        self.internal
+            .lock()
+            .await
+            .set_force_synchronization(universe, force_synchronization)
+    }
+
+    /// Sets the levels of the given universe which are then sent by the update task every refresh period until cleared or the universe is
+    /// terminated.
+    ///
+    /// See (SacnSource::set_universe_levels)[crate::source::SacnSource::set_universe_levels].
+    ///
+    /// # Errors
+    /// See (SacnSource::set_universe_levels)[crate::source::SacnSource::set_universe_levels].
+    pub async fn set_universe_levels(&mut self, universe: u16, levels: &[u8]) -> Result<()> {
+        self.internal
+            .lock()
+            .await
+            .set_universe_levels(universe, levels)?;
+        self.update_wake.notify_one();
+        Ok(())
+    }
+
+    /// Stops sending the levels of the given universe.
+    ///
+    /// # Errors
+    /// See (SacnSource::clear_universe_levels)[crate::source::SacnSource::clear_universe_levels].
+    pub async fn clear_universe_levels(&mut self, universe: u16) -> Result<()> {
+        self.internal.lock().await.clear_universe_levels(universe)
+    }
+
+    /// Sets the priority, destination ip and synchronisation address that the levels of the given universe are sent with.
+    ///
+    /// See (SacnSource::set_refresh_options)[crate::source::SacnSource::set_refresh_options].
+    ///
+    /// # Errors
+    /// See (SacnSource::set_refresh_options)[crate::source::SacnSource::set_refresh_options].
+    pub async fn set_refresh_options(
+        &mut self,
+        universe: u16,
+        priority: Option<u8>,
+        dst_ip: Option<SocketAddr>,
+        synchronisation_addr: Option<u16>,
+    ) -> Result<()> {
+        self.internal.lock().await.set_refresh_options(
+            universe,
+            priority,
+            dst_ip,
+            synchronisation_addr,
+        )
+    }
+
+    /// Sets how often the levels of the given universe are sent.
+    ///
+    /// See (SacnSource::set_transmission_policy)[crate::source::SacnSource::set_transmission_policy].
+    ///
+    /// # Errors
+    /// See (SacnSource::set_transmission_policy)[crate::source::SacnSource::set_transmission_policy].
+    pub async fn set_transmission_policy(
+        &mut self,
+        universe: u16,
+        policy: TransmissionPolicy,
+    ) -> Result<()> {
+        self.internal
+            .lock()
+            .await
+            .set_transmission_policy(universe, policy)
+    }
+
+    /// Sets the rate (frames per second) at which the universe levels are sent.
+    ///
+    /// # Errors
+    /// See (SacnSource::set_refresh_rate)[crate::source::SacnSource::set_refresh_rate].
+    pub async fn set_refresh_rate(&mut self, rate: u32) -> Result<()> {
+        self.internal.lock().await.set_refresh_rate(rate)
+    }
+
+    /// Returns the rate (frames per second) at which the universe levels are sent.
+    pub async fn refresh_rate(&self) -> u32 {
+        self.internal.lock().await.refresh_rate()
+    }
+
+    /// Sends a synchronisation packet to trigger the sending of packets waiting to be sent together.
+    ///
+    /// # Arguments
+    /// universe: The universe of this synchronisation packet.
+    /// dst_ip:   The destination IP address for this packet or None if it should be sent using multicast.
+    ///
+    /// # Errors
+    /// See (SacnSource::send_sync_packet)[crate::source::SacnSource::send_sync_packet].
+    pub async fn send_sync_packet(
+        &mut self,
+        universe: u16,
+        dst_ip: Option<SocketAddr>,
+    ) -> Result<()> {
+        let src = self.internal.lock().await;
+        let res = src.send_sync_packet(universe, dst_ip);
+        send_queued(&self.sockets.lock().await, take_queued(&src)).await?;
+        res
+    }
+
+    /// Terminates sending on the given universe.
+    ///
+    /// # Errors
+    /// See (SacnSource::terminate_stream)[crate::source::SacnSource::terminate_stream].
+    pub async fn terminate_stream(&mut self, universe: u16, start_code: u8) -> Result<()> {
+        let mut src = self.internal.lock().await;
+        let res = src.terminate_stream(universe, start_code);
+        send_queued(&self.sockets.lock().await, take_queued(&src)).await?;
+        res
+    }
+
+    /// Sets the network interfaces that this source sends on, replacing any previously set.
+    ///
+    /// See (SacnSource::set_interfaces)[crate::source::SacnSource::set_interfaces].
+    ///
+    /// # Errors
+    /// See (SacnSource::set_interfaces)[crate::source::SacnSource::set_interfaces].
+    ///
+    /// Io: Returned if the sockets of the interfaces cannot be registered with the tokio runtime.
+    pub async fn set_interfaces(&mut self, interfaces: &[NetworkInterface]) -> Result<()> {
+        let mut src = self.internal.lock().await;
+        src.set_interfaces(interfaces)?;
+        *self.sockets.lock().await = tokio_sockets(&src)?;
+        Ok(())
+    }
+
+    /// Returns the network interfaces that this source sends on, empty if the operating system chooses the interface.
+    pub async fn interfaces(&self) -> Vec<NetworkInterface> {
+        self.internal.lock().await.interfaces.clone()
+    }
+
+    /// Sets the is_sending_discovery flag, if false then no universe discovery packets are sent by this source.
+    pub async fn set_is_sending_discovery(&mut self, val: bool) {
+        self.internal.lock().await.set_is_sending_discovery(val);
+    }
+
+    /// Sets the clock that the discovery interval and refresh period are measured with, see (SacnSource::set_clock)[SacnSource::set_clock].
+    pub async fn set_clock<C: Clock + 'static>(&mut self, clock: C) {
+        self.internal.lock().await.set_clock(Arc::new(clock));
+    }
+
+    /// Sets the preview mode flag which is included in the packets sent by this source.
+    pub async fn set_preview_mode(&mut self, preview_mode: bool) {
+        self.internal.lock().await.set_preview_mode(preview_mode);
+    }
+
+    /// Returns the universes currently registered on this source.
+    pub async fn universes(&self) -> Vec<u16> {
+        self.internal.lock().await.universes()
+    }
+
+    /// Returns the ACN CID device identifier of this source.
+    pub async fn cid(&self) -> Uuid {
+        *self.internal.lock().await.cid()
+    }
+
+    /// Returns the human readable name of this source.
+    pub async fn name(&self) -> String {
+        self.internal.lock().await.name().to_string()
+    }
+
+    /// Takes the error of the most recent periodic update which failed, such as sending the universe levels or a universe discovery
+    /// packet, returning None if no periodic update has failed since this was last called.
+    ///
+    /// The source keeps operating after such an error and tries again on the next update, only the latest error is kept.
+    pub async fn take_periodic_error(&mut self) -> Option<SacnError> {
+        self.periodic_error.lock().await.take()
+    }
+}
+
+/// By implementing the Drop trait for AsyncSacnSource the discovery task is stopped and the termination packets are sent as a 'best attempt'.
+impl Drop for AsyncSacnSource {
+    fn drop(&mut self) {
+        self.update_task.abort();
+
+        // Drop cannot wait for the lock or for the socket so the termination packets are only sent if the source isn't in use and are sent
+        // directly on the non-blocking socket, errors are ignored for the same reasons as for SacnSource.
+        if let Ok(mut src) = self.internal.try_lock() {
+            src.queued_packets.replace(None);
+            let _ = src.terminate(DEFAULT_TERMINATE_START_CODE);
+        }
+    }
+}
+
+/// Takes the packets queued by the given source leaving the queue empty.
+fn take_queued(src: &SacnSourceInternal) -> QueuedPackets {
+    src.queued_packets
+        .replace(Some(Vec::new()))
+        .unwrap_or_default()
+}
+
+/// Returns tokio sockets sharing the underlying sockets that the given source sends on.
+///
+/// # Errors
+/// Io: Returned if a socket cannot be cloned or registered with the tokio runtime.
+fn tokio_sockets(src: &SacnSourceInternal) -> Result<Vec<UdpSocket>> {
+    src.send_sockets()
+        .iter()
+        .map(|s| {
+            let socket: std::net::UdpSocket = s.try_clone()?.into();
+            socket.set_nonblocking(true)?;
+            Ok(UdpSocket::from_std(socket)?)
+        })
+        .collect()
+}
+
+/// Sends the given packets in order, each on all of the given sockets.
+///
+/// A packet is sent on every socket even if sending on one fails so that an error on one network doesn't stop the others.
+///
+/// # Errors
+/// Io: Returned if a packet fails to be sent on a socket, the first such error is returned.
+///
+/// UnsupportedIpVersion: Returned if a destination is not an IPv4 or IPv6 address.
+async fn send_queued(sockets: &[UdpSocket], packets: QueuedPackets) -> Result<()> {
+    let mut res = Ok(());
+    for (packet, dst) in packets {
+        let dst = dst.as_socket().ok_or_else(|| {
+            SacnError::UnsupportedIpVersion("Destination is not IPv4 or IPv6".to_string())
+        })?;
+        for socket in sockets {
+            if let Err(e) = socket.send_to(&packet, dst).await
+                && res.is_ok()
+            {
+                res = Err(e.into());
+            }
+        }
+    }
+    res
+}
@@ -0,0 +1,798 @@
+// Copyright 2020 sacn Developers
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! Pluggable transports that sACN packets are sent and received over instead of the UDP sockets that sources and receivers create by default.
+//!
+//! A source created with (SacnSource::with_transport)[crate::source::SacnSource::with_transport] sends every packet using the given
+//! [`Transport`] and a receiver created with (SacnReceiver::with_transport)[crate::receive::SacnReceiver::with_transport] receives from it,
+//! joining and leaving the multicast groups of the universes it listens to through it.
+//!
+//! [`MemoryNetwork`] is an in-process network with UDP style unicast and multicast delivery. Sources and receivers with transports bound
+//! on the same MemoryNetwork talk to each other without touching the real network, which makes tests deterministic and independent of
+//! the network configuration of the machine running them.
+//!
+//! A MemoryNetwork can also lose, duplicate, reorder and delay packets as set by [`NetworkFaults`], with every decision drawn from a
+//! seeded generator so that a failing run can be reproduced from its seed.
+//!
+//! # Examples
+//!
+//! ```
+//! use sacn::packet::ACN_SDT_MULTICAST_PORT;
+//! use sacn::receive::SacnReceiver;
+//! use sacn::source::SacnSource;
+//! use sacn::transport::MemoryNetwork;
+//!
+//! use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+//! use std::time::Duration;
+//!
+//! let network = MemoryNetwork::new();
+//!
+//! let rcv_addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::UNSPECIFIED), ACN_SDT_MULTICAST_PORT);
+//! let mut dmx_rcv = SacnReceiver::with_transport(network.bind(rcv_addr), None).unwrap();
+//! dmx_rcv.listen_universes(&[1]).unwrap();
+//!
+//! let src_addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(192, 168, 0, 10)), 0);
+//! let mut src = SacnSource::with_transport("Source", network.bind(src_addr)).unwrap();
+//! src.register_universe(1).unwrap();
+//! src.send(&[1], &[0, 255, 128], None, None, None).unwrap();
+//!
+//! let data = dmx_rcv.recv(Some(Duration::from_secs(1))).unwrap();
+//! assert_eq!(data[0].values, vec![0, 255, 128]);
+//! ```
+
+use std::collections::{HashSet, VecDeque};
+use std::fmt;
+use std::io;
+use std::net::{IpAddr, Ipv4Addr, SocketAddr, UdpSocket};
+use std::sync::{Arc, Condvar, Mutex, MutexGuard, Weak};
+use std::time::{Duration, Instant};
+
+/// The first port given to transports bound to port 0 on a MemoryNetwork, the start of the IANA dynamic port range.
+const MEMORY_NETWORK_FIRST_EPHEMERAL_PORT: u16 = 49152;
+
+/// A way of sending and receiving datagrams, used by sources and receivers in place of their own UDP sockets.
+///
+/// Implementations must behave like a UDP socket: packets are delivered whole or not at all, a packet longer than the buffer it is
+/// received into is truncated and packets sent to a multicast address are received by every transport which has joined that group.
+pub trait Transport: fmt::Debug + Send {
+    /// Returns the address that this transport is bound to.
+    ///
+    /// # Errors
+    /// Io: Returned if the address cannot be determined.
+    fn local_addr(&self) -> io::Result<SocketAddr>;
+
+    /// Sends the given packet to the given address, returning the number of bytes sent.
+    ///
+    /// # Errors
+    /// Io: Returned if the packet cannot be sent.
+    fn send_to(&self, packet: &[u8], dst: SocketAddr) -> io::Result<usize>;
+
+    /// Receives a packet into the given buffer, returning the number of bytes received and the address the packet was sent from.
+    ///
+    /// # Arguments
+    /// buf: The buffer to receive the packet into, any part of the packet which doesn't fit is discarded.
+    ///
+    /// timeout: The maximum time to wait for a packet, None to wait forever.
+    ///
+    /// # Errors
+    /// Io: Returned with the kind WouldBlock or TimedOut if no packet arrives within the timeout, or another kind if receiving fails.
+    fn recv_from(
+        &self,
+        buf: &mut [u8],
+        timeout: Option<Duration>,
+    ) -> io::Result<(usize, SocketAddr)>;
+
+    /// Joins the given multicast group so that packets sent to it are received.
+    ///
+    /// # Errors
+    /// Io: Returned if the group cannot be joined.
+    fn join_multicast(&self, group: IpAddr) -> io::Result<()>;
+
+    /// Leaves the given multicast group so that packets sent to it are no longer received.
+    ///
+    /// # Errors
+    /// Io: Returned if the group cannot be left, e.g. because it wasn't joined.
+    fn leave_multicast(&self, group: IpAddr) -> io::Result<()>;
+}
+
+/// A plain UDP socket can be used as a transport, with multicast groups joined on the interface chosen by the operating system.
+impl Transport for UdpSocket {
+    fn local_addr(&self) -> io::Result<SocketAddr> {
+        UdpSocket::local_addr(self)
+    }
+
+    fn send_to(&self, packet: &[u8], dst: SocketAddr) -> io::Result<usize> {
+        UdpSocket::send_to(self, packet, dst)
+    }
+
+    fn recv_from(
+        &self,
+        buf: &mut [u8],
+        timeout: Option<Duration>,
+    ) -> io::Result<(usize, SocketAddr)> {
+        // A zero read timeout is rejected by the socket so the shortest possible timeout is used instead.
+        self.set_read_timeout(timeout.map(|t| t.max(Duration::from_nanos(1))))?;
+        UdpSocket::recv_from(self, buf)
+    }
+
+    fn join_multicast(&self, group: IpAddr) -> io::Result<()> {
+        match group {
+            IpAddr::V4(g) => self.join_multicast_v4(&g, &Ipv4Addr::UNSPECIFIED),
+            IpAddr::V6(g) => self.join_multicast_v6(&g, 0),
+        }
+    }
+
+    fn leave_multicast(&self, group: IpAddr) -> io::Result<()> {
+        match group {
+            IpAddr::V4(g) => self.leave_multicast_v4(&g, &Ipv4Addr::UNSPECIFIED),
+            IpAddr::V6(g) => self.leave_multicast_v6(&g, 0),
+        }
+    }
+}
+
+/// An in-process network that [`MemoryTransport`]s are bound to, delivering packets between them in the order they are sent.
+///
+/// Delivery follows UDP with the address reuse that sources and receivers enable on their sockets:
+/// - A packet sent to a multicast address is delivered to every transport bound to the destination port which has joined the group,
+///   including the sender.
+/// - A packet sent to a unicast address is delivered to every transport bound to the destination port and either that address or the
+///   unspecified address of the same IP version.
+///
+/// By default packets are never lost, duplicated or reordered, see (MemoryNetwork::with_faults)[MemoryNetwork::with_faults] to simulate
+/// an unreliable network. Cloning a MemoryNetwork gives another handle to the same network.
+#[derive(Clone, Debug, Default)]
+pub struct MemoryNetwork {
+    /// The state shared by every handle to the network and every transport bound to it.
+    inner: Arc<Mutex<MemoryNetworkInner>>,
+}
+
+/// The state of a MemoryNetwork.
+#[derive(Debug, Default)]
+struct MemoryNetworkInner {
+    /// The transports bound to the network, in the order they were bound. Entries of dropped transports are removed when next sending.
+    endpoints: Vec<Weak<MemoryEndpoint>>,
+
+    /// The port given to the next transport bound to port 0, 0 if none have been yet.
+    next_ephemeral_port: u16,
+
+    /// The faults applied to packets delivered on the network.
+    faults: NetworkFaults,
+
+    /// The generator that every fault decision is drawn from, seeded from the faults.
+    rng: FaultRng,
+}
+
+/// The faults that a [`MemoryNetwork`] applies to the packets it delivers, the default is a perfect network.
+///
+/// Faults are decided separately for each transport a packet is delivered to, as if each was at the end of its own link. The
+/// probabilities are from 0 (never) to 1 (always). Decisions are drawn from a generator seeded with seed, so the same faults and seed
+/// with the same packets sent in the same order always give the same decisions.
+///
+/// # Examples
+///
+/// ```
+/// use sacn::transport::{MemoryNetwork, NetworkFaults};
+///
+/// use std::time::Duration;
+///
+/// let network = MemoryNetwork::with_faults(NetworkFaults {
+///     loss: 0.05,
+///     duplication: 0.02,
+///     reordering: 0.1,
+///     delay: 0.1,
+///     max_delay: Duration::from_millis(20),
+///     seed: 42,
+/// });
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct NetworkFaults {
+    /// The probability that a packet is lost.
+    pub loss: f64,
+
+    /// The probability that a packet is delivered twice, each copy can then be lost, reordered or delayed separately.
+    pub duplication: f64,
+
+    /// The probability that a packet is held back and delivered after the next packet, or once there is nothing else to receive.
+    pub reordering: f64,
+
+    /// The probability that a packet is delayed by a random time up to max_delay, which can also reorder it after later packets.
+    pub delay: f64,
+
+    /// The longest time that a delayed packet is delayed by.
+    pub max_delay: Duration,
+
+    /// The seed of the generator that the faults are drawn from.
+    pub seed: u64,
+}
+
+/// A small SplitMix64 pseudo-random generator, good enough for drawing faults and reproducible across platforms.
+#[derive(Debug, Default)]
+struct FaultRng {
+    /// The state of the generator, advanced on every draw.
+    state: u64,
+}
+
+impl FaultRng {
+    /// Creates a generator which produces the sequence given by the seed.
+    fn new(seed: u64) -> FaultRng {
+        FaultRng { state: seed }
+    }
+
+    /// Returns the next number in the sequence.
+    fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Returns the next number in the sequence as a value in [0, 1).
+    fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// Returns true with the given probability.
+    fn chance(&mut self, probability: f64) -> bool {
+        self.next_f64() < probability
+    }
+}
+
+/// The receiving side of a MemoryTransport which the network delivers packets to.
+#[derive(Debug)]
+struct MemoryEndpoint {
+    /// The address the transport is bound to, with the port filled in if it was bound to port 0.
+    addr: SocketAddr,
+
+    /// The multicast groups the transport has joined.
+    groups: Mutex<HashSet<IpAddr>>,
+
+    /// The packets delivered to the transport which haven't been received yet.
+    queue: Mutex<EndpointQueue>,
+
+    /// Notified whenever a packet is added to the queue.
+    ready: Condvar,
+}
+
+/// The packets waiting to be received by a MemoryTransport.
+#[derive(Debug, Default)]
+struct EndpointQueue {
+    /// The packets delivered to the transport, ordered by the time they can be received.
+    packets: VecDeque<QueuedPacket>,
+
+    /// A packet held back to be reordered after the next packet delivered.
+    held: Option<QueuedPacket>,
+}
+
+/// A packet delivered to a MemoryTransport.
+#[derive(Debug)]
+struct QueuedPacket {
+    /// The contents of the packet.
+    data: Vec<u8>,
+
+    /// The address the packet was sent from.
+    src: SocketAddr,
+
+    /// The time from which the packet can be received, later than when it was sent if it was delayed.
+    release: Instant,
+}
+
+/// A transport bound to an address on a [`MemoryNetwork`], see (MemoryNetwork::bind)[MemoryNetwork::bind].
+///
+/// The transport is removed from the network when dropped.
+#[derive(Debug)]
+pub struct MemoryTransport {
+    /// The network the transport is bound to.
+    network: MemoryNetwork,
+
+    /// The endpoint packets sent to this transport are delivered to.
+    endpoint: Arc<MemoryEndpoint>,
+}
+
+impl MemoryNetwork {
+    /// Creates a new, empty, network.
+    pub fn new() -> MemoryNetwork {
+        MemoryNetwork::default()
+    }
+
+    /// Creates a new, empty, network which applies the given faults to the packets it delivers.
+    ///
+    /// # Arguments
+    /// faults: The faults to apply, along with the seed they are drawn from.
+    pub fn with_faults(faults: NetworkFaults) -> MemoryNetwork {
+        let network = MemoryNetwork::new();
+        network.set_faults(faults);
+        network
+    }
+
+    /// Sets the faults applied to packets delivered from now on, restarting the generator they are drawn from at their seed.
+    ///
+    /// Packets already delayed or held back for reordering are still delivered as decided when they were sent.
+    ///
+    /// # Arguments
+    /// faults: The faults to apply, NetworkFaults::default() for a perfect network.
+    pub fn set_faults(&self, faults: NetworkFaults) {
+        let mut inner = self.lock();
+        inner.faults = faults;
+        inner.rng = FaultRng::new(faults.seed);
+    }
+
+    /// Returns the faults currently applied to packets delivered on the network.
+    pub fn faults(&self) -> NetworkFaults {
+        self.lock().faults
+    }
+
+    /// Creates a transport bound to the given address on this network.
+    ///
+    /// Several transports can be bound to the same address, as with sources and receivers sharing the ACN port on a real network. If the
+    /// port is 0 then a port which no other transport on the network is bound to is chosen.
+    ///
+    /// # Arguments
+    /// addr: The address to bind to, packets sent by the transport appear to come from this address.
+    pub fn bind(&self, mut addr: SocketAddr) -> MemoryTransport {
+        let mut inner = self.lock();
+        inner.endpoints.retain(|e| e.strong_count() > 0);
+
+        if addr.port() == 0 {
+            addr.set_port(inner.ephemeral_port());
+        }
+
+        let endpoint = Arc::new(MemoryEndpoint {
+            addr,
+            groups: Mutex::new(HashSet::new()),
+            queue: Mutex::new(EndpointQueue::default()),
+            ready: Condvar::new(),
+        });
+        inner.endpoints.push(Arc::downgrade(&endpoint));
+
+        MemoryTransport {
+            network: self.clone(),
+            endpoint,
+        }
+    }
+
+    /// Delivers the given packet sent from src to every transport that would receive a packet sent to dst, applying the faults of the
+    /// network, returning the number of bytes sent.
+    fn deliver(&self, packet: &[u8], src: SocketAddr, dst: SocketAddr) -> usize {
+        let mut inner = self.lock();
+        inner.endpoints.retain(|e| e.strong_count() > 0);
+
+        let now = Instant::now();
+        let endpoints: Vec<Arc<MemoryEndpoint>> = inner
+            .endpoints
+            .iter()
+            .filter_map(Weak::upgrade)
+            .filter(|e| e.receives(dst))
+            .collect();
+
+        for endpoint in endpoints {
+            let copies = if inner.draw_duplication() { 2 } else { 1 };
+            for _ in 0..copies {
+                if inner.draw_loss() {
+                    continue;
+                }
+                let queued = QueuedPacket {
+                    data: packet.to_vec(),
+                    src,
+                    release: now + inner.draw_delay(),
+                };
+                let reorder = inner.draw_reordering();
+
+                let mut queue = lock(&endpoint.queue);
+                if reorder && queue.held.is_none() {
+                    queue.held = Some(queued);
+                } else {
+                    let release = queued.release;
+                    queue.push(queued);
+                    if let Some(mut held) = queue.held.take() {
+                        held.release = held.release.max(release);
+                        queue.push(held);
+                    }
+                }
+                endpoint.ready.notify_all();
+            }
+        }
+
+        packet.len()
+    }
+
+    /// Locks the state of the network.
+    fn lock(&self) -> MutexGuard<'_, MemoryNetworkInner> {
+        lock(&self.inner)
+    }
+}
+
+impl MemoryNetworkInner {
+    /// Returns true if the next packet should be lost.
+    fn draw_loss(&mut self) -> bool {
+        self.rng.chance(self.faults.loss)
+    }
+
+    /// Returns true if the next packet should be duplicated.
+    fn draw_duplication(&mut self) -> bool {
+        self.rng.chance(self.faults.duplication)
+    }
+
+    /// Returns true if the next packet should be held back to be reordered.
+    fn draw_reordering(&mut self) -> bool {
+        self.rng.chance(self.faults.reordering)
+    }
+
+    /// Returns the time the next packet should be delayed by, zero if it isn't delayed.
+    fn draw_delay(&mut self) -> Duration {
+        if self.rng.chance(self.faults.delay) {
+            self.faults.max_delay.mul_f64(self.rng.next_f64())
+        } else {
+            Duration::ZERO
+        }
+    }
+
+    /// Returns a port which no transport on the network is bound to.
+    fn ephemeral_port(&mut self) -> u16 {
+        loop {
+            let port = self
+                .next_ephemeral_port
+                .max(MEMORY_NETWORK_FIRST_EPHEMERAL_PORT);
+            self.next_ephemeral_port = port.checked_add(1).unwrap_or(0);
+            let in_use = self
+                .endpoints
+                .iter()
+                .filter_map(Weak::upgrade)
+                .any(|e| e.addr.port() == port);
+            if !in_use {
+                return port;
+            }
+        }
+    }
+}
+
+impl MemoryEndpoint {
+    /// Returns true if a packet sent to the given address is delivered to this endpoint.
+    fn receives(&self, dst: SocketAddr) -> bool {
+        if dst.port() != self.addr.port() {
+            return false;
+        }
+
+        if dst.ip().is_multicast() {
+            lock(&self.groups).contains(&dst.ip())
+        } else {
+            let bound = self.addr.ip();
+            bound == dst.ip() || (bound.is_unspecified() && bound.is_ipv4() == dst.is_ipv4())
+        }
+    }
+}
+
+impl EndpointQueue {
+    /// Adds the given packet after every packet which can be received no later than it.
+    fn push(&mut self, packet: QueuedPacket) {
+        let index = self
+            .packets
+            .iter()
+            .rposition(|p| p.release <= packet.release)
+            .map_or(0, |i| i + 1);
+        self.packets.insert(index, packet);
+    }
+
+    /// Removes and returns the next packet if it can be received at the given time.
+    ///
+    /// A packet held back for reordering is released if no other packet can be received, so it is only ever reordered after packets
+    /// which were already waiting or arrive before the transport would otherwise wait.
+    fn pop(&mut self, now: Instant) -> Option<QueuedPacket> {
+        if self.packets.front().is_none_or(|p| p.release > now)
+            && let Some(held) = self.held.take()
+        {
+            self.push(held);
+        }
+
+        if self.packets.front()?.release <= now {
+            self.packets.pop_front()
+        } else {
+            None
+        }
+    }
+
+    /// Returns the time at which the next packet can be received, if there is one.
+    fn next_release(&self) -> Option<Instant> {
+        self.packets.front().map(|p| p.release)
+    }
+}
+
+impl Transport for MemoryTransport {
+    fn local_addr(&self) -> io::Result<SocketAddr> {
+        Ok(self.endpoint.addr)
+    }
+
+    fn send_to(&self, packet: &[u8], dst: SocketAddr) -> io::Result<usize> {
+        Ok(self.network.deliver(packet, self.endpoint.addr, dst))
+    }
+
+    fn recv_from(
+        &self,
+        buf: &mut [u8],
+        timeout: Option<Duration>,
+    ) -> io::Result<(usize, SocketAddr)> {
+        let deadline = timeout.map(|t| Instant::now() + t);
+        let mut queue = lock(&self.endpoint.queue);
+        loop {
+            let now = Instant::now();
+            if let Some(packet) = queue.pop(now) {
+                let len = packet.data.len().min(buf.len());
+                buf[..len].copy_from_slice(&packet.data[..len]);
+                return Ok((len, packet.src));
+            }
+
+            // Wake at the deadline or when the next delayed packet can be received, whichever is first.
+            let wake = match (deadline, queue.next_release()) {
+                (Some(d), Some(r)) => Some(d.min(r)),
+                (d, r) => d.or(r),
+            };
+
+            queue = match wake {
+                None => self
+                    .endpoint
+                    .ready
+                    .wait(queue)
+                    .unwrap_or_else(|e| e.into_inner()),
+                Some(wake) => {
+                    if deadline.is_some_and(|d| now >= d) {
+                        return Err(io::Error::new(
+                            io::ErrorKind::WouldBlock,
+                            "No packet received within the timeout",
+                        ));
+                    }
+                    self.endpoint
+                        .ready
+                        .wait_timeout(queue, wake.saturating_duration_since(now))
+                        .unwrap_or_else(|e| e.into_inner())
+                        .0
+                }
+            };
+        }
+    }
+
+    fn join_multicast(&self, group: IpAddr) -> io::Result<()> {
+        if !group.is_multicast() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("{group} is not a multicast address"),
+            ));
+        }
+        if !lock(&self.endpoint.groups).insert(group) {
+            return Err(io::Error::new(
+                io::ErrorKind::AddrInUse,
+                format!("Multicast group {group} already joined"),
+            ));
+        }
+        Ok(())
+    }
+
+    fn leave_multicast(&self, group: IpAddr) -> io::Result<()> {
+        if !lock(&self.endpoint.groups).remove(&group) {
+            return Err(io::Error::new(
+                io::ErrorKind::AddrNotAvailable,
+                format!("Multicast group {group} not joined"),
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Locks the given mutex, a panic while it was held can't leave the data it protects inconsistent so poisoning is ignored.
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use std::net::Ipv6Addr;
+
+    fn addr(ip: [u8; 4], port: u16) -> SocketAddr {
+        SocketAddr::new(IpAddr::from(ip), port)
+    }
+
+    /// Packets sent to a multicast group should only reach the transports on the destination port which have joined the group.
+    #[test]
+    fn test_memory_network_multicast() {
+        let network = MemoryNetwork::new();
+        let group = IpAddr::from([239, 255, 0, 1]);
+
+        let joined = network.bind(addr([0, 0, 0, 0], 5568));
+        let not_joined = network.bind(addr([0, 0, 0, 0], 5568));
+        let other_port = network.bind(addr([0, 0, 0, 0], 5569));
+        joined.join_multicast(group).unwrap();
+        other_port.join_multicast(group).unwrap();
+
+        let src = network.bind(addr([10, 0, 0, 1], 0));
+        src.send_to(&[1, 2, 3], SocketAddr::new(group, 5568))
+            .unwrap();
+
+        let mut buf = [0; 8];
+        let timeout = Some(Duration::ZERO);
+        assert_eq!(
+            joined.recv_from(&mut buf, timeout).unwrap(),
+            (3, src.local_addr().unwrap())
+        );
+        assert_eq!(&buf[..3], &[1, 2, 3]);
+        assert_eq!(
+            not_joined.recv_from(&mut buf, timeout).unwrap_err().kind(),
+            io::ErrorKind::WouldBlock
+        );
+        assert!(other_port.recv_from(&mut buf, timeout).is_err());
+
+        joined.leave_multicast(group).unwrap();
+        src.send_to(&[4], SocketAddr::new(group, 5568)).unwrap();
+        assert!(joined.recv_from(&mut buf, timeout).is_err());
+        assert!(joined.leave_multicast(group).is_err());
+    }
+
+    /// Unicast packets should reach transports bound to the destination address or the unspecified address, in the order sent.
+    #[test]
+    fn test_memory_network_unicast() {
+        let network = MemoryNetwork::new();
+
+        let exact = network.bind(addr([10, 0, 0, 2], 5568));
+        let any = network.bind(addr([0, 0, 0, 0], 5568));
+        let other = network.bind(addr([10, 0, 0, 3], 5568));
+        let any_v6 = network.bind(SocketAddr::new(IpAddr::V6(Ipv6Addr::UNSPECIFIED), 5568));
+
+        let src = network.bind(addr([10, 0, 0, 1], 0));
+        src.send_to(&[1], addr([10, 0, 0, 2], 5568)).unwrap();
+        src.send_to(&[2, 2], addr([10, 0, 0, 2], 5568)).unwrap();
+
+        let mut buf = [0; 1];
+        let timeout = Some(Duration::ZERO);
+        for t in [&exact, &any] {
+            assert_eq!(t.recv_from(&mut buf, timeout).unwrap().0, 1);
+            assert_eq!(buf[0], 1);
+            // Truncated as the buffer is too small.
+            assert_eq!(t.recv_from(&mut buf, timeout).unwrap().0, 1);
+            assert_eq!(buf[0], 2);
+        }
+        assert!(other.recv_from(&mut buf, timeout).is_err());
+        assert!(any_v6.recv_from(&mut buf, timeout).is_err());
+    }
+
+    /// Transports bound to port 0 should get distinct ports and dropped transports should no longer be delivered to.
+    #[test]
+    fn test_memory_network_ephemeral_ports() {
+        let network = MemoryNetwork::new();
+
+        let a = network.bind(addr([10, 0, 0, 1], 0));
+        let b = network.bind(addr([10, 0, 0, 1], 0));
+        assert_ne!(a.local_addr().unwrap().port(), 0);
+        assert_ne!(
+            a.local_addr().unwrap().port(),
+            b.local_addr().unwrap().port()
+        );
+
+        drop(b);
+        a.send_to(&[1], addr([10, 0, 0, 1], 0)).unwrap();
+        assert_eq!(network.lock().endpoints.len(), 1);
+    }
+
+    /// A receive without a timeout should wait until a packet is sent from another thread.
+    #[test]
+    fn test_memory_network_blocking_recv() {
+        let network = MemoryNetwork::new();
+        let rcv = network.bind(addr([10, 0, 0, 2], 5568));
+        let src = network.bind(addr([10, 0, 0, 1], 0));
+
+        let sender = std::thread::spawn(move || {
+            std::thread::sleep(Duration::from_millis(50));
+            src.send_to(&[7], addr([10, 0, 0, 2], 5568)).unwrap();
+        });
+
+        let mut buf = [0; 4];
+        assert_eq!(rcv.recv_from(&mut buf, None).unwrap().0, 1);
+        assert_eq!(buf[0], 7);
+        sender.join().unwrap();
+    }
+
+    /// Sends the numbers 0..count from src to rcv and returns the numbers received, in the order received.
+    fn send_numbers(network: &MemoryNetwork, count: u8) -> Vec<u8> {
+        let rcv = network.bind(addr([10, 0, 0, 2], 5568));
+        let src = network.bind(addr([10, 0, 0, 1], 0));
+        for i in 0..count {
+            src.send_to(&[i], addr([10, 0, 0, 2], 5568)).unwrap();
+        }
+
+        let mut buf = [0; 1];
+        let mut received = Vec::new();
+        while rcv
+            .recv_from(&mut buf, Some(Duration::from_millis(20)))
+            .is_ok()
+        {
+            received.push(buf[0]);
+        }
+        received
+    }
+
+    /// Certain faults should apply to every packet.
+    #[test]
+    fn test_memory_network_certain_faults() {
+        let lossy = MemoryNetwork::with_faults(NetworkFaults {
+            loss: 1.0,
+            ..NetworkFaults::default()
+        });
+        assert!(send_numbers(&lossy, 10).is_empty());
+
+        let duplicating = MemoryNetwork::with_faults(NetworkFaults {
+            duplication: 1.0,
+            ..NetworkFaults::default()
+        });
+        assert_eq!(send_numbers(&duplicating, 3), vec![0, 0, 1, 1, 2, 2]);
+
+        // Every other packet is held back and released by the one after it.
+        let reordering = MemoryNetwork::with_faults(NetworkFaults {
+            reordering: 1.0,
+            ..NetworkFaults::default()
+        });
+        assert_eq!(send_numbers(&reordering, 5), vec![1, 0, 3, 2, 4]);
+
+        let perfect = MemoryNetwork::with_faults(NetworkFaults::default());
+        assert_eq!(send_numbers(&perfect, 5), vec![0, 1, 2, 3, 4]);
+    }
+
+    /// Delayed packets shouldn't be received before their delay has passed and should then be received in the order of their delays.
+    #[test]
+    fn test_memory_network_delay() {
+        let network = MemoryNetwork::with_faults(NetworkFaults {
+            delay: 1.0,
+            max_delay: Duration::from_millis(100),
+            seed: 7,
+            ..NetworkFaults::default()
+        });
+        let rcv = network.bind(addr([10, 0, 0, 2], 5568));
+        let src = network.bind(addr([10, 0, 0, 1], 0));
+        let start = Instant::now();
+        for i in 0..20 {
+            src.send_to(&[i], addr([10, 0, 0, 2], 5568)).unwrap();
+        }
+
+        let mut buf = [0; 1];
+        let mut received = Vec::new();
+        while let Ok((_, _)) = rcv.recv_from(&mut buf, Some(Duration::from_millis(200))) {
+            received.push(buf[0]);
+        }
+        assert!(start.elapsed() >= Duration::from_millis(50));
+
+        let mut sorted = received.clone();
+        sorted.sort();
+        assert_eq!(sorted, (0..20).collect::<Vec<u8>>());
+        assert_ne!(received, sorted);
+    }
+
+    /// The same faults and seed should give the same packets in the same order, a different seed should give different ones.
+    #[test]
+    fn test_memory_network_faults_reproducible() {
+        let faults = NetworkFaults {
+            loss: 0.2,
+            duplication: 0.2,
+            reordering: 0.2,
+            seed: 1234,
+            ..NetworkFaults::default()
+        };
+
+        let first = send_numbers(&MemoryNetwork::with_faults(faults), 100);
+        let second = send_numbers(&MemoryNetwork::with_faults(faults), 100);
+        assert_eq!(first, second);
+        assert_ne!(first, (0..100).collect::<Vec<u8>>());
+
+        let network = MemoryNetwork::with_faults(NetworkFaults {
+            seed: 4321,
+            ..faults
+        });
+        assert_eq!(network.faults().seed, 4321);
+        assert_ne!(send_numbers(&network, 100), first);
+
+        // Setting the faults again restarts the generator.
+        network.set_faults(faults);
+        assert_eq!(send_numbers(&network, 100), first);
+    }
+}
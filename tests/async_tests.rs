@@ -0,0 +1,176 @@
+// Copyright 2020 sacn Developers
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+#![cfg(feature = "async")]
+
+extern crate sacn;
+
+use futures_core::Stream;
+use sacn::interface::NetworkInterface;
+use sacn::packet::ACN_SDT_MULTICAST_PORT;
+use sacn::receive::{AsyncSacnReceiver, ReceiveEvent};
+use sacn::source::AsyncSacnSource;
+use serial_test::serial;
+
+use std::future::poll_fn;
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::pin::Pin;
+use std::time::Duration;
+
+use tokio::time::timeout;
+
+/// Maximum time to wait for data in the tests before failing.
+const TEST_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Creates a source bound to an ephemeral localhost port which doesn't send discovery packets and is registered for the given universe.
+async fn local_source(universe: u16) -> AsyncSacnSource {
+    let mut src = AsyncSacnSource::with_ip(
+        "Async Source",
+        SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), 0),
+    )
+    .unwrap();
+    src.set_is_sending_discovery(false).await;
+    src.register_universe(universe).await.unwrap();
+    src
+}
+
+/// Sends a single unsynchronised packet unicast from an async source to an async receiver on localhost and checks it is received.
+#[tokio::test]
+#[serial]
+#[cfg_attr(ci, ignore)]
+async fn test_async_send_recv_unicast() {
+    const UNIVERSE: u16 = 1;
+    let rcv_addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), ACN_SDT_MULTICAST_PORT);
+
+    let mut dmx_rcv = AsyncSacnReceiver::with_ip(rcv_addr, None).unwrap();
+    dmx_rcv
+        .receiver_mut()
+        .listen_universes(&[UNIVERSE])
+        .unwrap();
+
+    let mut src = local_source(UNIVERSE).await;
+    src.send(&[UNIVERSE], &[0, 1, 2, 3], Some(100), Some(rcv_addr), None)
+        .await
+        .unwrap();
+
+    let data = timeout(TEST_TIMEOUT, dmx_rcv.recv())
+        .await
+        .expect("Timed out waiting for data")
+        .unwrap();
+
+    assert_eq!(data.len(), 1);
+    assert_eq!(data[0].universe, UNIVERSE);
+    assert_eq!(data[0].values, vec![0, 1, 2, 3]);
+    assert_eq!(data[0].src_cid, Some(src.cid().await));
+    assert_eq!(data[0].src_name.as_deref(), Some("Async Source"));
+    assert!(data[0].src_addr.is_some());
+    #[cfg(target_os = "linux")]
+    assert_eq!(
+        data[0]
+            .recv_interface
+            .and_then(sacn::interface::interface_name)
+            .as_deref(),
+        Some("lo")
+    );
+    assert!(src.take_periodic_error().await.is_none());
+}
+
+/// Sends synchronised data and then a sync packet from a spawned task and checks that the receiver stream only yields the data
+/// event once the sync packet arrives.
+#[tokio::test(flavor = "multi_thread")]
+#[serial]
+#[cfg_attr(ci, ignore)]
+async fn test_async_stream_sync_from_spawned_task() {
+    const UNIVERSE: u16 = 2;
+    const SYNC_UNI: u16 = 3;
+    let rcv_addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), ACN_SDT_MULTICAST_PORT);
+
+    let mut dmx_rcv = AsyncSacnReceiver::with_ip(rcv_addr, None).unwrap();
+    dmx_rcv
+        .receiver_mut()
+        .listen_universes(&[UNIVERSE, SYNC_UNI])
+        .unwrap();
+
+    let mut src = local_source(UNIVERSE).await;
+    src.register_universe(SYNC_UNI).await.unwrap();
+
+    // The source futures must be Send to be used from a spawned task.
+    let sender = tokio::spawn(async move {
+        src.send(
+            &[UNIVERSE],
+            &[0, 9, 8],
+            None,
+            Some(rcv_addr),
+            Some(SYNC_UNI),
+        )
+        .await
+        .unwrap();
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        src.send_sync_packet(SYNC_UNI, Some(rcv_addr))
+            .await
+            .unwrap();
+        src
+    });
+
+    let event = timeout(
+        TEST_TIMEOUT,
+        poll_fn(|cx| Pin::new(&mut dmx_rcv).poll_next(cx)),
+    )
+    .await
+    .expect("Timed out waiting for data")
+    .expect("Stream ended")
+    .unwrap();
+
+    let data = match event {
+        ReceiveEvent::Data(data) => data,
+        e => panic!("Unexpected event {e:?}"),
+    };
+
+    assert_eq!(data.len(), 1);
+    assert_eq!(data[0].universe, UNIVERSE);
+    assert_eq!(data[0].values, vec![0, 9, 8]);
+    assert_eq!(data[0].sync_uni, SYNC_UNI);
+
+    sender.await.unwrap();
+}
+
+/// Listens to more universes than a single socket can join multicast groups for after creating the receiver, so that sockets are added
+/// while it is in use, and checks that multicast data on the first and last universes is received.
+#[tokio::test]
+#[serial]
+#[cfg(target_os = "linux")]
+#[cfg_attr(ci, ignore)]
+async fn test_async_recv_multicast_universe_range() {
+    const LAST_UNIVERSE: u16 = 100;
+    let rcv_addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), ACN_SDT_MULTICAST_PORT);
+
+    let mut dmx_rcv = AsyncSacnReceiver::with_ip(rcv_addr, None).unwrap();
+    dmx_rcv
+        .receiver_mut()
+        .listen_universe_range(1..=LAST_UNIVERSE)
+        .unwrap();
+
+    let mut src = local_source(1).await;
+    src.register_universe(LAST_UNIVERSE).await.unwrap();
+    src.set_interfaces(&[NetworkInterface::Addr(IpAddr::V4(Ipv4Addr::LOCALHOST))])
+        .await
+        .unwrap();
+
+    for universe in [1, LAST_UNIVERSE] {
+        src.send(&[universe], &[0, 1, 2], None, None, None)
+            .await
+            .unwrap();
+
+        let data = timeout(TEST_TIMEOUT, dmx_rcv.recv())
+            .await
+            .expect("Timed out waiting for data")
+            .unwrap();
+
+        assert_eq!(data[0].universe, universe);
+        assert_eq!(data[0].values, vec![0, 1, 2]);
+    }
+}
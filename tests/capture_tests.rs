@@ -0,0 +1,208 @@
+// Copyright 2020 sacn Developers
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! Replaying pcap captures of sACN traffic into a receiver.
+
+extern crate sacn;
+
+use sacn::capture::{CaptureReader, CaptureTransport, ReplayTiming};
+use sacn::error::errors::*;
+use sacn::packet::*;
+use sacn::receive::{ReceiveEvent, SacnReceiver};
+use uuid::Uuid;
+
+use std::borrow::Cow;
+use std::io::{self, Cursor};
+use std::time::{Duration, Instant};
+
+/// The CID of the source in the test captures.
+const TEST_CID: Uuid = Uuid::from_bytes([7; 16]);
+
+/// Returns a packed data packet for the given universe.
+fn data_packet(universe: u16, sequence_number: u8, values: &[u8]) -> Vec<u8> {
+    AcnRootLayerProtocol {
+        pdu: E131RootLayer {
+            cid: TEST_CID,
+            data: E131RootLayerData::DataPacket(DataPacketFramingLayer {
+                source_name: Cow::from("Captured Source"),
+                priority: 100,
+                synchronization_address: 0,
+                sequence_number,
+                preview_data: false,
+                stream_terminated: false,
+                force_synchronization: false,
+                universe,
+                data: DataPacketDmpLayer {
+                    property_values: values.into(),
+                },
+            }),
+        },
+    }
+    .pack_alloc()
+    .unwrap()
+}
+
+/// Returns an Ethernet frame holding an IPv4 UDP datagram with the given payload, sent to the multicast address of the given universe.
+fn ethernet_frame(universe: u16, payload: &[u8]) -> Vec<u8> {
+    let udp_len = (8 + payload.len()) as u16;
+    let mut frame = vec![0; 12];
+    frame.extend_from_slice(&[0x08, 0x00]);
+    frame.extend_from_slice(&[0x45, 0]);
+    frame.extend_from_slice(&(20 + udp_len).to_be_bytes());
+    frame.extend_from_slice(&[0, 0, 0, 0, 64, 17, 0, 0]);
+    frame.extend_from_slice(&[192, 168, 1, 10]);
+    frame.extend_from_slice(&[239, 255, (universe >> 8) as u8, universe as u8]);
+    frame.extend_from_slice(&ACN_SDT_MULTICAST_PORT.to_be_bytes());
+    frame.extend_from_slice(&ACN_SDT_MULTICAST_PORT.to_be_bytes());
+    frame.extend_from_slice(&udp_len.to_be_bytes());
+    frame.extend_from_slice(&[0, 0]);
+    frame.extend_from_slice(payload);
+    frame
+}
+
+/// Returns a little endian pcap capture of Ethernet frames holding the given payloads at the given times in milliseconds.
+fn pcap(packets: &[(u64, u16, Vec<u8>)]) -> Vec<u8> {
+    let mut capture = 0xa1b2_c3d4u32.to_le_bytes().to_vec();
+    capture.extend_from_slice(&[2, 0, 4, 0]);
+    capture.extend_from_slice(&[0; 8]);
+    capture.extend_from_slice(&65535u32.to_le_bytes());
+    capture.extend_from_slice(&1u32.to_le_bytes());
+    for (millis, universe, payload) in packets {
+        let frame = ethernet_frame(*universe, payload);
+        capture.extend_from_slice(&((1_700_000_000 + millis / 1000) as u32).to_le_bytes());
+        capture.extend_from_slice(&((millis % 1000 * 1000) as u32).to_le_bytes());
+        capture.extend_from_slice(&(frame.len() as u32).to_le_bytes());
+        capture.extend_from_slice(&(frame.len() as u32).to_le_bytes());
+        capture.extend_from_slice(&frame);
+    }
+    capture
+}
+
+/// Creates a receiver replaying the given capture which listens to the given universes.
+fn capture_receiver(capture: Vec<u8>, timing: ReplayTiming, universes: &[u16]) -> SacnReceiver {
+    let transport = CaptureTransport::new(Cursor::new(capture), timing).unwrap();
+    let mut rcv = SacnReceiver::with_capture(transport, None).unwrap();
+    rcv.listen_universes(universes).unwrap();
+    rcv
+}
+
+/// Returns true if the error is the one returned once every packet in the capture has been replayed.
+fn is_end_of_capture(e: &SacnError) -> bool {
+    matches!(e, SacnError::Io(e) if e.kind() == io::ErrorKind::UnexpectedEof)
+}
+
+/// Reading a capture should give each sACN packet with its source and destination.
+#[test]
+fn test_capture_reader_packets() {
+    let capture = pcap(&[
+        (0, 1, data_packet(1, 0, &[0, 1])),
+        (40, 2, data_packet(2, 0, &[0, 2])),
+    ]);
+    let packets = CaptureReader::new(Cursor::new(capture))
+        .unwrap()
+        .collect::<Result<Vec<_>>>()
+        .unwrap();
+
+    assert_eq!(packets.len(), 2);
+    assert_eq!(packets[0].src, "192.168.1.10:5568".parse().unwrap());
+    assert_eq!(packets[1].dst, "239.255.0.2:5568".parse().unwrap());
+    assert_eq!(
+        packets[1].timestamp - packets[0].timestamp,
+        Duration::from_millis(40)
+    );
+    assert_eq!(
+        AcnRootLayerProtocol::parse(&packets[1].data)
+            .unwrap()
+            .pdu
+            .cid,
+        TEST_CID
+    );
+}
+
+/// Replaying as fast as possible should still time out a source which stopped sending for longer than E131_NETWORK_DATA_LOSS_TIMEOUT
+/// in the capture, and the end of the capture should be reported.
+#[test]
+fn test_capture_replay_as_fast_as_possible() {
+    let capture = pcap(&[
+        (0, 1, data_packet(1, 0, &[0, 1])),
+        (1000, 1, data_packet(1, 1, &[0, 2])),
+        (1000, 2, data_packet(2, 0, &[0, 3])),
+        (3000, 2, data_packet(2, 1, &[0, 3])),
+        (5000, 2, data_packet(2, 2, &[0, 4])),
+    ]);
+    let mut rcv = capture_receiver(capture, ReplayTiming::AsFastAsPossible, &[1, 2]);
+
+    let start = Instant::now();
+    let mut events = Vec::new();
+    loop {
+        match rcv.recv_event(Some(Duration::from_secs(1))) {
+            Ok(event) => events.push(event),
+            Err(e) if is_end_of_capture(&e) => break,
+            Err(e) => panic!("Unexpected error replaying the capture: {e:?}"),
+        }
+    }
+    assert!(start.elapsed() < Duration::from_secs(2));
+
+    let values: Vec<Vec<u8>> = events
+        .iter()
+        .filter_map(|e| match e {
+            ReceiveEvent::Data(data) => Some(data[0].values.clone()),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(
+        values,
+        vec![vec![0, 1], vec![0, 2], vec![0, 3], vec![0, 3], vec![0, 4]]
+    );
+
+    // Universe 1 times out in the gap before the last packet, universe 2 keeps the source alive.
+    let timed_out = events
+        .iter()
+        .position(|e| matches!(e, ReceiveEvent::UniverseTimedOut(TEST_CID, 1)))
+        .expect("Universe 1 should time out");
+    let last = events
+        .iter()
+        .position(|e| matches!(e, ReceiveEvent::Data(d) if d[0].values == [0, 4]))
+        .unwrap();
+    assert!(timed_out < last);
+    assert!(
+        !events
+            .iter()
+            .any(|e| matches!(e, ReceiveEvent::SourceLost(_)))
+    );
+}
+
+/// Replaying at the original speed should take as long as the capture and receiving with a shorter timeout than the gap to the next
+/// packet should time out without losing it.
+#[test]
+fn test_capture_replay_original_timing() {
+    let capture = pcap(&[
+        (0, 1, data_packet(1, 0, &[0, 1])),
+        (300, 1, data_packet(1, 1, &[0, 2])),
+    ]);
+    let mut rcv = capture_receiver(capture, ReplayTiming::Original, &[1]);
+
+    let start = Instant::now();
+    assert_eq!(
+        rcv.recv(Some(Duration::from_secs(1))).unwrap()[0].values,
+        vec![0, 1]
+    );
+    match rcv.recv(Some(Duration::from_millis(50))) {
+        Err(SacnError::Io(e)) if e.kind() == io::ErrorKind::WouldBlock => {}
+        result => panic!("Expected the receive to time out, got {result:?}"),
+    }
+    assert_eq!(
+        rcv.recv(Some(Duration::from_secs(1))).unwrap()[0].values,
+        vec![0, 2]
+    );
+    assert!(start.elapsed() >= Duration::from_millis(300));
+
+    assert!(
+        rcv.recv(Some(Duration::from_secs(1)))
+            .is_err_and(|e| is_end_of_capture(&e))
+    );
+}
@@ -0,0 +1,148 @@
+// Copyright 2020 sacn Developers
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! Protocol timeouts driven by a manual clock over an in-memory network, so they are tested without waiting for them.
+
+extern crate sacn;
+
+use sacn::clock::ManualClock;
+use sacn::error::errors::*;
+use sacn::packet::{
+    ACN_SDT_MULTICAST_PORT, E131_NETWORK_DATA_LOSS_TIMEOUT, E131_UNIVERSE_DISCOVERY_INTERVAL,
+    UNIVERSE_DISCOVERY_SOURCE_TIMEOUT,
+};
+use sacn::receive::{ReceiveEvent, SacnReceiver};
+use sacn::source::SacnSource;
+use sacn::transport::MemoryNetwork;
+
+use std::io;
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::time::Duration;
+
+/// Maximum time to wait for packets in the tests before failing, this is real time rather than time on the manual clock.
+const TEST_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// A little longer than the given timeout, used to advance the clock past it.
+fn past(timeout: Duration) -> Duration {
+    timeout + Duration::from_millis(100)
+}
+
+/// Creates a receiver using the given clock on the given network which listens to the given universes.
+fn manual_receiver(
+    network: &MemoryNetwork,
+    clock: &ManualClock,
+    universes: &[u16],
+) -> SacnReceiver {
+    let addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::UNSPECIFIED), ACN_SDT_MULTICAST_PORT);
+    let mut rcv = SacnReceiver::with_transport(network.bind(addr), None).unwrap();
+    rcv.set_clock(clock.clone());
+    rcv.listen_universes(universes).unwrap();
+    rcv
+}
+
+/// Creates a source using the given clock on the given network which is registered for the given universes.
+fn manual_source(network: &MemoryNetwork, clock: &ManualClock, universes: &[u16]) -> SacnSource {
+    let addr = SocketAddr::new(IpAddr::from([10, 0, 0, 1]), 0);
+    let mut src = SacnSource::with_transport("Clock Source", network.bind(addr)).unwrap();
+    src.set_clock(clock.clone()).unwrap();
+    src.register_universes(universes).unwrap();
+    src
+}
+
+/// Returns true if the error is the one returned when no data arrives within the timeout.
+fn is_timeout(e: &SacnError) -> bool {
+    matches!(e, SacnError::Io(e) if matches!(e.kind(), io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut))
+}
+
+/// A source should only time out once E131_NETWORK_DATA_LOSS_TIMEOUT has passed on the clock of the receiver.
+#[test]
+fn test_manual_clock_data_loss_timeout() {
+    let network = MemoryNetwork::new();
+    let clock = ManualClock::new();
+    let mut rcv = manual_receiver(&network, &clock, &[1]);
+    let mut src = manual_source(&network, &clock, &[1]);
+    src.set_is_sending_discovery(false);
+
+    src.send(&[1], &[0, 1, 2], None, None, None).unwrap();
+    rcv.recv(Some(TEST_TIMEOUT)).unwrap();
+
+    clock.advance(E131_NETWORK_DATA_LOSS_TIMEOUT - Duration::from_millis(100));
+    assert!(
+        rcv.recv_event(Some(Duration::ZERO))
+            .is_err_and(|e| is_timeout(&e))
+    );
+
+    clock.advance(Duration::from_millis(200));
+    let cid = src.cid().unwrap();
+    match rcv.recv_event(Some(Duration::ZERO)).unwrap() {
+        ReceiveEvent::UniverseTimedOut(c, 1) => assert_eq!(c, cid),
+        event => panic!("Expected the universe to time out, got {event:?}"),
+    }
+    match rcv.recv_event(Some(Duration::ZERO)).unwrap() {
+        ReceiveEvent::SourceLost(c) => assert_eq!(c, cid),
+        event => panic!("Expected the source to be lost, got {event:?}"),
+    }
+}
+
+/// Data waiting for a synchronisation packet which doesn't arrive within E131_NETWORK_DATA_LOSS_TIMEOUT on the clock of the receiver
+/// should be discarded.
+#[test]
+fn test_manual_clock_waiting_data_timeout() {
+    let network = MemoryNetwork::new();
+    let clock = ManualClock::new();
+    let mut rcv = manual_receiver(&network, &clock, &[1, 2]);
+    let mut src = manual_source(&network, &clock, &[1, 2]);
+    src.set_is_sending_discovery(false);
+
+    src.send(&[1], &[0, 5], None, None, Some(2)).unwrap();
+    assert!(
+        rcv.recv(Some(Duration::from_millis(50)))
+            .is_err_and(|e| is_timeout(&e))
+    );
+
+    clock.advance(past(E131_NETWORK_DATA_LOSS_TIMEOUT));
+    src.send_sync_packet(2, None).unwrap();
+    assert!(
+        rcv.recv(Some(Duration::from_millis(50)))
+            .is_err_and(|e| is_timeout(&e))
+    );
+
+    // Data sent after the timeout is still synchronised as normal.
+    src.send(&[1], &[0, 6], None, None, Some(2)).unwrap();
+    src.send_sync_packet(2, None).unwrap();
+    assert_eq!(rcv.recv(Some(TEST_TIMEOUT)).unwrap()[0].values, vec![0, 6]);
+}
+
+/// A source should send universe discovery packets once E131_UNIVERSE_DISCOVERY_INTERVAL has passed on its clock and a receiver should
+/// forget the discovered source once UNIVERSE_DISCOVERY_SOURCE_TIMEOUT has passed on its clock.
+#[test]
+fn test_manual_clock_discovery_interval() {
+    let network = MemoryNetwork::new();
+    let clock = ManualClock::new();
+    let mut rcv = manual_receiver(&network, &clock, &[]);
+    let mut src = manual_source(&network, &clock, &[1, 2, 3]);
+
+    // Nothing is sent until the interval has passed on the clock, however long the update thread runs for.
+    assert!(
+        rcv.recv_event(Some(Duration::from_millis(1500)))
+            .is_err_and(|e| is_timeout(&e))
+    );
+
+    clock.advance(past(E131_UNIVERSE_DISCOVERY_INTERVAL));
+    match rcv.recv_event(Some(TEST_TIMEOUT)).unwrap() {
+        ReceiveEvent::SourceDiscovered(discovered) => {
+            assert_eq!(discovered.name, "Clock Source");
+            assert_eq!(discovered.get_all_universes(), vec![1, 2, 3]);
+        }
+        event => panic!("Expected the source to be discovered, got {event:?}"),
+    }
+    assert_eq!(rcv.get_discovered_sources().len(), 1);
+
+    src.set_is_sending_discovery(false);
+    clock.advance(past(UNIVERSE_DISCOVERY_SOURCE_TIMEOUT));
+    assert!(rcv.get_discovered_sources().is_empty());
+}
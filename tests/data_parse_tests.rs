@@ -3190,7 +3190,7 @@ fn test_data_packet_empty_capacity_parse_pack() {
     };
 
     assert_eq!(
-        AcnRootLayerProtocol::parse(&TEST_DATA_PACKET_EMPTY).unwrap(),
+        AcnRootLayerProtocol::parse(TEST_DATA_PACKET_EMPTY).unwrap(),
         packet
     );
 
@@ -3222,7 +3222,7 @@ fn test_data_packet_partial_capacity_parse_pack() {
     };
 
     assert_eq!(
-        AcnRootLayerProtocol::parse(&TEST_DATA_PACKET_PARTIAL).unwrap(),
+        AcnRootLayerProtocol::parse(TEST_DATA_PACKET_PARTIAL).unwrap(),
         packet
     );
 
@@ -3254,7 +3254,7 @@ fn test_data_packet_parse_pack() {
     };
 
     assert_eq!(
-        AcnRootLayerProtocol::parse(&TEST_DATA_PACKET).unwrap(),
+        AcnRootLayerProtocol::parse(TEST_DATA_PACKET).unwrap(),
         packet
     );
 
@@ -3266,372 +3266,324 @@ fn test_data_packet_parse_pack() {
 
 #[test]
 fn test_malformed_data_packet_wrong_preample_lower_byte_parse() {
-    match AcnRootLayerProtocol::parse(&TEST_DATA_PACKET_ROOT_LAYER_WRONG_PREAMBLE_SIZE_LOWER_BYTE) {
+    match AcnRootLayerProtocol::parse(TEST_DATA_PACKET_ROOT_LAYER_WRONG_PREAMBLE_SIZE_LOWER_BYTE) {
         Err(e) => {
             match e {
                 SacnError::SacnParsePackError(sacn_parse_pack_error::ParsePacketError::ParseInvalidData(_)) => {
-                    assert!(true, "Expected error returned");
+                    // Expected error returned
                 }
                 _ => {
-                    assert!(false, "Unexpected error type returned");
+                    panic!("Unexpected error type returned");
                 }
             }
 
         }
         Ok(_) => {
-            assert!(
-                false,
-                "Malformed packet was parsed when should have been rejected"
-            );
+            panic!("Malformed packet was parsed when should have been rejected");
         }
     }
 }
 
 #[test]
 fn test_malformed_data_packet_wrong_preample_upper_byte_parse() {
-    match AcnRootLayerProtocol::parse(&TEST_DATA_PACKET_ROOT_LAYER_WRONG_PREAMBLE_SIZE_UPPER_BYTE) {
+    match AcnRootLayerProtocol::parse(TEST_DATA_PACKET_ROOT_LAYER_WRONG_PREAMBLE_SIZE_UPPER_BYTE) {
         Err(e) => {
             match e {
                 SacnError::SacnParsePackError(sacn_parse_pack_error::ParsePacketError::ParseInvalidData(_)) => {
-                    assert!(true, "Expected error returned");
+                    // Expected error returned
                 }
                 _ => {
-                    assert!(false, "Unexpected error type returned");
+                    panic!("Unexpected error type returned");
                 }
             }
 
         }
         Ok(_) => {
-            assert!(
-                false,
-                "Malformed packet was parsed when should have been rejected"
-            );
+            panic!("Malformed packet was parsed when should have been rejected");
         }
     }
 }
 
 #[test]
 fn test_malformed_data_packet_wrong_postample_lower_byte_parse() {
-    match AcnRootLayerProtocol::parse(&TEST_DATA_PACKET_ROOT_LAYER_WRONG_POSTAMBLE_SIZE_LOWER_BYTE) {
+    match AcnRootLayerProtocol::parse(TEST_DATA_PACKET_ROOT_LAYER_WRONG_POSTAMBLE_SIZE_LOWER_BYTE) {
         Err(e) => {
             match e {
                 SacnError::SacnParsePackError(sacn_parse_pack_error::ParsePacketError::ParseInvalidData(_)) => {
-                    assert!(true, "Expected error returned");
+                    // Expected error returned
                 }
                 _ => {
-                    assert!(false, "Unexpected error type returned");
+                    panic!("Unexpected error type returned");
                 }
             }
 
         }
         Ok(_) => {
-            assert!(
-                false,
-                "Malformed packet was parsed when should have been rejected"
-            );
+            panic!("Malformed packet was parsed when should have been rejected");
         }
     }
 }
 
 #[test]
 fn test_malformed_data_packet_wrong_postample_upper_byte_parse() {
-    match AcnRootLayerProtocol::parse(&TEST_DATA_PACKET_ROOT_LAYER_WRONG_POSTAMBLE_SIZE_UPPER_BYTE) {
+    match AcnRootLayerProtocol::parse(TEST_DATA_PACKET_ROOT_LAYER_WRONG_POSTAMBLE_SIZE_UPPER_BYTE) {
         Err(e) => {
             match e {
                 SacnError::SacnParsePackError(sacn_parse_pack_error::ParsePacketError::ParseInvalidData(_)) => {
-                    assert!(true, "Expected error returned");
+                    // Expected error returned
                 }
                 _ => {
-                    assert!(false, "Unexpected error type returned");
+                    panic!("Unexpected error type returned");
                 }
             }
 
         }
         Ok(_) => {
-            assert!(
-                false,
-                "Malformed packet was parsed when should have been rejected"
-            );
+            panic!("Malformed packet was parsed when should have been rejected");
         }
     }
 }
 
 #[test]
 fn test_malformed_data_packet_root_layer_wrong_flags() {
-    match AcnRootLayerProtocol::parse(&TEST_DATA_PACKET_ROOT_LAYER_WRONG_FLAGS) {
+    match AcnRootLayerProtocol::parse(TEST_DATA_PACKET_ROOT_LAYER_WRONG_FLAGS) {
         Err(e) => {
             match e {
                 SacnError::SacnParsePackError(sacn_parse_pack_error::ParsePacketError::ParsePduInvalidFlags(_)) => {
-                    assert!(true, "Expected error returned");
+                    // Expected error returned
                 }
                 _ => {
-                    assert!(false, "Unexpected error type returned");
+                    panic!("Unexpected error type returned");
                 }
             }
 
         }
         Ok(_) => {
-            assert!(
-                false,
-                "Malformed packet was parsed when should have been rejected"
-            );
+            panic!("Malformed packet was parsed when should have been rejected");
         }
     }
 }
 
 #[test]
 fn test_malformed_data_packet_root_layer_too_low_length() {
-    match AcnRootLayerProtocol::parse(&TEST_DATA_PACKET_ROOT_LAYER_TOO_LOW_LENGTH) {
+    match AcnRootLayerProtocol::parse(TEST_DATA_PACKET_ROOT_LAYER_TOO_LOW_LENGTH) {
         Err(e) => {
             match e {
                 SacnError::SacnParsePackError(sacn_parse_pack_error::ParsePacketError::ParseInsufficientData(_)) => {
-                    assert!(true, "Expected error returned");
+                    // Expected error returned
                 }
                 _ => {
-                    assert!(false, "Unexpected error type returned");
+                    panic!("Unexpected error type returned");
                 }
             }
 
         }
         Ok(_) => {
-            assert!(
-                false,
-                "Malformed packet was parsed when should have been rejected"
-            );
+            panic!("Malformed packet was parsed when should have been rejected");
         }
     }
 }
 
 #[test]
 fn test_malformed_data_packet_root_layer_too_high_length() {
-    match AcnRootLayerProtocol::parse(&TEST_DATA_PACKET_ROOT_LAYER_TOO_HIGH_LENGTH) {
+    match AcnRootLayerProtocol::parse(TEST_DATA_PACKET_ROOT_LAYER_TOO_HIGH_LENGTH) {
         Err(e) => {
             match e {
                 SacnError::SacnParsePackError(sacn_parse_pack_error::ParsePacketError::ParseInsufficientData(_)) => {
-                    assert!(true, "Expected error returned");
+                    // Expected error returned
                 }
                 _ => {
-                    assert!(false, "Unexpected error type returned");
+                    panic!("Unexpected error type returned");
                 }
             }
 
         }
         Ok(_) => {
-            assert!(
-                false,
-                "Malformed packet was parsed when should have been rejected"
-            );
+            panic!("Malformed packet was parsed when should have been rejected");
         }
     }
 }
 
 #[test]
 fn test_malformed_data_packet_wrong_acn_identifier_parse() {
-    match AcnRootLayerProtocol::parse(&TEST_DATA_PACKET_ROOT_LAYER_WRONG_ACN_IDENTIFIER) {
+    match AcnRootLayerProtocol::parse(TEST_DATA_PACKET_ROOT_LAYER_WRONG_ACN_IDENTIFIER) {
         Err(e) => {
             match e {
                 SacnError::SacnParsePackError(sacn_parse_pack_error::ParsePacketError::ParseInvalidData(_)) => {
-                    assert!(true, "Expected error returned");
+                    // Expected error returned
                 }
                 _ => {
-                    assert!(false, "Unexpected error type returned");
+                    panic!("Unexpected error type returned");
                 }
             }
 
         }
         Ok(_) => {
-            assert!(
-                false,
-                "Malformed packet was parsed when should have been rejected"
-            );
+            panic!("Malformed packet was parsed when should have been rejected");
         }
     }
 }
 
 #[test]
 fn test_malformed_data_packet_unknown_acn_vector_parse() {
-    match AcnRootLayerProtocol::parse(&TEST_DATA_PACKET_ROOT_LAYER_UNKNOWN_ACN_VECTOR) {
+    match AcnRootLayerProtocol::parse(TEST_DATA_PACKET_ROOT_LAYER_UNKNOWN_ACN_VECTOR) {
         Err(e) => {
             match e {
                 SacnError::SacnParsePackError(sacn_parse_pack_error::ParsePacketError::PduInvalidVector(_)) => {
-                    assert!(true, "Expected error returned");
+                    // Expected error returned
                 }
                 _ => {
-                    assert!(false, "Unexpected error type returned");
+                    panic!("Unexpected error type returned");
                 }
             }
 
         }
         Ok(_) => {
-            assert!(
-                false,
-                "Malformed packet was parsed when should have been rejected"
-            );
+            panic!("Malformed packet was parsed when should have been rejected");
         }
     }
 }
 
 #[test]
 fn test_malformed_data_packet_extended_acn_vector_parse() {
-    match AcnRootLayerProtocol::parse(&TEST_DATA_PACKET_ROOT_LAYER_EXTENDED_VECTOR) {
+    match AcnRootLayerProtocol::parse(TEST_DATA_PACKET_ROOT_LAYER_EXTENDED_VECTOR) {
         Err(e) => {
             match e {
                 SacnError::SacnParsePackError(_) => {
                     // As this is a byzantine type error because the packet is otherwise correct except the vector is the wrong vector type the exact
                     // parse error isn't enforced but the packet must still be rejected.
-                    assert!(true, "Malformed packet successfully rejected");
+                    // Malformed packet successfully rejected
                 }
                 _ => {
-                    assert!(false, "Unexpected error type returned");
+                    panic!("Unexpected error type returned");
                 }
             }
 
         }
         Ok(_) => {
-            assert!(
-                false,
-                "Malformed packet was parsed when should have been rejected"
-            );
+            panic!("Malformed packet was parsed when should have been rejected");
         }
     }
 }
 
 #[test]
 fn test_malformed_data_packet_too_long_cid_parse() {
-    match AcnRootLayerProtocol::parse(&TEST_DATA_PACKET_TOO_LONG_CID) {
+    match AcnRootLayerProtocol::parse(TEST_DATA_PACKET_TOO_LONG_CID) {
         Err(e) => {
             match e {
                 SacnError::SacnParsePackError(_) => {
                     // The exact error in this case is not defined as other fields will be effected as all the data
                     // is shifted, therefore just check for any parse error.
-                    assert!(true, "Malformed packet successfully rejected");
+                    // Malformed packet successfully rejected
                 }
                 _ => {
-                    assert!(false, "Unexpected error type returned");
+                    panic!("Unexpected error type returned");
                 }
             }
 
         }
         Ok(_) => {
-            assert!(
-                false,
-                "Malformed packet was parsed when should have been rejected"
-            );
+            panic!("Malformed packet was parsed when should have been rejected");
         }
     }
 }
 
 #[test]
 fn test_malformed_data_packet_too_short_cid_parse() {
-    match AcnRootLayerProtocol::parse(&TEST_DATA_PACKET_TOO_SHORT_CID) {
+    match AcnRootLayerProtocol::parse(TEST_DATA_PACKET_TOO_SHORT_CID) {
         Err(e) => {
             match e {
                 SacnError::SacnParsePackError(sacn_parse_pack_error::ParsePacketError::ParseInsufficientData(_)) => {
-                    assert!(true, "Expected error returned");
+                    // Expected error returned
                 }
                 x => {
-                    assert!(false, "Unexpected error type returned: {}", x);
+                    panic!("Unexpected error type returned: {}", x);
                 }
             }
 
         }
         Ok(_) => {
-            assert!(
-                false,
-                "Malformed packet was parsed when should have been rejected"
-            );
+            panic!("Malformed packet was parsed when should have been rejected");
         }
     }
 }
 
 #[test]
 fn test_malformed_data_packet_framing_layer_wrong_flags_parse() {
-    match AcnRootLayerProtocol::parse(&TEST_DATA_PACKET_FRAMING_LAYER_WRONG_FLAGS) {
+    match AcnRootLayerProtocol::parse(TEST_DATA_PACKET_FRAMING_LAYER_WRONG_FLAGS) {
         Err(e) => {
             match e {
                 SacnError::SacnParsePackError(sacn_parse_pack_error::ParsePacketError::ParsePduInvalidFlags(_)) => {
-                    assert!(true, "Expected error returned");
+                    // Expected error returned
                 }
                 x => {
-                    assert!(false, "Unexpected error type returned: {}", x);
+                    panic!("Unexpected error type returned: {}", x);
                 }
             }
 
         }
         Ok(_) => {
-            assert!(
-                false,
-                "Malformed packet was parsed when should have been rejected"
-            );
+            panic!("Malformed packet was parsed when should have been rejected");
         }
     }
 }
 
 #[test]
 fn test_malformed_data_packet_framing_layer_low_length_parse() {
-    match AcnRootLayerProtocol::parse(&TEST_DATA_PACKET_FRAMING_LAYER_LOW_LENGTH) {
+    match AcnRootLayerProtocol::parse(TEST_DATA_PACKET_FRAMING_LAYER_LOW_LENGTH) {
         Err(e) => {
             match e {
                 SacnError::SacnParsePackError(sacn_parse_pack_error::ParsePacketError::ParseInsufficientData(_)) => {
-                    assert!(true, "Expected error returned");
+                    // Expected error returned
                 }
                 x => {
-                    assert!(false, "Unexpected error type returned: {}", x);
+                    panic!("Unexpected error type returned: {}", x);
                 }
             }
 
         }
         Ok(_) => {
-            assert!(
-                false,
-                "Malformed packet was parsed when should have been rejected"
-            );
+            panic!("Malformed packet was parsed when should have been rejected");
         }
     }
 }
 
 #[test]
 fn test_malformed_data_packet_framing_layer_high_length_parse() {
-    match AcnRootLayerProtocol::parse(&TEST_DATA_PACKET_FRAMING_LAYER_HIGH_LENGTH) {
+    match AcnRootLayerProtocol::parse(TEST_DATA_PACKET_FRAMING_LAYER_HIGH_LENGTH) {
         Err(e) => {
             match e {
                 SacnError::SacnParsePackError(sacn_parse_pack_error::ParsePacketError::ParseInsufficientData(_)) => {
-                    assert!(true, "Expected error returned");
+                    // Expected error returned
                 }
                 x => {
-                    assert!(false, "Unexpected error type returned: {}", x);
+                    panic!("Unexpected error type returned: {}", x);
                 }
             }
 
         }
         Ok(_) => {
-            assert!(
-                false,
-                "Malformed packet was parsed when should have been rejected"
-            );
+            panic!("Malformed packet was parsed when should have been rejected");
         }
     }
 }
 
 #[test]
 fn test_malformed_data_packet_framing_layer_wrong_vector_parse() {
-    match AcnRootLayerProtocol::parse(&TEST_DATA_PACKET_FRAMING_LAYER_WRONG_VECTOR) {
+    match AcnRootLayerProtocol::parse(TEST_DATA_PACKET_FRAMING_LAYER_WRONG_VECTOR) {
         Err(e) => {
             match e {
                 SacnError::SacnParsePackError(sacn_parse_pack_error::ParsePacketError::PduInvalidVector(_)) => {
-                    assert!(true, "Expected error returned");
+                    // Expected error returned
                 }
                 x => {
-                    assert!(false, "Unexpected error type returned: {}", x);
+                    panic!("Unexpected error type returned: {}", x);
                 }
             }
 
         }
         Ok(_) => {
-            assert!(
-                false,
-                "Malformed packet was parsed when should have been rejected"
-            );
+            panic!("Malformed packet was parsed when should have been rejected");
         }
     }
 }
@@ -3658,7 +3610,7 @@ fn test_data_packet_max_source_name_length_parse(){
     };
 
     assert_eq!(
-        AcnRootLayerProtocol::parse(&TEST_DATA_PACKET_MAX_SOURCE_NAME).unwrap(),
+        AcnRootLayerProtocol::parse(TEST_DATA_PACKET_MAX_SOURCE_NAME).unwrap(),
         packet
     );
 
@@ -3670,55 +3622,49 @@ fn test_data_packet_max_source_name_length_parse(){
 
 #[test]
 fn test_malformed_data_packet_source_name_not_null_terminated_parse() {
-    match AcnRootLayerProtocol::parse(&TEST_DATA_PACKET_NOT_NULL_TERMINATED_SOURCE_NAME) {
+    match AcnRootLayerProtocol::parse(TEST_DATA_PACKET_NOT_NULL_TERMINATED_SOURCE_NAME) {
         Err(e) => {
             match e {
                 SacnError::SacnParsePackError(sacn_parse_pack_error::ParsePacketError::SourceNameNotNullTerminated()) => {
-                    assert!(true, "Expected error returned");
+                    // Expected error returned
                 }
                 x => {
-                    assert!(false, "Unexpected error type returned: {}", x);
+                    panic!("Unexpected error type returned: {}", x);
                 }
             }
 
         }
         Ok(_) => {
-            assert!(
-                false,
-                "Malformed packet was parsed when should have been rejected"
-            );
+            panic!("Malformed packet was parsed when should have been rejected");
         }
     }
 }
 
 #[test]
 fn test_malformed_data_packet_too_high_priority_parse() {
-    match AcnRootLayerProtocol::parse(&TEST_DATA_PACKET_TOO_HIGH_PRIORITY) {
+    match AcnRootLayerProtocol::parse(TEST_DATA_PACKET_TOO_HIGH_PRIORITY) {
         Err(e) => {
             match e {
                 SacnError::SacnParsePackError(sacn_parse_pack_error::ParsePacketError::ParseInvalidPriority(_)) => {
-                    assert!(true, "Expected error returned");
+                    // Expected error returned
                 }
                 x => {
-                    assert!(false, "Unexpected error type returned: {}", x);
+                    panic!("Unexpected error type returned: {}", x);
                 }
             }
 
         }
         Ok(_) => {
-            assert!(
-                false,
-                "Malformed packet was parsed when should have been rejected"
-            );
+            panic!("Malformed packet was parsed when should have been rejected");
         }
     }
 }
 
 #[test]
 fn test_data_packet_lowest_priority_parse() {
-    match AcnRootLayerProtocol::parse(&TEST_DATA_PACKET_LOWEST_PRIORITY) {
+    match AcnRootLayerProtocol::parse(TEST_DATA_PACKET_LOWEST_PRIORITY) {
         Err(e) => {
-            assert!(false, "Unexpected error returned: {}", e);
+            panic!("Unexpected error returned: {}", e);
         }
         Ok(p) => {
             match p.pdu.data {
@@ -3753,7 +3699,7 @@ fn test_data_packet_lowest_priority_parse() {
                     );
                 }
                 _ => {
-                    assert!(false, "Packet not parsed as data-packet as expected");
+                    panic!("Packet not parsed as data-packet as expected");
                 }
             }
         }
@@ -3762,9 +3708,9 @@ fn test_data_packet_lowest_priority_parse() {
 
 #[test]
 fn test_data_packet_no_sync_parse() {
-    match AcnRootLayerProtocol::parse(&TEST_DATA_PACKET_NO_SYNC_PACKET) {
+    match AcnRootLayerProtocol::parse(TEST_DATA_PACKET_NO_SYNC_PACKET) {
         Err(e) => {
-            assert!(false, "Unexpected error returned: {}", e);
+            panic!("Unexpected error returned: {}", e);
         }
         Ok(p) => {
             match p.pdu.data {
@@ -3799,7 +3745,7 @@ fn test_data_packet_no_sync_parse() {
                     );
                 }
                 _ => {
-                    assert!(false, "Packet not parsed as data-packet as expected");
+                    panic!("Packet not parsed as data-packet as expected");
                 }
             }
         }
@@ -3808,32 +3754,29 @@ fn test_data_packet_no_sync_parse() {
 
 #[test]
 fn test_malformed_data_packet_too_high_sync_addr_parse() {
-    match AcnRootLayerProtocol::parse(&TEST_DATA_PACKET_TOO_HIGH_SYNC_ADDR_PACKET) {
+    match AcnRootLayerProtocol::parse(TEST_DATA_PACKET_TOO_HIGH_SYNC_ADDR_PACKET) {
         Err(e) => {
             match e {
                 SacnError::SacnParsePackError(sacn_parse_pack_error::ParsePacketError::ParseInvalidSyncAddr(_)) => {
-                    assert!(true, "Expected error returned");
+                    // Expected error returned
                 }
                 x => {
-                    assert!(false, "Unexpected error type returned: {}", x);
+                    panic!("Unexpected error type returned: {}", x);
                 }
             }
 
         }
         Ok(_) => {
-            assert!(
-                false,
-                "Malformed packet was parsed when should have been rejected"
-            );
+            panic!("Malformed packet was parsed when should have been rejected");
         }
     }
 }
 
 #[test]
 fn test_data_packet_options_bit_0_set_parse() {
-    match AcnRootLayerProtocol::parse(&TEST_DATA_PACKET_OPTIONS_BIT_0_SET_PACKET) {
+    match AcnRootLayerProtocol::parse(TEST_DATA_PACKET_OPTIONS_BIT_0_SET_PACKET) {
         Err(e) => {
-            assert!(false, "Unexpected error returned: {}", e);
+            panic!("Unexpected error returned: {}", e);
         }
         Ok(p) => {
             match p.pdu.data {
@@ -3868,7 +3811,7 @@ fn test_data_packet_options_bit_0_set_parse() {
                     );
                 }
                 _ => {
-                    assert!(false, "Packet not parsed as data-packet as expected");
+                    panic!("Packet not parsed as data-packet as expected");
                 }
             }
 
@@ -3879,9 +3822,9 @@ fn test_data_packet_options_bit_0_set_parse() {
 
 #[test]
 fn test_data_packet_options_bit_1_set_parse() {
-    match AcnRootLayerProtocol::parse(&TEST_DATA_PACKET_OPTIONS_BIT_1_SET_PACKET) {
+    match AcnRootLayerProtocol::parse(TEST_DATA_PACKET_OPTIONS_BIT_1_SET_PACKET) {
         Err(e) => {
-            assert!(false, "Unexpected error returned: {}", e);
+            panic!("Unexpected error returned: {}", e);
         }
         Ok(p) => {
             match p.pdu.data {
@@ -3916,7 +3859,7 @@ fn test_data_packet_options_bit_1_set_parse() {
                     );
                 }
                 _ => {
-                    assert!(false, "Packet not parsed as data-packet as expected");
+                    panic!("Packet not parsed as data-packet as expected");
                 }
             }
 
@@ -3927,9 +3870,9 @@ fn test_data_packet_options_bit_1_set_parse() {
 
 #[test]
 fn test_data_packet_options_bit_2_set_parse() {
-    match AcnRootLayerProtocol::parse(&TEST_DATA_PACKET_OPTIONS_BIT_2_SET_PACKET) {
+    match AcnRootLayerProtocol::parse(TEST_DATA_PACKET_OPTIONS_BIT_2_SET_PACKET) {
         Err(e) => {
-            assert!(false, "Unexpected error returned: {}", e);
+            panic!("Unexpected error returned: {}", e);
         }
         Ok(p) => {
             match p.pdu.data {
@@ -3964,7 +3907,7 @@ fn test_data_packet_options_bit_2_set_parse() {
                     );
                 }
                 _ => {
-                    assert!(false, "Packet not parsed as data-packet as expected");
+                    panic!("Packet not parsed as data-packet as expected");
                 }
             }
 
@@ -3975,9 +3918,9 @@ fn test_data_packet_options_bit_2_set_parse() {
 
 #[test]
 fn test_data_packet_options_bit_3_set_parse() {
-    match AcnRootLayerProtocol::parse(&TEST_DATA_PACKET_OPTIONS_BIT_3_SET_PACKET) {
+    match AcnRootLayerProtocol::parse(TEST_DATA_PACKET_OPTIONS_BIT_3_SET_PACKET) {
         Err(e) => {
-            assert!(false, "Unexpected error returned: {}", e);
+            panic!("Unexpected error returned: {}", e);
         }
         Ok(p) => {
             match p.pdu.data {
@@ -4012,7 +3955,7 @@ fn test_data_packet_options_bit_3_set_parse() {
                     );
                 }
                 _ => {
-                    assert!(false, "Packet not parsed as data-packet as expected");
+                    panic!("Packet not parsed as data-packet as expected");
                 }
             }
 
@@ -4023,9 +3966,9 @@ fn test_data_packet_options_bit_3_set_parse() {
 
 #[test]
 fn test_data_packet_options_bit_4_set_parse() {
-    match AcnRootLayerProtocol::parse(&TEST_DATA_PACKET_OPTIONS_BIT_4_SET_PACKET) {
+    match AcnRootLayerProtocol::parse(TEST_DATA_PACKET_OPTIONS_BIT_4_SET_PACKET) {
         Err(e) => {
-            assert!(false, "Unexpected error returned: {}", e);
+            panic!("Unexpected error returned: {}", e);
         }
         Ok(p) => {
             match p.pdu.data {
@@ -4060,7 +4003,7 @@ fn test_data_packet_options_bit_4_set_parse() {
                     );
                 }
                 _ => {
-                    assert!(false, "Packet not parsed as data-packet as expected");
+                    panic!("Packet not parsed as data-packet as expected");
                 }
             }
         }
@@ -4069,9 +4012,9 @@ fn test_data_packet_options_bit_4_set_parse() {
 
 #[test]
 fn test_data_packet_options_bit_5_set_parse() {
-    match AcnRootLayerProtocol::parse(&TEST_DATA_PACKET_OPTIONS_BIT_5_SET_PACKET) {
+    match AcnRootLayerProtocol::parse(TEST_DATA_PACKET_OPTIONS_BIT_5_SET_PACKET) {
         Err(e) => {
-            assert!(false, "Unexpected error returned: {}", e);
+            panic!("Unexpected error returned: {}", e);
         }
         Ok(p) => {
             match p.pdu.data {
@@ -4106,7 +4049,7 @@ fn test_data_packet_options_bit_5_set_parse() {
                     );
                 }
                 _ => {
-                    assert!(false, "Packet not parsed as data-packet as expected");
+                    panic!("Packet not parsed as data-packet as expected");
                 }
             }
 
@@ -4117,9 +4060,9 @@ fn test_data_packet_options_bit_5_set_parse() {
 
 #[test]
 fn test_data_packet_options_bit_6_set_parse() {
-    match AcnRootLayerProtocol::parse(&TEST_DATA_PACKET_OPTIONS_BIT_6_SET_PACKET) {
+    match AcnRootLayerProtocol::parse(TEST_DATA_PACKET_OPTIONS_BIT_6_SET_PACKET) {
         Err(e) => {
-            assert!(false, "Unexpected error returned: {}", e);
+            panic!("Unexpected error returned: {}", e);
         }
         Ok(p) => {
             match p.pdu.data {
@@ -4154,7 +4097,7 @@ fn test_data_packet_options_bit_6_set_parse() {
                     );
                 }
                 _ => {
-                    assert!(false, "Packet not parsed as data-packet as expected");
+                    panic!("Packet not parsed as data-packet as expected");
                 }
             }
 
@@ -4165,9 +4108,9 @@ fn test_data_packet_options_bit_6_set_parse() {
 
 #[test]
 fn test_data_packet_options_bit_7_set_parse() {
-    match AcnRootLayerProtocol::parse(&TEST_DATA_PACKET_OPTIONS_BIT_7_SET_PACKET) {
+    match AcnRootLayerProtocol::parse(TEST_DATA_PACKET_OPTIONS_BIT_7_SET_PACKET) {
         Err(e) => {
-            assert!(false, "Unexpected error returned: {}", e);
+            panic!("Unexpected error returned: {}", e);
         }
         Ok(p) => {
             match p.pdu.data {
@@ -4202,7 +4145,7 @@ fn test_data_packet_options_bit_7_set_parse() {
                     );
                 }
                 _ => {
-                    assert!(false, "Packet not parsed as data-packet as expected");
+                    panic!("Packet not parsed as data-packet as expected");
                 }
             }
         }
@@ -4211,262 +4154,229 @@ fn test_data_packet_options_bit_7_set_parse() {
 
 #[test]
 fn test_malformed_data_packet_too_high_universe_parse() {
-    match AcnRootLayerProtocol::parse(&TEST_DATA_PACKET_TOO_HIGH_UNIVERSE) {
+    match AcnRootLayerProtocol::parse(TEST_DATA_PACKET_TOO_HIGH_UNIVERSE) {
         Err(e) => {
             match e {
                 SacnError::SacnParsePackError(sacn_parse_pack_error::ParsePacketError::ParseInvalidUniverse(_)) => {
-                    assert!(true, "Expected error returned");
+                    // Expected error returned
                 }
                 x => {
-                    assert!(false, "Unexpected error type returned: {}", x);
+                    panic!("Unexpected error type returned: {}", x);
                 }
             }
 
         }
         Ok(_) => {
-            assert!(
-                false,
-                "Malformed packet was parsed when should have been rejected"
-            );
+            panic!("Malformed packet was parsed when should have been rejected");
         }
     }
 }
 
 #[test]
 fn test_malformed_data_packet_too_low_universe_parse() {
-    match AcnRootLayerProtocol::parse(&TEST_DATA_PACKET_TOO_LOW_UNIVERSE) {
+    match AcnRootLayerProtocol::parse(TEST_DATA_PACKET_TOO_LOW_UNIVERSE) {
         Err(e) => {
             match e {
                 SacnError::SacnParsePackError(sacn_parse_pack_error::ParsePacketError::ParseInvalidUniverse(_)) => {
-                    assert!(true, "Expected error returned");
+                    // Expected error returned
                 }
                 x => {
-                    assert!(false, "Unexpected error type returned: {}", x);
+                    panic!("Unexpected error type returned: {}", x);
                 }
             }
 
         }
         Ok(_) => {
-            assert!(
-                false,
-                "Malformed packet was parsed when should have been rejected"
-            );
+            panic!("Malformed packet was parsed when should have been rejected");
         }
     }
 }
 
 #[test]
 fn test_malformed_data_packet_dmp_layer_too_high_length_parse() {
-    match AcnRootLayerProtocol::parse(&TEST_DATA_PACKET_DMP_LAYER_TOO_HIGH_LENGTH) {
+    match AcnRootLayerProtocol::parse(TEST_DATA_PACKET_DMP_LAYER_TOO_HIGH_LENGTH) {
         Err(e) => {
             match e {
                 SacnError::SacnParsePackError(sacn_parse_pack_error::ParsePacketError::ParseInsufficientData(_)) => {
-                    assert!(true, "Expected error returned");
+                    // Expected error returned
                 }
                 x => {
-                    assert!(false, "Unexpected error type returned: {}", x);
+                    panic!("Unexpected error type returned: {}", x);
                 }
             }
 
         }
         Ok(_) => {
-            assert!(
-                false,
-                "Malformed packet was parsed when should have been rejected"
-            );
+            panic!("Malformed packet was parsed when should have been rejected");
         }
     }
 }
 
 #[test]
 fn test_malformed_data_packet_dmp_layer_too_low_length_parse() {
-    match AcnRootLayerProtocol::parse(&TEST_DATA_PACKET_DMP_LAYER_TOO_LOW_LENGTH) {
+    match AcnRootLayerProtocol::parse(TEST_DATA_PACKET_DMP_LAYER_TOO_LOW_LENGTH) {
         Err(e) => {
             match e {
                 SacnError::SacnParsePackError(sacn_parse_pack_error::ParsePacketError::ParseInsufficientData(_)) => {
-                    assert!(true, "Expected error returned");
+                    // Expected error returned
                 }
                 x => {
-                    assert!(false, "Unexpected error type returned: {}", x);
+                    panic!("Unexpected error type returned: {}", x);
                 }
             }
 
         }
         Ok(_) => {
-            assert!(
-                false,
-                "Malformed packet was parsed when should have been rejected"
-            );
+            panic!("Malformed packet was parsed when should have been rejected");
         }
     }
 }
 
 #[test]
 fn test_malformed_data_packet_dmp_layer_wrong_flags_parse() {
-    match AcnRootLayerProtocol::parse(&TEST_DATA_PACKET_DMP_LAYER_WRONG_FLAGS) {
+    match AcnRootLayerProtocol::parse(TEST_DATA_PACKET_DMP_LAYER_WRONG_FLAGS) {
         Err(e) => {
             match e {
                 SacnError::SacnParsePackError(sacn_parse_pack_error::ParsePacketError::ParsePduInvalidFlags(_)) => {
-                    assert!(true, "Expected error returned");
+                    // Expected error returned
                 }
                 x => {
-                    assert!(false, "Unexpected error type returned: {}", x);
+                    panic!("Unexpected error type returned: {}", x);
                 }
             }
 
         }
         Ok(_) => {
-            assert!(
-                false,
-                "Malformed packet was parsed when should have been rejected"
-            );
+            panic!("Malformed packet was parsed when should have been rejected");
         }
     }
 }
 
 #[test]
 fn test_malformed_data_packet_dmp_layer_wrong_vector_parse() {
-    match AcnRootLayerProtocol::parse(&TEST_DATA_PACKET_DMP_LAYER_WRONG_VECTOR) {
+    match AcnRootLayerProtocol::parse(TEST_DATA_PACKET_DMP_LAYER_WRONG_VECTOR) {
         Err(e) => {
             match e {
                 SacnError::SacnParsePackError(sacn_parse_pack_error::ParsePacketError::PduInvalidVector(_)) => {
-                    assert!(true, "Expected error returned");
+                    // Expected error returned
                 }
                 x => {
-                    assert!(false, "Unexpected error type returned: {}", x);
+                    panic!("Unexpected error type returned: {}", x);
                 }
             }
 
         }
         Ok(_) => {
-            assert!(
-                false,
-                "Malformed packet was parsed when should have been rejected"
-            );
+            panic!("Malformed packet was parsed when should have been rejected");
         }
     }
 }
 
 #[test]
 fn test_malformed_data_packet_dmp_layer_wrong_address_data_parse() {
-    match AcnRootLayerProtocol::parse(&TEST_DATA_PACKET_DMP_LAYER_WRONG_ADDRESS_DATA) {
+    match AcnRootLayerProtocol::parse(TEST_DATA_PACKET_DMP_LAYER_WRONG_ADDRESS_DATA) {
         Err(e) => {
             match e {
                 SacnError::SacnParsePackError(sacn_parse_pack_error::ParsePacketError::ParseInvalidData(_)) => {
-                    assert!(true, "Expected error returned");
+                    // Expected error returned
                 }
                 x => {
-                    assert!(false, "Unexpected error type returned: {}", x);
+                    panic!("Unexpected error type returned: {}", x);
                 }
             }
 
         }
         Ok(_) => {
-            assert!(
-                false,
-                "Malformed packet was parsed when should have been rejected"
-            );
+            panic!("Malformed packet was parsed when should have been rejected");
         }
     }
 }
 
 #[test]
 fn test_malformed_data_packet_dmp_layer_wrong_first_property_address_parse() {
-    match AcnRootLayerProtocol::parse(&TEST_DATA_PACKET_DMP_LAYER_WRONG_FIRST_PROPERTY_ADDRESS) {
+    match AcnRootLayerProtocol::parse(TEST_DATA_PACKET_DMP_LAYER_WRONG_FIRST_PROPERTY_ADDRESS) {
         Err(e) => {
             match e {
                 SacnError::SacnParsePackError(sacn_parse_pack_error::ParsePacketError::ParseInvalidData(_)) => {
-                    assert!(true, "Expected error returned");
+                    // Expected error returned
                 }
                 x => {
-                    assert!(false, "Unexpected error type returned: {}", x);
+                    panic!("Unexpected error type returned: {}", x);
                 }
             }
 
         }
         Ok(_) => {
-            assert!(
-                false,
-                "Malformed packet was parsed when should have been rejected"
-            );
+            panic!("Malformed packet was parsed when should have been rejected");
         }
     }
 }
 
 #[test]
 fn test_malformed_data_packet_dmp_layer_wrong_address_increment_parse() {
-    match AcnRootLayerProtocol::parse(&TEST_DATA_PACKET_DMP_LAYER_WRONG_ADDRESS_INCREMENT) {
+    match AcnRootLayerProtocol::parse(TEST_DATA_PACKET_DMP_LAYER_WRONG_ADDRESS_INCREMENT) {
         Err(e) => {
             match e {
                 SacnError::SacnParsePackError(sacn_parse_pack_error::ParsePacketError::ParseInvalidData(_)) => {
-                    assert!(true, "Expected error returned");
+                    // Expected error returned
                 }
                 x => {
-                    assert!(false, "Unexpected error type returned: {}", x);
+                    panic!("Unexpected error type returned: {}", x);
                 }
             }
 
         }
         Ok(_) => {
-            assert!(
-                false,
-                "Malformed packet was parsed when should have been rejected"
-            );
+            panic!("Malformed packet was parsed when should have been rejected");
         }
     }
 }
 
 #[test]
 fn test_malformed_data_packet_dmp_layer_too_high_property_count_parse() {
-    match AcnRootLayerProtocol::parse(&TEST_DATA_PACKET_DMP_LAYER_TOO_HIGH_PROPERTY_COUNT) {
+    match AcnRootLayerProtocol::parse(TEST_DATA_PACKET_DMP_LAYER_TOO_HIGH_PROPERTY_COUNT) {
         Err(e) => {
             match e {
                 SacnError::SacnParsePackError(sacn_parse_pack_error::ParsePacketError::ParseInsufficientData(_)) => {
-                    assert!(true, "Expected error returned");
+                    // Expected error returned
                 }
                 x => {
-                    assert!(false, "Unexpected error type returned: {}", x);
+                    panic!("Unexpected error type returned: {}", x);
                 }
             }
 
         }
         Ok(_) => {
-            assert!(
-                false,
-                "Malformed packet was parsed when should have been rejected"
-            );
+            panic!("Malformed packet was parsed when should have been rejected");
         }
     }
 }
 
 #[test]
 fn test_malformed_data_packet_dmp_layer_too_low_property_count_parse() {
-    match AcnRootLayerProtocol::parse(&TEST_DATA_PACKET_DMP_LAYER_TOO_LOW_PROPERTY_COUNT) {
+    match AcnRootLayerProtocol::parse(TEST_DATA_PACKET_DMP_LAYER_TOO_LOW_PROPERTY_COUNT) {
         Err(e) => {
             match e {
                 SacnError::SacnParsePackError(sacn_parse_pack_error::ParsePacketError::ParseInsufficientData(_)) => {
-                    assert!(true, "Expected error returned");
+                    // Expected error returned
                 }
                 x => {
-                    assert!(false, "Unexpected error type returned: {}", x);
+                    panic!("Unexpected error type returned: {}", x);
                 }
             }
 
         }
         Ok(_) => {
-            assert!(
-                false,
-                "Malformed packet was parsed when should have been rejected"
-            );
+            panic!("Malformed packet was parsed when should have been rejected");
         }
     }
 }
 
 #[test]
 fn test_termination_packet_full_property_values_parse() {
-    match AcnRootLayerProtocol::parse(&TEST_TERMINATION_FULL_PROPERTY_VALUES_PACKET) {
+    match AcnRootLayerProtocol::parse(TEST_TERMINATION_FULL_PROPERTY_VALUES_PACKET) {
         Err(e) => {
-            assert!(false, "Unexpected error returned: {}", e);
+            panic!("Unexpected error returned: {}", e);
         }
         Ok(p) => {
             match p.pdu.data {
@@ -4501,7 +4411,7 @@ fn test_termination_packet_full_property_values_parse() {
                     );
                 }
                 _ => {
-                    assert!(false, "Packet not parsed as data termination packet as expected");
+                    panic!("Packet not parsed as data termination packet as expected");
                 }
             }
         }
@@ -4510,9 +4420,9 @@ fn test_termination_packet_full_property_values_parse() {
 
 #[test]
 fn test_termination_packet_partial_property_values_parse() {
-    match AcnRootLayerProtocol::parse(&TEST_TERMINATION_PARTIAL_PROPERTY_VALUES_PACKET) {
+    match AcnRootLayerProtocol::parse(TEST_TERMINATION_PARTIAL_PROPERTY_VALUES_PACKET) {
         Err(e) => {
-            assert!(false, "Unexpected error returned: {}", e);
+            panic!("Unexpected error returned: {}", e);
         }
         Ok(p) => {
             match p.pdu.data {
@@ -4537,7 +4447,7 @@ fn test_termination_packet_partial_property_values_parse() {
                     );
                 }
                 _ => {
-                    assert!(false, "Packet not parsed as data termination packet as expected");
+                    panic!("Packet not parsed as data termination packet as expected");
                 }
             }
         }
@@ -4546,9 +4456,9 @@ fn test_termination_packet_partial_property_values_parse() {
 
 #[test]
 fn test_termination_packet_empty_property_values_parse() {
-    match AcnRootLayerProtocol::parse(&TEST_TERMINATION_EMPTY_PROPERTY_VALUES_PACKET) {
+    match AcnRootLayerProtocol::parse(TEST_TERMINATION_EMPTY_PROPERTY_VALUES_PACKET) {
         Err(e) => {
-            assert!(false, "Unexpected error returned: {}", e);
+            panic!("Unexpected error returned: {}", e);
         }
         Ok(p) => {
             match p.pdu.data {
@@ -4564,7 +4474,7 @@ fn test_termination_packet_empty_property_values_parse() {
                     assert_eq!(dpfl.data.property_values, Vec::new());
                 }
                 _ => {
-                    assert!(false, "Packet not parsed as data termination packet as expected");
+                    panic!("Packet not parsed as data termination packet as expected");
                 }
             }
         }
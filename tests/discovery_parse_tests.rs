@@ -797,7 +797,7 @@ fn test_discovery_packet_parse_pack() {
     };
 
     assert_eq!(
-        AcnRootLayerProtocol::parse(&TEST_UNIVERSE_DISCOVERY_PACKET).unwrap(),
+        AcnRootLayerProtocol::parse(TEST_UNIVERSE_DISCOVERY_PACKET).unwrap(),
         packet
     );
 
@@ -810,247 +810,217 @@ fn test_discovery_packet_parse_pack() {
 
 #[test]
 fn test_discovery_packet_root_layer_unknown_vector_parse() {
-    match AcnRootLayerProtocol::parse(&TEST_UNIVERSE_DISCOVERY_PACKET_ROOT_LAYER_UNKNOWN_VECTOR) {
+    match AcnRootLayerProtocol::parse(TEST_UNIVERSE_DISCOVERY_PACKET_ROOT_LAYER_UNKNOWN_VECTOR) {
         Err(e) => {
             match e {
                 SacnError::SacnParsePackError(sacn_parse_pack_error::ParsePacketError::PduInvalidVector(_)) => {
-                    assert!(true, "Expected error returned");
+                    // Expected error returned
                 }
                 x => {
-                    assert!(false, "Unexpected error type returned: {}", x);
+                    panic!("Unexpected error type returned: {}", x);
                 }
             }
 
         }
         Ok(_) => {
-            assert!(
-                false,
-                "Malformed packet was parsed when should have been rejected"
-            );
+            panic!("Malformed packet was parsed when should have been rejected");
         }
     }
 }
 
 #[test]
 fn test_discovery_packet_root_layer_data_vector_parse() {
-    match AcnRootLayerProtocol::parse(&TEST_UNIVERSE_DISCOVERY_PACKET_ROOT_LAYER_DATA_VECTOR) {
+    match AcnRootLayerProtocol::parse(TEST_UNIVERSE_DISCOVERY_PACKET_ROOT_LAYER_DATA_VECTOR) {
         Err(e) => {
             match e {
                 SacnError::SacnParsePackError(_) => {
                     // As the packet will be treated as a data packet it is unclear where the parse will fail so only assert that it must fail
                     // with a parse type error rather than a specific error.
-                    assert!(true, "Expected error family returned");
+                    // Expected error family returned
                 }
                 x => {
-                    assert!(false, "Unexpected error type returned: {}", x);
+                    panic!("Unexpected error type returned: {}", x);
                 }
             }
 
         }
         Ok(_) => {
-            assert!(
-                false,
-                "Malformed packet was parsed when should have been rejected"
-            );
+            panic!("Malformed packet was parsed when should have been rejected");
         }
     }
 }
 
 #[test]
 fn test_discovery_packet_too_short_cid_parse() {
-    match AcnRootLayerProtocol::parse(&TEST_UNIVERSE_DISCOVERY_PACKET_TOO_SHORT_CID) {
+    match AcnRootLayerProtocol::parse(TEST_UNIVERSE_DISCOVERY_PACKET_TOO_SHORT_CID) {
         Err(e) => {
             match e {
                 SacnError::SacnParsePackError(sacn_parse_pack_error::ParsePacketError::ParseInsufficientData(_)) => {
-                    assert!(true, "Expected error returned");
+                    // Expected error returned
                 }
                 x => {
-                    assert!(false, "Unexpected error type returned: {}", x);
+                    panic!("Unexpected error type returned: {}", x);
                 }
             }
 
         }
         Ok(_) => {
-            assert!(
-                false,
-                "Malformed packet was parsed when should have been rejected"
-            );
+            panic!("Malformed packet was parsed when should have been rejected");
         }
     }
 }
 
 #[test]
 fn test_discovery_packet_too_long_cid_parse() {
-    match AcnRootLayerProtocol::parse(&TEST_UNIVERSE_DISCOVERY_PACKET_TOO_LONG_CID) {
+    match AcnRootLayerProtocol::parse(TEST_UNIVERSE_DISCOVERY_PACKET_TOO_LONG_CID) {
         Err(e) => {
             match e {
                 SacnError::SacnParsePackError(_) => {
                     // Difficult to predict / assert what error should be caused by a field being too long as all
                     // other fields will be shifted and no clear way to know the true end of the CID field.
                     // Therefore just assert that the packet was detected as malformed rather than a specific error.
-                    assert!(true, "Expected error family returned");
+                    // Expected error family returned
                 }
                 x => {
-                    assert!(false, "Unexpected error type returned: {}", x);
+                    panic!("Unexpected error type returned: {}", x);
                 }
             }
 
         }
         Ok(_) => {
-            assert!(
-                false,
-                "Malformed packet was parsed when should have been rejected"
-            );
+            panic!("Malformed packet was parsed when should have been rejected");
         }
     }
 }
 
 #[test]
 fn test_discovery_packet_wrong_flags_parse() {
-    match AcnRootLayerProtocol::parse(&TEST_UNIVERSE_DISCOVERY_PACKET_WRONG_FLAGS) {
+    match AcnRootLayerProtocol::parse(TEST_UNIVERSE_DISCOVERY_PACKET_WRONG_FLAGS) {
         Err(e) => {
             match e {
                 SacnError::SacnParsePackError(sacn_parse_pack_error::ParsePacketError::ParsePduInvalidFlags(_)) => {
-                    assert!(true, "Expected error returned");
+                    // Expected error returned
                 }
                 x => {
-                    assert!(false, "Unexpected error type returned: {}", x);
+                    panic!("Unexpected error type returned: {}", x);
                 }
             }
 
         }
         Ok(_) => {
-            assert!(
-                false,
-                "Malformed packet was parsed when should have been rejected"
-            );
+            panic!("Malformed packet was parsed when should have been rejected");
         }
     }
 }
 
 #[test]
 fn test_discovery_packet_framing_layer_length_too_long_parse() {
-    match AcnRootLayerProtocol::parse(&TEST_UNIVERSE_DISCOVERY_PACKET_FRAMING_LAYER_LENGTH_TOO_LONG) {
+    match AcnRootLayerProtocol::parse(TEST_UNIVERSE_DISCOVERY_PACKET_FRAMING_LAYER_LENGTH_TOO_LONG) {
         Err(e) => {
             match e {
                 SacnError::SacnParsePackError(sacn_parse_pack_error::ParsePacketError::ParseInsufficientData(_)) => {
-                    assert!(true, "Expected error returned");
+                    // Expected error returned
                 }
                 x => {
-                    assert!(false, "Unexpected error type returned: {}", x);
+                    panic!("Unexpected error type returned: {}", x);
                 }
             }
 
         }
         Ok(_) => {
-            assert!(
-                false,
-                "Malformed packet was parsed when should have been rejected"
-            );
+            panic!("Malformed packet was parsed when should have been rejected");
         }
     }
 }
 
 #[test]
 fn test_discovery_packet_framing_layer_length_too_short_parse() {
-    match AcnRootLayerProtocol::parse(&TEST_UNIVERSE_DISCOVERY_PACKET_FRAMING_LAYER_LENGTH_TOO_SHORT) {
+    match AcnRootLayerProtocol::parse(TEST_UNIVERSE_DISCOVERY_PACKET_FRAMING_LAYER_LENGTH_TOO_SHORT) {
         Err(e) => {
             match e {
                 SacnError::SacnParsePackError(sacn_parse_pack_error::ParsePacketError::ParseInsufficientData(_)) => {
-                    assert!(true, "Expected error returned");
+                    // Expected error returned
                 }
                 x => {
-                    assert!(false, "Unexpected error type returned: {}", x);
+                    panic!("Unexpected error type returned: {}", x);
                 }
             }
 
         }
         Ok(_) => {
-            assert!(
-                false,
-                "Malformed packet was parsed when should have been rejected"
-            );
+            panic!("Malformed packet was parsed when should have been rejected");
         }
     }
 }
 
 #[test]
 fn test_discovery_packet_framing_layer_wrong_flags_parse() {
-    match AcnRootLayerProtocol::parse(&TEST_UNIVERSE_DISCOVERY_PACKET_FRAMING_LAYER_WRONG_FLAGS) {
+    match AcnRootLayerProtocol::parse(TEST_UNIVERSE_DISCOVERY_PACKET_FRAMING_LAYER_WRONG_FLAGS) {
         Err(e) => {
             match e {
                 SacnError::SacnParsePackError(sacn_parse_pack_error::ParsePacketError::ParsePduInvalidFlags(_)) => {
-                    assert!(true, "Expected error returned");
+                    // Expected error returned
                 }
                 x => {
-                    assert!(false, "Unexpected error type returned: {}", x);
+                    panic!("Unexpected error type returned: {}", x);
                 }
             }
 
         }
         Ok(_) => {
-            assert!(
-                false,
-                "Malformed packet was parsed when should have been rejected"
-            );
+            panic!("Malformed packet was parsed when should have been rejected");
         }
     }
 }
 
 #[test]
 fn test_discovery_packet_sync_framing_vector_parse() {
-    match AcnRootLayerProtocol::parse(&TEST_UNIVERSE_DISCOVERY_PACKET_SYNC_FRAMING_VECTOR) {
+    match AcnRootLayerProtocol::parse(TEST_UNIVERSE_DISCOVERY_PACKET_SYNC_FRAMING_VECTOR) {
         Err(e) => {
             match e {
                 SacnError::SacnParsePackError(_) => {
                     // Difficult to assert the exact error caused by using the sync vector as the packet will then
                     // be parsed as a sync packet and could be rejected for multiple reasons.
                     // The key part is that it is rejected successfully for a parse error.
-                    assert!(true, "Expected error family returned");
+                    // Expected error family returned
                 }
                 x => {
-                    assert!(false, "Unexpected error type returned: {}", x);
+                    panic!("Unexpected error type returned: {}", x);
                 }
             }
 
         }
         Ok(_) => {
-            assert!(
-                false,
-                "Malformed packet was parsed when should have been rejected"
-            );
+            panic!("Malformed packet was parsed when should have been rejected");
         }
     }
 }
 
 #[test]
 fn test_discovery_packet_unknown_framing_vector_parse() {
-    match AcnRootLayerProtocol::parse(&TEST_UNIVERSE_DISCOVERY_PACKET_UNKNOWN_FRAMING_VECTOR) {
+    match AcnRootLayerProtocol::parse(TEST_UNIVERSE_DISCOVERY_PACKET_UNKNOWN_FRAMING_VECTOR) {
         Err(e) => {
             match e {
                 SacnError::SacnParsePackError(sacn_parse_pack_error::ParsePacketError::PduInvalidVector(_)) => {
-                    assert!(true, "Expected error returned");
+                    // Expected error returned
                 }
                 x => {
-                    assert!(false, "Unexpected error type returned: {}", x);
+                    panic!("Unexpected error type returned: {}", x);
                 }
             }
 
         }
         Ok(_) => {
-            assert!(
-                false,
-                "Malformed packet was parsed when should have been rejected"
-            );
+            panic!("Malformed packet was parsed when should have been rejected");
         }
     }
 }
 
 #[test]
 fn test_discovery_packet_arbitrary_reserved_parse() {
-    match AcnRootLayerProtocol::parse(&TEST_UNIVERSE_DISCOVERY_PACKET_ARBITRARY_RESERVED) {
+    match AcnRootLayerProtocol::parse(TEST_UNIVERSE_DISCOVERY_PACKET_ARBITRARY_RESERVED) {
         Err(e) => {
-                assert!(false, "Unexpected error returned: {}", e);
+                panic!("Unexpected error returned: {}", e);
             }
         Ok(p) => {
             match p.pdu.data {
@@ -1061,7 +1031,7 @@ fn test_discovery_packet_arbitrary_reserved_parse() {
                     assert_eq!(udpfl.data.universes, vec!(0x01, 0x0203, 0x0405));
                 }
                 _ => {
-                    assert!(false, "Packet not parsed as discovery-packet as expected");
+                    panic!("Packet not parsed as discovery-packet as expected");
                 }
             }
         }
@@ -1070,161 +1040,140 @@ fn test_discovery_packet_arbitrary_reserved_parse() {
 
 #[test]
 fn test_discovery_packet_discovery_layer_wrong_flags_parse() {
-    match AcnRootLayerProtocol::parse(&TEST_UNIVERSE_DISCOVERY_PACKET_DISCOVERY_LAYER_WRONG_FLAGS) {
+    match AcnRootLayerProtocol::parse(TEST_UNIVERSE_DISCOVERY_PACKET_DISCOVERY_LAYER_WRONG_FLAGS) {
         Err(e) => {
             match e {
                 SacnError::SacnParsePackError(sacn_parse_pack_error::ParsePacketError::ParsePduInvalidFlags(_)) => {
-                    assert!(true, "Expected error returned");
+                    // Expected error returned
                 }
                 x => {
-                    assert!(false, "Unexpected error type returned: {}", x);
+                    panic!("Unexpected error type returned: {}", x);
                 }
             }
 
         }
         Ok(_) => {
-            assert!(
-                false,
-                "Malformed packet was parsed when should have been rejected"
-            );
+            panic!("Malformed packet was parsed when should have been rejected");
         }
     }
 }
 
 #[test]
 fn test_discovery_packet_discovery_layer_length_too_short_parse() {
-    match AcnRootLayerProtocol::parse(&TEST_UNIVERSE_DISCOVERY_PACKET_DISCOVERY_LAYER_LENGTH_TOO_SHORT) {
+    match AcnRootLayerProtocol::parse(TEST_UNIVERSE_DISCOVERY_PACKET_DISCOVERY_LAYER_LENGTH_TOO_SHORT) {
         Err(e) => {
             match e {
                 SacnError::SacnParsePackError(sacn_parse_pack_error::ParsePacketError::ParseInsufficientData(_)) => {
-                    assert!(true, "Expected error returned");
+                    // Expected error returned
                 }
                 x => {
-                    assert!(false, "Unexpected error type returned: {}", x);
+                    panic!("Unexpected error type returned: {}", x);
                 }
             }
 
         }
         Ok(p) => {
-            assert!(
-                false,
-                "Malformed packet was parsed when should have been rejected: {:?}", p
-            );
+            panic!("Malformed packet was parsed when should have been rejected: {:?}", p);
         }
     }
 }
 
 #[test]
 fn test_discovery_packet_discovery_layer_length_too_long_parse() {
-    match AcnRootLayerProtocol::parse(&TEST_UNIVERSE_DISCOVERY_PACKET_DISCOVERY_LAYER_LENGTH_TOO_LONG) {
+    match AcnRootLayerProtocol::parse(TEST_UNIVERSE_DISCOVERY_PACKET_DISCOVERY_LAYER_LENGTH_TOO_LONG) {
         Err(e) => {
             match e {
                 SacnError::SacnParsePackError(sacn_parse_pack_error::ParsePacketError::ParseInsufficientData(_)) => {
-                    assert!(true, "Expected error returned");
+                    // Expected error returned
                 }
                 x => {
-                    assert!(false, "Unexpected error type returned: {}", x);
+                    panic!("Unexpected error type returned: {}", x);
                 }
             }
 
         }
         Ok(_) => {
-            assert!(
-                false,
-                "Malformed packet was parsed when should have been rejected"
-            );
+            panic!("Malformed packet was parsed when should have been rejected");
         }
     }
 }
 
 #[test]
 fn test_discovery_packet_discovery_layer_vector_unknown_parse() {
-    match AcnRootLayerProtocol::parse(&TEST_UNIVERSE_DISCOVERY_PACKET_DISCOVERY_LAYER_VECTOR_UNKNOWN) {
+    match AcnRootLayerProtocol::parse(TEST_UNIVERSE_DISCOVERY_PACKET_DISCOVERY_LAYER_VECTOR_UNKNOWN) {
         Err(e) => {
             match e {
                 SacnError::SacnParsePackError(sacn_parse_pack_error::ParsePacketError::PduInvalidVector(_)) => {
-                    assert!(true, "Expected error returned");
+                    // Expected error returned
                 }
                 x => {
-                    assert!(false, "Unexpected error type returned: {}", x);
+                    panic!("Unexpected error type returned: {}", x);
                 }
             }
 
         }
         Ok(_) => {
-            assert!(
-                false,
-                "Malformed packet was parsed when should have been rejected"
-            );
+            panic!("Malformed packet was parsed when should have been rejected");
         }
     }
 }
 
 #[test]
 fn test_discovery_packet_page_higher_than_last_page_parse() {
-    match AcnRootLayerProtocol::parse(&TEST_UNIVERSE_DISCOVERY_PACKET_PAGE_HIGHER_THAN_LAST_PAGE) {
+    match AcnRootLayerProtocol::parse(TEST_UNIVERSE_DISCOVERY_PACKET_PAGE_HIGHER_THAN_LAST_PAGE) {
         Err(e) => {
             match e {
                 SacnError::SacnParsePackError(sacn_parse_pack_error::ParsePacketError::ParseInvalidPage(_)) => {
-                    assert!(true, "Expected error returned");
+                    // Expected error returned
                 }
                 x => {
-                    assert!(false, "Unexpected error type returned: {}", x);
+                    panic!("Unexpected error type returned: {}", x);
                 }
             }
 
         }
         Ok(_) => {
-            assert!(
-                false,
-                "Malformed packet was parsed when should have been rejected"
-            );
+            panic!("Malformed packet was parsed when should have been rejected");
         }
     }
 }
 
 #[test]
 fn test_discovery_packet_decending_order_parse() {
-    match AcnRootLayerProtocol::parse(&TEST_UNIVERSE_DISCOVERY_PACKET_DECENDING_ORDER) {
+    match AcnRootLayerProtocol::parse(TEST_UNIVERSE_DISCOVERY_PACKET_DECENDING_ORDER) {
         Err(e) => {
             match e {
                 SacnError::SacnParsePackError(sacn_parse_pack_error::ParsePacketError::ParseInvalidUniverseOrder(_)) => {
-                    assert!(true, "Expected error returned");
+                    // Expected error returned
                 }
                 x => {
-                    assert!(false, "Unexpected error type returned: {}", x);
+                    panic!("Unexpected error type returned: {}", x);
                 }
             }
 
         }
         Ok(_) => {
-            assert!(
-                false,
-                "Malformed packet was parsed when should have been rejected"
-            );
+            panic!("Malformed packet was parsed when should have been rejected");
         }
     }
 }
 
 #[test]
 fn test_discovery_packet_random_order_parse() {
-    match AcnRootLayerProtocol::parse(&TEST_UNIVERSE_DISCOVERY_PACKET_RANDOM_ORDER) {
+    match AcnRootLayerProtocol::parse(TEST_UNIVERSE_DISCOVERY_PACKET_RANDOM_ORDER) {
         Err(e) => {
             match e {
                 SacnError::SacnParsePackError(sacn_parse_pack_error::ParsePacketError::ParseInvalidUniverseOrder(_)) => {
-                    assert!(true, "Expected error returned");
+                    // Expected error returned
                 }
                 x => {
-                    assert!(false, "Unexpected error type returned: {}", x);
+                    panic!("Unexpected error type returned: {}", x);
                 }
             }
 
         }
         Ok(_) => {
-            assert!(
-                false,
-                "Malformed packet was parsed when should have been rejected"
-            );
+            panic!("Malformed packet was parsed when should have been rejected");
         }
     }
 }
@@ -1308,7 +1257,7 @@ fn generate_test_universe_discovery_packet(universes_to_generate: u16) -> Vec<u8
         test_universe_discovery_packet.push(vals[1]);
     }
 
-    return test_universe_discovery_packet;
+    test_universe_discovery_packet
 }
 
 #[test]
@@ -1319,7 +1268,7 @@ fn test_discovery_packet_no_universes() {
 
     match AcnRootLayerProtocol::parse(&generated_packet) {
         Err(e) => {
-            assert!(false, "Unexpected error returned: {}", e);
+            panic!("Unexpected error returned: {}", e);
         }
         Ok(p) => {
             match p.pdu.data {
@@ -1330,7 +1279,7 @@ fn test_discovery_packet_no_universes() {
                     assert_eq!(udpfl.data.universes, Vec::new());
                 }
                 _ => {
-                    assert!(false, "Packet not parsed as discovery-packet as expected");
+                    panic!("Packet not parsed as discovery-packet as expected");
                 }
             }
         }
@@ -1345,7 +1294,7 @@ fn test_discovery_packet_max_universe_capacity() {
 
     match AcnRootLayerProtocol::parse(&generated_packet) {
         Err(e) => {
-            assert!(false, "Unexpected error returned: {}", e);
+            panic!("Unexpected error returned: {}", e);
         }
         Ok(p) => {
             match p.pdu.data {
@@ -1356,7 +1305,7 @@ fn test_discovery_packet_max_universe_capacity() {
                     assert_eq!(udpfl.data.universes.into_owned().len(), DISCOVERY_UNI_PER_PAGE);
                 }
                 _ => {
-                    assert!(false, "Packet not parsed as discovery-packet as expected");
+                    panic!("Packet not parsed as discovery-packet as expected");
                 }
             }
         }
@@ -1369,18 +1318,15 @@ fn test_discovery_packet_above_max_universe_capacity() {
         Err(e) => {
             match e {
                 SacnError::SacnParsePackError(sacn_parse_pack_error::ParsePacketError::PduInvalidLength(_)) => {
-                    assert!(true, "Expected error returned");
+                    // Expected error returned
                 }
                 x => {
-                    assert!(false, "Unexpected error type returned: {}", x);
+                    panic!("Unexpected error type returned: {}", x);
                 }
             }
         }
         Ok(_) => {
-            assert!(
-                false,
-                "Malformed packet was parsed when should have been rejected"
-            );
+            panic!("Malformed packet was parsed when should have been rejected");
         }
     }
 }
@@ -7,13 +7,15 @@
 //
 // This file was created as part of a University of St Andrews Computer Science BSC Senior Honours Dissertation Project.
 
+#![allow(clippy::type_complexity, clippy::needless_range_loop)]
+
 extern crate sacn;
 extern crate socket2;
 extern crate uuid;
 
 pub mod ipv4_tests;
 
-const TEST_NETWORK_INTERFACE_IPV6: [&'static str; 3] = [
+const TEST_NETWORK_INTERFACE_IPV6: [&str; 3] = [
     "2a02:c7f:d20a:c600:a502:2dae:7716:601b",
     "2a02:c7f:d20a:c600:a502:2dae:7716:601c",
     "2a02:c7f:d20a:c600:a502:2dae:7716:601d",
@@ -26,7 +28,6 @@ const TEST_NETWORK_INTERFACE_IPV6: [&'static str; 3] = [
 mod sacn_ipv6_multicast_test {
 
     use std::io::Read;
-    use std::iter;
     use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
     use std::sync::mpsc;
     use std::sync::mpsc::{Receiver, RecvTimeoutError, Sender, SyncSender};
@@ -112,10 +113,7 @@ mod sacn_ipv6_multicast_test {
 
         rcv_thread.join().unwrap();
 
-        assert!(
-            !received_result.is_err(),
-            "Failed: Error when receiving data"
-        );
+        assert!(received_result.is_ok(), "Failed: Error when receiving data");
 
         let received_data: Vec<DMXData> = received_result.unwrap();
 
@@ -187,10 +185,7 @@ mod sacn_ipv6_multicast_test {
 
         rcv_thread.join().unwrap();
 
-        assert!(
-            !received_result.is_err(),
-            "Failed: Error when receiving data"
-        );
+        assert!(received_result.is_ok(), "Failed: Error when receiving data");
 
         let received_data: Vec<DMXData> = received_result.unwrap();
 
@@ -255,10 +250,7 @@ mod sacn_ipv6_multicast_test {
 
         rcv_thread.join().unwrap();
 
-        assert!(
-            !sync_pkt_res.is_err(),
-            "Failed: Error when receiving packets"
-        );
+        assert!(sync_pkt_res.is_ok(), "Failed: Error when receiving packets");
 
         let mut received_data: Vec<DMXData> = sync_pkt_res.unwrap();
 
@@ -343,10 +335,7 @@ mod sacn_ipv6_multicast_test {
 
         rcv_thread.join().unwrap();
 
-        assert!(
-            !sync_pkt_res.is_err(),
-            "Failed: Error when receiving packets"
-        );
+        assert!(sync_pkt_res.is_ok(), "Failed: Error when receiving packets");
 
         let mut received_data: Vec<DMXData> = sync_pkt_res.unwrap();
 
@@ -417,24 +406,20 @@ mod sacn_ipv6_multicast_test {
 
         src.register_universe(universe).unwrap();
 
-        let _ = src
-            .send(
-                &[universe],
-                &TEST_DATA_SINGLE_UNIVERSE,
-                Some(priority),
-                None,
-                None,
-            )
-            .unwrap();
+        src.send(
+            &[universe],
+            &TEST_DATA_SINGLE_UNIVERSE,
+            Some(priority),
+            None,
+            None,
+        )
+        .unwrap();
 
         let received_result: Result<Vec<DMXData>> = rx.recv().unwrap();
 
         rcv_thread.join().unwrap();
 
-        assert!(
-            !received_result.is_err(),
-            "Failed: Error when receiving data"
-        );
+        assert!(received_result.is_ok(), "Failed: Error when receiving data");
 
         let received_data: Vec<DMXData> = received_result.unwrap();
 
@@ -507,10 +492,7 @@ mod sacn_ipv6_multicast_test {
 
         rcv_thread.join().unwrap();
 
-        assert!(
-            !sync_pkt_res.is_err(),
-            "Failed: Error when receiving packets"
-        );
+        assert!(sync_pkt_res.is_ok(), "Failed: Error when receiving packets");
 
         let mut received_data: Vec<DMXData> = sync_pkt_res.unwrap();
 
@@ -633,7 +615,7 @@ mod sacn_ipv6_multicast_test {
         match attempt_recv {
             Ok(o) => {
                 println!("{:#?}", o);
-                assert!(false, "Receivers received without waiting for sync");
+                panic!("Receivers received without waiting for sync");
             }
             Err(e) => assert_eq!(e, RecvTimeoutError::Timeout),
         }
@@ -651,7 +633,7 @@ mod sacn_ipv6_multicast_test {
         assert_eq!(received_result1.len(), 1); // Check only 1 universe received as expected.
         assert_eq!(received_result2.len(), 1); // Check only 1 universe received as expected.
 
-        let mut results = vec![received_result1[0].clone(), received_result2[0].clone()];
+        let mut results = [received_result1[0].clone(), received_result2[0].clone()];
         results.sort_unstable(); // Ordering of received data is undefined, to make it easier to check sort first.
 
         assert_eq!(results[0].universe, universe1); // Check that the universe 1 received is as expected.
@@ -741,7 +723,7 @@ mod sacn_ipv6_multicast_test {
                 .unwrap();
 
                 // Receivers listen to all universes
-                for i in (BASE_UNIVERSE as u16)..((SND_THREADS as u16) + (BASE_UNIVERSE as u16)) {
+                for i in BASE_UNIVERSE..((SND_THREADS as u16) + BASE_UNIVERSE) {
                     dmx_recv.listen_universes(&[i]).unwrap();
                 }
 
@@ -807,7 +789,7 @@ mod sacn_ipv6_multicast_test {
         const SND_THREADS: usize = 1;
         const BASE_UNIVERSE: u16 = 2;
         const UNIVERSE_COUNT: usize = 1;
-        const SOURCE_NAMES: [&'static str; 1] = ["Source 1"];
+        const SOURCE_NAMES: [&str; 1] = ["Source 1"];
 
         let (snd_tx, snd_rx): (SyncSender<()>, Receiver<()>) = mpsc::sync_channel(0);
 
@@ -857,23 +839,23 @@ mod sacn_ipv6_multicast_test {
                                 }
                                 std::io::ErrorKind::TimedOut => {}
                                 _ => {
-                                    assert!(false, "Unexpected error returned");
+                                    panic!("Unexpected error returned");
                                 }
                             }
                         }
                         _ => {
-                            assert!(false, "Unexpected error returned");
+                            panic!("Unexpected error returned");
                         }
                     }
                 }
                 Ok(_) => {
-                    assert!(false, "No data should have been passed up!");
+                    panic!("No data should have been passed up!");
                 }
             }
 
             let discovered = dmx_recv.get_discovered_sources();
 
-            if discovered.len() > 0 {
+            if !discovered.is_empty() {
                 assert_eq!(discovered.len(), 1);
                 assert_eq!(discovered[0].name, SOURCE_NAMES[0]);
                 let universes = discovered[0].get_all_universes();
@@ -898,7 +880,7 @@ mod sacn_ipv6_multicast_test {
         const SND_THREADS: usize = 1;
         const BASE_UNIVERSE: u16 = 2;
         const UNIVERSE_COUNT: usize = 5;
-        const SOURCE_NAMES: [&'static str; 1] = ["Source 1"];
+        const SOURCE_NAMES: [&str; 1] = ["Source 1"];
 
         let (snd_tx, snd_rx): (SyncSender<()>, Receiver<()>) = mpsc::sync_channel(0);
 
@@ -948,23 +930,23 @@ mod sacn_ipv6_multicast_test {
                                 }
                                 std::io::ErrorKind::TimedOut => {}
                                 _ => {
-                                    assert!(false, "Unexpected error returned");
+                                    panic!("Unexpected error returned");
                                 }
                             }
                         }
                         _ => {
-                            assert!(false, "Unexpected error returned");
+                            panic!("Unexpected error returned");
                         }
                     }
                 }
                 Ok(_) => {
-                    assert!(false, "No data should have been passed up!");
+                    panic!("No data should have been passed up!");
                 }
             }
 
             let discovered = dmx_recv.get_discovered_sources();
 
-            if discovered.len() > 0 {
+            if !discovered.is_empty() {
                 assert_eq!(discovered.len(), 1);
                 assert_eq!(discovered[0].name, SOURCE_NAMES[0]);
 
@@ -990,7 +972,7 @@ mod sacn_ipv6_multicast_test {
         const SND_THREADS: usize = 1;
         const BASE_UNIVERSE: u16 = 2;
         const UNIVERSE_COUNT: usize = 600;
-        const SOURCE_NAMES: [&'static str; 1] = ["Source 1"];
+        const SOURCE_NAMES: [&str; 1] = ["Source 1"];
 
         let (snd_tx, snd_rx): (SyncSender<()>, Receiver<()>) = mpsc::sync_channel(0);
 
@@ -1045,23 +1027,23 @@ mod sacn_ipv6_multicast_test {
                                 }
                                 std::io::ErrorKind::TimedOut => {}
                                 _ => {
-                                    assert!(false, "Unexpected error returned");
+                                    panic!("Unexpected error returned");
                                 }
                             }
                         }
                         _ => {
-                            assert!(false, "Unexpected error returned");
+                            panic!("Unexpected error returned");
                         }
                     }
                 }
                 Ok(_) => {
-                    assert!(false, "No data should have been passed up!");
+                    panic!("No data should have been passed up!");
                 }
             }
 
             let discovered = dmx_recv.get_discovered_sources();
 
-            if discovered.len() > 0 {
+            if !discovered.is_empty() {
                 assert_eq!(discovered.len(), 1);
                 assert_eq!(discovered[0].name, SOURCE_NAMES[0]);
                 let universes = discovered[0].get_all_universes();
@@ -1130,11 +1112,11 @@ mod sacn_ipv6_multicast_test {
         rcv_thread.join().unwrap();
 
         assert!(
-            !received_result.is_err(),
+            received_result.is_ok(),
             "Failed: Error when receiving 1st universe of data"
         );
         assert!(
-            !received_result_2.is_err(),
+            received_result_2.is_ok(),
             "Failed: Error when receiving 2nd universe of data"
         );
 
@@ -1162,6 +1144,12 @@ mod sacn_ipv6_multicast_test {
     fn test_two_senders_one_recv_same_universe_no_sync_multicast_ipv6() {
         let universe = 1;
 
+        // The second source only sends once the data of the first has been received and both sources are kept alive until the merged
+        // data is received as dropping a source terminates its universes.
+        let (go_tx, go_rx): (Sender<()>, Receiver<()>) = mpsc::channel();
+        let (done_tx_1, done_rx_1): (Sender<()>, Receiver<()>) = mpsc::channel();
+        let (done_tx_2, done_rx_2): (Sender<()>, Receiver<()>) = mpsc::channel();
+
         let mut dmx_recv = SacnReceiver::with_ip(
             SocketAddr::new(IpAddr::V6(Ipv6Addr::UNSPECIFIED), ACN_SDT_MULTICAST_PORT),
             None,
@@ -1181,15 +1169,15 @@ mod sacn_ipv6_multicast_test {
 
             src.register_universe(universe).unwrap();
 
-            let _ = src
-                .send(
-                    &[universe],
-                    &TEST_DATA_SINGLE_UNIVERSE,
-                    Some(priority),
-                    None,
-                    None,
-                )
-                .unwrap();
+            src.send(
+                &[universe],
+                &TEST_DATA_SINGLE_UNIVERSE,
+                Some(priority),
+                None,
+                None,
+            )
+            .unwrap();
+            done_rx_1.recv().unwrap();
         });
 
         let snd_thread_2 = thread::spawn(move || {
@@ -1203,43 +1191,49 @@ mod sacn_ipv6_multicast_test {
 
             src.register_universe(universe).unwrap();
 
-            let _ = src
-                .send(
-                    &[universe],
-                    &TEST_DATA_PARTIAL_CAPACITY_UNIVERSE,
-                    Some(priority),
-                    None,
-                    None,
-                )
-                .unwrap();
+            go_rx.recv().unwrap();
+            src.send(
+                &[universe],
+                &TEST_DATA_PARTIAL_CAPACITY_UNIVERSE,
+                Some(priority),
+                None,
+                None,
+            )
+            .unwrap();
+            done_rx_2.recv().unwrap();
         });
 
         let res1: Vec<DMXData> = dmx_recv.recv(None).unwrap();
+        go_tx.send(()).unwrap();
         let res2: Vec<DMXData> = dmx_recv.recv(None).unwrap();
 
+        done_tx_1.send(()).unwrap();
+        done_tx_2.send(()).unwrap();
         snd_thread_1.join().unwrap();
         snd_thread_2.join().unwrap();
 
         assert_eq!(res1.len(), 1);
         assert_eq!(res2.len(), 1);
 
-        let res = vec![res1[0].clone(), res2[0].clone()];
+        assert_eq!(res1[0].universe, universe);
+        assert_eq!(res2[0].universe, universe);
 
-        assert_eq!(res[0].universe, universe);
-        assert_eq!(res[1].universe, universe);
+        // Once both sources are sending at the same priority the receiver returns the HTP merge of their levels.
+        let merged: Vec<u8> = TEST_DATA_SINGLE_UNIVERSE
+            .iter()
+            .enumerate()
+            .map(|(i, v)| (*v).max(*TEST_DATA_PARTIAL_CAPACITY_UNIVERSE.get(i).unwrap_or(&0)))
+            .collect();
 
-        if res[0].values == TEST_DATA_SINGLE_UNIVERSE.to_vec() {
-            assert_eq!(res[1].values, TEST_DATA_PARTIAL_CAPACITY_UNIVERSE.to_vec());
-        } else {
-            assert_eq!(res[0].values, TEST_DATA_PARTIAL_CAPACITY_UNIVERSE.to_vec());
-            assert_eq!(res[1].values, TEST_DATA_SINGLE_UNIVERSE.to_vec());
-        }
+        assert_eq!(res1[0].values, TEST_DATA_SINGLE_UNIVERSE.to_vec());
+        assert_eq!(res2[0].values, merged);
     }
 
     /// Setups and runs through the scenario as described in ANSI E1.31-2018 Appendix B.
     /// This asserts that the behaviour of this implementation is exactly as outlined within that section.
     /// This shows that the implementation handles universe synchronisation in the way specified by the protocol document.
-    /// As the force synchronisation option is not implemented as part of this library that section is ignored.
+    /// The part of the scenario where Source A times out isn't run, the Force_Synchronization option is instead tested by the receiver's
+    /// unit tests.
     ///
     /// This is exactly the same as the IPv4 variant test of the same name but done over IPv6 to show equivalence.
     #[test]
@@ -1268,14 +1262,10 @@ mod sacn_ipv6_multicast_test {
         let data2 = [
             0x00, 0xa, 0xb, 0xc, 0xd, 0xe, 0xf, 0xa, 0xb, 0xc, 0xd, 0xe, 0xf, 0xa,
         ];
-        let src_cid: Uuid = Uuid::from_bytes(
-            [
-                0xef, 0x07, 0xc8, 0xdd, 0x00, 0x64, 0x44, 0x01, 0xa3, 0xa2, 0x45, 0x9e, 0xf8, 0xe6,
-                0x14, 0x3e,
-            ]
-            .try_into()
-            .unwrap(),
-        );
+        let src_cid: Uuid = Uuid::from_bytes([
+            0xef, 0x07, 0xc8, 0xdd, 0x00, 0x64, 0x44, 0x01, 0xa3, 0xa2, 0x45, 0x9e, 0xf8, 0xe6,
+            0x14, 0x3e,
+        ]);
 
         let snd_thread = thread::spawn(move || {
             let ip: SocketAddr = SocketAddr::new(
@@ -1372,11 +1362,11 @@ mod sacn_ipv6_multicast_test {
                             "Unexpected data within second data packet of a set of synchronised packets"
                         );
                     } else {
-                        assert!(false, "Unrecognised universe within data packet");
+                        panic!("Unrecognised universe within data packet");
                     }
                 }
                 Err(e) => {
-                    assert!(false, "{}", format!("Unexpected error returned: {:?}", e));
+                    panic!("Unexpected error returned: {:?}", e);
                 }
             }
         }
@@ -1408,7 +1398,8 @@ mod sacn_ipv6_multicast_test {
         // "Since the the Force_Synchronization bit in the Options field of the E1.31 Data Packet has been set to 0,
         // even if Source A times out the E131_NETWORK_DATA_LOSS_TIMEOUT, Receiver B will stay in its last
         // look until a new E1.31 Synchronization Packet arrives."
-        // The implementation does not support the force synchronisation bit so always acts as if is set to 1 and times out.
+        // The receiver follows this, data sent without the bit waits for synchronisation once it has been lost, however Source A isn't
+        // left to time out here.
 
         snd_thread.join().unwrap();
     }
@@ -1455,14 +1446,10 @@ mod sacn_ipv6_multicast_test {
         ];
 
         // The source CID.
-        let src_cid: Uuid = Uuid::from_bytes(
-            [
-                0xef, 0x07, 0xc8, 0xdd, 0x00, 0x64, 0x44, 0x01, 0xa3, 0xa2, 0x45, 0x9e, 0xf8, 0xe6,
-                0x14, 0x3e,
-            ]
-            .try_into()
-            .unwrap(),
-        );
+        let src_cid: Uuid = Uuid::from_bytes([
+            0xef, 0x07, 0xc8, 0xdd, 0x00, 0x64, 0x44, 0x01, 0xa3, 0xa2, 0x45, 0x9e, 0xf8, 0xe6,
+            0x14, 0x3e,
+        ]);
 
         let snd_thread = thread::spawn(move || {
             let ip: SocketAddr = SocketAddr::new(
@@ -1551,7 +1538,7 @@ mod sacn_ipv6_multicast_test {
                             break;
                         }
                         _ => {
-                            assert!(false, "Unexpected error returned");
+                            panic!("Unexpected error returned");
                         }
                     }
                 }
@@ -1586,7 +1573,7 @@ mod sacn_ipv6_multicast_test {
                             "Unexpected data within second data packet of a set of synchronised packets"
                         );
                     } else {
-                        assert!(false, "Unrecognised universe within data packet");
+                        panic!("Unrecognised universe within data packet");
                     }
                 }
             }
@@ -1618,7 +1605,7 @@ mod sacn_ipv6_multicast_test {
             + "\0\0\0\0";
         let mut dmx_data: Vec<u8> = Vec::new();
         dmx_data.push(0); // Start code
-        dmx_data.extend(iter::repeat(100).take(255));
+        dmx_data.extend(std::iter::repeat_n(100, 255));
 
         /*  */
 
@@ -1631,7 +1618,7 @@ mod sacn_ipv6_multicast_test {
             SacnSource::with_cid_ip(&source_name.clone(), Uuid::from_bytes(CID), ipv4).unwrap();
         ipv4_source.set_preview_mode(false).unwrap();
         ipv4_source.set_multicast_loop_v4(true).unwrap();
-        ipv4_source.register_universes(&[universe as u16]).unwrap();
+        ipv4_source.register_universes(&[universe]).unwrap();
 
         // Create and setup the ipv4 receiver socket.
         let mut ipv4_recv = Socket::new(Domain::IPV4, Type::DGRAM, None).unwrap();
@@ -1652,13 +1639,13 @@ mod sacn_ipv6_multicast_test {
             .unwrap();
         ipv4_recv
             .join_multicast_v4(
-                &ipv4_multicast_addr.as_socket_ipv4().unwrap().ip(),
+                ipv4_multicast_addr.as_socket_ipv4().unwrap().ip(),
                 &Ipv4Addr::UNSPECIFIED,
             )
             .unwrap();
         ipv4_recv
             .join_multicast_v4(
-                &ipv4_discovery_multicast_addr.as_socket_ipv4().unwrap().ip(),
+                ipv4_discovery_multicast_addr.as_socket_ipv4().unwrap().ip(),
                 &Ipv4Addr::UNSPECIFIED,
             )
             .unwrap();
@@ -1690,11 +1677,11 @@ mod sacn_ipv6_multicast_test {
             )
             .unwrap();
         ipv6_recv
-            .join_multicast_v6(&ipv6_multicast_addr.as_socket_ipv6().unwrap().ip(), 0)
+            .join_multicast_v6(ipv6_multicast_addr.as_socket_ipv6().unwrap().ip(), 0)
             .unwrap();
         ipv6_recv
             .join_multicast_v6(
-                &ipv6_discovery_multicast_addr.as_socket_ipv6().unwrap().ip(),
+                ipv6_discovery_multicast_addr.as_socket_ipv6().unwrap().ip(),
                 0,
             )
             .unwrap();
@@ -1840,24 +1827,20 @@ mod sacn_ipv6_unicast_test {
             ACN_SDT_MULTICAST_PORT,
         );
 
-        let _ = src
-            .send(
-                &[universe],
-                &TEST_DATA_SINGLE_UNIVERSE,
-                Some(priority),
-                Some(dst_ip),
-                None,
-            )
-            .unwrap();
+        src.send(
+            &[universe],
+            &TEST_DATA_SINGLE_UNIVERSE,
+            Some(priority),
+            Some(dst_ip),
+            None,
+        )
+        .unwrap();
 
         let received_result: Result<Vec<DMXData>> = rx.recv().unwrap();
 
         rcv_thread.join().unwrap();
 
-        assert!(
-            !received_result.is_err(),
-            "Failed: Error when receiving data"
-        );
+        assert!(received_result.is_ok(), "Failed: Error when receiving data");
 
         let received_data: Vec<DMXData> = received_result.unwrap();
 
@@ -1915,31 +1898,24 @@ mod sacn_ipv6_unicast_test {
 
         src.register_universes(&UNIVERSES).unwrap();
 
-        let _ = src
-            .send(
-                &UNIVERSES,
-                &TEST_DATA_MULTIPLE_UNIVERSE,
-                Some(priority),
-                Some(
-                    SocketAddr::new(
-                        IpAddr::V6(TEST_NETWORK_INTERFACE_IPV6[0].parse().unwrap()),
-                        ACN_SDT_MULTICAST_PORT,
-                    )
-                    .into(),
-                ),
-                Some(UNIVERSES[0]),
-            )
-            .unwrap();
+        src.send(
+            &UNIVERSES,
+            &TEST_DATA_MULTIPLE_UNIVERSE,
+            Some(priority),
+            Some(SocketAddr::new(
+                IpAddr::V6(TEST_NETWORK_INTERFACE_IPV6[0].parse().unwrap()),
+                ACN_SDT_MULTICAST_PORT,
+            )),
+            Some(UNIVERSES[0]),
+        )
+        .unwrap();
         sleep(Duration::from_millis(500)); // Small delay to allow the data packets to get through as per NSI-E1.31-2018 Appendix B.1 recommendation.
         src.send_sync_packet(
             UNIVERSES[0],
-            Some(
-                SocketAddr::new(
-                    IpAddr::V6(TEST_NETWORK_INTERFACE_IPV6[0].parse().unwrap()),
-                    ACN_SDT_MULTICAST_PORT,
-                )
-                .into(),
-            ),
+            Some(SocketAddr::new(
+                IpAddr::V6(TEST_NETWORK_INTERFACE_IPV6[0].parse().unwrap()),
+                ACN_SDT_MULTICAST_PORT,
+            )),
         )
         .unwrap();
 
@@ -1947,10 +1923,7 @@ mod sacn_ipv6_unicast_test {
 
         rcv_thread.join().unwrap();
 
-        assert!(
-            !sync_pkt_res.is_err(),
-            "Failed: Error when receiving packets"
-        );
+        assert!(sync_pkt_res.is_ok(), "Failed: Error when receiving packets");
 
         let mut received_data: Vec<DMXData> = sync_pkt_res.unwrap();
 
@@ -0,0 +1,204 @@
+// Copyright 2020 sacn Developers
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! Recording data from a receiver and playing it back through a source over an in-memory network, timed with manual clocks.
+
+extern crate sacn;
+
+use sacn::clock::ManualClock;
+use sacn::error::errors::*;
+use sacn::packet::ACN_SDT_MULTICAST_PORT;
+use sacn::receive::SacnReceiver;
+use sacn::recording::{Player, RecordedFrame, Recorder, Recording};
+use sacn::source::SacnSource;
+use sacn::transport::MemoryNetwork;
+
+use std::io;
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::time::Duration;
+
+/// Maximum time to wait for packets in the tests before failing.
+const TEST_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// Creates a receiver on the given network which listens to the given universes.
+fn memory_receiver(network: &MemoryNetwork, universes: &[u16]) -> SacnReceiver {
+    let addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::UNSPECIFIED), ACN_SDT_MULTICAST_PORT);
+    let mut rcv = SacnReceiver::with_transport(network.bind(addr), None).unwrap();
+    rcv.listen_universes(universes).unwrap();
+    rcv
+}
+
+/// Creates a source on the given network with the given address which doesn't send discovery packets.
+fn memory_source(network: &MemoryNetwork, name: &str, ip: [u8; 4]) -> SacnSource {
+    let addr = SocketAddr::new(IpAddr::from(ip), 0);
+    let mut src = SacnSource::with_transport(name, network.bind(addr)).unwrap();
+    src.set_is_sending_discovery(false);
+    src
+}
+
+/// Returns a frame of DMX data for the given universe, recorded at the given time.
+fn frame(timestamp_ms: u64, universe: u16, values: &[u8]) -> RecordedFrame {
+    RecordedFrame {
+        timestamp: Duration::from_millis(timestamp_ms),
+        universe,
+        start_code: 0,
+        values: values.to_vec(),
+        priority: 100,
+        src_cid: None,
+        sync_uni: 0,
+        preview: false,
+    }
+}
+
+/// Returns true if the error is the one returned when no data arrives within the timeout.
+fn is_timeout(e: &SacnError) -> bool {
+    matches!(e, SacnError::Io(e) if matches!(e.kind(), io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut))
+}
+
+/// Receives a single universe of data and returns its universe and values, not including the start code.
+fn recv_values(rcv: &mut SacnReceiver) -> (u16, Vec<u8>) {
+    let data = rcv.recv(Some(TEST_TIMEOUT)).unwrap();
+    assert_eq!(data.len(), 1);
+    (data[0].universe, data[0].values[1..].to_vec())
+}
+
+/// Asserts that no data is waiting to be received.
+fn assert_nothing_received(rcv: &mut SacnReceiver) {
+    assert!(
+        rcv.recv(Some(Duration::from_millis(20)))
+            .is_err_and(|e| is_timeout(&e))
+    );
+}
+
+/// Data received from a source should be recorded with when it arrived and played back with the same timing, priority and values on
+/// the remapped universe.
+#[test]
+fn test_record_and_play_back() {
+    let network = MemoryNetwork::new();
+    let clock = ManualClock::new();
+    let mut rcv = memory_receiver(&network, &[1, 5]);
+    let mut src = memory_source(&network, "Live Source", [10, 0, 0, 1]);
+    src.register_universe(1).unwrap();
+
+    let mut recorder = Recorder::new(Vec::new()).unwrap();
+    recorder.set_clock(clock.clone());
+    src.send(&[1], &[0, 10, 20], Some(150), None, None).unwrap();
+    recorder.record_from(&mut rcv, Some(TEST_TIMEOUT)).unwrap();
+    clock.advance(Duration::from_millis(100));
+    src.send(&[1], &[0, 30, 40], Some(150), None, None).unwrap();
+    recorder.record_from(&mut rcv, Some(TEST_TIMEOUT)).unwrap();
+    clock.advance(Duration::from_millis(50));
+
+    let recording = Recording::read(recorder.finish().unwrap().as_slice()).unwrap();
+    assert_eq!(recording.frames().len(), 2);
+    assert_eq!(recording.frames()[1].timestamp, Duration::from_millis(100));
+    assert_eq!(recording.frames()[1].src_cid, Some(src.cid().unwrap()));
+    assert_eq!(recording.frames()[1].priority, 150);
+    assert_eq!(recording.duration(), Duration::from_millis(150));
+
+    let mut player = Player::new(recording);
+    player.set_clock(clock.clone());
+    player.remap_universe(1, 5).unwrap();
+    let mut player_src = memory_source(&network, "Player", [10, 0, 0, 2]);
+
+    assert_eq!(
+        player.update(&mut player_src).unwrap(),
+        Some(Duration::from_millis(100))
+    );
+    let data = rcv.recv(Some(TEST_TIMEOUT)).unwrap();
+    assert_eq!(data[0].universe, 5);
+    assert_eq!(data[0].values, vec![0, 10, 20]);
+    assert_eq!(data[0].priority, 150);
+    assert_eq!(data[0].src_cid, Some(player_src.cid().unwrap()));
+
+    clock.advance(Duration::from_millis(99));
+    assert_eq!(
+        player.update(&mut player_src).unwrap(),
+        Some(Duration::from_millis(1))
+    );
+    assert_nothing_received(&mut rcv);
+
+    clock.advance(Duration::from_millis(1));
+    assert_eq!(player.update(&mut player_src).unwrap(), None);
+    assert_eq!(recv_values(&mut rcv), (5, vec![30, 40]));
+    assert!(player.is_finished());
+}
+
+/// Frames recorded together waiting for a synchronisation packet should be played back together followed by the synchronisation packet.
+#[test]
+fn test_play_synchronised_frames() {
+    let network = MemoryNetwork::new();
+    let mut rcv = memory_receiver(&network, &[1, 2, 3]);
+    let mut src = memory_source(&network, "Player", [10, 0, 0, 2]);
+
+    let synced = |universe, value| RecordedFrame {
+        sync_uni: 3,
+        ..frame(0, universe, &[value])
+    };
+    let mut player = Player::new(Recording::from_frames(vec![synced(1, 11), synced(2, 22)]));
+    player.set_clock(ManualClock::new());
+    assert_eq!(player.update(&mut src).unwrap(), None);
+
+    let mut data = rcv.recv(Some(TEST_TIMEOUT)).unwrap();
+    data.sort_by_key(|d| d.universe);
+    assert_eq!(data.len(), 2);
+    assert_eq!(data[0].values, vec![0, 11]);
+    assert_eq!(data[1].values, vec![0, 22]);
+    assert!(data.iter().all(|d| d.sync_uni == 3));
+}
+
+/// Playback should follow the speed, jump to the position seeked to and loop back to the start at the end of the recording.
+#[test]
+fn test_play_speed_seek_and_loop() {
+    let network = MemoryNetwork::new();
+    let clock = ManualClock::new();
+    let mut rcv = memory_receiver(&network, &[1]);
+    let mut src = memory_source(&network, "Player", [10, 0, 0, 2]);
+
+    let recording = Recording::from_frames(vec![
+        frame(0, 1, &[1]),
+        frame(1000, 1, &[2]),
+        frame(2000, 1, &[3]),
+    ]);
+    let mut player = Player::new(recording);
+    player.set_clock(clock.clone());
+    player.set_speed(2.0).unwrap();
+    player.set_looping(true);
+
+    assert_eq!(
+        player.update(&mut src).unwrap(),
+        Some(Duration::from_millis(500))
+    );
+    assert_eq!(recv_values(&mut rcv), (1, vec![1]));
+
+    // Skip the second frame.
+    player.seek(Duration::from_millis(1500));
+    assert_eq!(
+        player.update(&mut src).unwrap(),
+        Some(Duration::from_millis(250))
+    );
+    assert_nothing_received(&mut rcv);
+
+    // The last frame is at the end of the recording so the first is sent straight after it.
+    clock.advance(Duration::from_millis(250));
+    assert_eq!(
+        player.update(&mut src).unwrap(),
+        Some(Duration::from_millis(500))
+    );
+    assert_eq!(recv_values(&mut rcv), (1, vec![3]));
+    assert_eq!(recv_values(&mut rcv), (1, vec![1]));
+    assert_eq!(player.position(), Duration::ZERO);
+
+    // Half speed from here.
+    player.set_speed(0.5).unwrap();
+    clock.advance(Duration::from_millis(1999));
+    player.update(&mut src).unwrap();
+    assert_nothing_received(&mut rcv);
+    clock.advance(Duration::from_millis(1));
+    player.update(&mut src).unwrap();
+    assert_eq!(recv_values(&mut rcv), (1, vec![2]));
+}
@@ -12,11 +12,13 @@ extern crate uuid;
 
 use sacn::error::errors::*;
 
+use sacn::interface::NetworkInterface;
 use sacn::packet::*;
-use sacn::source::SacnSource;
+use sacn::source::{SacnSource, TransmissionPolicy};
 use serial_test::serial;
 
-use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::net::{IpAddr, Ipv4Addr, SocketAddr, UdpSocket};
+use std::time::{Duration, Instant};
 
 /// UUID library used to handle the UUID's used in the CID fields.
 use uuid::Uuid;
@@ -29,17 +31,14 @@ fn test_new_ipv4_one_too_long_source_name() {
     match SacnSource::new_v4(SRC_NAME) {
         Err(e) => match e {
             SacnError::MalformedSourceName(_) => {
-                assert!(true, "Expected error returned");
+                // Expected error returned
             }
             _ => {
-                assert!(false, "Unexpected error returned");
+                panic!("Unexpected error returned");
             }
         },
         Ok(_) => {
-            assert!(
-                false,
-                "SacnSource created with a source name length greater than the allowed maximum"
-            );
+            panic!("SacnSource created with a source name length greater than the allowed maximum");
         }
     }
 }
@@ -51,17 +50,14 @@ fn test_new_ipv6_one_too_long_source_name() {
     match SacnSource::new_v6(SRC_NAME) {
         Err(e) => match e {
             SacnError::MalformedSourceName(_) => {
-                assert!(true, "Expected error returned");
+                // Expected error returned
             }
             _ => {
-                assert!(false, "Unexpected error returned");
+                panic!("Unexpected error returned");
             }
         },
         Ok(_) => {
-            assert!(
-                false,
-                "SacnSource created with a source name length greater than the allowed maximum"
-            );
+            panic!("SacnSource created with a source name length greater than the allowed maximum");
         }
     }
 }
@@ -77,17 +73,14 @@ fn test_new_with_cid_ip_too_long_source_name() {
     ) {
         Err(e) => match e {
             SacnError::MalformedSourceName(_) => {
-                assert!(true, "Expected error returned");
+                // Expected error returned
             }
             _ => {
-                assert!(false, "Unexpected error returned");
+                panic!("Unexpected error returned");
             }
         },
         Ok(_) => {
-            assert!(
-                false,
-                "SacnSource created with a source name length greater than the allowed maximum"
-            );
+            panic!("SacnSource created with a source name length greater than the allowed maximum");
         }
     }
 }
@@ -99,17 +92,14 @@ fn test_new_with_cid_ip_v4_too_long_source_name() {
     match SacnSource::with_cid_v4(SRC_NAME, Uuid::new_v4()) {
         Err(e) => match e {
             SacnError::MalformedSourceName(_) => {
-                assert!(true, "Expected error returned");
+                // Expected error returned
             }
             _ => {
-                assert!(false, "Unexpected error returned");
+                panic!("Unexpected error returned");
             }
         },
         Ok(_) => {
-            assert!(
-                false,
-                "SacnSource created with a source name length greater than the allowed maximum"
-            );
+            panic!("SacnSource created with a source name length greater than the allowed maximum");
         }
     }
 }
@@ -121,17 +111,14 @@ fn test_new_with_cid_ip_v6_too_long_source_name() {
     match SacnSource::with_cid_v6(SRC_NAME, Uuid::new_v4()) {
         Err(e) => match e {
             SacnError::MalformedSourceName(_) => {
-                assert!(true, "Expected error returned");
+                // Expected error returned
             }
             _ => {
-                assert!(false, "Unexpected error returned");
+                panic!("Unexpected error returned");
             }
         },
         Ok(_) => {
-            assert!(
-                false,
-                "SacnSource created with a source name length greater than the allowed maximum"
-            );
+            panic!("SacnSource created with a source name length greater than the allowed maximum");
         }
     }
 }
@@ -146,17 +133,14 @@ fn test_new_with_ip_too_long_source_name() {
     ) {
         Err(e) => match e {
             SacnError::MalformedSourceName(_) => {
-                assert!(true, "Expected error returned");
+                // Expected error returned
             }
             _ => {
-                assert!(false, "Unexpected error returned");
+                panic!("Unexpected error returned");
             }
         },
         Ok(_) => {
-            assert!(
-                false,
-                "SacnSource created with a source name length greater than the allowed maximum"
-            );
+            panic!("SacnSource created with a source name length greater than the allowed maximum");
         }
     }
 }
@@ -170,17 +154,14 @@ fn test_set_name_too_long_source_name() {
     match src.set_name(SRC_NAME) {
         Err(e) => match e {
             SacnError::MalformedSourceName(_) => {
-                assert!(true, "Expected error returned");
+                // Expected error returned
             }
             _ => {
-                assert!(false, "Unexpected error returned");
+                panic!("Unexpected error returned");
             }
         },
         Ok(_) => {
-            assert!(
-                false,
-                "SacnSource created with a source name length greater than the allowed maximum"
-            );
+            panic!("SacnSource created with a source name length greater than the allowed maximum");
         }
     }
 }
@@ -358,11 +339,11 @@ fn test_send_without_registering() {
 
     match src.send(&[1], &TEST_DATA_SINGLE_UNIVERSE, Some(priority), None, None) {
         Ok(_) => {
-            assert!(false, "Source didn't prevent sending without registering")
+            panic!("Source didn't prevent sending without registering")
         }
         Err(e) => match e {
-            SacnError::UniverseNotRegistered(ref _s) => assert!(true),
-            _ => assert!(false, "Unexpected error type returned, {}", e),
+            SacnError::UniverseNotRegistered(ref _s) => {}
+            _ => panic!("Unexpected error type returned, {}", e),
         },
     }
 }
@@ -386,14 +367,14 @@ fn test_send_above_priority() {
     ) {
         Err(e) => match e {
             SacnError::InvalidPriority(_) => {
-                assert!(true, "Expected error returned");
+                // Expected error returned
             }
             x => {
-                assert!(false, "Unexpected error type returned, {:?}", x);
+                panic!("Unexpected error type returned, {:?}", x);
             }
         },
         Ok(_) => {
-            assert!(false, "Invalid priority (> limit) was not rejected");
+            panic!("Invalid priority (> limit) was not rejected");
         }
     }
 }
@@ -450,17 +431,14 @@ fn test_register_below_min_universe() {
     match src.register_universes(&[UNIVERSE]) {
         Err(e) => match e {
             SacnError::IllegalUniverse(_) => {
-                assert!(true, "Expected error returned");
+                // Expected error returned
             }
             _ => {
-                assert!(false, "Unexpected error type returned");
+                panic!("Unexpected error type returned");
             }
         },
         _ => {
-            assert!(
-                false,
-                "Attempt to register universe below minimum succeeded when should have failed"
-            );
+            panic!("Attempt to register universe below minimum succeeded when should have failed");
         }
     }
 }
@@ -476,17 +454,14 @@ fn test_register_above_max_universe() {
     match src.register_universes(&[UNIVERSE]) {
         Err(e) => match e {
             SacnError::IllegalUniverse(_) => {
-                assert!(true, "Expected error returned");
+                // Expected error returned
             }
             _ => {
-                assert!(false, "Unexpected error type returned");
+                panic!("Unexpected error type returned");
             }
         },
         _ => {
-            assert!(
-                false,
-                "Attempt to register universe above maximum succeeded when should have failed"
-            );
+            panic!("Attempt to register universe above maximum succeeded when should have failed");
         }
     }
 }
@@ -499,14 +474,13 @@ fn test_register_discovery_universe() {
     let mut src = SacnSource::new_v4("Controller").unwrap();
     match src.register_universes(&[E131_DISCOVERY_UNIVERSE]) {
         Err(e) => {
-            assert!(
-                false,
+            panic!(
                 "Unexpected error returned when attempting to register discovery universe, {:?}",
                 e
             );
         }
         _ => {
-            assert!(true, "Registration successful");
+            // Registration successful
         }
     }
 }
@@ -519,14 +493,13 @@ fn test_register_max_universe() {
     let mut src = SacnSource::new_v4("Controller").unwrap();
     match src.register_universes(&[E131_MAX_MULTICAST_UNIVERSE]) {
         Err(e) => {
-            assert!(
-                false,
+            panic!(
                 "Unexpected error returned when attempting to register the maximum allowed universe, {:?}",
                 e
             );
         }
         _ => {
-            assert!(true, "Registration successful");
+            // Registration successful
         }
     }
 }
@@ -539,14 +512,13 @@ fn test_register_min_universe() {
     let mut src = SacnSource::new_v4("Controller").unwrap();
     match src.register_universes(&[E131_MIN_MULTICAST_UNIVERSE]) {
         Err(e) => {
-            assert!(
-                false,
+            panic!(
                 "Unexpected error returned when attempting to register the maximum allowed universe, {:?}",
                 e
             );
         }
         _ => {
-            assert!(true, "Registration successful");
+            // Registration successful
         }
     }
 }
@@ -561,15 +533,14 @@ fn test_sync_addr_0() {
     match src.send_sync_packet(SYNC_UNI, None) {
         Err(e) => match e {
             SacnError::IllegalUniverse(_) => {
-                assert!(true, "Expected error returned");
+                // Expected error returned
             }
             _ => {
-                assert!(false, "Unexpected error type returned");
+                panic!("Unexpected error type returned");
             }
         },
         _ => {
-            assert!(
-                false,
+            panic!(
                 "Attempt to send a synchronisation packet with a synchronisation address of 0 succeeded when it should have been rejected"
             );
         }
@@ -657,3 +628,482 @@ const TEST_DATA_MULTIPLE_UNIVERSE: [u8; 712] = [
     0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
     0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 100,
 ];
+
+/// Attempts to set per-address priorities containing a priority higher than the maximum allowed, this should be rejected.
+#[test]
+#[serial]
+fn test_set_per_address_priority_above_max_priority() {
+    let mut src = SacnSource::new_v4("Controller").unwrap();
+    let universe = 1;
+
+    src.register_universe(universe).unwrap();
+
+    match src.set_per_address_priority(universe, Some(&[100, E131_MAX_PRIORITY + 1])) {
+        Err(SacnError::InvalidPriority(p)) => assert_eq!(p, E131_MAX_PRIORITY + 1),
+        x => panic!("Unexpected result, {:?}", x),
+    }
+}
+
+/// Attempts to set more per-address priorities than there are slots in a universe, this should be rejected.
+#[test]
+#[serial]
+fn test_set_per_address_priority_exceed_capacity() {
+    let mut src = SacnSource::new_v4("Controller").unwrap();
+    let universe = 1;
+
+    src.register_universe(universe).unwrap();
+
+    match src.set_per_address_priority(universe, Some(&[100; UNIVERSE_CHANNEL_CAPACITY])) {
+        Err(SacnError::ExceedUniverseCapacity(_)) => {
+            // Expected error returned
+        }
+        x => panic!("Unexpected result, {:?}", x),
+    }
+}
+
+/// Attempts to set per-address priorities on a universe that isn't registered, this should be rejected.
+#[test]
+#[serial]
+fn test_set_per_address_priority_without_registering() {
+    let mut src = SacnSource::new_v4("Controller").unwrap();
+
+    match src.set_per_address_priority(1, Some(&[100])) {
+        Err(SacnError::UniverseNotRegistered(_)) => {
+            // Expected error returned
+        }
+        x => panic!("Unexpected result, {:?}", x),
+    }
+}
+
+/// Sends data unicast to a local socket with per-address priorities set and checks that the DMX data is followed by a per-address priority
+/// packet using the next sequence number.
+#[test]
+#[serial]
+#[cfg_attr(ci, ignore)]
+fn test_send_per_address_priority_unicast() {
+    let universe = 1;
+    let priorities = [200, 0, 50];
+
+    let rcv_socket = UdpSocket::bind(SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), 0)).unwrap();
+    rcv_socket
+        .set_read_timeout(Some(Duration::from_secs(1)))
+        .unwrap();
+    let dst_ip = rcv_socket.local_addr().unwrap();
+
+    let mut src = SacnSource::with_ip(
+        "Controller",
+        SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), 0),
+    )
+    .unwrap();
+    src.set_is_sending_discovery(false);
+    src.register_universe(universe).unwrap();
+    src.set_per_address_priority(universe, Some(&priorities))
+        .unwrap();
+
+    src.send(&[universe], &[0, 1, 2, 3], Some(120), Some(dst_ip), None)
+        .unwrap();
+
+    let mut buf = [0; 1144];
+    let mut pkts = Vec::new();
+    for _ in 0..2 {
+        let len = rcv_socket.recv(&mut buf).unwrap();
+        match AcnRootLayerProtocol::parse(&buf[..len]).unwrap().pdu.data {
+            E131RootLayerData::DataPacket(d) => pkts.push((
+                d.sequence_number,
+                d.priority,
+                d.data.property_values.into_owned(),
+            )),
+            _ => panic!("Packet not parsed as data-packet as expected"),
+        }
+    }
+
+    assert_eq!(pkts[0].2, vec![0, 1, 2, 3]);
+    assert_eq!(
+        pkts[1].2,
+        vec![E131_PER_ADDRESS_PRIORITY_START_CODE, 200, 0, 50]
+    );
+    assert_eq!(pkts[1].1, 120);
+    assert_eq!(pkts[1].0, pkts[0].0.wrapping_add(1));
+}
+
+/// Attempts to set the force synchronisation option on a universe that isn't registered, this should be rejected.
+#[test]
+#[serial]
+fn test_set_force_synchronization_without_registering() {
+    let mut src = SacnSource::new_v4("Controller").unwrap();
+
+    match src.set_force_synchronization(1, true) {
+        Err(SacnError::UniverseNotRegistered(_)) => {
+            // Expected error returned
+        }
+        x => panic!("Unexpected result, {:?}", x),
+    }
+}
+
+/// Sends data unicast to a local socket with the force synchronisation option set for the universe and checks that the option bit is
+/// set in the packet and cleared again once the option is turned off.
+#[test]
+#[serial]
+#[cfg_attr(ci, ignore)]
+fn test_send_force_synchronization_unicast() {
+    let universe = 1;
+
+    let rcv_socket = UdpSocket::bind(SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), 0)).unwrap();
+    rcv_socket
+        .set_read_timeout(Some(Duration::from_secs(1)))
+        .unwrap();
+    let dst_ip = rcv_socket.local_addr().unwrap();
+
+    let mut src = SacnSource::with_ip(
+        "Controller",
+        SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), 0),
+    )
+    .unwrap();
+    src.set_is_sending_discovery(false);
+    src.register_universe(universe).unwrap();
+
+    let mut buf = [0; 1144];
+    for force_synchronization in [true, false] {
+        src.set_force_synchronization(universe, force_synchronization)
+            .unwrap();
+        src.send(&[universe], &[0, 1, 2], None, Some(dst_ip), Some(universe))
+            .unwrap();
+
+        let len = rcv_socket.recv(&mut buf).unwrap();
+        match AcnRootLayerProtocol::parse(&buf[..len]).unwrap().pdu.data {
+            E131RootLayerData::DataPacket(d) => {
+                assert_eq!(d.force_synchronization, force_synchronization)
+            }
+            _ => panic!("Packet not parsed as data-packet as expected"),
+        }
+    }
+}
+
+/// Attempts to set the levels of a universe that isn't registered, this should be rejected.
+#[test]
+#[serial]
+fn test_set_universe_levels_without_registering() {
+    let mut src = SacnSource::new_v4("Controller").unwrap();
+
+    match src.set_universe_levels(1, &[1, 2, 3]) {
+        Err(SacnError::UniverseNotRegistered(_)) => {
+            // Expected error returned
+        }
+        x => panic!("Unexpected result, {:?}", x),
+    }
+}
+
+/// Attempts to set more levels than there are slots in a universe, this should be rejected.
+#[test]
+#[serial]
+fn test_set_universe_levels_exceed_capacity() {
+    let mut src = SacnSource::new_v4("Controller").unwrap();
+    src.register_universe(1).unwrap();
+
+    match src.set_universe_levels(1, &[0; UNIVERSE_CHANNEL_CAPACITY]) {
+        Err(SacnError::ExceedUniverseCapacity(_)) => {
+            // Expected error returned
+        }
+        x => panic!("Unexpected result, {:?}", x),
+    }
+}
+
+/// Sets the refresh rate of a source, checks that a rate of 0 is rejected and that rates above the DMX512 maximum are capped.
+#[test]
+#[serial]
+fn test_set_refresh_rate() {
+    let mut src = SacnSource::new_v4("Controller").unwrap();
+    assert_eq!(src.refresh_rate().unwrap(), DMX512_MAX_REFRESH_RATE);
+
+    src.set_refresh_rate(10).unwrap();
+    assert_eq!(src.refresh_rate().unwrap(), 10);
+
+    src.set_refresh_rate(1000).unwrap();
+    assert_eq!(src.refresh_rate().unwrap(), DMX512_MAX_REFRESH_RATE);
+
+    match src.set_refresh_rate(0) {
+        Err(SacnError::InvalidRefreshRate(0)) => {
+            // Expected error returned
+        }
+        x => panic!("Unexpected result, {:?}", x),
+    }
+}
+
+/// Sets the levels of a synchronised universe sent unicast to a local socket and checks that the source repeatedly sends the levels
+/// followed by a synchronisation packet, and that updated levels are sent in later frames.
+#[test]
+#[serial]
+#[cfg_attr(ci, ignore)]
+fn test_refresh_universe_levels_unicast() {
+    let universe = 1;
+    let sync_uni = 2;
+
+    let rcv_socket = UdpSocket::bind(SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), 0)).unwrap();
+    rcv_socket
+        .set_read_timeout(Some(Duration::from_secs(1)))
+        .unwrap();
+    let dst_ip = rcv_socket.local_addr().unwrap();
+
+    let mut src = SacnSource::with_ip(
+        "Controller",
+        SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), 0),
+    )
+    .unwrap();
+    src.set_is_sending_discovery(false);
+    src.register_universes(&[universe, sync_uni]).unwrap();
+    src.set_refresh_options(universe, Some(150), Some(dst_ip), Some(sync_uni))
+        .unwrap();
+    src.set_universe_levels(universe, &[1, 2, 3]).unwrap();
+
+    let mut buf = [0; 1144];
+    let mut recv_frame = || {
+        let len = rcv_socket.recv(&mut buf).unwrap();
+        let data = match AcnRootLayerProtocol::parse(&buf[..len]).unwrap().pdu.data {
+            E131RootLayerData::DataPacket(d) => {
+                assert_eq!(d.universe, universe);
+                assert_eq!(d.priority, 150);
+                assert_eq!(d.synchronization_address, sync_uni);
+                d.data.property_values.to_vec()
+            }
+            _ => panic!("Packet not parsed as data-packet as expected"),
+        };
+
+        let len = rcv_socket.recv(&mut buf).unwrap();
+        match AcnRootLayerProtocol::parse(&buf[..len]).unwrap().pdu.data {
+            E131RootLayerData::SynchronizationPacket(s) => {
+                assert_eq!(s.synchronization_address, sync_uni)
+            }
+            _ => panic!("Packet not parsed as sync-packet as expected"),
+        }
+        data
+    };
+
+    for _ in 0..3 {
+        assert_eq!(recv_frame(), vec![0, 1, 2, 3]);
+    }
+
+    src.set_universe_levels(universe, &[4, 5]).unwrap();
+
+    // A frame may have already been sent with the previous levels.
+    let mut data = recv_frame();
+    if data == vec![0, 1, 2, 3] {
+        data = recv_frame();
+    }
+    assert_eq!(data, vec![0, 4, 5]);
+}
+
+/// Attempts to set a keep-alive transmission policy with an interval outwith the range allowed by ANSI E1.31-2018 Section 6.6.2,
+/// this should be rejected.
+#[test]
+#[serial]
+fn test_set_transmission_policy_invalid_interval() {
+    let mut src = SacnSource::new_v4("Controller").unwrap();
+    src.register_universe(1).unwrap();
+
+    for interval in [Duration::from_millis(500), Duration::from_millis(1500)] {
+        match src.set_transmission_policy(1, TransmissionPolicy::KeepAlive(interval)) {
+            Err(SacnError::InvalidKeepAliveInterval(i)) => assert_eq!(i, interval),
+            x => panic!("Unexpected result, {:?}", x),
+        }
+    }
+
+    src.set_transmission_policy(
+        1,
+        TransmissionPolicy::KeepAlive(E131_KEEP_ALIVE_MIN_INTERVAL),
+    )
+    .unwrap();
+}
+
+/// Sets unchanging levels on a universe with a keep-alive transmission policy and checks that the source sends the levels followed by
+/// the required number of copies at the refresh rate, then reduces to the keep-alive interval and returns to the refresh rate once the
+/// levels change.
+#[test]
+#[serial]
+#[cfg_attr(ci, ignore)]
+fn test_refresh_universe_levels_keep_alive() {
+    let universe = 1;
+    let interval = Duration::from_millis(800);
+
+    let rcv_socket = UdpSocket::bind(SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), 0)).unwrap();
+    rcv_socket
+        .set_read_timeout(Some(Duration::from_secs(2)))
+        .unwrap();
+    let dst_ip = rcv_socket.local_addr().unwrap();
+
+    let mut src = SacnSource::with_ip(
+        "Controller",
+        SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), 0),
+    )
+    .unwrap();
+    src.set_is_sending_discovery(false);
+    src.register_universe(universe).unwrap();
+    src.set_refresh_options(universe, None, Some(dst_ip), None)
+        .unwrap();
+    src.set_transmission_policy(universe, TransmissionPolicy::KeepAlive(interval))
+        .unwrap();
+    src.set_universe_levels(universe, &[1, 2, 3]).unwrap();
+
+    let mut buf = [0; 1144];
+    let mut recv_levels = || {
+        let len = rcv_socket.recv(&mut buf).unwrap();
+        match AcnRootLayerProtocol::parse(&buf[..len]).unwrap().pdu.data {
+            E131RootLayerData::DataPacket(d) => d.data.property_values.to_vec(),
+            _ => panic!("Packet not parsed as data-packet as expected"),
+        }
+    };
+
+    // The first packet and the required copies are sent at the refresh rate.
+    let start = Instant::now();
+    for _ in 0..=E131_KEEP_ALIVE_REPEAT_COUNT {
+        assert_eq!(recv_levels(), vec![0, 1, 2, 3]);
+    }
+    assert!(start.elapsed() < interval);
+
+    // Setting the same levels again doesn't count as a change.
+    src.set_universe_levels(universe, &[1, 2, 3]).unwrap();
+
+    for _ in 0..2 {
+        let sent = Instant::now();
+        assert_eq!(recv_levels(), vec![0, 1, 2, 3]);
+        let elapsed = sent.elapsed();
+        assert!(elapsed > interval / 2, "Keep-alive sent after {elapsed:?}");
+        assert!(elapsed < E131_KEEP_ALIVE_MAX_INTERVAL * 2);
+    }
+
+    src.set_universe_levels(universe, &[4, 5, 6]).unwrap();
+
+    let changed = Instant::now();
+    for _ in 0..=E131_KEEP_ALIVE_REPEAT_COUNT {
+        assert_eq!(recv_levels(), vec![0, 4, 5, 6]);
+    }
+    assert!(changed.elapsed() < interval);
+}
+
+/// Attempts to send on a network interface which doesn't exist, this should be rejected and leave the source sending as before.
+#[test]
+#[serial]
+#[cfg(not(target_os = "windows"))]
+fn test_set_interfaces_not_found() {
+    let mut src = SacnSource::with_ip(
+        "Controller",
+        SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), 0),
+    )
+    .unwrap();
+
+    match src.set_interfaces(&[NetworkInterface::Name("sacn-no-such-if".to_string())]) {
+        Err(SacnError::InterfaceNotFound(_)) => {}
+        res => panic!("Expected InterfaceNotFound, got {res:?}"),
+    }
+    assert!(src.interfaces().unwrap().is_empty());
+}
+
+/// Sends data on two interfaces (both loopback) and checks that each packet arrives once per interface with a shared sequence number.
+#[test]
+#[serial]
+fn test_send_multiple_interfaces_unicast() {
+    let universe = 1;
+
+    let rcv_socket = UdpSocket::bind(SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), 0)).unwrap();
+    rcv_socket
+        .set_read_timeout(Some(Duration::from_secs(1)))
+        .unwrap();
+    let dst_ip = rcv_socket.local_addr().unwrap();
+
+    let interfaces = [
+        NetworkInterface::Addr(IpAddr::V4(Ipv4Addr::LOCALHOST)),
+        NetworkInterface::Addr(IpAddr::V4(Ipv4Addr::LOCALHOST)),
+    ];
+
+    let mut src = SacnSource::with_ip(
+        "Controller",
+        SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), 0),
+    )
+    .unwrap();
+    src.set_is_sending_discovery(false);
+    src.set_interfaces(&interfaces).unwrap();
+    assert_eq!(src.interfaces().unwrap(), interfaces);
+    src.register_universe(universe).unwrap();
+
+    let mut buf = [0; 1144];
+    for (i, values) in [[0, 1, 2], [0, 3, 4]].iter().enumerate() {
+        src.send(&[universe], values, None, Some(dst_ip), None)
+            .unwrap();
+
+        let mut senders = Vec::new();
+        for _ in 0..interfaces.len() {
+            let (len, from) = rcv_socket.recv_from(&mut buf).unwrap();
+            senders.push(from);
+            match AcnRootLayerProtocol::parse(&buf[..len]).unwrap().pdu.data {
+                E131RootLayerData::DataPacket(d) => {
+                    assert_eq!(d.sequence_number, STARTING_SEQUENCE_NUMBER + i as u8);
+                    assert_eq!(d.data.property_values.to_vec(), values.to_vec());
+                }
+                _ => panic!("Packet not parsed as data-packet as expected"),
+            }
+        }
+        assert_ne!(senders[0], senders[1]);
+    }
+}
+
+/// Sends data spanning several universes repeatedly, changing the levels, data length, priority and source name between sends, and checks
+/// that every packet is received in order with the current fields even though the packets are reused and sent in a batch.
+#[test]
+#[serial]
+fn test_send_multiple_universes_reused_packets() {
+    let universes = [1, 2, 3];
+
+    let rcv_socket = UdpSocket::bind(SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), 0)).unwrap();
+    rcv_socket
+        .set_read_timeout(Some(Duration::from_secs(1)))
+        .unwrap();
+    let dst_ip = rcv_socket.local_addr().unwrap();
+
+    let mut src = SacnSource::with_ip(
+        "Controller",
+        SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), 0),
+    )
+    .unwrap();
+    src.set_is_sending_discovery(false);
+    src.register_universes(&universes).unwrap();
+
+    let mut buf = [0; 1144];
+    for i in 0..6u8 {
+        let len = if i == 3 {
+            2 * UNIVERSE_CHANNEL_CAPACITY + 10
+        } else {
+            3 * UNIVERSE_CHANNEL_CAPACITY
+        };
+        let values: Vec<u8> = (0..len)
+            .map(|j| {
+                if j % UNIVERSE_CHANNEL_CAPACITY == 0 {
+                    0
+                } else {
+                    (j as u8).wrapping_add(i)
+                }
+            })
+            .collect();
+        let priority = if i == 4 { 150 } else { 100 };
+        let name = if i == 5 { "Renamed" } else { "Controller" };
+        src.set_name(name).unwrap();
+
+        src.send(&universes, &values, Some(priority), Some(dst_ip), None)
+            .unwrap();
+
+        for (u, chunk) in universes
+            .iter()
+            .zip(values.chunks(UNIVERSE_CHANNEL_CAPACITY))
+        {
+            let (n, _) = rcv_socket.recv_from(&mut buf).unwrap();
+            match AcnRootLayerProtocol::parse(&buf[..n]).unwrap().pdu.data {
+                E131RootLayerData::DataPacket(d) => {
+                    assert_eq!(d.universe, *u);
+                    assert_eq!(d.sequence_number, STARTING_SEQUENCE_NUMBER + i);
+                    assert_eq!(d.priority, priority);
+                    assert_eq!(d.source_name.as_ref(), name);
+                    assert_eq!(d.data.property_values.to_vec(), chunk.to_vec());
+                }
+                _ => panic!("Packet not parsed as data-packet as expected"),
+            }
+        }
+    }
+}
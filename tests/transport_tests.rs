@@ -0,0 +1,383 @@
+// Copyright 2020 sacn Developers
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! Sources and receivers talking over an in-memory network, including one which loses, duplicates, reorders and delays packets. These
+//! don't use the real network so don't need to run serially.
+
+extern crate sacn;
+
+use sacn::error::errors::*;
+use sacn::interface::NetworkInterface;
+use sacn::packet::{
+    ACN_SDT_MULTICAST_PORT, AcnRootLayerProtocol, DISCOVERY_UNI_PER_PAGE, E131_DISCOVERY_UNIVERSE,
+    E131_NULL_START_CODE, E131RootLayer, E131RootLayerData, UniverseDiscoveryPacketFramingLayer,
+    UniverseDiscoveryPacketUniverseDiscoveryLayer, universe_to_ipv4_multicast_addr,
+};
+use sacn::receive::{ReceiveEvent, SacnReceiver};
+use sacn::source::SacnSource;
+use sacn::transport::{MemoryNetwork, NetworkFaults, Transport};
+
+use std::io;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::time::Duration;
+use uuid::Uuid;
+
+/// Maximum time to wait for data in the tests before failing.
+const TEST_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Creates a receiver bound to the ACN port on the given network which listens to the given universes.
+fn memory_receiver(network: &MemoryNetwork, universes: &[u16]) -> SacnReceiver {
+    let addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::UNSPECIFIED), ACN_SDT_MULTICAST_PORT);
+    let mut rcv = SacnReceiver::with_transport(network.bind(addr), None).unwrap();
+    rcv.listen_universes(universes).unwrap();
+    rcv
+}
+
+/// Creates a source with the given address on the given network which doesn't send discovery packets and is registered for the given
+/// universes.
+fn memory_source(network: &MemoryNetwork, ip: [u8; 4], universes: &[u16]) -> SacnSource {
+    let addr = SocketAddr::new(IpAddr::from(ip), 0);
+    let mut src = SacnSource::with_transport("Memory Source", network.bind(addr)).unwrap();
+    src.set_is_sending_discovery(false);
+    src.register_universes(universes).unwrap();
+    src
+}
+
+/// Returns true if the error is the one returned when no data arrives within the timeout.
+fn is_timeout(e: &SacnError) -> bool {
+    matches!(e, SacnError::Io(e) if matches!(e.kind(), io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut))
+}
+
+/// Data sent using multicast should be received by every receiver listening to the universe, along with where it came from.
+#[test]
+fn test_memory_multicast_data() {
+    let network = MemoryNetwork::new();
+    let mut rcv_a = memory_receiver(&network, &[1]);
+    let mut rcv_b = memory_receiver(&network, &[1]);
+    let mut rcv_other = memory_receiver(&network, &[2]);
+
+    let mut src = memory_source(&network, [10, 0, 0, 1], &[1]);
+    let values = [E131_NULL_START_CODE, 1, 2, 3];
+    src.send(&[1], &values, None, None, None).unwrap();
+
+    for rcv in [&mut rcv_a, &mut rcv_b] {
+        let data = rcv.recv(Some(TEST_TIMEOUT)).unwrap();
+        assert_eq!(data.len(), 1);
+        assert_eq!(data[0].universe, 1);
+        assert_eq!(data[0].values, values.to_vec());
+        assert_eq!(data[0].src_cid, Some(src.cid().unwrap()));
+        assert_eq!(data[0].src_name.as_deref(), Some("Memory Source"));
+        assert_eq!(
+            data[0].src_addr.map(|a| a.ip()),
+            Some(IpAddr::from([10, 0, 0, 1]))
+        );
+    }
+
+    match rcv_other.recv(Some(Duration::from_millis(50))) {
+        Err(e) if is_timeout(&e) => {}
+        res => panic!("Receiver not listening to the universe got {res:?}"),
+    }
+}
+
+/// Data sent using unicast should only be received by receivers bound to the destination address or the unspecified address.
+#[test]
+fn test_memory_unicast_data() {
+    let network = MemoryNetwork::new();
+    let addr = SocketAddr::new(IpAddr::from([10, 0, 0, 2]), ACN_SDT_MULTICAST_PORT);
+    let mut rcv = SacnReceiver::with_transport(network.bind(addr), None).unwrap();
+    rcv.set_is_multicast_enabled(false).unwrap();
+    rcv.listen_universes(&[1]).unwrap();
+
+    let other_addr = SocketAddr::new(IpAddr::from([10, 0, 0, 3]), ACN_SDT_MULTICAST_PORT);
+    let mut rcv_other = SacnReceiver::with_transport(network.bind(other_addr), None).unwrap();
+    rcv_other.listen_universes(&[1]).unwrap();
+    let mut rcv_any = memory_receiver(&network, &[1]);
+
+    let mut src = memory_source(&network, [10, 0, 0, 1], &[1]);
+    src.send(&[1], &[0, 9], None, Some(addr), None).unwrap();
+
+    assert_eq!(rcv.recv(Some(TEST_TIMEOUT)).unwrap()[0].values, vec![0, 9]);
+    assert_eq!(
+        rcv_any.recv(Some(TEST_TIMEOUT)).unwrap()[0].values,
+        vec![0, 9]
+    );
+    assert!(
+        rcv_other
+            .recv(Some(Duration::from_millis(50)))
+            .is_err_and(|e| is_timeout(&e))
+    );
+}
+
+/// Data sent over IPv6 should use the IPv6 multicast groups and only reach IPv6 receivers.
+#[test]
+fn test_memory_ipv6_multicast_data() {
+    let network = MemoryNetwork::new();
+    let addr = SocketAddr::new(IpAddr::V6(Ipv6Addr::UNSPECIFIED), ACN_SDT_MULTICAST_PORT);
+    let mut rcv = SacnReceiver::with_transport(network.bind(addr), None).unwrap();
+    rcv.listen_universes(&[1]).unwrap();
+    let mut rcv_v4 = memory_receiver(&network, &[1]);
+
+    let src_addr = SocketAddr::new(IpAddr::V6(Ipv6Addr::LOCALHOST), 0);
+    let mut src = SacnSource::with_transport("Memory Source", network.bind(src_addr)).unwrap();
+    src.set_is_sending_discovery(false);
+    src.register_universe(1).unwrap();
+    src.send(&[1], &[0, 6], None, None, None).unwrap();
+
+    assert_eq!(rcv.recv(Some(TEST_TIMEOUT)).unwrap()[0].values, vec![0, 6]);
+    assert!(
+        rcv_v4
+            .recv(Some(Duration::from_millis(50)))
+            .is_err_and(|e| is_timeout(&e))
+    );
+}
+
+/// Synchronised data should only be returned once the synchronisation packet arrives.
+#[test]
+fn test_memory_synchronised_data() {
+    let network = MemoryNetwork::new();
+    let mut rcv = memory_receiver(&network, &[1, 2, 3]);
+
+    let mut src = memory_source(&network, [10, 0, 0, 1], &[1, 2, 3]);
+    let values = vec![0; 2 * 513];
+    src.send(&[1, 2], &values, None, None, Some(3)).unwrap();
+
+    assert!(
+        rcv.recv(Some(Duration::from_millis(50)))
+            .is_err_and(|e| is_timeout(&e))
+    );
+
+    src.send_sync_packet(3, None).unwrap();
+    let mut data = rcv.recv(Some(TEST_TIMEOUT)).unwrap();
+    data.sort_by_key(|d| d.universe);
+    assert_eq!(
+        data.iter().map(|d| d.universe).collect::<Vec<u16>>(),
+        vec![1, 2]
+    );
+}
+
+/// Terminating a stream should be reported by the receiver and muting a universe should stop its data being received.
+#[test]
+fn test_memory_terminate_and_mute() {
+    let network = MemoryNetwork::new();
+    let mut rcv = memory_receiver(&network, &[1, 2]);
+
+    let mut src = memory_source(&network, [10, 0, 0, 1], &[1, 2]);
+    src.send(&[1], &[0, 1], None, None, None).unwrap();
+    rcv.recv(Some(TEST_TIMEOUT)).unwrap();
+
+    src.terminate_stream(1, E131_NULL_START_CODE).unwrap();
+    match rcv.recv_event(Some(TEST_TIMEOUT)).unwrap() {
+        ReceiveEvent::UniverseTerminated(cid, 1) => assert_eq!(cid, src.cid().unwrap()),
+        event => panic!("Expected the universe to be terminated, got {event:?}"),
+    }
+
+    rcv.mute_universe(2).unwrap();
+    src.send(&[2], &[0, 2], None, None, None).unwrap();
+    assert!(
+        rcv.recv(Some(Duration::from_millis(50)))
+            .is_err_and(|e| is_timeout(&e))
+    );
+}
+
+/// Options that only apply to UDP sockets should be rejected when a transport is used.
+#[test]
+fn test_memory_socket_options_unsupported() {
+    let network = MemoryNetwork::new();
+    let mut rcv = memory_receiver(&network, &[1]);
+    let mut src = memory_source(&network, [10, 0, 0, 1], &[1]);
+
+    let interfaces = [NetworkInterface::Index(1)];
+    assert!(matches!(
+        src.set_ttl(4),
+        Err(SacnError::TransportUnsupported(_))
+    ));
+    assert!(matches!(
+        src.set_interfaces(&interfaces),
+        Err(SacnError::TransportUnsupported(_))
+    ));
+    assert!(matches!(
+        rcv.set_interfaces(&interfaces),
+        Err(SacnError::TransportUnsupported(_))
+    ));
+    assert!(matches!(
+        rcv.set_recv_batch_size(8),
+        Err(SacnError::TransportUnsupported(_))
+    ));
+}
+
+/// Sends the given number of frames of universe 1 from a source on a network with the given faults, each frame holding its number,
+/// and returns the frame numbers of the data received along with the number of packets discarded as out of sequence.
+fn send_frames_with_faults(faults: NetworkFaults, frames: u16) -> (Vec<u16>, usize) {
+    let network = MemoryNetwork::with_faults(faults);
+    let mut rcv = memory_receiver(&network, &[1]);
+    let mut src = memory_source(&network, [10, 0, 0, 1], &[1]);
+
+    for frame in 0..frames {
+        let [hi, lo] = frame.to_be_bytes();
+        src.send(&[1], &[E131_NULL_START_CODE, hi, lo], None, None, None)
+            .unwrap();
+    }
+
+    let mut received = Vec::new();
+    let mut sequence_errors = 0;
+    loop {
+        match rcv.recv_event(Some(Duration::from_millis(100))) {
+            Ok(ReceiveEvent::Data(data)) => {
+                for d in data {
+                    received.push(u16::from_be_bytes([d.values[1], d.values[2]]));
+                }
+            }
+            Ok(ReceiveEvent::SequenceError(..)) => sequence_errors += 1,
+            Ok(_) => {}
+            Err(e) if is_timeout(&e) => break,
+            Err(e) => panic!("Unexpected error receiving: {e:?}"),
+        }
+    }
+    (received, sequence_errors)
+}
+
+/// Duplicated and reordered packets should fall in the -20..0 sequence window and be discarded so the receiver only ever moves
+/// forwards, across several wraps of the sequence number, and the same seed should give the same result.
+#[test]
+fn test_memory_faults_sequence_window() {
+    let faults = NetworkFaults {
+        loss: 0.05,
+        duplication: 0.1,
+        reordering: 0.2,
+        seed: 2024,
+        ..NetworkFaults::default()
+    };
+    let (received, sequence_errors) = send_frames_with_faults(faults, 600);
+
+    assert!(
+        received.windows(2).all(|w| w[0] < w[1]),
+        "Frames received out of order: {received:?}"
+    );
+    assert!(received.len() > 400, "Too few frames: {}", received.len());
+    assert!(sequence_errors > 0);
+
+    assert_eq!(
+        send_frames_with_faults(faults, 600),
+        (received, sequence_errors)
+    );
+}
+
+/// Synchronised data sent over a lossy network should only ever move forwards for each universe and synchronisation packets which are
+/// lost shouldn't stop later frames being returned.
+#[test]
+fn test_memory_faults_synchronised_data() {
+    let network = MemoryNetwork::with_faults(NetworkFaults {
+        loss: 0.1,
+        duplication: 0.1,
+        reordering: 0.2,
+        seed: 99,
+        ..NetworkFaults::default()
+    });
+    let mut rcv = memory_receiver(&network, &[1, 2, 3]);
+    let mut src = memory_source(&network, [10, 0, 0, 1], &[1, 2, 3]);
+
+    let frames: u8 = 200;
+    let mut values = vec![E131_NULL_START_CODE; 2 * 513];
+    for frame in 0..frames {
+        values[1] = frame;
+        values[514] = frame;
+        src.send(&[1, 2], &values, None, None, Some(3)).unwrap();
+        src.send_sync_packet(3, None).unwrap();
+    }
+
+    let mut last_frames: [Option<u8>; 2] = [None; 2];
+    let mut syncs = 0;
+    loop {
+        match rcv.recv_event(Some(Duration::from_millis(100))) {
+            Ok(ReceiveEvent::Data(data)) => {
+                syncs += 1;
+                for d in data {
+                    assert_eq!(d.sync_uni, 3);
+                    let last = &mut last_frames[d.universe as usize - 1];
+                    assert!(
+                        last.is_none_or(|l| l < d.values[1]),
+                        "Universe {} went from frame {last:?} to {}",
+                        d.universe,
+                        d.values[1]
+                    );
+                    *last = Some(d.values[1]);
+                }
+            }
+            Ok(_) => {}
+            Err(e) if is_timeout(&e) => break,
+            Err(e) => panic!("Unexpected error receiving: {e:?}"),
+        }
+    }
+
+    assert!(syncs > frames as usize / 2, "Too few syncs: {syncs}");
+    assert!(
+        last_frames
+            .iter()
+            .all(|l| l.is_some_and(|l| l > frames - 20))
+    );
+}
+
+/// A source whose universe discovery pages are lost, duplicated and reordered should still be discovered with all its universes once
+/// a full set of pages gets through.
+#[test]
+fn test_memory_faults_discovery_pages() {
+    let network = MemoryNetwork::with_faults(NetworkFaults {
+        loss: 0.3,
+        duplication: 0.3,
+        reordering: 0.3,
+        seed: 5,
+        ..NetworkFaults::default()
+    });
+    let mut rcv = memory_receiver(&network, &[]);
+    let src = network.bind(SocketAddr::new(IpAddr::from([10, 0, 0, 1]), 0));
+
+    let cid = Uuid::new_v4();
+    let universes: Vec<u16> = (1..=1100).collect();
+    let pages: Vec<&[u16]> = universes.chunks(DISCOVERY_UNI_PER_PAGE).collect();
+    let dst = universe_to_ipv4_multicast_addr(E131_DISCOVERY_UNIVERSE)
+        .unwrap()
+        .as_socket()
+        .unwrap();
+
+    for _ in 0..20 {
+        for (page, page_universes) in pages.iter().enumerate() {
+            let packet = AcnRootLayerProtocol {
+                pdu: E131RootLayer {
+                    cid,
+                    data: E131RootLayerData::UniverseDiscoveryPacket(
+                        UniverseDiscoveryPacketFramingLayer {
+                            source_name: "Faulty Source".into(),
+                            data: UniverseDiscoveryPacketUniverseDiscoveryLayer {
+                                page: page as u8,
+                                last_page: (pages.len() - 1) as u8,
+                                universes: (*page_universes).into(),
+                            },
+                        },
+                    ),
+                },
+            };
+            src.send_to(&packet.pack_alloc().unwrap(), dst).unwrap();
+        }
+
+        match rcv.recv_event(Some(Duration::from_millis(50))) {
+            Ok(ReceiveEvent::SourceDiscovered(discovered)) => {
+                assert_eq!(discovered.cid, cid);
+                assert_eq!(discovered.name, "Faulty Source");
+                let mut discovered_universes = discovered.get_all_universes();
+                discovered_universes.sort();
+                assert_eq!(discovered_universes, universes);
+                return;
+            }
+            Ok(event) => panic!("Unexpected event {event:?}"),
+            // Some pages were lost, the source sends them all again.
+            Err(e) if is_timeout(&e) => {}
+            Err(e) => panic!("Unexpected error receiving: {e:?}"),
+        }
+    }
+
+    panic!("Source never discovered");
+}